@@ -9,4 +9,4 @@ pub mod system;
 
 pub use behavior_tree::{BehaviorContext, BehaviorNode, BehaviorStatus};
 pub use ships::{AICommand, AIPersonality, CombatAI, PatrolAI, ShipAIContext, TradingAI};
-pub use system::{AIContextUpdate, AIManager, ShipAI};
+pub use system::{AIContextUpdate, AIManager, AIRegistration, ShipAI};
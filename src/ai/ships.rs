@@ -40,6 +40,8 @@ pub struct ShipAIContext {
     pub faction: String,
     /// Hostile factions
     pub hostile_factions: Vec<String>,
+    /// Teams whose standing with the faction makes them hostile
+    pub hostile_teams: Vec<String>,
     /// Current waypoint (for patrol)
     pub current_waypoint: Option<[f64; 3]>,
     /// Patrol route
@@ -95,6 +97,7 @@ impl ShipAIContext {
             personality: AIPersonality::Defensive,
             faction,
             hostile_factions: Vec::new(),
+            hostile_teams: Vec::new(),
             current_waypoint: None,
             patrol_route: Vec::new(),
             waypoint_index: 0,
@@ -2,6 +2,7 @@
 //!
 //! This module integrates AI behaviors with the game simulation.
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use uuid::Uuid;
@@ -79,6 +80,16 @@ impl ShipAI {
             self.context.hostile_factions.push(faction);
         }
     }
+
+    /// Treat a team as hostile or not
+    pub fn set_team_hostility(&mut self, team_id: &str, hostile: bool) {
+        let teams = &mut self.context.hostile_teams;
+        if hostile && !teams.iter().any(|id| id == team_id) {
+            teams.push(team_id.to_string());
+        } else if !hostile {
+            teams.retain(|id| id != team_id);
+        }
+    }
 }
 
 /// Update data for AI context
@@ -105,6 +116,21 @@ impl Default for AIContextUpdate {
     }
 }
 
+/// The lasting configuration of an AI-controlled ship
+///
+/// Captures what was set through registration, patrol routes, and hostile
+/// factions; per-tick context is rebuilt by the simulation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AIRegistration {
+    pub ship_id: Uuid,
+    pub faction: String,
+    pub personality: AIPersonality,
+    pub patrol_route: Vec<[f64; 3]>,
+    pub hostile_factions: Vec<String>,
+    #[serde(default)]
+    pub hostile_teams: Vec<String>,
+}
+
 /// Manager for all AI-controlled ships
 pub struct AIManager {
     /// Map of ship ID to AI controller
@@ -181,6 +207,49 @@ impl AIManager {
             ai.add_hostile_faction(faction);
         }
     }
+
+    /// Make every AI ship of a faction treat a team as hostile or not
+    pub fn set_team_hostility(&self, faction: &str, team_id: &str, hostile: bool) {
+        let mut ais = self.ais.write().unwrap();
+        for ai in ais.values_mut() {
+            if ai.context.faction == faction {
+                ai.set_team_hostility(team_id, hostile);
+            }
+        }
+    }
+
+    /// Get the registration of every AI-controlled ship
+    pub fn registrations(&self) -> Vec<AIRegistration> {
+        let ais = self.ais.read().unwrap();
+        ais.values()
+            .map(|ai| AIRegistration {
+                ship_id: ai.context.ship_id,
+                faction: ai.context.faction.clone(),
+                personality: ai.context.personality,
+                patrol_route: ai.context.patrol_route.clone(),
+                hostile_factions: ai.context.hostile_factions.clone(),
+                hostile_teams: ai.context.hostile_teams.clone(),
+            })
+            .collect()
+    }
+
+    /// Register a ship from a saved registration
+    pub fn restore(&self, registration: AIRegistration) {
+        let mut ai = ShipAI::new(
+            registration.ship_id,
+            registration.faction,
+            registration.personality,
+        );
+        ai.set_patrol_route(registration.patrol_route);
+        for faction in registration.hostile_factions {
+            ai.add_hostile_faction(faction);
+        }
+        for team_id in registration.hostile_teams {
+            ai.set_team_hostility(&team_id, true);
+        }
+        let mut ais = self.ais.write().unwrap();
+        ais.insert(registration.ship_id, ai);
+    }
 }
 
 impl Default for AIManager {
@@ -283,4 +352,27 @@ mod tests {
             Some(AIPersonality::Defensive)
         );
     }
+
+    #[test]
+    fn test_registration_round_trip() {
+        let manager = AIManager::new();
+        let ship_id = Uuid::new_v4();
+
+        manager.register_ship(ship_id, "Federation".to_string(), AIPersonality::Patrol);
+        manager.set_patrol_route(ship_id, vec![[0.0, 0.0, 0.0], [100.0, 0.0, 0.0]]);
+        manager.add_hostile_faction(ship_id, "Klingon".to_string());
+        manager.set_team_hostility("Federation", "team-1", true);
+
+        let restored = AIManager::new();
+        for registration in manager.registrations() {
+            restored.restore(registration);
+        }
+
+        let registration = restored.registrations().pop().unwrap();
+        assert_eq!(registration.ship_id, ship_id);
+        assert_eq!(registration.personality, AIPersonality::Patrol);
+        assert_eq!(registration.patrol_route.len(), 2);
+        assert_eq!(registration.hostile_factions, vec!["Klingon".to_string()]);
+        assert_eq!(registration.hostile_teams, vec!["team-1".to_string()]);
+    }
 }
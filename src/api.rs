@@ -3,14 +3,19 @@
 //! Defines REST and GraphQL API endpoints for game clients.
 
 pub mod ai;
+pub mod auth;
 pub mod blueprints;
 pub mod catalog;
 pub mod factions;
 pub mod generation;
+pub mod graphql;
 pub mod lookup;
 pub mod modules;
 pub mod players;
 pub mod positions;
+pub mod replays;
+pub mod salvage;
+pub mod saves;
 pub mod ship_classes;
 pub mod ships;
 pub mod stations;
@@ -65,8 +70,11 @@ pub fn routes() -> Vec<Route> {
     api_routes.extend(modules::routes());
     api_routes.extend(catalog::routes());
     api_routes.extend(stations::routes());
+    api_routes.extend(salvage::routes());
     api_routes.extend(ai::routes());
     api_routes.extend(generation::routes());
+    api_routes.extend(saves::routes());
+    api_routes.extend(replays::routes());
     api_routes.extend(positions::captain::routes());
     api_routes.extend(positions::comms::routes());
     api_routes.extend(positions::countermeasures::routes());
@@ -85,8 +93,7 @@ pub fn websocket_routes() -> Vec<Route> {
     routes![websocket::ws_handler, websocket::ws_info]
 }
 
-/// Returns all GraphQL API routes (placeholder)
+/// Returns all GraphQL API routes (mounted under `/graphql`)
 pub fn graphql_routes() -> Vec<Route> {
-    // GraphQL routes will be implemented here
-    routes![]
+    graphql::routes()
 }
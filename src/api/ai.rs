@@ -93,6 +93,11 @@ pub fn create_ai_ship(
         request.faction.clone(),
         request.personality,
     );
+    for team_id in world.hostile_teams(&request.faction) {
+        world
+            .ai_manager
+            .set_team_hostility(&request.faction, &team_id, true);
+    }
 
     if let Some(route) = &request.patrol_route {
         world
@@ -0,0 +1,691 @@
+//! Player authentication and authorization
+//!
+//! Registering a player issues a secret session token. Clients present it as
+//! `Authorization: Bearer <token>`, and the request guards here resolve it to
+//! the calling [`Player`] and check what that player may do:
+//!
+//! - [`AuthenticatedPlayer`] - any registered player
+//! - [`StationCrew`] - crew holding a bridge station's role aboard the ship
+//!   named by the route's first parameter; the Captain may act at any station
+//! - [`TeamMember`] - a member of the team named by the route's first parameter
+//! - [`ShipTeamMember`] - a member of the team flying the ship named by the
+//!   route's first parameter
+//! - [`Operator`] - the server operator, holding the [`OperatorToken`] rather
+//!   than a player's token; required to save, load, and record the session
+//!
+//! Guards fail with `404 Not Found` for an unknown ship or team, `401
+//! Unauthorized` for a missing or unknown token, and `403 Forbidden` when the
+//! player is not crew, lacks the station's role, or is not a team member. The
+//! [`catchers`] render these rejections as a JSON [`AuthError`] body.
+
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::serde::json::Json;
+use rocket::{Catcher, catch, catchers};
+use serde::Serialize;
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::models::Player;
+use crate::models::role::ShipRole;
+use crate::state::{GameWorld, SharedGameWorld, token_digest};
+
+/// Environment variable the server reads its operator token from
+pub const OPERATOR_TOKEN_VAR: &str = "HYPERION_OPERATOR_TOKEN";
+
+/// Why a guard rejected a request
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthError {
+    /// HTTP status the rejection maps to
+    #[serde(skip)]
+    pub status: Status,
+    /// Machine-readable reason: `unauthorized`, `forbidden`, or `not_found`
+    pub error: String,
+    /// Human-readable explanation
+    pub message: String,
+    /// Role the request required, for station commands
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_role: Option<ShipRole>,
+}
+
+impl AuthError {
+    fn new(status: Status, message: String) -> Self {
+        let error = match status.code {
+            401 => "unauthorized",
+            403 => "forbidden",
+            404 => "not_found",
+            _ => "error",
+        };
+        Self {
+            status,
+            error: error.to_string(),
+            message,
+            required_role: None,
+        }
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(Status::Unauthorized, message.into())
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(Status::Forbidden, message.into())
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(Status::NotFound, message.into())
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// The rejection recorded by a failed guard, for the catchers to render
+struct Rejection(Option<AuthError>);
+
+/// Extract the token from an `Authorization: Bearer <token>` header
+pub fn bearer_token<'r>(request: &'r Request<'_>) -> Option<&'r str> {
+    request
+        .headers()
+        .get_one("Authorization")?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+/// Resolve the request's bearer token to a player
+fn authenticate(world: &GameWorld, request: &Request<'_>) -> Result<Player, AuthError> {
+    let token =
+        bearer_token(request).ok_or_else(|| AuthError::unauthorized("Missing bearer token"))?;
+    world
+        .authenticate_token(token)
+        .cloned()
+        .ok_or_else(|| AuthError::unauthorized("Invalid session token"))
+}
+
+/// Run a guard check against the managed game world
+fn with_world<T>(
+    request: &Request<'_>,
+    check: impl FnOnce(&GameWorld) -> Result<T, AuthError>,
+) -> Outcome<T, AuthError> {
+    let Some(world) = request.rocket().state::<SharedGameWorld>() else {
+        return Outcome::Error((
+            Status::InternalServerError,
+            AuthError::new(
+                Status::InternalServerError,
+                "Game world is not managed".to_string(),
+            ),
+        ));
+    };
+    match check(&world.read().unwrap()) {
+        Ok(value) => Outcome::Success(value),
+        Err(error) => {
+            request.local_cache(|| Rejection(Some(error.clone())));
+            Outcome::Error((error.status, error))
+        }
+    }
+}
+
+/// The route's first dynamic parameter, which names the guarded entity
+fn first_param(request: &Request<'_>) -> Option<String> {
+    let index = request
+        .route()?
+        .uri
+        .unmounted_origin
+        .path()
+        .segments()
+        .position(|segment| segment.starts_with('<'))?;
+    request.param::<String>(index).and_then(Result::ok)
+}
+
+/// Any player holding a valid session token
+#[derive(Debug, Clone)]
+pub struct AuthenticatedPlayer(pub Player);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AuthenticatedPlayer {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        with_world(request, |world| {
+            authenticate(world, request).map(AuthenticatedPlayer)
+        })
+    }
+}
+
+/// The secret that identifies the server operator
+///
+/// Only its digest is kept in memory.
+#[derive(Debug, Clone)]
+pub struct OperatorToken {
+    digest: String,
+}
+
+impl OperatorToken {
+    pub fn new(token: &str) -> Self {
+        Self {
+            digest: token_digest(token),
+        }
+    }
+
+    fn matches(&self, token: &str) -> bool {
+        token_digest(token) == self.digest
+    }
+}
+
+/// The server operator, presenting the managed [`OperatorToken`]
+#[derive(Debug, Clone, Copy)]
+pub struct Operator;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Operator {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let operator = request.rocket().state::<OperatorToken>();
+        with_world(request, |world| {
+            let token = bearer_token(request)
+                .ok_or_else(|| AuthError::unauthorized("Missing bearer token"))?;
+            if operator.is_some_and(|operator| operator.matches(token)) {
+                return Ok(Operator);
+            }
+            match world.authenticate_token(token) {
+                Some(player) => Err(AuthError::forbidden(format!(
+                    "Player {} is not the server operator",
+                    player.id
+                ))),
+                None => Err(AuthError::unauthorized("Invalid operator token")),
+            }
+        })
+    }
+}
+
+/// A bridge station, identified by the role that operates it
+pub trait Station: Send + Sync + 'static {
+    const ROLE: ShipRole;
+}
+
+/// Marker types naming each bridge station for [`StationCrew`]
+pub mod station {
+    use super::Station;
+    use crate::models::role::ShipRole;
+
+    macro_rules! stations {
+        ($($name:ident => $role:ident),* $(,)?) => {
+            $(
+                #[derive(Debug, Clone, Copy)]
+                pub struct $name;
+
+                impl Station for $name {
+                    const ROLE: ShipRole = ShipRole::$role;
+                }
+            )*
+        };
+    }
+
+    stations! {
+        Captain => Captain,
+        Helm => Helm,
+        Engineering => Engineering,
+        Science => Science,
+        Comms => Communications,
+        Countermeasures => Countermeasures,
+        EnergyWeapons => EnergyWeapons,
+        KineticWeapons => KineticWeapons,
+        MissileWeapons => MissileWeapons,
+    }
+}
+
+/// A player manning station `S` aboard the ship in the route's `<ship_id>`
+/// parameter, either by holding its role or as the ship's Captain
+#[derive(Debug, Clone)]
+pub struct StationCrew<S: Station> {
+    pub player: Player,
+    pub ship_id: String,
+    station: PhantomData<S>,
+}
+
+#[rocket::async_trait]
+impl<'r, S: Station> FromRequest<'r> for StationCrew<S> {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(ship_id) = first_param(request) else {
+            return Outcome::Forward(Status::NotFound);
+        };
+        with_world(request, |world| {
+            let Some(ship) = world.get_ship(&ship_id) else {
+                return Err(AuthError::not_found(format!("Ship {} not found", ship_id)));
+            };
+            let player = authenticate(world, request)?;
+            let Some(roles) = ship.player_roles.get(&player.id) else {
+                return Err(AuthError::forbidden(format!(
+                    "Player {} is not crew aboard ship {}",
+                    player.id, ship_id
+                )));
+            };
+            if !roles.contains(&S::ROLE) && !roles.contains(&ShipRole::Captain) {
+                return Err(AuthError {
+                    required_role: Some(S::ROLE),
+                    ..AuthError::forbidden(format!(
+                        "Player {} does not hold the {:?} role aboard ship {}",
+                        player.id,
+                        S::ROLE,
+                        ship_id
+                    ))
+                });
+            }
+            Ok(StationCrew {
+                player,
+                ship_id,
+                station: PhantomData,
+            })
+        })
+    }
+}
+
+/// A member of the team in the route's `<id>` parameter
+#[derive(Debug, Clone)]
+pub struct TeamMember {
+    pub player: Player,
+    pub team_id: String,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for TeamMember {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(team_id) = first_param(request) else {
+            return Outcome::Forward(Status::NotFound);
+        };
+        with_world(request, |world| {
+            if world.get_team(&team_id).is_none() {
+                return Err(AuthError::not_found(format!("Team {} not found", team_id)));
+            }
+            let player = authenticate(world, request)?;
+            require_team_member(world, &team_id, &player)?;
+            Ok(TeamMember { player, team_id })
+        })
+    }
+}
+
+/// A member of the team flying the ship in the route's first parameter
+#[derive(Debug, Clone)]
+pub struct ShipTeamMember {
+    pub player: Player,
+    pub ship_id: String,
+    pub team_id: String,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ShipTeamMember {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(ship_id) = first_param(request) else {
+            return Outcome::Forward(Status::NotFound);
+        };
+        with_world(request, |world| {
+            let Some(ship) = world.get_ship(&ship_id) else {
+                return Err(AuthError::not_found(format!("Ship {} not found", ship_id)));
+            };
+            let team_id = ship.team_id.clone();
+            let player = authenticate(world, request)?;
+            require_team_member(world, &team_id, &player)?;
+            Ok(ShipTeamMember {
+                player,
+                ship_id,
+                team_id,
+            })
+        })
+    }
+}
+
+/// Check that a player may spend a team's credits
+pub fn require_team_member(
+    world: &GameWorld,
+    team_id: &str,
+    player: &Player,
+) -> Result<(), AuthError> {
+    if world.is_team_member(team_id, &player.id) {
+        Ok(())
+    } else {
+        Err(AuthError::forbidden(format!(
+            "Player {} is not a member of team {}",
+            player.id, team_id
+        )))
+    }
+}
+
+/// Check that a player is acting on their own behalf
+pub fn require_self(player: &Player, player_id: &str) -> Result<(), AuthError> {
+    if player.id == player_id {
+        Ok(())
+    } else {
+        Err(AuthError::forbidden(format!(
+            "Player {} may not act for player {}",
+            player.id, player_id
+        )))
+    }
+}
+
+/// Check that a player may add `player_id` to a team
+///
+/// Members may add anyone. Anyone else may only add themselves, and only to
+/// a team that has no members yet. A missing team is left for the caller to
+/// report.
+pub fn require_team_admission(
+    world: &GameWorld,
+    team_id: &str,
+    player: &Player,
+    player_id: &str,
+) -> Result<(), AuthError> {
+    let Some(team) = world.get_team(team_id) else {
+        return Ok(());
+    };
+    if team.members.contains(&player.id) {
+        return Ok(());
+    }
+    require_self(player, player_id)?;
+    if team.members.is_empty() {
+        Ok(())
+    } else {
+        Err(AuthError::forbidden(format!(
+            "Player {} is not a member of team {}",
+            player.id, team_id
+        )))
+    }
+}
+
+/// Render the rejection a guard recorded, or a generic body for the status
+fn rejection(status: Status, request: &Request<'_>) -> Json<AuthError> {
+    let recorded = &request.local_cache(|| Rejection(None)).0;
+    Json(
+        recorded
+            .clone()
+            .unwrap_or_else(|| AuthError::new(status, status.reason_lossy().to_string())),
+    )
+}
+
+#[catch(401)]
+fn unauthorized(request: &Request<'_>) -> Json<AuthError> {
+    rejection(Status::Unauthorized, request)
+}
+
+#[catch(403)]
+fn forbidden(request: &Request<'_>) -> Json<AuthError> {
+    rejection(Status::Forbidden, request)
+}
+
+/// Catchers rendering authentication and authorization failures as JSON
+pub fn catchers() -> Vec<Catcher> {
+    catchers![unauthorized, forbidden]
+}
+
+/// Guards for calling handlers directly in unit tests
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use super::*;
+    use rocket::http::Header;
+
+    /// Operator token for test servers that manage [`operator_token`]
+    pub const OPERATOR_TOKEN: &str = "operator-secret";
+
+    /// The operator token to manage in test servers
+    pub fn operator_token() -> OperatorToken {
+        OperatorToken::new(OPERATOR_TOKEN)
+    }
+
+    /// `Authorization` header presenting the test operator token
+    pub fn operator() -> Header<'static> {
+        Header::new("Authorization", format!("Bearer {}", OPERATOR_TOKEN))
+    }
+
+    /// A station guard for `ship_id`, as if its crew member had authenticated
+    pub fn crew<S: Station>(ship_id: &str) -> StationCrew<S> {
+        StationCrew {
+            player: Player::new("crew".to_string()),
+            ship_id: ship_id.to_string(),
+            station: PhantomData,
+        }
+    }
+
+    /// A member of the team flying `ship_id`, as if they had authenticated
+    pub fn team_member(ship_id: &str) -> ShipTeamMember {
+        ShipTeamMember {
+            player: Player::new("member".to_string()),
+            ship_id: ship_id.to_string(),
+            team_id: "team1".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Ship;
+    use rocket::http::Header;
+    use rocket::local::blocking::Client;
+    use rocket::{get, routes};
+
+    #[get("/ships/<ship_id>/helm")]
+    fn helm_only(ship_id: String, crew: StationCrew<station::Helm>) -> String {
+        assert_eq!(ship_id, crew.ship_id);
+        crew.player.name
+    }
+
+    #[get("/teams/<id>")]
+    fn members_only(id: String, member: TeamMember) -> String {
+        assert_eq!(id, member.team_id);
+        member.player.name
+    }
+
+    #[get("/ships/<ship_id>/status")]
+    fn own_ships_only(ship_id: String, member: ShipTeamMember) -> String {
+        assert_eq!(ship_id, member.ship_id);
+        member.player.name
+    }
+
+    #[get("/me")]
+    fn me(player: AuthenticatedPlayer) -> String {
+        player.0.name
+    }
+
+    #[get("/operator")]
+    fn operator_only(_operator: Operator) -> &'static str {
+        "operator"
+    }
+
+    /// Tokens for the players in the test world: `alice` at the helm, `carol`
+    /// as captain, `dave` at science, and `bob`, who is not crew
+    struct Tokens {
+        alice: String,
+        bob: String,
+        carol: String,
+        dave: String,
+    }
+
+    /// World with a crewed ship and a team `alice` belongs to; returns the
+    /// client, the team ID, and every player's token
+    fn setup() -> (Client, String, Tokens) {
+        let world = GameWorld::new_shared();
+        let (team_id, tokens) = {
+            let mut world = world.write().unwrap();
+            let mut register = |name: &str| world.register_player(name.to_string()).unwrap();
+            let (alice, bob, carol, dave) = (
+                register("alice"),
+                register("bob"),
+                register("carol"),
+                register("dave"),
+            );
+            let team_id = world
+                .create_team("Alpha".to_string(), "Federation".to_string())
+                .unwrap();
+            world.add_player_to_team(&team_id, &alice).unwrap();
+            world.add_ship(Ship {
+                id: "ship1".to_string(),
+                name: "Endeavour".to_string(),
+                class: "cruiser".to_string(),
+                team_id: team_id.clone(),
+                player_roles: [
+                    (alice.clone(), vec![ShipRole::Helm]),
+                    (carol.clone(), vec![ShipRole::Captain]),
+                    (dave.clone(), vec![ShipRole::Science]),
+                ]
+                .into(),
+                status: Default::default(),
+                modules: vec![],
+                weapons: vec![],
+                inventory: Default::default(),
+            });
+            let mut token = |id: &str| world.issue_player_token(id).unwrap();
+            let tokens = Tokens {
+                alice: token(&alice),
+                bob: token(&bob),
+                carol: token(&carol),
+                dave: token(&dave),
+            };
+            (team_id, tokens)
+        };
+
+        let rocket = rocket::build()
+            .manage(world)
+            .manage(test_utils::operator_token())
+            .mount(
+                "/",
+                routes![helm_only, members_only, own_ships_only, me, operator_only],
+            )
+            .register("/", catchers());
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        (client, team_id, tokens)
+    }
+
+    fn bearer(token: &str) -> Header<'static> {
+        Header::new("Authorization", format!("Bearer {}", token))
+    }
+
+    #[test]
+    fn test_authenticated_player() {
+        let (client, _, tokens) = setup();
+
+        let response = client.get("/me").header(bearer(&tokens.alice)).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "alice");
+
+        assert_eq!(client.get("/me").dispatch().status(), Status::Unauthorized);
+        let response = client.get("/me").header(bearer("forged")).dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn test_operator_guard() {
+        let (client, _, tokens) = setup();
+        let get = |token: &str| client.get("/operator").header(bearer(token)).dispatch();
+
+        assert_eq!(get(test_utils::OPERATOR_TOKEN).status(), Status::Ok);
+        assert_eq!(get(&tokens.carol).status(), Status::Forbidden);
+        assert_eq!(get("forged").status(), Status::Unauthorized);
+        let response = client.get("/operator").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn test_station_crew_guard() {
+        let (client, _, tokens) = setup();
+        let get = |token: &str| {
+            client
+                .get("/ships/ship1/helm")
+                .header(bearer(token))
+                .dispatch()
+        };
+
+        let response = get(&tokens.alice);
+        assert_eq!(response.status(), Status::Ok);
+
+        // The captain may take over any station
+        let response = get(&tokens.carol);
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "carol");
+
+        let response = get(&tokens.bob);
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client.get("/ships/ship1/helm").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client
+            .get("/ships/ship2/helm")
+            .header(bearer(&tokens.alice))
+            .dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn test_wrong_station_is_forbidden_with_structured_body() {
+        let (client, _, tokens) = setup();
+
+        let response = client
+            .get("/ships/ship1/helm")
+            .header(bearer(&tokens.dave))
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let body: serde_json::Value = response.into_json().unwrap();
+        assert_eq!(body["error"], "forbidden");
+        assert_eq!(body["required_role"], "helm");
+        assert!(body["message"].as_str().unwrap().contains("Helm"));
+
+        let response = client.get("/ships/ship1/helm").dispatch();
+        let body: serde_json::Value = response.into_json().unwrap();
+        assert_eq!(body["error"], "unauthorized");
+        assert!(body.get("required_role").is_none());
+    }
+
+    #[test]
+    fn test_team_member_guard() {
+        let (client, team_id, tokens) = setup();
+        let uri = format!("/teams/{}", team_id);
+
+        let response = client.get(&uri).header(bearer(&tokens.alice)).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get(&uri).header(bearer(&tokens.bob)).dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client
+            .get("/teams/missing")
+            .header(bearer(&tokens.alice))
+            .dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn test_ship_team_member_guard() {
+        let (client, _, tokens) = setup();
+
+        let response = client
+            .get("/ships/ship1/status")
+            .header(bearer(&tokens.alice))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "alice");
+
+        let response = client
+            .get("/ships/ship1/status")
+            .header(bearer(&tokens.bob))
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client.get("/ships/ship1/status").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client
+            .get("/ships/ship2/status")
+            .header(bearer(&tokens.alice))
+            .dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
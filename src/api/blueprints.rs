@@ -2,6 +2,7 @@
 //!
 //! Provides REST API endpoints for managing ship blueprints.
 
+use crate::api::auth::{AuthenticatedPlayer, require_self, require_team_member};
 use crate::api::lookup::WorldLookup;
 use crate::blueprint::BlueprintValidator;
 use crate::config::GameConfig;
@@ -225,21 +226,21 @@ pub fn get_blueprint(
 }
 
 /// POST /v1/blueprints/<id>/join - Join existing blueprint
+///
+/// Players join on their own behalf, and only their own team's blueprints.
 #[post("/v1/blueprints/<id>/join", data = "<request>")]
 pub fn join_blueprint(
     id: &str,
     request: Json<JoinBlueprintRequest>,
     world: &State<SharedGameWorld>,
+    player: AuthenticatedPlayer,
 ) -> Result<Json<BlueprintResponse>, Status> {
-    // Verify player exists
-    {
-        let world_guard = world.read().unwrap();
-        if world_guard.get_player(&request.player_id).is_none() {
-            return Err(Status::BadRequest);
-        }
-    }
+    require_self(&player.0, &request.player_id).map_err(|error| error.status)?;
     let mut game_world = world.write().unwrap();
 
+    let team_id = game_world.find_blueprint(id)?.team_id.clone();
+    require_team_member(&game_world, &team_id, &player.0).map_err(|error| error.status)?;
+
     // Get blueprint and add player with empty roles
     let blueprint = game_world.find_blueprint_mut(id)?;
 
@@ -250,11 +251,15 @@ pub fn join_blueprint(
 }
 
 /// PATCH /v1/blueprints/<id>/roles - Update player roles
+///
+/// The caller and the player being assigned must both be on the
+/// blueprint's team.
 #[patch("/v1/blueprints/<id>/roles", data = "<request>")]
 pub fn update_roles(
     id: &str,
     request: Json<UpdateRolesRequest>,
     world: &State<SharedGameWorld>,
+    player: AuthenticatedPlayer,
 ) -> Result<Json<BlueprintResponse>, Status> {
     eprintln!(
         "Received role update request: player_id={}, roles={:?}",
@@ -269,6 +274,12 @@ pub fn update_roles(
         return Err(Status::BadRequest);
     }
 
+    let team_id = game_world.find_blueprint(id)?.team_id.clone();
+    require_team_member(&game_world, &team_id, &player.0).map_err(|error| error.status)?;
+    if !game_world.is_team_member(&team_id, &request.player_id) {
+        return Err(Status::BadRequest);
+    }
+
     let blueprint = game_world.find_blueprint_mut(id).inspect_err(|_| {
         eprintln!("Blueprint not found: {}", id);
     })?;
@@ -359,13 +370,15 @@ pub fn configure_module(
     Ok(Json(blueprint_to_response(blueprint)))
 }
 
-/// POST /v1/blueprints/<id>/ready - Mark player as ready
+/// POST /v1/blueprints/<id>/ready - Mark the calling player as ready
 #[post("/v1/blueprints/<id>/ready", data = "<request>")]
 pub fn mark_ready(
     id: &str,
     request: Json<ReadyRequest>,
     world: &State<SharedGameWorld>,
+    player: AuthenticatedPlayer,
 ) -> Result<Json<BlueprintResponse>, Status> {
+    require_self(&player.0, &request.player_id).map_err(|error| error.status)?;
     let mut world = world.write().unwrap();
 
     let blueprint = world.find_blueprint_mut(id)?;
@@ -381,13 +394,15 @@ pub fn mark_ready(
     Ok(Json(blueprint_to_response(blueprint)))
 }
 
-/// DELETE /v1/blueprints/<id>/ready - Unmark player as ready
+/// DELETE /v1/blueprints/<id>/ready - Unmark the calling player as ready
 #[delete("/v1/blueprints/<id>/ready/<player_id>")]
 pub fn unmark_ready(
     id: &str,
     player_id: &str,
     world: &State<SharedGameWorld>,
+    player: AuthenticatedPlayer,
 ) -> Result<Json<BlueprintResponse>, Status> {
+    require_self(&player.0, player_id).map_err(|error| error.status)?;
     let mut world = world.write().unwrap();
 
     let blueprint = world.find_blueprint_mut(id)?;
@@ -451,6 +466,7 @@ mod tests {
     use crate::config::{Faction, GameConfig};
     use crate::state::GameWorld;
     use rocket::Build;
+    use rocket::http::Header;
     use rocket::local::blocking::Client;
 
     fn create_test_config() -> GameConfig {
@@ -496,6 +512,13 @@ mod tests {
             .mount("/", crate::api::teams::routes())
     }
 
+    fn bearer(player: &serde_json::Value) -> Header<'static> {
+        Header::new(
+            "Authorization",
+            format!("Bearer {}", player["token"].as_str().unwrap()),
+        )
+    }
+
     #[test]
     fn test_list_blueprints_empty() {
         let client = Client::tracked(create_test_rocket()).expect("valid rocket instance");
@@ -597,8 +620,12 @@ mod tests {
         let player_json: serde_json::Value = player_response.into_json().unwrap();
         let player_id = player_json["id"].as_str().unwrap().to_string();
 
-        let team_request = serde_json::json!({"name": "Gamma Team", "faction": "alliance"});
-        let team_response = client.post("/v1/teams").json(&team_request).dispatch();
+        let team_request = serde_json::json!({"name": "Gamma Team", "faction": "alliance", "player_id": player_id});
+        let team_response = client
+            .post("/v1/teams")
+            .header(bearer(&player_json))
+            .json(&team_request)
+            .dispatch();
         let team_json: serde_json::Value = team_response.into_json().unwrap();
         let team_id = team_json["id"].as_str().unwrap().to_string();
 
@@ -621,11 +648,34 @@ mod tests {
             .post(format!("/v1/blueprints/{}/join", blueprint.id))
             .json(&join_request)
             .dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client
+            .post(format!("/v1/blueprints/{}/join", blueprint.id))
+            .header(bearer(&player_json))
+            .json(&join_request)
+            .dispatch();
 
         assert_eq!(response.status(), Status::Ok);
         let updated: BlueprintResponse = response.into_json().unwrap();
         assert_eq!(updated.player_roles.len(), 1);
         assert_eq!(updated.player_roles[0].player_id, player_id);
+
+        // Players outside the team cannot join its blueprints
+        let outsider: serde_json::Value = client
+            .post("/v1/players")
+            .json(&serde_json::json!({"name": "Janeway"}))
+            .dispatch()
+            .into_json()
+            .unwrap();
+        let response = client
+            .post(format!("/v1/blueprints/{}/join", blueprint.id))
+            .header(bearer(&outsider))
+            .json(&JoinBlueprintRequest {
+                player_id: outsider["id"].as_str().unwrap().to_string(),
+            })
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
     }
 
     #[test]
@@ -642,7 +692,8 @@ mod tests {
 
         let team_response = client
             .post("/v1/teams")
-            .json(&serde_json::json!({"name": "Delta Team", "faction": "alliance"}))
+            .header(bearer(&player_json))
+            .json(&serde_json::json!({"name": "Delta Team", "faction": "alliance", "player_id": player_id}))
             .dispatch();
         let team_json: serde_json::Value = team_response.into_json().unwrap();
         let team_id = team_json["id"].as_str().unwrap().to_string();
@@ -659,6 +710,7 @@ mod tests {
 
         client
             .post(format!("/v1/blueprints/{}/join", blueprint.id))
+            .header(bearer(&player_json))
             .json(&JoinBlueprintRequest {
                 player_id: player_id.clone(),
             })
@@ -671,6 +723,7 @@ mod tests {
         };
         let response = client
             .patch(format!("/v1/blueprints/{}/roles", blueprint.id))
+            .header(bearer(&player_json))
             .json(&update_request)
             .dispatch();
 
@@ -790,7 +843,8 @@ mod tests {
 
         let team_response = client
             .post("/v1/teams")
-            .json(&serde_json::json!({"name": "Eta Team", "faction": "alliance"}))
+            .header(bearer(&player_json))
+            .json(&serde_json::json!({"name": "Eta Team", "faction": "alliance", "player_id": player_id}))
             .dispatch();
         let team_json: serde_json::Value = team_response.into_json().unwrap();
         let team_id = team_json["id"].as_str().unwrap().to_string();
@@ -807,6 +861,7 @@ mod tests {
 
         client
             .post(format!("/v1/blueprints/{}/join", blueprint.id))
+            .header(bearer(&player_json))
             .json(&JoinBlueprintRequest {
                 player_id: player_id.clone(),
             })
@@ -818,6 +873,7 @@ mod tests {
         };
         let response = client
             .post(format!("/v1/blueprints/{}/ready", blueprint.id))
+            .header(bearer(&player_json))
             .json(&ready_request)
             .dispatch();
 
@@ -832,6 +888,7 @@ mod tests {
                 "/v1/blueprints/{}/ready/{}",
                 blueprint.id, player_id
             ))
+            .header(bearer(&player_json))
             .dispatch();
         assert_eq!(response.status(), Status::Ok);
         let unready_bp: BlueprintResponse = response.into_json().unwrap();
@@ -9,18 +9,27 @@ use rocket::{State, get, post};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, RwLock};
 
-use crate::generation::ProceduralUniverse;
+use crate::generation::{ProceduralUniverse, UniverseSettings};
+use crate::state::SharedGameWorld;
 
 /// Application state for storing the current procedural universe
 #[derive(Default)]
 pub struct UniverseState {
     pub universe: Option<ProceduralUniverse>,
+    /// Settings the current universe was generated from
+    pub settings: Option<UniverseSettings>,
 }
 
 impl UniverseState {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Generate a universe from `settings` and make it current
+    pub fn generate(&mut self, settings: UniverseSettings) -> &ProceduralUniverse {
+        self.settings = Some(settings.clone());
+        self.universe.insert(settings.generate())
+    }
 }
 
 /// Request to generate a new universe
@@ -192,18 +201,21 @@ pub struct TimelineResponse {
 }
 
 /// Generate a new procedural universe
+///
+/// The relationships between its factions seed every team's reputation.
 #[post("/v1/generation/universe", format = "json", data = "<request>")]
 pub fn generate_universe(
     request: Json<GenerateUniverseRequest>,
     state: &State<Arc<RwLock<UniverseState>>>,
+    world: &State<SharedGameWorld>,
 ) -> Result<Json<UniverseResponse>, Status> {
-    let universe = ProceduralUniverse::generate(
-        request.name.clone(),
-        request.seed,
-        request.num_stars,
-        request.num_factions,
-    );
-
+    let mut state = state.write().unwrap();
+    let universe = state.generate(UniverseSettings {
+        name: request.name.clone(),
+        seed: request.seed,
+        num_stars: request.num_stars,
+        num_factions: request.num_factions,
+    });
     let response = UniverseResponse {
         name: universe.name.clone(),
         seed: universe.seed,
@@ -211,8 +223,14 @@ pub fn generate_universe(
         num_systems: universe.systems.len(),
         num_factions: universe.factions.len(),
     };
-
-    state.write().unwrap().universe = Some(universe);
+    let factions = universe.factions.clone();
+    // Autosaves lock the world before the universe, so release the universe
+    // first
+    drop(state);
+    world
+        .write()
+        .unwrap()
+        .configure_faction_relations(&factions);
 
     Ok(Json(response))
 }
@@ -625,28 +643,32 @@ pub fn routes() -> Vec<rocket::Route> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state::GameWorld;
     use rocket::local::blocking::Client;
 
     fn create_test_client() -> Client {
         let state = Arc::new(RwLock::new(UniverseState::new()));
 
-        let rocket = rocket::build().manage(state).mount(
-            "/",
-            rocket::routes![
-                generate_universe,
-                get_universe,
-                get_galaxy,
-                list_systems,
-                get_system,
-                list_factions,
-                get_faction,
-                get_language,
-                translate,
-                get_history,
-                get_faction_history,
-                get_timeline,
-            ],
-        );
+        let rocket = rocket::build()
+            .manage(state)
+            .manage(GameWorld::new_shared())
+            .mount(
+                "/",
+                rocket::routes![
+                    generate_universe,
+                    get_universe,
+                    get_galaxy,
+                    list_systems,
+                    get_system,
+                    list_factions,
+                    get_faction,
+                    get_language,
+                    translate,
+                    get_history,
+                    get_faction_history,
+                    get_timeline,
+                ],
+            );
 
         Client::tracked(rocket).expect("valid rocket instance")
     }
@@ -672,6 +694,30 @@ mod tests {
         let universe: UniverseResponse = response.into_json().unwrap();
         assert_eq!(universe.name, "Test Universe");
         assert_eq!(universe.seed, 12345);
+
+        // Teams now have a standing with every generated faction
+        let faction = client
+            .rocket()
+            .state::<Arc<RwLock<UniverseState>>>()
+            .unwrap()
+            .read()
+            .unwrap()
+            .universe
+            .as_ref()
+            .unwrap()
+            .factions[0]
+            .name
+            .clone();
+        let mut world = client
+            .rocket()
+            .state::<SharedGameWorld>()
+            .unwrap()
+            .write()
+            .unwrap();
+        let team_id = world
+            .create_team_with_credits("Red".to_string(), faction, 0)
+            .unwrap();
+        assert_eq!(world.reputation(&team_id).unwrap().len(), 3);
     }
 
     #[test]
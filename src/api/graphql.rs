@@ -0,0 +1,675 @@
+//! GraphQL API
+//!
+//! Exposes the same game state as the REST API through a single GraphQL
+//! schema mounted at `/graphql`, with a GraphiQL playground at
+//! `/graphql/graphiql`.
+//!
+//! ## Schema
+//!
+//! - **Query**: players, teams, blueprints, ships, stations, the module and
+//!   ammunition catalog, ship classes, and the procedural universe
+//! - **Mutation**: player/team setup, the blueprint editing flow, ship
+//!   compilation, and station docking and services
+//! - **Subscription**: live `GameEvent` streams (`shipEvents`, `damageTaken`,
+//!   `simulationTick`) served over WebSocket at `/graphql/ws`
+//!
+//! ## Authentication
+//!
+//! `createPlayer` returns a session token. HTTP requests present it as
+//! `Authorization: Bearer <token>`; WebSocket clients must send it in the
+//! `connection_init` payload as `{"token": "..."}`. Mutations that change
+//! teams, blueprints, or ships require it and apply the same checks as the
+//! REST guards; `deleteStation` requires the server operator's token.
+
+mod mutation;
+mod query;
+mod subscription;
+mod types;
+mod ws;
+
+pub use mutation::Mutation;
+pub use query::Query;
+pub use subscription::*;
+pub use types::*;
+
+use juniper::{FieldResult, RootNode};
+use juniper_rocket::{GraphQLRequest, GraphQLResponse};
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::response::content::RawHtml;
+use rocket::{Route, State, get, post, routes};
+use std::convert::Infallible;
+use std::sync::{Arc, RwLock};
+
+use crate::api::auth::{AuthenticatedPlayer, Operator};
+use crate::api::generation::UniverseState;
+use crate::config::GameConfig;
+use crate::models::Player;
+use crate::state::SharedGameWorld;
+use crate::websocket::WebSocketManager;
+
+/// Per-request context shared by all resolvers
+///
+/// Holds handles to the same state the REST handlers use.
+#[derive(Clone)]
+pub struct Context {
+    /// Shared game world
+    pub world: SharedGameWorld,
+    /// Loaded game configuration
+    pub config: Arc<GameConfig>,
+    /// Current procedural universe
+    pub universe: Arc<RwLock<UniverseState>>,
+    /// Source of the event stream behind subscriptions
+    pub events: Arc<WebSocketManager>,
+    /// Player identified by the request's session token, if any
+    pub player: Option<Player>,
+    /// Whether the request presented the server operator's token
+    pub operator: bool,
+}
+
+impl juniper::Context for Context {}
+
+impl Context {
+    /// The calling player, for resolvers that require authentication
+    pub fn authenticated(&self) -> FieldResult<&Player> {
+        self.player
+            .as_ref()
+            .ok_or_else(|| "Authentication required".into())
+    }
+
+    /// Fail unless the request came from the server operator
+    pub fn require_operator(&self) -> FieldResult<()> {
+        if self.operator {
+            Ok(())
+        } else {
+            Err("Operator token required".into())
+        }
+    }
+}
+
+/// The HYPERION GraphQL schema
+pub type Schema = RootNode<Query, Mutation, Subscription>;
+
+/// Build the GraphQL schema
+pub fn schema() -> Schema {
+    Schema::new(Query, Mutation, Subscription)
+}
+
+/// Who sent an HTTP request: a player's session, the operator, or neither
+#[derive(Debug, Clone, Default)]
+pub struct Caller {
+    player: Option<Player>,
+    operator: bool,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Caller {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let player = request
+            .guard::<Option<AuthenticatedPlayer>>()
+            .await
+            .succeeded()
+            .flatten();
+        let operator = request
+            .guard::<Option<Operator>>()
+            .await
+            .succeeded()
+            .flatten();
+        Outcome::Success(Caller {
+            player: player.map(|player| player.0),
+            operator: operator.is_some(),
+        })
+    }
+}
+
+/// Build a resolver context from managed Rocket state
+fn context(
+    world: &State<SharedGameWorld>,
+    config: &State<Arc<GameConfig>>,
+    universe: &State<Arc<RwLock<UniverseState>>>,
+    ws_manager: &State<Arc<WebSocketManager>>,
+    caller: Caller,
+) -> Context {
+    Context {
+        world: world.inner().clone(),
+        config: config.inner().clone(),
+        universe: universe.inner().clone(),
+        events: ws_manager.inner().clone(),
+        player: caller.player,
+        operator: caller.operator,
+    }
+}
+
+/// GET /graphql - Execute a query passed as URL parameters
+#[get("/?<request..>")]
+pub async fn graphql_get(
+    request: GraphQLRequest,
+    schema: &State<Schema>,
+    world: &State<SharedGameWorld>,
+    config: &State<Arc<GameConfig>>,
+    universe: &State<Arc<RwLock<UniverseState>>>,
+    ws_manager: &State<Arc<WebSocketManager>>,
+    caller: Caller,
+) -> GraphQLResponse {
+    request
+        .execute(
+            schema,
+            &context(world, config, universe, ws_manager, caller),
+        )
+        .await
+}
+
+/// POST /graphql - Execute a query or mutation
+#[post("/", data = "<request>")]
+pub async fn graphql_post(
+    request: GraphQLRequest,
+    schema: &State<Schema>,
+    world: &State<SharedGameWorld>,
+    config: &State<Arc<GameConfig>>,
+    universe: &State<Arc<RwLock<UniverseState>>>,
+    ws_manager: &State<Arc<WebSocketManager>>,
+    caller: Caller,
+) -> GraphQLResponse {
+    request
+        .execute(
+            schema,
+            &context(world, config, universe, ws_manager, caller),
+        )
+        .await
+}
+
+/// GET /graphql/graphiql - Interactive GraphiQL playground
+#[get("/graphiql")]
+pub fn graphiql() -> RawHtml<String> {
+    juniper_rocket::graphiql_source("/graphql", Some("/graphql/ws"))
+}
+
+/// Returns all GraphQL routes (mounted under `/graphql`)
+pub fn routes() -> Vec<Route> {
+    routes![graphql_get, graphql_post, graphiql, ws::graphql_ws]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Faction;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::state::GameWorld;
+    use juniper::{DefaultScalarValue, ScalarValue, Value, Variables};
+    use rocket::http::{ContentType, Status};
+    use rocket::local::blocking::Client;
+
+    fn create_test_context() -> Context {
+        let config = create_test_game_config().with_factions(vec![Faction {
+            id: "alliance".to_string(),
+            name: "Alliance".to_string(),
+            description: "Test faction".to_string(),
+        }]);
+        Context {
+            world: GameWorld::new_shared(),
+            config: Arc::new(config),
+            universe: Arc::new(RwLock::new(UniverseState::new())),
+            events: Arc::new(WebSocketManager::new()),
+            player: None,
+            operator: false,
+        }
+    }
+
+    fn execute(query: &str, context: &Context) -> Value<DefaultScalarValue> {
+        let (value, errors) =
+            juniper::execute_sync(query, None, &schema(), &Variables::new(), context).unwrap();
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+        value
+    }
+
+    fn field<'a>(value: &'a Value<DefaultScalarValue>, path: &[&str]) -> &'a Value {
+        path.iter().fold(value, |value, key| {
+            value
+                .as_object_value()
+                .unwrap()
+                .get_field_value(key)
+                .unwrap()
+        })
+    }
+
+    fn string(value: &Value<DefaultScalarValue>, path: &[&str]) -> String {
+        field(value, path)
+            .as_scalar()
+            .and_then(|s| s.try_as_str())
+            .unwrap()
+            .to_string()
+    }
+
+    /// Execute a query that must fail, returning the error messages
+    fn execute_err(query: &str, context: &Context) -> Vec<String> {
+        let (_, errors) =
+            juniper::execute_sync(query, None, &schema(), &Variables::new(), context).unwrap();
+        errors
+            .iter()
+            .map(|error| error.error().message().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_blueprint_flow() {
+        let mut context = create_test_context();
+
+        let player = execute(
+            r#"mutation { createPlayer(name: "alice") { id token } }"#,
+            &context,
+        );
+        let player_id = string(&player, &["createPlayer", "id"]);
+        let token = string(&player, &["createPlayer", "token"]);
+        assert_eq!(
+            context
+                .world
+                .read()
+                .unwrap()
+                .authenticate_token(&token)
+                .unwrap()
+                .id,
+            player_id
+        );
+
+        // Joining a player to a team takes that player's session
+        let create_team = format!(
+            r#"mutation {{ createTeam(name: "Alpha", faction: "alliance", playerId: "{}") {{ id members }} }}"#,
+            player_id
+        );
+        assert_eq!(
+            execute_err(&create_team, &context),
+            vec!["Authentication required"]
+        );
+        context.player = context
+            .world
+            .read()
+            .unwrap()
+            .get_player(&player_id)
+            .cloned();
+
+        let team = execute(&create_team, &context);
+        let team_id = string(&team, &["createTeam", "id"]);
+
+        let blueprint = execute(
+            &format!(
+                r#"mutation {{ createBlueprint(name: "Endeavour", shipClass: "cruiser", teamId: "{}") {{ id }} }}"#,
+                team_id
+            ),
+            &context,
+        );
+        let blueprint_id = string(&blueprint, &["createBlueprint", "id"]);
+
+        let updated = execute(
+            &format!(
+                r#"mutation {{ updateRoles(blueprintId: "{}", playerId: "{}", roles: [CAPTAIN, HELM]) {{ crew {{ playerId roles }} }} }}"#,
+                blueprint_id, player_id
+            ),
+            &context,
+        );
+        let crew = field(&updated, &["updateRoles", "crew"])
+            .as_list_value()
+            .unwrap();
+        assert_eq!(crew.len(), 1);
+
+        let query = execute(
+            &format!(
+                r#"{{ blueprint(id: "{}") {{ name teamId }} teams {{ name credits }} }}"#,
+                blueprint_id
+            ),
+            &context,
+        );
+        assert_eq!(string(&query, &["blueprint", "name"]), "Endeavour");
+        assert_eq!(string(&query, &["blueprint", "teamId"]), team_id);
+    }
+
+    #[test]
+    fn test_team_and_blueprint_mutations_check_the_caller() {
+        let mut context = create_test_context();
+        let (alice, bob, team_id, blueprint_id) = {
+            let mut world = context.world.write().unwrap();
+            let alice = world.register_player("alice".to_string()).unwrap();
+            let bob = world.register_player("bob".to_string()).unwrap();
+            let team_id = world
+                .create_team("Alpha".to_string(), "alliance".to_string())
+                .unwrap();
+            world.add_player_to_team(&team_id, &alice).unwrap();
+            let blueprint_id = world
+                .create_blueprint(
+                    "Endeavour".to_string(),
+                    "cruiser".to_string(),
+                    team_id.clone(),
+                )
+                .unwrap();
+            (alice, bob, team_id, blueprint_id)
+        };
+        let player = |id: &str| context.world.read().unwrap().get_player(id).cloned();
+        let join_team = format!(
+            r#"mutation {{ joinTeam(teamId: "{}", playerId: "{}") {{ members }} }}"#,
+            team_id, bob
+        );
+        let join_blueprint = format!(
+            r#"mutation {{ joinBlueprint(blueprintId: "{}", playerId: "{}") {{ id }} }}"#,
+            blueprint_id, bob
+        );
+
+        assert_eq!(
+            execute_err(&join_team, &context),
+            vec!["Authentication required"]
+        );
+
+        // Bob can neither let himself in nor crew Alice's blueprints
+        context.player = player(&bob);
+        assert_eq!(
+            execute_err(&join_team, &context),
+            vec![format!(
+                "Player {} is not a member of team {}",
+                bob, team_id
+            )]
+        );
+        assert_eq!(
+            execute_err(&join_blueprint, &context),
+            vec![format!(
+                "Player {} is not a member of team {}",
+                bob, team_id
+            )]
+        );
+
+        // Alice may add Bob, but not mark him ready on his behalf
+        context.player = player(&alice);
+        execute(&join_team, &context);
+        assert_eq!(
+            execute_err(&join_blueprint, &context),
+            vec![format!("Player {} may not act for player {}", alice, bob)]
+        );
+        let mark_ready = format!(
+            r#"mutation {{ markReady(blueprintId: "{}", playerId: "{}") {{ id }} }}"#,
+            blueprint_id, bob
+        );
+        assert_eq!(
+            execute_err(&mark_ready, &context),
+            vec![format!("Player {} may not act for player {}", alice, bob)]
+        );
+
+        context.player = player(&bob);
+        execute(&join_blueprint, &context);
+        execute(&mark_ready, &context);
+    }
+
+    #[test]
+    fn test_missing_entities_resolve_to_null() {
+        let context = create_test_context();
+
+        let value = execute(
+            r#"{ player(id: "nope") { id } ship(id: "nope") { id } universe { name } }"#,
+            &context,
+        );
+        assert!(field(&value, &["player"]).is_null());
+        assert!(field(&value, &["ship"]).is_null());
+        assert!(field(&value, &["universe"]).is_null());
+    }
+
+    #[test]
+    fn test_mutation_errors_are_reported() {
+        let context = create_test_context();
+
+        let (_, errors) = juniper::execute_sync(
+            r#"mutation { compileShip(blueprintId: "missing") { id } }"#,
+            None,
+            &schema(),
+            &Variables::new(),
+            &context,
+        )
+        .unwrap();
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_station_docking_flow() {
+        use crate::models::Ship;
+
+        let mut context = create_test_context();
+        let ship_id = uuid::Uuid::new_v4();
+        let player = {
+            let mut world = context.world.write().unwrap();
+            let player_id = world.register_player("alice".to_string()).unwrap();
+            let team_id = world
+                .create_team("Alpha".to_string(), "alliance".to_string())
+                .unwrap();
+            world.add_player_to_team(&team_id, &player_id).unwrap();
+            world.add_ship(Ship {
+                id: ship_id.to_string(),
+                name: "Shuttle".to_string(),
+                class: "cruiser".to_string(),
+                team_id,
+                player_roles: Default::default(),
+                status: Default::default(),
+                modules: vec![],
+                weapons: vec![],
+                inventory: Default::default(),
+            });
+            world.get_player(&player_id).cloned()
+        };
+
+        let station = execute(
+            r#"mutation { createStation(input: { name: "Outpost", position: [0.0, 0.0, 0.0], faction: "alliance" }) { id availableBays } }"#,
+            &context,
+        );
+        let station_id = string(&station, &["createStation", "id"]);
+
+        // Docking is requested by the ship's own team
+        let request_docking = format!(
+            r#"mutation {{ requestDocking(stationId: "{}", shipId: "{}") {{ approved status }} }}"#,
+            station_id, ship_id
+        );
+        assert_eq!(
+            execute_err(&request_docking, &context),
+            vec!["Authentication required"]
+        );
+
+        context.player = player;
+        let docking = execute(&request_docking, &context);
+        assert_eq!(
+            field(&docking, &["requestDocking", "approved"])
+                .as_scalar()
+                .and_then(|s| s.try_to_bool()),
+            Some(true)
+        );
+        assert_eq!(
+            string(&docking, &["requestDocking", "status"]),
+            "APPROACHING"
+        );
+        let complete_docking = format!(
+            r#"mutation {{ completeDocking(stationId: "{}", shipId: "{}") }}"#,
+            station_id, ship_id
+        );
+        execute(&complete_docking, &context);
+
+        // Services are billed to the ship's team, so they need a member
+        let anonymous = Context {
+            player: None,
+            ..context.clone()
+        };
+        let request_service = format!(
+            r#"mutation {{ requestService(stationId: "{}", shipId: "{}", service: {{ kind: REPAIR_ALL }}) {{ success }} }}"#,
+            station_id, ship_id
+        );
+        assert_eq!(execute_err(&request_service, &anonymous).len(), 1);
+
+        // Only the server operator may remove stations
+        let delete_station = format!(r#"mutation {{ deleteStation(id: "{}") }}"#, station_id);
+        assert_eq!(
+            execute_err(&delete_station, &context),
+            vec!["Operator token required"]
+        );
+        context.operator = true;
+        execute(&delete_station, &context);
+    }
+
+    /// Start a subscription and return its single root field's stream
+    async fn subscribe<'a>(
+        query: &'a str,
+        schema: &'a Schema,
+        context: &'a Context,
+    ) -> juniper::ValuesStream<'a> {
+        let (value, errors) =
+            juniper::resolve_into_stream(query, None, schema, &Variables::new(), context)
+                .await
+                .unwrap();
+        assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+        match value {
+            Value::Object(mut fields) => {
+                let (_, stream) = fields.iter_mut().next().unwrap();
+                match std::mem::replace(stream, Value::Null) {
+                    Value::Scalar(stream) => stream,
+                    _ => panic!("expected a stream"),
+                }
+            }
+            _ => panic!("expected an object"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_damage_taken_subscription() {
+        use crate::events::{DamageType, GameEvent};
+        use crate::models::Ship;
+        use futures::StreamExt;
+
+        let mut context = create_test_context();
+        let ship_id = uuid::Uuid::new_v4();
+        let player = {
+            let mut world = context.world.write().unwrap();
+            let player_id = world.register_player("alice".to_string()).unwrap();
+            let team_id = world
+                .create_team("Alpha".to_string(), "alliance".to_string())
+                .unwrap();
+            world.add_player_to_team(&team_id, &player_id).unwrap();
+            world.add_ship(Ship {
+                id: ship_id.to_string(),
+                name: "Target".to_string(),
+                class: "cruiser".to_string(),
+                team_id,
+                player_roles: Default::default(),
+                status: Default::default(),
+                modules: vec![],
+                weapons: vec![],
+                inventory: Default::default(),
+            });
+            world.get_player(&player_id).cloned()
+        };
+
+        let schema = schema();
+        let query = format!(
+            r#"subscription {{ damageTaken(shipId: "{}") {{ shipId damageType amount }} }}"#,
+            ship_id
+        );
+
+        // Only the ship's own team may listen in
+        let rejected =
+            juniper::resolve_into_stream(&query, None, &schema, &Variables::new(), &context)
+                .await
+                .map_or(true, |(_, errors)| !errors.is_empty());
+        assert!(rejected);
+
+        context.player = player;
+        let mut stream = subscribe(&query, &schema, &context).await;
+
+        // Events for other ships and of other kinds are filtered out
+        context.events.broadcast(GameEvent::DamageTaken {
+            ship_id: uuid::Uuid::new_v4(),
+            damage_type: DamageType::Energy,
+            amount: 1.0,
+            hull_percent: 99.0,
+            shield_percent: 99.0,
+        });
+        context
+            .events
+            .broadcast(GameEvent::SimulationTick { tick: 1, time: 0.1 });
+        context.events.broadcast(GameEvent::DamageTaken {
+            ship_id,
+            damage_type: DamageType::Kinetic,
+            amount: 25.0,
+            hull_percent: 90.0,
+            shield_percent: 50.0,
+        });
+
+        let value = stream.next().await.unwrap().unwrap();
+        assert_eq!(string(&value, &["shipId"]), ship_id.to_string());
+        assert_eq!(string(&value, &["damageType"]), "KINETIC");
+        assert_eq!(
+            field(&value, &["amount"])
+                .as_scalar()
+                .and_then(|s| s.try_to_float()),
+            Some(25.0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_simulation_tick_subscription() {
+        use crate::events::GameEvent;
+        use futures::StreamExt;
+
+        let context = create_test_context();
+        let schema = schema();
+        let mut stream = subscribe(
+            "subscription { simulationTick { tick time } }",
+            &schema,
+            &context,
+        )
+        .await;
+
+        context
+            .events
+            .broadcast(GameEvent::SimulationTick { tick: 7, time: 0.7 });
+
+        let value = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            field(&value, &["tick"])
+                .as_scalar()
+                .and_then(|s| s.try_to_float()),
+            Some(7.0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscription_to_unknown_ship_fails() {
+        let context = create_test_context();
+        let schema = schema();
+        let query = format!(
+            r#"subscription {{ shipEvents(shipId: "{}") {{ type }} }}"#,
+            uuid::Uuid::new_v4()
+        );
+
+        let (_, errors) =
+            juniper::resolve_into_stream(&query, None, &schema, &Variables::new(), &context)
+                .await
+                .unwrap();
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_graphql_routes() {
+        let context = create_test_context();
+        let rocket = rocket::build()
+            .manage(context.world)
+            .manage(context.config)
+            .manage(context.universe)
+            .manage(context.events)
+            .manage(schema())
+            .mount("/graphql", routes());
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+
+        let response = client
+            .post("/graphql")
+            .header(ContentType::JSON)
+            .body(r#"{"query": "{ players { id } stations { id } }"}"#)
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let body: serde_json::Value = response.into_json().unwrap();
+        assert_eq!(body["data"]["players"], serde_json::json!([]));
+
+        let response = client.get("/graphql/graphiql").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.into_string().unwrap().contains("graphiql"));
+    }
+}
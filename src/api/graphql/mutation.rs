@@ -0,0 +1,384 @@
+//! GraphQL mutation root
+//!
+//! Mirrors the REST player/team, blueprint, compile, and station endpoints,
+//! applying the same checks as their request guards to the context's player.
+//! Failures surface as GraphQL field errors carrying the same messages the
+//! `GameWorld` returns to the REST handlers.
+
+use juniper::{FieldResult, graphql_object};
+use uuid::Uuid;
+
+use super::Context;
+use super::types::*;
+use crate::api::auth::{require_self, require_team_admission, require_team_member};
+use crate::api::stations::{process_service, request_docking_at};
+use crate::compiler;
+use crate::models::Player;
+use crate::models::blueprint::ModuleInstance;
+use crate::models::role::ShipRole;
+use crate::state::GameWorld;
+use crate::stations::{ServiceRequest, Station};
+
+/// Root mutation type
+pub struct Mutation;
+
+fn parse_uuid(id: &str) -> FieldResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| format!("Invalid ID: {}", id).into())
+}
+
+#[graphql_object(context = Context)]
+impl Mutation {
+    // ==================== Players & Teams ====================
+
+    /// Register a new player, returning their session token
+    fn create_player(context: &Context, name: String) -> FieldResult<PlayerRegistration> {
+        let mut world = context.world.write().unwrap();
+        let player_id = world.register_player(name)?;
+        let token = world.issue_player_token(&player_id)?;
+        let player = world.get_player(&player_id).unwrap();
+        Ok(PlayerRegistration {
+            id: player.id.clone(),
+            name: player.name.clone(),
+            token,
+        })
+    }
+
+    /// Create a team with the configured starting credits, optionally
+    /// adding the authenticated caller as its first member
+    fn create_team(
+        context: &Context,
+        name: String,
+        faction: String,
+        player_id: Option<String>,
+    ) -> FieldResult<TeamObject> {
+        if !context
+            .config
+            .factions
+            .factions
+            .iter()
+            .any(|f| f.id == faction)
+        {
+            return Err(format!(
+                "Invalid faction_id: '{}' not found in configuration",
+                faction
+            )
+            .into());
+        }
+
+        let mut world = context.world.write().unwrap();
+        if let Some(player_id) = &player_id {
+            require_self(context.authenticated()?, player_id)?;
+            if world.get_player(player_id).is_none() {
+                return Err(format!("Player '{}' not found", player_id).into());
+            }
+        }
+
+        let starting_credits = context.config.game_settings.team_starting_credits;
+        let team_id = world.create_team_with_credits(name, faction, starting_credits)?;
+        if let Some(player_id) = &player_id {
+            world.add_player_to_team(&team_id, player_id)?;
+        }
+        Ok(TeamObject::from(world.get_team(&team_id).unwrap()))
+    }
+
+    /// Add a player to a team; members may add anyone, others may only
+    /// found an empty team by joining it themselves
+    fn join_team(context: &Context, team_id: String, player_id: String) -> FieldResult<TeamObject> {
+        let player = context.authenticated()?;
+        let mut world = context.world.write().unwrap();
+        require_team_admission(&world, &team_id, player, &player_id)?;
+        world.add_player_to_team(&team_id, &player_id)?;
+        Ok(TeamObject::from(world.get_team(&team_id).unwrap()))
+    }
+
+    // ==================== Blueprints ====================
+
+    /// Create a new ship blueprint
+    fn create_blueprint(
+        context: &Context,
+        name: String,
+        ship_class: String,
+        team_id: String,
+    ) -> FieldResult<BlueprintObject> {
+        if name.is_empty() || name.len() > 50 {
+            return Err("Ship name must be 1-50 characters".into());
+        }
+
+        let mut world = context.world.write().unwrap();
+        let blueprint_id = world.create_blueprint(name, ship_class, team_id)?;
+        Ok(BlueprintObject::from(
+            world.get_blueprint(&blueprint_id).unwrap(),
+        ))
+    }
+
+    /// Join a blueprint's crew with no roles; the caller may only join
+    /// their own team's blueprints themselves
+    fn join_blueprint(
+        context: &Context,
+        blueprint_id: String,
+        player_id: String,
+    ) -> FieldResult<BlueprintObject> {
+        let player = context.authenticated()?;
+        require_self(player, &player_id)?;
+        Self::edit_blueprint(context, &blueprint_id, |world, blueprint_id| {
+            let team_id = &world.get_blueprint(blueprint_id).unwrap().team_id;
+            require_team_member(world, team_id, player).map_err(|error| error.message)?;
+            let blueprint = world.get_blueprint_mut(blueprint_id).unwrap();
+            blueprint.set_player_roles(player_id.clone(), vec![]);
+            Ok(())
+        })
+    }
+
+    /// Set a team member's roles on a blueprint, joining it if necessary;
+    /// the caller must be on the blueprint's team
+    fn update_roles(
+        context: &Context,
+        blueprint_id: String,
+        player_id: String,
+        roles: Vec<ShipRole>,
+    ) -> FieldResult<BlueprintObject> {
+        let player = context.authenticated()?;
+        Self::edit_blueprint(context, &blueprint_id, |world, blueprint_id| {
+            if world.get_player(&player_id).is_none() {
+                return Err(format!("Player {} not found", player_id));
+            }
+            let team_id = &world.get_blueprint(blueprint_id).unwrap().team_id;
+            require_team_member(world, team_id, player).map_err(|error| error.message)?;
+            if !world.is_team_member(team_id, &player_id) {
+                return Err(format!(
+                    "Player {} is not a member of team {}",
+                    player_id, team_id
+                ));
+            }
+            let blueprint = world.get_blueprint_mut(blueprint_id).unwrap();
+            blueprint.set_player_roles(player_id.clone(), roles.clone());
+            Ok(())
+        })
+    }
+
+    /// Add a module slot to a blueprint
+    fn add_module(
+        context: &Context,
+        blueprint_id: String,
+        module_slot_id: String,
+        variant_id: Option<String>,
+    ) -> FieldResult<BlueprintObject> {
+        Self::edit_blueprint(context, &blueprint_id, |world, blueprint_id| {
+            let blueprint = world.get_blueprint_mut(blueprint_id).unwrap();
+            blueprint.modules.push(ModuleInstance {
+                id: Uuid::new_v4().to_string(),
+                module_slot_id: module_slot_id.clone(),
+                variant_id: variant_id.clone(),
+            });
+            Ok(())
+        })
+    }
+
+    /// Remove a module from a blueprint
+    fn remove_module(
+        context: &Context,
+        blueprint_id: String,
+        module_id: String,
+    ) -> FieldResult<BlueprintObject> {
+        Self::edit_blueprint(context, &blueprint_id, |world, blueprint_id| {
+            let blueprint = world.get_blueprint_mut(blueprint_id).unwrap();
+            let initial_len = blueprint.modules.len();
+            blueprint.modules.retain(|m| m.id != module_id);
+            if blueprint.modules.len() == initial_len {
+                return Err(format!("Module {} not found", module_id));
+            }
+            Ok(())
+        })
+    }
+
+    /// Select the variant of a blueprint module
+    fn configure_module(
+        context: &Context,
+        blueprint_id: String,
+        module_id: String,
+        variant_id: String,
+    ) -> FieldResult<BlueprintObject> {
+        Self::edit_blueprint(context, &blueprint_id, |world, blueprint_id| {
+            let blueprint = world.get_blueprint_mut(blueprint_id).unwrap();
+            let module = blueprint
+                .modules
+                .iter_mut()
+                .find(|m| m.id == module_id)
+                .ok_or_else(|| format!("Module {} not found", module_id))?;
+            module.variant_id = Some(variant_id.clone());
+            Ok(())
+        })
+    }
+
+    /// Mark the calling crew member as ready
+    fn mark_ready(
+        context: &Context,
+        blueprint_id: String,
+        player_id: String,
+    ) -> FieldResult<BlueprintObject> {
+        require_self(context.authenticated()?, &player_id)?;
+        Self::edit_blueprint(context, &blueprint_id, |world, blueprint_id| {
+            let blueprint = world.get_blueprint_mut(blueprint_id).unwrap();
+            if !blueprint.player_roles.contains_key(&player_id) {
+                return Err(format!(
+                    "Player {} is not part of this blueprint",
+                    player_id
+                ));
+            }
+            blueprint.mark_ready(player_id.clone());
+            Ok(())
+        })
+    }
+
+    /// Clear the calling crew member's ready flag
+    fn unmark_ready(
+        context: &Context,
+        blueprint_id: String,
+        player_id: String,
+    ) -> FieldResult<BlueprintObject> {
+        require_self(context.authenticated()?, &player_id)?;
+        Self::edit_blueprint(context, &blueprint_id, |world, blueprint_id| {
+            let blueprint = world.get_blueprint_mut(blueprint_id).unwrap();
+            blueprint.unmark_ready(&player_id);
+            Ok(())
+        })
+    }
+
+    // ==================== Ships ====================
+
+    /// Compile a blueprint into an active ship, spending the credits of the
+    /// blueprint's team; requires an authenticated member of that team
+    fn compile_ship(context: &Context, blueprint_id: String) -> FieldResult<ShipObject> {
+        let player = context.authenticated()?;
+        let mut world = context.world.write().unwrap();
+        let team_id = world
+            .get_blueprint(&blueprint_id)
+            .ok_or_else(|| format!("Blueprint {} not found", blueprint_id))?
+            .team_id
+            .clone();
+        if !world.is_team_member(&team_id, &player.id) {
+            return Err(format!("Player {} is not a member of team {}", player.id, team_id).into());
+        }
+        let ship_id = compiler::compile_and_spawn(&blueprint_id, &mut world, &context.config)?;
+        Ok(ShipObject::from(world.get_ship(&ship_id).unwrap()))
+    }
+
+    // ==================== Stations ====================
+
+    /// Create a new station
+    fn create_station(context: &Context, input: CreateStationInput) -> FieldResult<StationObject> {
+        let position: [f64; 3] = input
+            .position
+            .try_into()
+            .map_err(|_| "Position must have exactly 3 components")?;
+        let station = match input.size {
+            Some(size) => Station::with_size(input.name, position, input.faction, size),
+            None => Station::new(input.name, position, input.faction),
+        };
+
+        let response = StationObject::from(&station);
+        context.world.write().unwrap().register_station(station);
+        Ok(response)
+    }
+
+    /// Delete a station; requires the server operator
+    fn delete_station(context: &Context, id: String) -> FieldResult<bool> {
+        context.require_operator()?;
+        context.world.write().unwrap().remove_station(&id)?;
+        Ok(true)
+    }
+
+    /// Request docking clearance at a station for the faction of the ship's
+    /// team; requires an authenticated member of that team
+    fn request_docking(
+        context: &Context,
+        station_id: String,
+        ship_id: String,
+    ) -> FieldResult<DockingResult> {
+        let player = context.authenticated()?;
+        let ship_id = parse_uuid(&ship_id)?;
+        let mut world = context.world.write().unwrap();
+        Self::require_ship_team(&world, ship_id, player)?;
+        let response = request_docking_at(&mut world, &station_id, ship_id)
+            .ok_or_else(|| format!("Station {} not found", station_id))?;
+        Ok(response.into())
+    }
+
+    /// Complete docking once an approved ship is inside final range and
+    /// slow enough; requires an authenticated member of the ship's team
+    fn complete_docking(
+        context: &Context,
+        station_id: String,
+        ship_id: String,
+    ) -> FieldResult<bool> {
+        let player = context.authenticated()?;
+        let ship_id = parse_uuid(&ship_id)?;
+        let mut world = context.world.write().unwrap();
+        Self::require_ship_team(&world, ship_id, player)?;
+        world.complete_docking(&ship_id.to_string(), &station_id)?;
+        Ok(true)
+    }
+
+    /// Undock a ship from a station; requires an authenticated member of
+    /// the ship's team
+    fn undock(context: &Context, station_id: String, ship_id: String) -> FieldResult<bool> {
+        let player = context.authenticated()?;
+        let ship_id = parse_uuid(&ship_id)?;
+        let mut world = context.world.write().unwrap();
+        Self::require_ship_team(&world, ship_id, player)?;
+        let station = world
+            .get_station_mut(&station_id)
+            .ok_or_else(|| format!("Station {} not found", station_id))?;
+        if !station.undock_ship(ship_id) {
+            return Err("Ship is not docked at this station".into());
+        }
+        Ok(true)
+    }
+
+    /// Request a service for a docked ship, billed to the ship's team;
+    /// requires an authenticated member of that team
+    fn request_service(
+        context: &Context,
+        station_id: String,
+        ship_id: String,
+        service: ServiceInput,
+    ) -> FieldResult<ServiceResult> {
+        let player = context.authenticated()?;
+        let ship_id = parse_uuid(&ship_id)?;
+        let request = ServiceRequest::try_from(service)?;
+        let mut world = context.world.write().unwrap();
+        if world.get_station(&station_id).is_none() {
+            return Err(format!("Station {} not found", station_id).into());
+        }
+        Self::require_ship_team(&world, ship_id, player)?;
+        Ok(process_service(&mut world, &station_id, ship_id, request, &context.config).into())
+    }
+}
+
+impl Mutation {
+    /// Fail unless the player is a member of the team flying a ship
+    fn require_ship_team(world: &GameWorld, ship_id: Uuid, player: &Player) -> FieldResult<()> {
+        let team_id = &world
+            .get_ship(&ship_id.to_string())
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?
+            .team_id;
+        require_team_member(world, team_id, player)?;
+        Ok(())
+    }
+
+    /// Apply an edit to a blueprint under the write lock and return its new state
+    fn edit_blueprint(
+        context: &Context,
+        blueprint_id: &str,
+        edit: impl FnOnce(&mut GameWorld, &str) -> Result<(), String>,
+    ) -> FieldResult<BlueprintObject> {
+        let mut world = context.world.write().unwrap();
+        if world.get_blueprint(blueprint_id).is_none() {
+            return Err(format!("Blueprint {} not found", blueprint_id).into());
+        }
+        edit(&mut world, blueprint_id)?;
+        Ok(BlueprintObject::from(
+            world.get_blueprint(blueprint_id).unwrap(),
+        ))
+    }
+}
@@ -0,0 +1,218 @@
+//! GraphQL query root
+
+use juniper::{FieldResult, graphql_object};
+
+use super::Context;
+use super::types::*;
+use crate::blueprint::BlueprintValidator;
+
+/// Root query type
+pub struct Query;
+
+#[graphql_object(context = Context)]
+impl Query {
+    /// API version
+    fn api_version() -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    // ==================== Players & Teams ====================
+
+    /// All registered players
+    fn players(context: &Context) -> Vec<PlayerObject> {
+        let world = context.world.read().unwrap();
+        world
+            .get_all_players()
+            .into_iter()
+            .map(PlayerObject::from)
+            .collect()
+    }
+
+    /// A player by ID
+    fn player(context: &Context, id: String) -> Option<PlayerObject> {
+        let world = context.world.read().unwrap();
+        world.get_player(&id).map(PlayerObject::from)
+    }
+
+    /// All teams
+    fn teams(context: &Context) -> Vec<TeamObject> {
+        let world = context.world.read().unwrap();
+        world
+            .get_all_teams()
+            .into_iter()
+            .map(TeamObject::from)
+            .collect()
+    }
+
+    /// A team by ID
+    fn team(context: &Context, id: String) -> Option<TeamObject> {
+        let world = context.world.read().unwrap();
+        world.get_team(&id).map(TeamObject::from)
+    }
+
+    // ==================== Blueprints & Ships ====================
+
+    /// All blueprints, optionally limited to one team
+    fn blueprints(context: &Context, team_id: Option<String>) -> Vec<BlueprintObject> {
+        let world = context.world.read().unwrap();
+        let blueprints = match &team_id {
+            Some(team_id) => world.get_team_blueprints(team_id),
+            None => world.get_all_blueprints(),
+        };
+        blueprints.into_iter().map(BlueprintObject::from).collect()
+    }
+
+    /// A blueprint by ID
+    fn blueprint(context: &Context, id: String) -> Option<BlueprintObject> {
+        let world = context.world.read().unwrap();
+        world.get_blueprint(&id).map(BlueprintObject::from)
+    }
+
+    /// Validate a blueprint against the catalog and crew rules
+    fn validate_blueprint(context: &Context, id: String) -> Option<BlueprintValidation> {
+        let world = context.world.read().unwrap();
+        let blueprint = world.get_blueprint(&id)?;
+        let validator = BlueprintValidator::new(&context.config, world.players(), world.teams());
+        let result = validator.validate(blueprint);
+
+        Some(BlueprintValidation {
+            valid: result.is_valid,
+            errors: result.errors.iter().map(|e| format!("{:?}", e)).collect(),
+            warnings: result.warnings.iter().map(|w| format!("{:?}", w)).collect(),
+        })
+    }
+
+    /// Active ships of the caller's teams, optionally limited to one team
+    ///
+    /// Other teams' ships are only seen through a ship's sensors and scans.
+    fn ships(context: &Context, team_id: Option<String>) -> FieldResult<Vec<ShipObject>> {
+        let player = context.authenticated()?;
+        let world = context.world.read().unwrap();
+        let ships = match &team_id {
+            Some(team_id) => world.get_team_ships(team_id),
+            None => world.get_all_ships(),
+        };
+        Ok(ships
+            .into_iter()
+            .filter(|ship| world.is_team_member(&ship.team_id, &player.id))
+            .map(ShipObject::from)
+            .collect())
+    }
+
+    /// One of the caller's teams' ships by ID
+    ///
+    /// Other teams' ships are only revealed through scan reports.
+    fn ship(context: &Context, id: String) -> FieldResult<Option<ShipObject>> {
+        let world = context.world.read().unwrap();
+        let Some(ship) = world.get_ship(&id) else {
+            return Ok(None);
+        };
+        let player = context.authenticated()?;
+        if !world.is_team_member(&ship.team_id, &player.id) {
+            return Err(format!(
+                "Player {} is not a member of team {}",
+                player.id, ship.team_id
+            )
+            .into());
+        }
+        Ok(Some(ShipObject::from(ship)))
+    }
+
+    // ==================== Stations ====================
+
+    /// All stations
+    fn stations(context: &Context) -> Vec<StationObject> {
+        let world = context.world.read().unwrap();
+        world
+            .get_all_stations()
+            .into_iter()
+            .map(StationObject::from)
+            .collect()
+    }
+
+    /// A station by ID
+    fn station(context: &Context, id: String) -> Option<StationObject> {
+        let world = context.world.read().unwrap();
+        world.get_station(&id).map(StationObject::from)
+    }
+
+    // ==================== Catalog ====================
+
+    /// All module slot types
+    fn module_slots(context: &Context) -> Vec<ModuleSlotObject> {
+        let mut slots: Vec<ModuleSlotObject> = context
+            .config
+            .module_slots
+            .values()
+            .map(ModuleSlotObject::from)
+            .collect();
+        slots.sort_by(|a, b| a.id.cmp(&b.id));
+        slots
+    }
+
+    /// A module slot type by ID
+    fn module_slot(context: &Context, id: String) -> Option<ModuleSlotObject> {
+        context
+            .config
+            .module_slots
+            .get(&id)
+            .map(ModuleSlotObject::from)
+    }
+
+    /// Module variants, optionally limited to one slot type
+    fn module_variants(context: &Context, slot_id: Option<String>) -> Vec<ModuleVariantObject> {
+        let mut variants: Vec<ModuleVariantObject> = context
+            .config
+            .module_variants
+            .iter()
+            .filter(|(slot, _)| slot_id.as_ref().is_none_or(|id| id == *slot))
+            .flat_map(|(_, variants)| variants.iter().map(ModuleVariantObject::from))
+            .collect();
+        variants.sort_by(|a, b| (&a.module_type, &a.id).cmp(&(&b.module_type, &b.id)));
+        variants
+    }
+
+    /// Ammunition types, optionally limited to one category
+    /// (`kinetic`, `missiles`, or `torpedos`)
+    fn ammunition(context: &Context, category: Option<String>) -> Vec<AmmunitionObject> {
+        context
+            .config
+            .ammunition_types
+            .iter()
+            .filter(|a| category.as_ref().is_none_or(|c| *c == a.category))
+            .map(AmmunitionObject::from)
+            .collect()
+    }
+
+    /// Ship classes, optionally limited to those a faction manufactures
+    fn ship_classes(context: &Context, faction: Option<String>) -> Vec<ShipClassObject> {
+        let mut classes: Vec<&crate::config::ShipClassConfig> = context
+            .config
+            .ship_classes
+            .iter()
+            .filter(|sc| {
+                faction
+                    .as_ref()
+                    .is_none_or(|f| sc.manufacturers.contains_key(f))
+            })
+            .collect();
+        classes.sort_by(|a, b| a.build_points.total_cmp(&b.build_points));
+        classes.into_iter().map(ShipClassObject::from).collect()
+    }
+
+    /// A ship class by ID
+    fn ship_class(context: &Context, id: String) -> Option<ShipClassObject> {
+        context
+            .config
+            .get_ship_class(&id)
+            .map(ShipClassObject::from)
+    }
+
+    // ==================== Procedural Universe ====================
+
+    /// The current procedural universe, if one has been generated
+    fn universe(context: &Context) -> Option<UniverseObject> {
+        let state = context.universe.read().unwrap();
+        state.universe.as_ref().map(UniverseObject::from)
+    }
+}
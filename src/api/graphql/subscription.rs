@@ -0,0 +1,150 @@
+//! GraphQL subscription root
+//!
+//! Each subscription taps the same `GameEvent` broadcast channel the raw
+//! `/ws` endpoint uses, via [`WebSocketManager::subscribe`], and filters it
+//! down to the events the field describes.
+
+use futures::{Stream, StreamExt, future};
+use juniper::{FieldError, FieldResult, GraphQLObject, graphql_subscription};
+use std::pin::Pin;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use super::Context;
+use crate::events::{DamageType, GameEvent};
+use crate::websocket::WebSocketManager;
+
+/// Boxed stream of subscription payloads
+pub type EventStream<T> = Pin<Box<dyn Stream<Item = Result<T, FieldError>> + Send>>;
+
+/// Any event concerning a single ship
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct ShipEvent {
+    /// Event type, e.g. `ship_moved` or `weapon_fired`
+    #[graphql(name = "type")]
+    pub event_type: String,
+    pub ship_id: String,
+    /// The full event as JSON, in the same shape `/ws` clients receive
+    pub payload: String,
+}
+
+impl From<&GameEvent> for ShipEvent {
+    fn from(event: &GameEvent) -> Self {
+        let payload = serde_json::to_value(event).unwrap_or_default();
+        Self {
+            event_type: payload["type"].as_str().unwrap_or_default().to_string(),
+            ship_id: event.ship_id().map(|id| id.to_string()).unwrap_or_default(),
+            payload: payload.to_string(),
+        }
+    }
+}
+
+/// Damage dealt to a ship
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct DamageTakenEvent {
+    pub ship_id: String,
+    pub damage_type: DamageType,
+    pub amount: f64,
+    pub hull_percent: f64,
+    pub shield_percent: f64,
+}
+
+/// A completed simulation tick
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct SimulationTickEvent {
+    pub tick: f64,
+    /// Simulation time in seconds
+    pub time: f64,
+}
+
+/// Root subscription type
+pub struct Subscription;
+
+#[graphql_subscription(context = Context)]
+impl Subscription {
+    /// Every event concerning a ship
+    async fn ship_events(
+        context: &Context,
+        ship_id: String,
+    ) -> FieldResult<EventStream<ShipEvent>> {
+        let ship_id = own_ship(context, &ship_id)?;
+        Ok(events(&context.events, move |event| {
+            (event.ship_id() == Some(ship_id)).then(|| ShipEvent::from(&event))
+        }))
+    }
+
+    /// Damage dealt to a ship
+    async fn damage_taken(
+        context: &Context,
+        ship_id: String,
+    ) -> FieldResult<EventStream<DamageTakenEvent>> {
+        let ship_id = own_ship(context, &ship_id)?;
+        Ok(events(&context.events, move |event| match event {
+            GameEvent::DamageTaken {
+                ship_id: id,
+                damage_type,
+                amount,
+                hull_percent,
+                shield_percent,
+            } if id == ship_id => Some(DamageTakenEvent {
+                ship_id: id.to_string(),
+                damage_type,
+                amount: amount as f64,
+                hull_percent: hull_percent as f64,
+                shield_percent: shield_percent as f64,
+            }),
+            _ => None,
+        }))
+    }
+
+    /// Simulation tick heartbeat
+    async fn simulation_tick(context: &Context) -> EventStream<SimulationTickEvent> {
+        events(&context.events, |event| match event {
+            GameEvent::SimulationTick { tick, time } => Some(SimulationTickEvent {
+                tick: tick as f64,
+                time,
+            }),
+            _ => None,
+        })
+    }
+}
+
+/// Parse a ship ID and check that the ship exists and flies for one of the
+/// caller's teams
+fn own_ship(context: &Context, ship_id: &str) -> FieldResult<Uuid> {
+    let player = context.authenticated()?;
+    let id = Uuid::parse_str(ship_id).map_err(|_| format!("Invalid ship ID: {}", ship_id))?;
+    let world = context.world.read().unwrap();
+    let ship = world
+        .get_ship(ship_id)
+        .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+    if !world.is_team_member(&ship.team_id, &player.id) {
+        return Err(format!(
+            "Player {} is not a member of team {}",
+            player.id, ship.team_id
+        )
+        .into());
+    }
+    Ok(id)
+}
+
+/// Stream broadcast events through `select`, dropping those it rejects
+///
+/// A subscriber that falls behind skips the events it missed rather than
+/// ending the subscription.
+fn events<T, F>(manager: &WebSocketManager, select: F) -> EventStream<T>
+where
+    T: Send + 'static,
+    F: Fn(GameEvent) -> Option<T> + Send + 'static,
+{
+    let stream = futures::stream::unfold(manager.subscribe(), |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) => return Some((event, rx)),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    Box::pin(stream.filter_map(move |event| future::ready(select(event).map(Ok))))
+}
@@ -0,0 +1,741 @@
+//! GraphQL object types
+//!
+//! Owned snapshots of game state built while the world lock is held. GraphQL
+//! has no unsigned or 64-bit integer scalars, so counts become `i32` and
+//! credits, costs, and other large or fractional values become `f64`.
+
+use juniper::{GraphQLEnum, GraphQLInputObject, GraphQLObject};
+
+use crate::api::stations::DockingResponse;
+use crate::config::{AmmunitionConfig, ModuleSlot, ModuleVariant, ShipClassConfig};
+use crate::generation::{HistoricalEvent, ProceduralFaction, ProceduralUniverse, Star, StarSystem};
+use crate::models::role::ShipRole;
+use crate::models::{Player, Ship, ShipBlueprint, Team};
+use crate::stations::{
+    DockingStatus, ServiceRequest, ServiceResponse, Station, StationServices, StationSize,
+};
+use std::collections::HashMap;
+
+/// A registered player
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "Player")]
+pub struct PlayerObject {
+    pub id: String,
+    pub name: String,
+}
+
+impl From<&Player> for PlayerObject {
+    fn from(player: &Player) -> Self {
+        Self {
+            id: player.id.clone(),
+            name: player.name.clone(),
+        }
+    }
+}
+
+/// A newly registered player and their session token
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct PlayerRegistration {
+    pub id: String,
+    pub name: String,
+    /// Secret session token, sent back as `Authorization: Bearer <token>`
+    pub token: String,
+}
+
+/// A team of players sharing a credit balance
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "Team")]
+pub struct TeamObject {
+    pub id: String,
+    pub name: String,
+    pub faction: String,
+    pub members: Vec<String>,
+    pub credits: f64,
+}
+
+impl From<&Team> for TeamObject {
+    fn from(team: &Team) -> Self {
+        Self {
+            id: team.id.clone(),
+            name: team.name.clone(),
+            faction: team.faction.clone(),
+            members: team.members.clone(),
+            credits: team.credits as f64,
+        }
+    }
+}
+
+/// A player's roles aboard a blueprint or ship
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct CrewMember {
+    pub player_id: String,
+    pub roles: Vec<ShipRole>,
+}
+
+fn crew(player_roles: &HashMap<String, Vec<ShipRole>>) -> Vec<CrewMember> {
+    let mut crew: Vec<CrewMember> = player_roles
+        .iter()
+        .map(|(player_id, roles)| CrewMember {
+            player_id: player_id.clone(),
+            roles: roles.clone(),
+        })
+        .collect();
+    crew.sort_by(|a, b| a.player_id.cmp(&b.player_id));
+    crew
+}
+
+/// A module placed on a blueprint
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct BlueprintModule {
+    pub id: String,
+    pub module_slot_id: String,
+    pub variant_id: Option<String>,
+}
+
+/// A weapon placed on a blueprint or ship
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct WeaponMount {
+    pub id: String,
+    pub weapon_id: String,
+    pub kind: Option<String>,
+    pub loaded_ammunition: Option<String>,
+}
+
+/// A ship design being assembled by a team
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "Blueprint")]
+pub struct BlueprintObject {
+    pub id: String,
+    pub name: String,
+    pub class: String,
+    pub team_id: String,
+    pub crew: Vec<CrewMember>,
+    pub modules: Vec<BlueprintModule>,
+    pub weapons: Vec<WeaponMount>,
+    pub ready_players: Vec<String>,
+    pub all_ready: bool,
+}
+
+impl From<&ShipBlueprint> for BlueprintObject {
+    fn from(blueprint: &ShipBlueprint) -> Self {
+        let mut ready_players: Vec<String> = blueprint.ready_players.iter().cloned().collect();
+        ready_players.sort();
+        Self {
+            id: blueprint.id.clone(),
+            name: blueprint.name.clone(),
+            class: blueprint.class.clone(),
+            team_id: blueprint.team_id.clone(),
+            crew: crew(&blueprint.player_roles),
+            modules: blueprint
+                .modules
+                .iter()
+                .map(|m| BlueprintModule {
+                    id: m.id.clone(),
+                    module_slot_id: m.module_slot_id.clone(),
+                    variant_id: m.variant_id.clone(),
+                })
+                .collect(),
+            weapons: blueprint
+                .weapons
+                .iter()
+                .map(|w| WeaponMount {
+                    id: w.id.clone(),
+                    weapon_id: w.weapon_id.clone(),
+                    kind: w.kind.clone(),
+                    loaded_ammunition: w.loaded_ammunition.clone(),
+                })
+                .collect(),
+            ready_players,
+            all_ready: blueprint.all_players_ready(),
+        }
+    }
+}
+
+/// Result of validating a blueprint
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct BlueprintValidation {
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// A compiled module installed on a ship
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct ShipModule {
+    pub instance_id: String,
+    pub module_id: String,
+    pub kind: Option<String>,
+    pub name: String,
+    pub health: f64,
+    pub max_health: f64,
+    pub operational: bool,
+}
+
+/// A quantity of one item in a ship's hold
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct InventoryItem {
+    pub item_id: String,
+    pub quantity: i32,
+}
+
+fn inventory_items(items: &HashMap<String, u32>) -> Vec<InventoryItem> {
+    let mut items: Vec<InventoryItem> = items
+        .iter()
+        .map(|(item_id, quantity)| InventoryItem {
+            item_id: item_id.clone(),
+            quantity: *quantity as i32,
+        })
+        .collect();
+    items.sort_by(|a, b| a.item_id.cmp(&b.item_id));
+    items
+}
+
+/// An active ship compiled from a blueprint
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "Ship")]
+pub struct ShipObject {
+    pub id: String,
+    pub name: String,
+    pub class: String,
+    pub team_id: String,
+    pub crew: Vec<CrewMember>,
+    pub hull: f64,
+    pub max_hull: f64,
+    pub shields: f64,
+    pub max_shields: f64,
+    pub shields_raised: bool,
+    pub power_generation: f64,
+    pub power_capacity: f64,
+    pub position: Vec<f64>,
+    pub velocity: Vec<f64>,
+    pub modules: Vec<ShipModule>,
+    pub weapons: Vec<WeaponMount>,
+    pub ammunition: Vec<InventoryItem>,
+    pub cargo: Vec<InventoryItem>,
+}
+
+impl From<&Ship> for ShipObject {
+    fn from(ship: &Ship) -> Self {
+        let status = &ship.status;
+        Self {
+            id: ship.id.clone(),
+            name: ship.name.clone(),
+            class: ship.class.clone(),
+            team_id: ship.team_id.clone(),
+            crew: crew(&ship.player_roles),
+            hull: status.hull as f64,
+            max_hull: status.max_hull as f64,
+            shields: status.shields as f64,
+            max_shields: status.max_shields as f64,
+            shields_raised: status.shields_raised,
+            power_generation: status.power_generation as f64,
+            power_capacity: status.power_capacity as f64,
+            position: status.position.to_vec(),
+            velocity: status.velocity.to_vec(),
+            modules: ship
+                .modules
+                .iter()
+                .map(|m| ShipModule {
+                    instance_id: m.instance_id.clone(),
+                    module_id: m.module_id.clone(),
+                    kind: m.kind.clone(),
+                    name: m.name.clone(),
+                    health: m.current_health as f64,
+                    max_health: m.max_health as f64,
+                    operational: m.operational,
+                })
+                .collect(),
+            weapons: ship
+                .weapons
+                .iter()
+                .map(|w| WeaponMount {
+                    id: w.id.clone(),
+                    weapon_id: w.weapon_id.clone(),
+                    kind: w.kind.clone(),
+                    loaded_ammunition: w.loaded_ammunition.clone(),
+                })
+                .collect(),
+            ammunition: inventory_items(&ship.inventory.ammunition),
+            cargo: inventory_items(&ship.inventory.cargo),
+        }
+    }
+}
+
+/// Services offered by a station
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "StationServices")]
+pub struct StationServicesObject {
+    pub repair: bool,
+    pub refuel: bool,
+    pub rearm: bool,
+    pub trade: bool,
+    pub repair_cost: f64,
+    pub refuel_cost: f64,
+    pub rearm_cost: f64,
+}
+
+impl From<&StationServices> for StationServicesObject {
+    fn from(services: &StationServices) -> Self {
+        Self {
+            repair: services.repair,
+            refuel: services.refuel,
+            rearm: services.rearm,
+            trade: services.trade,
+            repair_cost: services.repair_cost as f64,
+            refuel_cost: services.refuel_cost as f64,
+            rearm_cost: services.rearm_cost as f64,
+        }
+    }
+}
+
+/// A space station ships can dock at
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "Station")]
+pub struct StationObject {
+    pub id: String,
+    pub name: String,
+    pub position: Vec<f64>,
+    pub faction: String,
+    pub size: StationSize,
+    pub max_docked_ships: i32,
+    pub docked_ships: Vec<String>,
+    pub available_bays: i32,
+    pub hostile_factions: Vec<String>,
+    pub services: StationServicesObject,
+}
+
+impl From<&Station> for StationObject {
+    fn from(station: &Station) -> Self {
+        Self {
+            id: station.id.to_string(),
+            name: station.name.clone(),
+            position: station.position.to_vec(),
+            faction: station.faction.clone(),
+            size: station.size,
+            max_docked_ships: station.max_docked_ships as i32,
+            docked_ships: station
+                .docked_ships
+                .iter()
+                .map(|id| id.to_string())
+                .collect(),
+            available_bays: station.available_docking_bays() as i32,
+            hostile_factions: station.hostile_factions.clone(),
+            services: StationServicesObject::from(&station.services),
+        }
+    }
+}
+
+/// Input for creating a station
+#[derive(Debug, Clone, GraphQLInputObject)]
+pub struct CreateStationInput {
+    pub name: String,
+    pub position: Vec<f64>,
+    pub faction: String,
+    pub size: Option<StationSize>,
+}
+
+/// Outcome of a docking request
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct DockingResult {
+    pub approved: bool,
+    pub status: Option<DockingStatus>,
+    pub message: String,
+}
+
+impl From<DockingResponse> for DockingResult {
+    fn from(response: DockingResponse) -> Self {
+        Self {
+            approved: response.approved,
+            status: response.status,
+            message: response.message,
+        }
+    }
+}
+
+/// Kind of station service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GraphQLEnum)]
+pub enum ServiceKind {
+    RepairModule,
+    RepairAll,
+    Refuel,
+    RearmWeapon,
+    RearmAll,
+}
+
+/// Input for requesting a station service
+///
+/// `moduleId` is required for `REPAIR_MODULE`, `amount` for `REFUEL`, and
+/// `weaponId`, `ammunitionType`, and `quantity` for `REARM_WEAPON`.
+#[derive(Debug, Clone, GraphQLInputObject)]
+pub struct ServiceInput {
+    pub kind: ServiceKind,
+    pub module_id: Option<String>,
+    pub amount: Option<f64>,
+    pub weapon_id: Option<String>,
+    pub ammunition_type: Option<String>,
+    pub quantity: Option<i32>,
+}
+
+impl TryFrom<ServiceInput> for ServiceRequest {
+    type Error = String;
+
+    fn try_from(input: ServiceInput) -> Result<Self, Self::Error> {
+        fn required<T>(value: Option<T>, name: &str) -> Result<T, String> {
+            value.ok_or_else(|| format!("'{}' is required for this service", name))
+        }
+
+        Ok(match input.kind {
+            ServiceKind::RepairModule => ServiceRequest::RepairModule {
+                module_id: required(input.module_id, "moduleId")?,
+            },
+            ServiceKind::RepairAll => ServiceRequest::RepairAll,
+            ServiceKind::Refuel => ServiceRequest::Refuel {
+                amount: required(input.amount, "amount")? as f32,
+            },
+            ServiceKind::RearmWeapon => ServiceRequest::RearmWeapon {
+                weapon_id: required(input.weapon_id, "weaponId")?,
+                ammunition_type: required(input.ammunition_type, "ammunitionType")?,
+                quantity: u32::try_from(required(input.quantity, "quantity")?)
+                    .map_err(|_| "'quantity' must not be negative".to_string())?,
+            },
+            ServiceKind::RearmAll => ServiceRequest::RearmAll,
+        })
+    }
+}
+
+/// Outcome of a station service request
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct ServiceResult {
+    pub success: bool,
+    pub message: String,
+    pub cost: f64,
+}
+
+impl From<ServiceResponse> for ServiceResult {
+    fn from(response: ServiceResponse) -> Self {
+        Self {
+            success: response.success,
+            message: response.message,
+            cost: response.cost as f64,
+        }
+    }
+}
+
+/// A module slot type from the catalog
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "ModuleSlot")]
+pub struct ModuleSlotObject {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub groups: Vec<String>,
+    pub required: bool,
+    pub has_variants: bool,
+    pub base_cost: i32,
+    pub credit_cost: f64,
+    pub max_slots: i32,
+    pub base_hp: i32,
+    pub base_power_consumption: f64,
+    pub base_heat_generation: f64,
+    pub base_weight: i32,
+}
+
+impl From<&ModuleSlot> for ModuleSlotObject {
+    fn from(slot: &ModuleSlot) -> Self {
+        Self {
+            id: slot.id.clone(),
+            name: slot.name.clone(),
+            description: slot.description.clone(),
+            groups: slot.groups.clone(),
+            required: slot.required,
+            has_variants: slot.has_varients,
+            base_cost: slot.base_cost,
+            credit_cost: slot.credit_cost as f64,
+            max_slots: slot.max_slots,
+            base_hp: slot.base_hp,
+            base_power_consumption: slot.base_power_consumption as f64,
+            base_heat_generation: slot.base_heat_generation as f64,
+            base_weight: slot.base_weight,
+        }
+    }
+}
+
+/// A module variant from the catalog
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "ModuleVariant")]
+pub struct ModuleVariantObject {
+    pub id: String,
+    pub module_type: String,
+    pub name: String,
+    pub model: String,
+    pub manufacturer: String,
+    pub description: String,
+    pub cost: i32,
+    pub credit_cost: f64,
+    pub additional_hp: i32,
+    pub additional_power_consumption: f64,
+    pub additional_heat_generation: f64,
+    pub additional_weight: i32,
+    /// Type-specific stats as a JSON object
+    pub stats: String,
+}
+
+impl From<&ModuleVariant> for ModuleVariantObject {
+    fn from(variant: &ModuleVariant) -> Self {
+        Self {
+            id: variant.id.clone(),
+            module_type: variant.module_type.clone(),
+            name: variant.name.clone(),
+            model: variant.model.clone(),
+            manufacturer: variant.manufacturer.clone(),
+            description: variant.description.clone(),
+            cost: variant.cost,
+            credit_cost: variant.credit_cost as f64,
+            additional_hp: variant.additional_hp,
+            additional_power_consumption: variant.additional_power_consumption as f64,
+            additional_heat_generation: variant.additional_heat_generation as f64,
+            additional_weight: variant.additional_weight,
+            stats: serde_json::to_string(&variant.stats.stats).unwrap_or_default(),
+        }
+    }
+}
+
+/// An ammunition type from the catalog
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "Ammunition")]
+pub struct AmmunitionObject {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    pub cost: f64,
+    pub weight: f64,
+    pub impact_damage: f64,
+    pub blast_radius: f64,
+    pub blast_damage: f64,
+    pub velocity: f64,
+    pub acceleration: f64,
+    pub max_speed: f64,
+    pub max_turn_rate: f64,
+    pub lifetime: f64,
+    pub weapon_tags: Vec<String>,
+}
+
+impl From<&AmmunitionConfig> for AmmunitionObject {
+    fn from(ammo: &AmmunitionConfig) -> Self {
+        Self {
+            id: ammo.id.clone(),
+            name: ammo.name.clone(),
+            description: ammo.description.clone(),
+            category: ammo.category.clone(),
+            cost: ammo.cost as f64,
+            weight: ammo.weight as f64,
+            impact_damage: ammo.impact_damage as f64,
+            blast_radius: ammo.blast_radius as f64,
+            blast_damage: ammo.blast_damage as f64,
+            velocity: ammo.velocity as f64,
+            acceleration: ammo.acceleration as f64,
+            max_speed: ammo.max_speed as f64,
+            max_turn_rate: ammo.max_turn_rate as f64,
+            lifetime: ammo.lifetime as f64,
+            weapon_tags: ammo.weapon_tags.clone(),
+        }
+    }
+}
+
+/// A ship class from the catalog
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "ShipClass")]
+pub struct ShipClassObject {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub size: String,
+    pub role: String,
+    pub max_weight: f64,
+    pub max_modules: i32,
+    pub base_hull: f64,
+    pub base_shields: f64,
+    pub build_points: f64,
+    pub cost: f64,
+    pub manufacturers: Vec<String>,
+}
+
+impl From<&ShipClassConfig> for ShipClassObject {
+    fn from(class: &ShipClassConfig) -> Self {
+        let mut manufacturers: Vec<String> = class.manufacturers.keys().cloned().collect();
+        manufacturers.sort();
+        Self {
+            id: class.id.clone(),
+            name: class.name.clone(),
+            description: class.description.clone(),
+            size: format!("{:?}", class.size),
+            role: format!("{:?}", class.role),
+            max_weight: class.max_weight as f64,
+            max_modules: class.max_modules as i32,
+            base_hull: class.base_hull as f64,
+            base_shields: class.base_shields as f64,
+            build_points: class.build_points as f64,
+            cost: class.cost as f64,
+            manufacturers,
+        }
+    }
+}
+
+/// A star in the procedural galaxy
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "Star")]
+pub struct StarObject {
+    pub id: String,
+    pub name: String,
+    pub position: Vec<f64>,
+    pub star_type: String,
+    pub inhabited: bool,
+}
+
+impl From<&Star> for StarObject {
+    fn from(star: &Star) -> Self {
+        Self {
+            id: star.id.clone(),
+            name: star.name.clone(),
+            position: star.position.to_vec(),
+            star_type: format!("{:?}", star.star_type),
+            inhabited: star.inhabited,
+        }
+    }
+}
+
+/// A generated star system
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "StarSystem")]
+pub struct StarSystemObject {
+    pub id: String,
+    pub name: String,
+    pub inhabited: bool,
+    pub planets: Vec<String>,
+    pub moon_count: i32,
+    pub asteroid_belt_count: i32,
+    pub stations: Vec<String>,
+}
+
+impl From<&StarSystem> for StarSystemObject {
+    fn from(system: &StarSystem) -> Self {
+        Self {
+            id: system.id.clone(),
+            name: system.name.clone(),
+            inhabited: system.inhabited,
+            planets: system.planets.iter().map(|p| p.name.clone()).collect(),
+            moon_count: system.planets.iter().map(|p| p.moons.len()).sum::<usize>() as i32,
+            asteroid_belt_count: system.asteroid_belts.len() as i32,
+            stations: system.stations.iter().map(|s| s.name.clone()).collect(),
+        }
+    }
+}
+
+/// A faction's standing toward another faction
+#[derive(Debug, Clone, GraphQLObject)]
+pub struct FactionRelationship {
+    pub faction_id: String,
+    pub relationship: String,
+}
+
+/// A generated faction
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "ProceduralFaction")]
+pub struct ProceduralFactionObject {
+    pub id: String,
+    pub name: String,
+    pub government: String,
+    pub traits: Vec<String>,
+    pub tech_level: i32,
+    pub military_strength: i32,
+    pub economic_power: i32,
+    pub territories: Vec<String>,
+    pub relationships: Vec<FactionRelationship>,
+}
+
+impl From<&ProceduralFaction> for ProceduralFactionObject {
+    fn from(faction: &ProceduralFaction) -> Self {
+        let mut relationships: Vec<FactionRelationship> = faction
+            .relationships
+            .iter()
+            .map(|(faction_id, relationship)| FactionRelationship {
+                faction_id: faction_id.clone(),
+                relationship: format!("{:?}", relationship),
+            })
+            .collect();
+        relationships.sort_by(|a, b| a.faction_id.cmp(&b.faction_id));
+        Self {
+            id: faction.id.clone(),
+            name: faction.name.clone(),
+            government: format!("{:?}", faction.government),
+            traits: faction.traits.iter().map(|t| format!("{:?}", t)).collect(),
+            tech_level: faction.tech_level as i32,
+            military_strength: faction.military_strength as i32,
+            economic_power: faction.economic_power as i32,
+            territories: faction.territories.clone(),
+            relationships,
+        }
+    }
+}
+
+/// An event in the generated history
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "HistoricalEvent")]
+pub struct HistoricalEventObject {
+    pub year: i32,
+    pub event_type: String,
+    pub factions: Vec<String>,
+    pub description: String,
+}
+
+impl From<&HistoricalEvent> for HistoricalEventObject {
+    fn from(event: &HistoricalEvent) -> Self {
+        Self {
+            year: event.year,
+            event_type: format!("{:?}", event.event_type),
+            factions: event.factions.clone(),
+            description: event.description.clone(),
+        }
+    }
+}
+
+/// The current procedural universe
+#[derive(Debug, Clone, GraphQLObject)]
+#[graphql(name = "Universe")]
+pub struct UniverseObject {
+    pub name: String,
+    /// Generation seed (a 64-bit integer, as a string)
+    pub seed: String,
+    pub galaxy_radius: f64,
+    pub stars: Vec<StarObject>,
+    pub systems: Vec<StarSystemObject>,
+    pub factions: Vec<ProceduralFactionObject>,
+    pub history: Vec<HistoricalEventObject>,
+}
+
+impl From<&ProceduralUniverse> for UniverseObject {
+    fn from(universe: &ProceduralUniverse) -> Self {
+        Self {
+            name: universe.name.clone(),
+            seed: universe.seed.to_string(),
+            galaxy_radius: universe.galaxy.radius,
+            stars: universe.galaxy.stars.iter().map(StarObject::from).collect(),
+            systems: universe
+                .systems
+                .iter()
+                .map(StarSystemObject::from)
+                .collect(),
+            factions: universe
+                .factions
+                .iter()
+                .map(ProceduralFactionObject::from)
+                .collect(),
+            history: universe
+                .history
+                .iter()
+                .map(HistoricalEventObject::from)
+                .collect(),
+        }
+    }
+}
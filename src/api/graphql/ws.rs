@@ -0,0 +1,435 @@
+//! GraphQL over WebSocket
+//!
+//! Serves subscriptions (and one-shot queries and mutations) at
+//! `/graphql/ws`. Both the `graphql-transport-ws` protocol and the legacy
+//! `graphql-ws` (subscriptions-transport-ws) protocol are spoken; which one
+//! is chosen from the client's `Sec-WebSocket-Protocol` header, defaulting to
+//! `graphql-transport-ws`.
+//!
+//! The `connection_init` payload must carry a session token as
+//! `{"token": "..."}`; operations on the connection then run as that player.
+//! A missing token closes the connection with `4401 Unauthorized` and an
+//! unknown one with `4403 Forbidden`.
+//!
+//! Every operation runs on its own task and reports back to the connection
+//! loop over a channel, so a slow subscription never blocks the socket.
+
+use futures::{SinkExt, StreamExt};
+use juniper::http::{GraphQLRequest, GraphQLResponse};
+use juniper::{GraphQLError, Object, Value};
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::response::{self, Responder};
+use rocket::{State, get};
+use rocket_ws::frame::{CloseCode, CloseFrame};
+use rocket_ws::{Channel, Message, WebSocket};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::convert::Infallible;
+use std::sync::{Arc, RwLock};
+use tokio::sync::mpsc;
+
+use super::{Caller, Context, Schema, schema};
+use crate::api::generation::UniverseState;
+use crate::config::GameConfig;
+use crate::models::Player;
+use crate::state::{GameWorld, SharedGameWorld};
+use crate::websocket::WebSocketManager;
+
+/// WebSocket subprotocol spoken on a GraphQL connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// `graphql-transport-ws`, from the `graphql-ws` client library
+    TransportWs,
+    /// `graphql-ws`, from the legacy `subscriptions-transport-ws` library
+    LegacyWs,
+}
+
+impl Protocol {
+    /// Subprotocol name as negotiated in `Sec-WebSocket-Protocol`
+    pub fn name(self) -> &'static str {
+        match self {
+            Protocol::TransportWs => "graphql-transport-ws",
+            Protocol::LegacyWs => "graphql-ws",
+        }
+    }
+
+    /// Message type carrying an operation result
+    fn data_type(self) -> &'static str {
+        match self {
+            Protocol::TransportWs => "next",
+            Protocol::LegacyWs => "data",
+        }
+    }
+}
+
+/// Subprotocol requested by the client, if it named one we speak
+pub struct RequestedProtocol(Option<Protocol>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RequestedProtocol {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let offered: Vec<&str> = request
+            .headers()
+            .get("Sec-WebSocket-Protocol")
+            .flat_map(|header| header.split(','))
+            .map(str::trim)
+            .collect();
+
+        let protocol = [Protocol::TransportWs, Protocol::LegacyWs]
+            .into_iter()
+            .find(|protocol| offered.contains(&protocol.name()));
+        Outcome::Success(RequestedProtocol(protocol))
+    }
+}
+
+/// A WebSocket upgrade that echoes the negotiated subprotocol
+///
+/// Browsers drop the connection if they offered a subprotocol and the
+/// handshake response does not name one.
+pub struct GraphQLWsChannel {
+    channel: Channel<'static>,
+    protocol: Option<Protocol>,
+}
+
+impl<'r> Responder<'r, 'static> for GraphQLWsChannel {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = self.channel.respond_to(request)?;
+        if let Some(protocol) = self.protocol {
+            response.set_raw_header("Sec-WebSocket-Protocol", protocol.name());
+        }
+        Ok(response)
+    }
+}
+
+/// Payload of `connection_init`, carrying the player's session token
+#[derive(Debug, Default, Deserialize)]
+struct InitPayload {
+    #[serde(default)]
+    token: Option<String>,
+}
+
+/// Messages a client may send, across both protocols
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    ConnectionInit {
+        #[serde(default)]
+        payload: Option<InitPayload>,
+    },
+    ConnectionTerminate {},
+    Ping {},
+    Pong {},
+    /// Start an operation (`graphql-transport-ws`)
+    Subscribe {
+        id: String,
+        payload: GraphQLRequest,
+    },
+    /// Start an operation (`graphql-ws`)
+    Start {
+        id: String,
+        payload: GraphQLRequest,
+    },
+    /// Stop an operation (`graphql-transport-ws`)
+    Complete {
+        id: String,
+    },
+    /// Stop an operation (`graphql-ws`)
+    Stop {
+        id: String,
+    },
+}
+
+/// Output from an operation task to the connection loop
+enum Outgoing {
+    /// A message to forward to the client
+    Message(serde_json::Value),
+    /// The operation ended; `completed` is false if it ended in an error
+    Finished { id: String, completed: bool },
+}
+
+/// GET /graphql/ws - GraphQL subscriptions over WebSocket
+#[get("/ws")]
+pub fn graphql_ws(
+    ws: WebSocket,
+    requested: RequestedProtocol,
+    world: &State<SharedGameWorld>,
+    config: &State<Arc<GameConfig>>,
+    universe: &State<Arc<RwLock<UniverseState>>>,
+    ws_manager: &State<Arc<WebSocketManager>>,
+) -> GraphQLWsChannel {
+    let protocol = requested.0.unwrap_or(Protocol::TransportWs);
+    let context = super::context(world, config, universe, ws_manager, Caller::default());
+    let world = world.inner().clone();
+
+    let channel = ws.channel(move |stream| {
+        Box::pin(async move {
+            let schema = Arc::new(schema());
+            let (mut sink, mut stream) = stream.split();
+            let (out_tx, mut out_rx) = mpsc::unbounded_channel();
+            let mut operations: HashMap<String, tokio::task::JoinHandle<()>> = HashMap::new();
+            let mut context = Arc::new(context);
+            let mut initialized = false;
+
+            loop {
+                let reply = tokio::select! {
+                    message = stream.next() => {
+                        let text = match message {
+                            Some(Ok(Message::Text(text))) => text,
+                            Some(Ok(msg)) if msg.is_close() => break,
+                            Some(Ok(_)) => continue,
+                            Some(Err(_)) | None => break,
+                        };
+
+                        match serde_json::from_str::<ClientMessage>(&text) {
+                            Ok(ClientMessage::ConnectionInit { payload }) => {
+                                let token = payload.unwrap_or_default().token;
+                                let player = authenticate_init(&world.read().unwrap(), token.as_deref());
+                                if initialized {
+                                    close(protocol, 4429, "Too many initialisation requests")
+                                } else {
+                                    match player {
+                                        Ok(player) => {
+                                            initialized = true;
+                                            Arc::make_mut(&mut context).player = Some(player);
+                                            Some(Message::Text(json!({"type": "connection_ack"}).to_string()))
+                                        }
+                                        Err((code, reason)) => close(protocol, code, reason),
+                                    }
+                                }
+                            }
+                            Ok(ClientMessage::ConnectionTerminate {}) => break,
+                            Ok(ClientMessage::Ping {}) => {
+                                Some(Message::Text(json!({"type": "pong"}).to_string()))
+                            }
+                            Ok(ClientMessage::Pong {}) => None,
+                            Ok(ClientMessage::Subscribe { id, payload })
+                            | Ok(ClientMessage::Start { id, payload }) => {
+                                if !initialized {
+                                    close(protocol, 4401, "Unauthorized")
+                                } else if let Entry::Vacant(entry) = operations.entry(id.clone()) {
+                                    entry.insert(tokio::spawn(run_operation(
+                                        schema.clone(),
+                                        context.clone(),
+                                        protocol,
+                                        id,
+                                        payload,
+                                        out_tx.clone(),
+                                    )));
+                                    None
+                                } else {
+                                    close(protocol, 4409, &format!("Subscriber for {} already exists", id))
+                                }
+                            }
+                            Ok(ClientMessage::Complete { id }) | Ok(ClientMessage::Stop { id }) => {
+                                if let Some(task) = operations.remove(&id) {
+                                    task.abort();
+                                }
+                                None
+                            }
+                            Err(e) => close(protocol, 4400, &format!("Invalid message: {}", e)),
+                        }
+                    }
+
+                    Some(outgoing) = out_rx.recv() => match outgoing {
+                        Outgoing::Message(message) => Some(Message::Text(message.to_string())),
+                        Outgoing::Finished { id, completed } => {
+                            operations.remove(&id);
+                            completed.then(|| {
+                                Message::Text(json!({"type": "complete", "id": id}).to_string())
+                            })
+                        }
+                    },
+                };
+
+                if let Some(reply) = reply {
+                    let closing = reply.is_close();
+                    if sink.send(reply).await.is_err() || closing {
+                        break;
+                    }
+                }
+            }
+
+            for task in operations.values() {
+                task.abort();
+            }
+
+            Ok(())
+        })
+    });
+
+    GraphQLWsChannel {
+        channel,
+        protocol: requested.0,
+    }
+}
+
+/// Resolve the session token sent with `connection_init`
+///
+/// Fails with the close code and reason for a missing or unknown token.
+fn authenticate_init(
+    world: &GameWorld,
+    token: Option<&str>,
+) -> Result<Player, (u16, &'static str)> {
+    let token = token.ok_or((4401, "Unauthorized"))?;
+    world
+        .authenticate_token(token)
+        .cloned()
+        .ok_or((4403, "Forbidden"))
+}
+
+/// Build the message that closes a connection for a protocol violation
+///
+/// The legacy protocol has no close codes, so it reports a
+/// `connection_error` instead and leaves the socket open.
+fn close(protocol: Protocol, code: u16, reason: &str) -> Option<Message> {
+    match protocol {
+        Protocol::TransportWs => Some(Message::Close(Some(CloseFrame {
+            code: CloseCode::from(code),
+            reason: reason.to_string().into(),
+        }))),
+        Protocol::LegacyWs => Some(Message::Text(
+            json!({"type": "connection_error", "payload": {"message": reason}}).to_string(),
+        )),
+    }
+}
+
+/// Execute one operation, streaming its results back to the connection
+///
+/// Subscriptions yield a message per event until the client stops them;
+/// queries and mutations yield a single result.
+async fn run_operation(
+    schema: Arc<Schema>,
+    context: Arc<Context>,
+    protocol: Protocol,
+    id: String,
+    request: GraphQLRequest,
+    out: mpsc::UnboundedSender<Outgoing>,
+) {
+    let data = |payload: serde_json::Value| {
+        Outgoing::Message(json!({"type": protocol.data_type(), "id": id, "payload": payload}))
+    };
+    let error = |payload: serde_json::Value| {
+        Outgoing::Message(json!({"type": "error", "id": id, "payload": payload}))
+    };
+
+    let completed = match juniper::http::resolve_into_stream(&request, &schema, &context).await {
+        Ok((Value::Object(mut fields), errors)) if errors.is_empty() => {
+            let streams = fields.iter_mut().filter_map(|(name, value)| {
+                let name = name.clone();
+                match std::mem::replace(value, Value::Null) {
+                    Value::Scalar(stream) => Some(stream.map(move |item| (name.clone(), item))),
+                    _ => None,
+                }
+            });
+            let mut events = futures::stream::select_all(streams);
+
+            while let Some((name, item)) = events.next().await {
+                let result = match item {
+                    Ok(value) => Ok((Value::object(Object::from_iter([(name, value)])), vec![])),
+                    Err(e) => Ok((Value::null(), vec![e])),
+                };
+                let response = GraphQLResponse::from_result(result);
+                if out.send(data(json!(response))).is_err() {
+                    return;
+                }
+            }
+            true
+        }
+        Ok((_, errors)) => {
+            let _ = out.send(error(json!(errors)));
+            false
+        }
+        Err(GraphQLError::NotSubscription) => {
+            let response = request.execute(&schema, &context).await;
+            let _ = out.send(data(json!(response)));
+            true
+        }
+        Err(e) => {
+            let _ = out.send(error(json!(e)));
+            false
+        }
+    };
+
+    let _ = out.send(Outgoing::Finished { id, completed });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_transport_ws_messages() {
+        let init: ClientMessage =
+            serde_json::from_str(r#"{"type": "connection_init", "payload": {"token": "x"}}"#)
+                .unwrap();
+        match init {
+            ClientMessage::ConnectionInit { payload } => {
+                assert_eq!(payload.unwrap().token.as_deref(), Some("x"));
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+
+        let init: ClientMessage = serde_json::from_str(r#"{"type": "connection_init"}"#).unwrap();
+        assert!(matches!(
+            init,
+            ClientMessage::ConnectionInit { payload: None }
+        ));
+
+        let subscribe: ClientMessage = serde_json::from_str(
+            r#"{"type": "subscribe", "id": "1", "payload": {"query": "subscription { simulationTick { tick } }"}}"#,
+        )
+        .unwrap();
+        match subscribe {
+            ClientMessage::Subscribe { id, payload } => {
+                assert_eq!(id, "1");
+                assert!(payload.query.contains("simulationTick"));
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_legacy_ws_messages() {
+        let start: ClientMessage = serde_json::from_str(
+            r#"{"type": "start", "id": "a", "payload": {"query": "{ players { id } }", "variables": {}}}"#,
+        )
+        .unwrap();
+        assert!(matches!(start, ClientMessage::Start { .. }));
+
+        let stop: ClientMessage = serde_json::from_str(r#"{"type": "stop", "id": "a"}"#).unwrap();
+        assert!(matches!(stop, ClientMessage::Stop { id } if id == "a"));
+    }
+
+    #[test]
+    fn test_legacy_protocol_reports_errors_without_closing() {
+        let message = close(Protocol::LegacyWs, 4400, "bad").unwrap();
+        assert!(!message.is_close());
+
+        let message = close(Protocol::TransportWs, 4400, "bad").unwrap();
+        assert!(message.is_close());
+    }
+
+    #[test]
+    fn test_connection_init_requires_a_valid_token() {
+        let mut world = GameWorld::new();
+        let player_id = world.register_player("Ada".to_string()).unwrap();
+        let token = world.issue_player_token(&player_id).unwrap();
+
+        assert_eq!(
+            authenticate_init(&world, None).unwrap_err(),
+            (4401, "Unauthorized")
+        );
+        assert_eq!(
+            authenticate_init(&world, Some("bogus")).unwrap_err(),
+            (4403, "Forbidden")
+        );
+        assert_eq!(
+            authenticate_init(&world, Some(&token)).unwrap().id,
+            player_id
+        );
+    }
+}
@@ -8,6 +8,7 @@
 use rocket::http::Status;
 
 use crate::models::{Player, Ship, ShipBlueprint, Team};
+use crate::salvage::Wreck;
 use crate::state::GameWorld;
 use crate::stations::Station;
 
@@ -20,6 +21,7 @@ pub trait WorldLookup {
     fn find_player(&self, id: &str) -> Result<&Player, Status>;
     fn find_team(&self, id: &str) -> Result<&Team, Status>;
     fn find_station(&self, id: &str) -> Result<&Station, Status>;
+    fn find_wreck(&self, id: &str) -> Result<&Wreck, Status>;
 }
 
 impl WorldLookup for GameWorld {
@@ -50,4 +52,8 @@ impl WorldLookup for GameWorld {
     fn find_station(&self, id: &str) -> Result<&Station, Status> {
         self.get_station(id).ok_or(Status::NotFound)
     }
+
+    fn find_wreck(&self, id: &str) -> Result<&Wreck, Status> {
+        self.get_wreck(id).ok_or(Status::NotFound)
+    }
 }
@@ -23,6 +23,8 @@ pub struct CreatePlayerResponse {
     pub id: String,
     /// Player name
     pub name: String,
+    /// Secret session token, sent back as `Authorization: Bearer <token>`
+    pub token: String,
 }
 
 /// Response for player details
@@ -78,7 +80,8 @@ pub fn list_players(world: &State<SharedGameWorld>) -> Json<ListPlayersResponse>
 ///
 /// Creates a new player with the given name. The name must be unique,
 /// 1-50 characters long, and contain only alphanumeric characters,
-/// underscores, and hyphens.
+/// underscores, and hyphens. The response carries the player's session
+/// token; it is only ever returned here.
 #[post("/v1/players", data = "<request>")]
 pub fn create_player(
     world: &State<SharedGameWorld>,
@@ -88,10 +91,12 @@ pub fn create_player(
 
     match world.register_player(request.name.clone()) {
         Ok(player_id) => {
+            let token = world.issue_player_token(&player_id).unwrap();
             let player = world.get_player(&player_id).unwrap();
             Ok(Json(CreatePlayerResponse {
                 id: player.id.clone(),
                 name: player.name.clone(),
+                token,
             }))
         }
         Err(err) => Err((Status::BadRequest, Json(ErrorResponse { error: err }))),
@@ -200,6 +205,7 @@ mod tests {
         let body: CreatePlayerResponse = response.into_json().unwrap();
         assert_eq!(body.name, "Alice");
         assert!(!body.id.is_empty());
+        assert!(!body.token.is_empty());
     }
 
     #[test]
@@ -4,6 +4,7 @@
 //! - Crew reassignment
 //! - Captain's log management
 
+use crate::api::auth::{ShipTeamMember, StationCrew, station};
 use crate::api::lookup::WorldLookup;
 use crate::models::role::ShipRole;
 use crate::models::ship::CaptainLogEntry;
@@ -56,12 +57,16 @@ pub struct GetLogResponse {
 
 /// Reassign crew members to positions
 ///
+/// Only the Captain may reassign crew, and once a ship is compiled this is
+/// the only way its role assignments change.
+///
 /// POST /v1/ships/<id>/reassign
 #[post("/v1/ships/<ship_id>/reassign", data = "<request>")]
 pub fn reassign_crew(
     ship_id: String,
     request: Json<ReassignCrewRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Captain>,
 ) -> Result<Json<ReassignCrewResponse>, Status> {
     let mut game_world = world.write().map_err(|_| Status::InternalServerError)?;
 
@@ -98,6 +103,7 @@ pub fn add_log_entry(
     ship_id: String,
     request: Json<AddLogEntryRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Captain>,
 ) -> Result<Json<AddLogEntryResponse>, Status> {
     let mut game_world = world.write().map_err(|_| Status::InternalServerError)?;
 
@@ -144,6 +150,7 @@ pub fn add_log_entry(
 pub fn get_log(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _member: ShipTeamMember,
 ) -> Result<Json<GetLogResponse>, Status> {
     let game_world = world.read().map_err(|_| Status::InternalServerError)?;
 
@@ -174,7 +181,7 @@ mod tests {
     use crate::models::Ship;
     use crate::models::status::{Inventory, ShipStatus};
     use crate::state::GameWorld;
-    use rocket::http::{ContentType, Status};
+    use rocket::http::{ContentType, Header, Status};
     use rocket::local::blocking::Client;
 
     fn setup_test_world() -> SharedGameWorld {
@@ -194,13 +201,13 @@ mod tests {
         // Add player to team
         game_world.add_player_to_team(&team_id, &player_id).unwrap();
 
-        // Create a ship
+        // Create a ship crewed by the player
         let ship = Ship {
             id: "ship1".to_string(),
             name: "USS Enterprise".to_string(),
             class: "Constitution".to_string(),
             team_id: team_id.clone(),
-            player_roles: std::collections::HashMap::new(),
+            player_roles: [(player_id.clone(), vec![ShipRole::Captain])].into(),
             status: ShipStatus::default(),
             modules: vec![],
             weapons: vec![],
@@ -212,11 +219,20 @@ mod tests {
         world
     }
 
+    /// Authorization header for the test crew's captain
+    fn captain_auth(world: &SharedGameWorld) -> Header<'static> {
+        let mut game_world = world.write().unwrap();
+        let player_id = game_world.players().values().next().unwrap().id.clone();
+        let token = game_world.issue_player_token(&player_id).unwrap();
+        Header::new("Authorization", format!("Bearer {}", token))
+    }
+
     fn build_test_client(world: SharedGameWorld) -> Client {
         let rocket = rocket::build()
             .manage(world)
             // Mount at root because test request paths already include /v1
-            .mount("/", routes());
+            .mount("/", routes())
+            .register("/", crate::api::auth::catchers());
         Client::tracked(rocket).expect("valid rocket instance")
     }
 
@@ -238,6 +254,7 @@ mod tests {
 
         let response = client
             .post("/v1/ships/ship1/reassign")
+            .header(captain_auth(&world))
             .header(ContentType::JSON)
             .json(&request)
             .dispatch();
@@ -249,10 +266,50 @@ mod tests {
         assert_eq!(body.updated_assignments.get(&player_id).unwrap().len(), 2);
     }
 
+    #[test]
+    fn test_reassign_crew_requires_captain() {
+        let world = setup_test_world();
+        let client = build_test_client(world.clone());
+
+        // A helmsman aboard ship1 tries to promote themselves
+        let (helm_id, token) = {
+            let mut game_world = world.write().unwrap();
+            let team_id = game_world.get_ship("ship1").unwrap().team_id.clone();
+            let helm_id = game_world.register_player("Sulu".to_string()).unwrap();
+            game_world.add_player_to_team(&team_id, &helm_id).unwrap();
+            game_world
+                .get_ship_mut("ship1")
+                .unwrap()
+                .player_roles
+                .insert(helm_id.clone(), vec![ShipRole::Helm]);
+            let token = game_world.issue_player_token(&helm_id).unwrap();
+            (helm_id, token)
+        };
+
+        let mut assignments = std::collections::HashMap::new();
+        assignments.insert(helm_id.clone(), vec![ShipRole::Captain]);
+
+        let response = client
+            .post("/v1/ships/ship1/reassign")
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
+            .header(ContentType::JSON)
+            .json(&ReassignCrewRequest { assignments })
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let body: serde_json::Value = response.into_json().unwrap();
+        assert_eq!(body["error"], "forbidden");
+        assert_eq!(body["required_role"], "captain");
+
+        let game_world = world.read().unwrap();
+        let roles = &game_world.get_ship("ship1").unwrap().player_roles[&helm_id];
+        assert_eq!(roles, &vec![ShipRole::Helm]);
+    }
+
     #[test]
     fn test_reassign_crew_invalid_ship() {
         let world = setup_test_world();
-        let client = build_test_client(world);
+        let client = build_test_client(world.clone());
 
         let assignments = std::collections::HashMap::new();
         let request = ReassignCrewRequest { assignments };
@@ -269,7 +326,7 @@ mod tests {
     #[test]
     fn test_reassign_crew_invalid_player() {
         let world = setup_test_world();
-        let client = build_test_client(world);
+        let client = build_test_client(world.clone());
 
         let mut assignments = std::collections::HashMap::new();
         assignments.insert("invalid_player".to_string(), vec![ShipRole::Captain]);
@@ -278,6 +335,7 @@ mod tests {
 
         let response = client
             .post("/v1/ships/ship1/reassign")
+            .header(captain_auth(&world))
             .header(ContentType::JSON)
             .json(&request)
             .dispatch();
@@ -288,7 +346,7 @@ mod tests {
     #[test]
     fn test_add_log_entry() {
         let world = setup_test_world();
-        let client = build_test_client(world);
+        let client = build_test_client(world.clone());
 
         let request = AddLogEntryRequest {
             entry: "Captain's log, stardate 41153.7. Our destination is planet Deneb IV."
@@ -298,6 +356,7 @@ mod tests {
 
         let response = client
             .post("/v1/ships/ship1/log")
+            .header(captain_auth(&world))
             .header(ContentType::JSON)
             .json(&request)
             .dispatch();
@@ -313,7 +372,7 @@ mod tests {
     #[test]
     fn test_add_log_entry_auto_stardate() {
         let world = setup_test_world();
-        let client = build_test_client(world);
+        let client = build_test_client(world.clone());
 
         let request = AddLogEntryRequest {
             entry: "Routine patrol sector".to_string(),
@@ -322,6 +381,7 @@ mod tests {
 
         let response = client
             .post("/v1/ships/ship1/log")
+            .header(captain_auth(&world))
             .header(ContentType::JSON)
             .json(&request)
             .dispatch();
@@ -335,7 +395,7 @@ mod tests {
     #[test]
     fn test_add_log_entry_invalid_ship() {
         let world = setup_test_world();
-        let client = build_test_client(world);
+        let client = build_test_client(world.clone());
 
         let request = AddLogEntryRequest {
             entry: "Test entry".to_string(),
@@ -364,12 +424,16 @@ mod tests {
 
         client
             .post("/v1/ships/ship1/log")
+            .header(captain_auth(&world))
             .header(ContentType::JSON)
             .json(&request)
             .dispatch();
 
         // Retrieve the log
-        let response = client.get("/v1/ships/ship1/log").dispatch();
+        let response = client
+            .get("/v1/ships/ship1/log")
+            .header(captain_auth(&world))
+            .dispatch();
 
         assert_eq!(response.status(), Status::Ok);
 
@@ -383,7 +447,7 @@ mod tests {
     #[test]
     fn test_get_log_invalid_ship() {
         let world = setup_test_world();
-        let client = build_test_client(world);
+        let client = build_test_client(world.clone());
 
         let response = client.get("/v1/ships/nonexistent/log").dispatch();
 
@@ -393,9 +457,16 @@ mod tests {
     #[test]
     fn test_get_log_empty() {
         let world = setup_test_world();
-        let client = build_test_client(world);
+        let client = build_test_client(world.clone());
 
+        // Only the ship's team may read it
         let response = client.get("/v1/ships/ship1/log").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client
+            .get("/v1/ships/ship1/log")
+            .header(captain_auth(&world))
+            .dispatch();
 
         assert_eq!(response.status(), Status::Ok);
 
@@ -403,4 +474,37 @@ mod tests {
         assert_eq!(body.total, 0);
         assert!(body.entries.is_empty());
     }
+
+    #[test]
+    fn test_add_log_entry_requires_crew() {
+        let world = setup_test_world();
+        let client = build_test_client(world.clone());
+
+        let request = AddLogEntryRequest {
+            entry: "Unauthorised entry".to_string(),
+            stardate: None,
+        };
+
+        // No session token
+        let response = client
+            .post("/v1/ships/ship1/log")
+            .header(ContentType::JSON)
+            .json(&request)
+            .dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        // Authenticated, but not aboard ship1
+        let token = {
+            let mut game_world = world.write().unwrap();
+            let outsider = game_world.register_player("Outsider".to_string()).unwrap();
+            game_world.issue_player_token(&outsider).unwrap()
+        };
+        let response = client
+            .post("/v1/ships/ship1/log")
+            .header(ContentType::JSON)
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
+            .json(&request)
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+    }
 }
@@ -1,6 +1,9 @@
 //! Communications Officer API endpoints
 //!
-//! Handles docking requests, hailing, and fighter commands.
+//! Handles docking requests, hailing, broadcasts, and fighter commands.
+//!
+//! Hails and broadcasts are transmitted on the next simulation tick, so a
+//! hail only becomes answerable once it has reached its addressee.
 
 use rocket::{Route, State, http::Status, serde::json::Json};
 use rocket::{post, routes};
@@ -8,9 +11,8 @@ use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
-use crate::models::ship::{
-    DockingRequest, DockingStatus, FighterCommand, FighterCommandType, HailMessage,
-};
+use crate::api::auth::{StationCrew, station};
+use crate::models::ship::{DockingRequest, DockingStatus, FighterCommand, FighterCommandType};
 use crate::state::SharedGameWorld;
 
 /// Request to dock with a station
@@ -45,6 +47,18 @@ pub struct HailResponse {
     pub message_id: String,
 }
 
+/// Request to broadcast to every ship in range
+#[derive(Debug, Deserialize)]
+pub struct BroadcastRequest {
+    pub message: String,
+}
+
+/// Response for broadcast
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BroadcastResponse {
+    pub message_id: String,
+}
+
 /// Request to respond to a hail
 #[derive(Debug, Deserialize)]
 pub struct RespondRequest {
@@ -101,6 +115,7 @@ pub fn dock_request(
     ship_id: String,
     request: Json<DockRequestRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Comms>,
 ) -> Result<Json<DockRequestResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -143,6 +158,7 @@ pub fn dock_request(
 pub fn undock(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Comms>,
 ) -> Result<Json<UndockResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -163,6 +179,7 @@ pub fn hail(
     ship_id: String,
     request: Json<HailRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Comms>,
 ) -> Result<Json<HailResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -182,25 +199,44 @@ pub fn hail(
         return Err(Status::BadRequest);
     }
 
-    // Create hail message
-    let message_id = Uuid::new_v4().to_string();
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as i64;
+    let message_id = world
+        .send_message(
+            &ship_id,
+            Some(&request.target_ship_id),
+            &request.message,
+            None,
+        )
+        .map_err(|_| Status::BadRequest)?;
 
-    let hail_message = HailMessage {
-        id: message_id.clone(),
-        from_ship_id: ship_id.clone(),
-        to_ship_id: request.target_ship_id.clone(),
-        message: request.message.clone(),
-        timestamp,
-        in_response_to: None,
-    };
+    Ok(Json(HailResponse { message_id }))
+}
 
-    world.add_hail_message(hail_message);
+/// Broadcast to every ship in range
+#[post("/v1/ships/<ship_id>/broadcast", data = "<request>")]
+pub fn broadcast(
+    ship_id: String,
+    request: Json<BroadcastRequest>,
+    world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Comms>,
+) -> Result<Json<BroadcastResponse>, Status> {
+    let mut world = world.write().unwrap();
 
-    Ok(Json(HailResponse { message_id }))
+    // Check if ship exists
+    if !world.ships().contains_key(&ship_id) {
+        return Err(Status::NotFound);
+    }
+
+    // Check if communications are jammed
+    let ship = world.ships().get(&ship_id).unwrap();
+    if ship.status.is_ion_jammed() {
+        return Err(Status::BadRequest);
+    }
+
+    let message_id = world
+        .send_message(&ship_id, None, &request.message, None)
+        .map_err(|_| Status::BadRequest)?;
+
+    Ok(Json(BroadcastResponse { message_id }))
 }
 
 /// Respond to an incoming hail
@@ -209,6 +245,7 @@ pub fn respond(
     ship_id: String,
     request: Json<RespondRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Comms>,
 ) -> Result<Json<RespondResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -236,23 +273,14 @@ pub fn respond(
         return Err(Status::BadRequest);
     }
 
-    // Create response message
-    let response_id = Uuid::new_v4().to_string();
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs() as i64;
-
-    let response_message = HailMessage {
-        id: response_id.clone(),
-        from_ship_id: ship_id.clone(),
-        to_ship_id: original_message.from_ship_id.clone(),
-        message: request.response.clone(),
-        timestamp,
-        in_response_to: Some(request.message_id.clone()),
-    };
-
-    world.add_hail_message(response_message);
+    let response_id = world
+        .send_message(
+            &ship_id,
+            Some(&original_message.from_ship_id),
+            &request.response,
+            Some(&request.message_id),
+        )
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(RespondResponse { response_id }))
 }
@@ -263,6 +291,7 @@ pub fn jam(
     ship_id: String,
     request: Json<JamRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Comms>,
 ) -> Result<Json<JamResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -289,6 +318,7 @@ pub fn command_fighters(
     ship_id: String,
     request: Json<FighterCommandRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Comms>,
 ) -> Result<Json<FighterCommandResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -340,12 +370,23 @@ pub fn command_fighters(
 
 /// Returns all routes for the communications officer position
 pub fn routes() -> Vec<Route> {
-    routes![dock_request, undock, hail, respond, jam, command_fighters]
+    routes![
+        dock_request,
+        undock,
+        hail,
+        broadcast,
+        respond,
+        jam,
+        command_fighters
+    ]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::auth::test_utils::crew;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::CompiledModule;
     use crate::models::ship::Ship;
     use crate::models::status::{ShipStatus, StatusEffect, StatusEffectType};
     use crate::state::GameWorld;
@@ -369,6 +410,37 @@ mod tests {
         }
     }
 
+    /// A world where every listed ship is simulated with a comms system
+    fn setup_comms_world(ships: &[(&str, &str)]) -> SharedGameWorld {
+        let world = setup_test_world();
+        {
+            let mut world = world.write().unwrap();
+            let config = create_test_game_config();
+            for (id, team_id) in ships {
+                let mut ship = create_test_ship(id, team_id);
+                ship.modules.push(CompiledModule {
+                    instance_id: "comms".to_string(),
+                    module_id: "comms-system".to_string(),
+                    kind: None,
+                    name: "Comms".to_string(),
+                    stats: serde_json::from_value(serde_json::json!({
+                        "comm_range": 5000.0,
+                        "encryption_lvl": 5,
+                    }))
+                    .unwrap(),
+                    current_health: 100.0,
+                    max_health: 100.0,
+                    operational: true,
+                    power_allocated: 1.0,
+                    cooling_allocated: 1.0,
+                });
+                world.add_ship(ship);
+                world.spawn_ship_entity(id, &config).unwrap();
+            }
+        }
+        world
+    }
+
     #[test]
     fn test_dock_request() {
         let world = setup_test_world();
@@ -379,7 +451,12 @@ mod tests {
             station_id: "station1".to_string(),
         });
 
-        let result = dock_request("ship1".to_string(), request, State::from(&world));
+        let result = dock_request(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -394,7 +471,12 @@ mod tests {
             station_id: "station1".to_string(),
         });
 
-        let result = dock_request("ship1".to_string(), request, State::from(&world));
+        let result = dock_request(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert_eq!(result.err(), Some(Status::NotFound));
     }
 
@@ -413,7 +495,12 @@ mod tests {
             station_id: "station1".to_string(),
         });
 
-        let result = dock_request("ship1".to_string(), request, State::from(&world));
+        let result = dock_request(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert_eq!(result.err(), Some(Status::BadRequest));
     }
 
@@ -423,7 +510,7 @@ mod tests {
         let ship = create_test_ship("ship1", "team1");
         world.write().unwrap().add_ship(ship);
 
-        let result = undock("ship1".to_string(), State::from(&world));
+        let result = undock("ship1".to_string(), State::from(&world), crew("ship1"));
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -432,18 +519,75 @@ mod tests {
 
     #[test]
     fn test_hail() {
+        let world = setup_comms_world(&[("ship1", "team1"), ("ship2", "team2")]);
+
+        let request = Json(HailRequest {
+            target_ship_id: "ship2".to_string(),
+            message: "Greetings".to_string(),
+        });
+
+        let result = hail(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
+        let message_id = result.unwrap().into_inner().message_id;
+
+        // Delivered on the next tick
+        assert!(
+            world
+                .read()
+                .unwrap()
+                .get_hail_message(&message_id)
+                .is_none()
+        );
+        world.write().unwrap().step_simulation();
+        let delivered = world.read().unwrap().get_hail_message(&message_id).unwrap();
+        assert_eq!(delivered.to_ship_id, "ship2");
+        assert_eq!(delivered.message, "Greetings");
+    }
+
+    #[test]
+    fn test_hail_without_comms() {
         let world = setup_test_world();
-        let ship1 = create_test_ship("ship1", "team1");
-        let ship2 = create_test_ship("ship2", "team2");
-        world.write().unwrap().add_ship(ship1);
-        world.write().unwrap().add_ship(ship2);
+        world
+            .write()
+            .unwrap()
+            .add_ship(create_test_ship("ship1", "team1"));
+        world
+            .write()
+            .unwrap()
+            .add_ship(create_test_ship("ship2", "team2"));
 
         let request = Json(HailRequest {
             target_ship_id: "ship2".to_string(),
             message: "Greetings".to_string(),
         });
 
-        let result = hail("ship1".to_string(), request, State::from(&world));
+        let result = hail(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
+        assert_eq!(result.err(), Some(Status::BadRequest));
+    }
+
+    #[test]
+    fn test_broadcast() {
+        let world = setup_comms_world(&[("ship1", "team1")]);
+
+        let request = Json(BroadcastRequest {
+            message: "Mayday".to_string(),
+        });
+
+        let result = broadcast(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
     }
 
@@ -458,53 +602,75 @@ mod tests {
             message: "Greetings".to_string(),
         });
 
-        let result = hail("ship1".to_string(), request, State::from(&world));
+        let result = hail(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert_eq!(result.err(), Some(Status::BadRequest));
     }
 
     #[test]
     fn test_respond() {
-        let world = setup_test_world();
-        let ship1 = create_test_ship("ship1", "team1");
-        let ship2 = create_test_ship("ship2", "team2");
-        world.write().unwrap().add_ship(ship1);
-        world.write().unwrap().add_ship(ship2);
+        let world = setup_comms_world(&[("ship1", "team1"), ("ship2", "team2")]);
 
         // First, create a hail
         let hail_request = Json(HailRequest {
             target_ship_id: "ship2".to_string(),
             message: "Greetings".to_string(),
         });
-        let hail_result = hail("ship1".to_string(), hail_request, State::from(&world));
+        let hail_result = hail(
+            "ship1".to_string(),
+            hail_request,
+            State::from(&world),
+            crew("ship1"),
+        );
         let message_id = hail_result.unwrap().into_inner().message_id;
+        world.write().unwrap().step_simulation();
 
         // Now respond
         let respond_request = Json(RespondRequest {
-            message_id,
+            message_id: message_id.clone(),
             response: "Hello".to_string(),
         });
 
-        let result = respond("ship2".to_string(), respond_request, State::from(&world));
-        assert!(result.is_ok());
+        let result = respond(
+            "ship2".to_string(),
+            respond_request,
+            State::from(&world),
+            crew("ship2"),
+        );
+        let response_id = result.unwrap().into_inner().response_id;
+        world.write().unwrap().step_simulation();
+
+        let response = world
+            .read()
+            .unwrap()
+            .get_hail_message(&response_id)
+            .unwrap();
+        assert_eq!(response.to_ship_id, "ship1");
+        assert_eq!(response.in_response_to, Some(message_id));
     }
 
     #[test]
     fn test_respond_wrong_recipient() {
-        let world = setup_test_world();
-        let ship1 = create_test_ship("ship1", "team1");
-        let ship2 = create_test_ship("ship2", "team2");
-        let ship3 = create_test_ship("ship3", "team3");
-        world.write().unwrap().add_ship(ship1);
-        world.write().unwrap().add_ship(ship2);
-        world.write().unwrap().add_ship(ship3);
+        let world =
+            setup_comms_world(&[("ship1", "team1"), ("ship2", "team2"), ("ship3", "team3")]);
 
         // Create a hail from ship1 to ship2
         let hail_request = Json(HailRequest {
             target_ship_id: "ship2".to_string(),
             message: "Greetings".to_string(),
         });
-        let hail_result = hail("ship1".to_string(), hail_request, State::from(&world));
+        let hail_result = hail(
+            "ship1".to_string(),
+            hail_request,
+            State::from(&world),
+            crew("ship1"),
+        );
         let message_id = hail_result.unwrap().into_inner().message_id;
+        world.write().unwrap().step_simulation();
 
         // Try to respond from ship3 (not the recipient)
         let respond_request = Json(RespondRequest {
@@ -512,7 +678,12 @@ mod tests {
             response: "Hello".to_string(),
         });
 
-        let result = respond("ship3".to_string(), respond_request, State::from(&world));
+        let result = respond(
+            "ship3".to_string(),
+            respond_request,
+            State::from(&world),
+            crew("ship3"),
+        );
         assert_eq!(result.err(), Some(Status::BadRequest));
     }
 
@@ -529,7 +700,12 @@ mod tests {
             duration: 10.0,
         });
 
-        let result = jam("ship1".to_string(), request, State::from(&world));
+        let result = jam(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -549,7 +725,12 @@ mod tests {
             },
         });
 
-        let result = command_fighters("ship1".to_string(), request, State::from(&world));
+        let result = command_fighters(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
     }
 
@@ -569,7 +750,12 @@ mod tests {
             command: FighterCommandTypeDto::Launch,
         });
 
-        let result = command_fighters("ship1".to_string(), request, State::from(&world));
+        let result = command_fighters(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert_eq!(result.err(), Some(Status::BadRequest));
     }
 }
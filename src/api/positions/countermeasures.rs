@@ -6,6 +6,7 @@ use rocket::{Route, State, http::Status, serde::json::Json};
 use rocket::{get, post, routes};
 use serde::{Deserialize, Serialize};
 
+use crate::api::auth::{ShipTeamMember, StationCrew, station};
 use crate::api::lookup::WorldLookup;
 use crate::state::SharedGameWorld;
 
@@ -61,6 +62,12 @@ pub struct ActivateCountermeasuresResponse {
     pub threats_engaged: u32,
 }
 
+/// Response for deploying chaff
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeployChaffResponse {
+    pub success: bool,
+}
+
 /// Request to toggle point defense
 #[derive(Debug, Deserialize)]
 pub struct TogglePointDefenseRequest {
@@ -79,14 +86,13 @@ pub struct TogglePointDefenseResponse {
 pub fn raise_shields(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Countermeasures>,
 ) -> Result<Json<ShieldResponse>, Status> {
     let mut world = world.write().unwrap();
 
-    // Check if ship exists
-    let ship = world.find_ship_mut(&ship_id)?;
-
-    // Raise shields
-    ship.status.shields_raised = true;
+    world
+        .set_shields_raised(&ship_id, true)
+        .map_err(|_| Status::NotFound)?;
 
     Ok(Json(ShieldResponse {
         success: true,
@@ -99,14 +105,13 @@ pub fn raise_shields(
 pub fn lower_shields(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Countermeasures>,
 ) -> Result<Json<ShieldResponse>, Status> {
     let mut world = world.write().unwrap();
 
-    // Check if ship exists
-    let ship = world.find_ship_mut(&ship_id)?;
-
-    // Lower shields
-    ship.status.shields_raised = false;
+    world
+        .set_shields_raised(&ship_id, false)
+        .map_err(|_| Status::NotFound)?;
 
     Ok(Json(ShieldResponse {
         success: true,
@@ -119,6 +124,7 @@ pub fn lower_shields(
 pub fn get_shield_status(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _member: ShipTeamMember,
 ) -> Result<Json<ShieldStatusResponse>, Status> {
     let world = world.read().unwrap();
 
@@ -145,6 +151,7 @@ pub fn load_countermeasures(
     ship_id: String,
     request: Json<LoadCountermeasuresRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Countermeasures>,
 ) -> Result<Json<LoadCountermeasuresResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -172,6 +179,7 @@ pub fn activate_countermeasures(
     ship_id: String,
     request: Json<ActivateCountermeasuresRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Countermeasures>,
 ) -> Result<Json<ActivateCountermeasuresResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -191,12 +199,27 @@ pub fn activate_countermeasures(
     }))
 }
 
+/// Deploy a chaff cloud to seduce incoming guided missiles
+#[post("/v1/ships/<ship_id>/countermeasures/chaff")]
+pub fn deploy_chaff(
+    ship_id: String,
+    world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Countermeasures>,
+) -> Result<Json<DeployChaffResponse>, Status> {
+    let mut world = world.write().unwrap();
+
+    world.deploy_chaff(&ship_id).map_err(|_| Status::NotFound)?;
+
+    Ok(Json(DeployChaffResponse { success: true }))
+}
+
 /// Toggle automated point defense weapons
 #[post("/v1/ships/<ship_id>/point-defense/toggle", data = "<request>")]
 pub fn toggle_point_defense(
     ship_id: String,
     request: Json<TogglePointDefenseRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Countermeasures>,
 ) -> Result<Json<TogglePointDefenseResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -222,6 +245,7 @@ pub fn routes() -> Vec<Route> {
         get_shield_status,
         load_countermeasures,
         activate_countermeasures,
+        deploy_chaff,
         toggle_point_defense
     ]
 }
@@ -229,6 +253,7 @@ pub fn routes() -> Vec<Route> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::auth::test_utils::{crew, team_member};
     use crate::models::ship::Ship;
     use crate::models::status::ShipStatus;
     use crate::state::GameWorld;
@@ -258,7 +283,7 @@ mod tests {
         let ship = create_test_ship("ship1", "team1");
         world.write().unwrap().add_ship(ship);
 
-        let result = raise_shields("ship1".to_string(), State::from(&world));
+        let result = raise_shields("ship1".to_string(), State::from(&world), crew("ship1"));
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -278,7 +303,7 @@ mod tests {
         ship.status.shields_raised = true;
         world.write().unwrap().add_ship(ship);
 
-        let result = lower_shields("ship1".to_string(), State::from(&world));
+        let result = lower_shields("ship1".to_string(), State::from(&world), crew("ship1"));
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -295,7 +320,7 @@ mod tests {
     fn test_raise_shields_not_found() {
         let world = setup_test_world();
 
-        let result = raise_shields("ship1".to_string(), State::from(&world));
+        let result = raise_shields("ship1".to_string(), State::from(&world), crew("ship1"));
         assert_eq!(result.err(), Some(Status::NotFound));
     }
 
@@ -308,7 +333,11 @@ mod tests {
         ship.status.shields_raised = true;
         world.write().unwrap().add_ship(ship);
 
-        let result = get_shield_status("ship1".to_string(), State::from(&world));
+        let result = get_shield_status(
+            "ship1".to_string(),
+            State::from(&world),
+            team_member("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -322,7 +351,11 @@ mod tests {
     fn test_get_shield_status_not_found() {
         let world = setup_test_world();
 
-        let result = get_shield_status("ship1".to_string(), State::from(&world));
+        let result = get_shield_status(
+            "ship1".to_string(),
+            State::from(&world),
+            team_member("ship1"),
+        );
         assert_eq!(result.err(), Some(Status::NotFound));
     }
 
@@ -337,7 +370,12 @@ mod tests {
             quantity: 10,
         });
 
-        let result = load_countermeasures("ship1".to_string(), request, State::from(&world));
+        let result = load_countermeasures(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -354,7 +392,22 @@ mod tests {
             quantity: 5,
         });
 
-        let result = load_countermeasures("ship1".to_string(), request, State::from(&world));
+        let result = load_countermeasures(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
+        assert_eq!(result.err(), Some(Status::NotFound));
+    }
+
+    #[test]
+    fn test_deploy_chaff_not_in_simulation() {
+        let world = setup_test_world();
+        let ship = create_test_ship("ship1", "team1");
+        world.write().unwrap().add_ship(ship);
+
+        let result = deploy_chaff("ship1".to_string(), State::from(&world), crew("ship1"));
         assert_eq!(result.err(), Some(Status::NotFound));
     }
 
@@ -368,7 +421,12 @@ mod tests {
             target_threat_ids: vec!["missile1".to_string(), "missile2".to_string()],
         });
 
-        let result = activate_countermeasures("ship1".to_string(), request, State::from(&world));
+        let result = activate_countermeasures(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -384,7 +442,12 @@ mod tests {
             target_threat_ids: vec!["missile1".to_string()],
         });
 
-        let result = activate_countermeasures("ship1".to_string(), request, State::from(&world));
+        let result = activate_countermeasures(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert_eq!(result.err(), Some(Status::NotFound));
     }
 
@@ -396,7 +459,12 @@ mod tests {
 
         let request = Json(TogglePointDefenseRequest { enabled: true });
 
-        let result = toggle_point_defense("ship1".to_string(), request, State::from(&world));
+        let result = toggle_point_defense(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -412,7 +480,12 @@ mod tests {
 
         let request = Json(TogglePointDefenseRequest { enabled: false });
 
-        let result = toggle_point_defense("ship1".to_string(), request, State::from(&world));
+        let result = toggle_point_defense(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -426,7 +499,12 @@ mod tests {
 
         let request = Json(TogglePointDefenseRequest { enabled: true });
 
-        let result = toggle_point_defense("ship1".to_string(), request, State::from(&world));
+        let result = toggle_point_defense(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert_eq!(result.err(), Some(Status::NotFound));
     }
 }
@@ -6,6 +6,7 @@ use rocket::{Route, State, http::Status, serde::json::Json};
 use rocket::{get, post, routes};
 use serde::{Deserialize, Serialize};
 
+use crate::api::auth::{ShipTeamMember, StationCrew, station};
 use crate::api::lookup::WorldLookup;
 use crate::state::SharedGameWorld;
 
@@ -86,6 +87,7 @@ pub fn set_target(
     ship_id: String,
     request: Json<SetTargetRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::EnergyWeapons>,
 ) -> Result<Json<SetTargetResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -94,8 +96,10 @@ pub fn set_target(
         return Err(Status::NotFound);
     }
 
-    // Set energy weapon target
-    world.set_energy_weapon_target(ship_id, request.target_id.clone());
+    // Point the energy weapons at the target
+    world
+        .set_energy_weapon_target(ship_id, request.target_id.clone())
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(SetTargetResponse {
         success: true,
@@ -109,6 +113,7 @@ pub fn fire_weapon(
     ship_id: String,
     request: Json<FireWeaponRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::EnergyWeapons>,
 ) -> Result<Json<FireWeaponResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -117,8 +122,10 @@ pub fn fire_weapon(
         return Err(Status::NotFound);
     }
 
-    // Record weapon fire command
-    world.add_weapon_fire_command(ship_id, request.weapon_id.clone());
+    // Fire at the current target
+    world
+        .add_weapon_fire_command(ship_id, request.weapon_id.clone())
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(FireWeaponResponse {
         success: true,
@@ -132,6 +139,7 @@ pub fn toggle_auto(
     ship_id: String,
     request: Json<ToggleAutoRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::EnergyWeapons>,
 ) -> Result<Json<ToggleAutoResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -141,7 +149,9 @@ pub fn toggle_auto(
     }
 
     // Set auto-fire mode
-    world.set_weapon_auto_fire(ship_id, request.weapon_id.clone(), request.enabled);
+    world
+        .set_weapon_auto_fire(ship_id, request.weapon_id.clone(), request.enabled)
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(ToggleAutoResponse {
         success: true,
@@ -156,6 +166,7 @@ pub fn activate_radial(
     ship_id: String,
     request: Json<ActivateRadialRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::EnergyWeapons>,
 ) -> Result<Json<ActivateRadialResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -178,22 +189,24 @@ pub fn activate_radial(
 pub fn get_status(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _member: ShipTeamMember,
 ) -> Result<Json<WeaponStatusResponse>, Status> {
     let world = world.read().unwrap();
 
     // Check if ship exists
-    let ship = world.find_ship(&ship_id)?;
+    world.find_ship(&ship_id)?;
 
-    // Get energy weapons from ship
-    let weapons: Vec<EnergyWeaponStatus> = ship
-        .weapons
-        .iter()
+    // Get energy weapons from the simulation
+    let weapons: Vec<EnergyWeaponStatus> = world
+        .get_ship_weapons(&ship_id)
+        .into_iter()
+        .filter(|w| w.weapon_type == "energy")
         .map(|w| EnergyWeaponStatus {
             weapon_id: w.id.clone(),
-            cooldown: 0.0, // Would be retrieved from simulation
-            ready: true,
-            auto_fire: false,
-            tags: vec![], // Tags would be looked up from WeaponConfig
+            cooldown: w.cooldown,
+            ready: w.can_fire(),
+            auto_fire: w.is_automatic,
+            tags: w.tags.iter().map(|t| format!("{:?}", t)).collect(),
         })
         .collect();
 
@@ -219,13 +232,25 @@ pub fn routes() -> Vec<Route> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::ship::Ship;
+    use crate::api::auth::test_utils::{crew, team_member};
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::ship::{CompiledModule, Ship};
     use crate::models::status::ShipStatus;
     use crate::state::GameWorld;
     use std::sync::{Arc, RwLock};
 
+    /// World with an armed `ship1` and an `enemy1` target, both simulated
     fn setup_test_world() -> SharedGameWorld {
-        Arc::new(RwLock::new(GameWorld::new()))
+        let world = Arc::new(RwLock::new(GameWorld::new()));
+        let config = create_test_game_config();
+        {
+            let mut world = world.write().unwrap();
+            world.add_ship(create_test_ship("ship1", "team1"));
+            world.add_ship(create_test_ship("enemy1", "team2"));
+            world.spawn_ship_entity("ship1", &config).unwrap();
+            world.spawn_ship_entity("enemy1", &config).unwrap();
+        }
+        world
     }
 
     fn create_test_ship(id: &str, team_id: &str) -> Ship {
@@ -236,7 +261,23 @@ mod tests {
             team_id: team_id.to_string(),
             player_roles: std::collections::HashMap::new(),
             status: ShipStatus::default(),
-            modules: vec![],
+            modules: vec![CompiledModule {
+                instance_id: "laser1".to_string(),
+                module_id: "de-weapon".to_string(),
+                kind: None,
+                name: "Laser".to_string(),
+                stats: serde_json::from_value(serde_json::json!({
+                    "damage": 10,
+                    "recharge_time": 2.0,
+                    "weapon_tags": ["Beam"]
+                }))
+                .unwrap(),
+                current_health: 50.0,
+                max_health: 50.0,
+                operational: true,
+                power_allocated: 1.0,
+                cooling_allocated: 1.0,
+            }],
             weapons: vec![],
             inventory: Default::default(),
         }
@@ -245,44 +286,81 @@ mod tests {
     #[test]
     fn test_set_target() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
 
         let request = Json(SetTargetRequest {
             target_id: "enemy1".to_string(),
         });
 
-        let result = set_target("ship1".to_string(), request, State::from(&world));
+        let result = set_target(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
         assert!(response.success);
         assert_eq!(response.target_id, "enemy1");
+        assert_eq!(
+            world.read().unwrap().get_energy_weapon_target("ship1"),
+            Some("enemy1".to_string())
+        );
     }
 
     #[test]
     fn test_set_target_not_found() {
-        let world = setup_test_world();
+        let world = Arc::new(RwLock::new(GameWorld::new()));
 
         let request = Json(SetTargetRequest {
             target_id: "enemy1".to_string(),
         });
 
-        let result = set_target("ship1".to_string(), request, State::from(&world));
+        let result = set_target(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert_eq!(result.err(), Some(Status::NotFound));
     }
 
+    #[test]
+    fn test_set_target_unknown_target() {
+        let world = setup_test_world();
+
+        let request = Json(SetTargetRequest {
+            target_id: "ghost".to_string(),
+        });
+
+        let result = set_target(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
+        assert_eq!(result.err(), Some(Status::BadRequest));
+    }
+
     #[test]
     fn test_fire_weapon() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
+        world
+            .write()
+            .unwrap()
+            .set_energy_weapon_target("ship1".to_string(), "enemy1".to_string())
+            .unwrap();
 
         let request = Json(FireWeaponRequest {
             weapon_id: "laser1".to_string(),
         });
 
-        let result = fire_weapon("ship1".to_string(), request, State::from(&world));
+        let result = fire_weapon(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -290,18 +368,38 @@ mod tests {
         assert_eq!(response.weapon_id, "laser1");
     }
 
+    #[test]
+    fn test_fire_weapon_without_target() {
+        let world = setup_test_world();
+
+        let request = Json(FireWeaponRequest {
+            weapon_id: "laser1".to_string(),
+        });
+
+        let result = fire_weapon(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
+        assert_eq!(result.err(), Some(Status::BadRequest));
+    }
+
     #[test]
     fn test_toggle_auto() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
 
         let request = Json(ToggleAutoRequest {
             weapon_id: "laser1".to_string(),
             enabled: true,
         });
 
-        let result = toggle_auto("ship1".to_string(), request, State::from(&world));
+        let result = toggle_auto(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -313,14 +411,17 @@ mod tests {
     #[test]
     fn test_activate_radial() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
 
         let request = Json(ActivateRadialRequest {
             weapon_id: "emp1".to_string(),
         });
 
-        let result = activate_radial("ship1".to_string(), request, State::from(&world));
+        let result = activate_radial(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -331,14 +432,18 @@ mod tests {
     #[test]
     fn test_get_status() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
 
-        let result = get_status("ship1".to_string(), State::from(&world));
+        let result = get_status(
+            "ship1".to_string(),
+            State::from(&world),
+            team_member("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
-        assert_eq!(response.weapons.len(), 0);
+        assert_eq!(response.weapons.len(), 1);
+        assert!(response.weapons[0].ready);
+        assert_eq!(response.weapons[0].tags, vec!["Beam".to_string()]);
         assert_eq!(response.current_target, None);
     }
 }
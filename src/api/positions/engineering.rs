@@ -7,6 +7,7 @@ use rocket::{get, patch, post, routes};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::api::auth::{ShipTeamMember, StationCrew, station};
 use crate::api::lookup::WorldLookup;
 use crate::state::SharedGameWorld;
 
@@ -23,6 +24,19 @@ pub struct AllocatePowerResponse {
     pub allocated_modules: usize,
 }
 
+/// Request to set module power priorities
+#[derive(Debug, Deserialize)]
+pub struct PowerPriorityRequest {
+    pub priorities: HashMap<String, u8>, // module_id -> priority (0 = shed first)
+}
+
+/// Response for power priorities
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PowerPriorityResponse {
+    pub success: bool,
+    pub prioritized_modules: usize,
+}
+
 /// Request to allocate cooling to modules
 #[derive(Debug, Deserialize)]
 pub struct AllocateCoolingRequest {
@@ -47,6 +61,8 @@ pub struct RepairRequest {
 pub struct RepairResponse {
     pub success: bool,
     pub module_id: String,
+    /// Modules queued for repair, in the order teams take them
+    pub queue: Vec<String>,
 }
 
 /// Ship status information
@@ -94,12 +110,21 @@ pub struct ActivateModuleResponse {
     pub remaining_uses: Option<u32>,
 }
 
+/// Response for auxiliary module recharge
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RechargeModuleResponse {
+    pub success: bool,
+    pub module_id: String,
+    pub remaining_uses: u32,
+}
+
 /// Allocate power to modules
 #[patch("/v1/ships/<ship_id>/power/allocate", data = "<request>")]
 pub fn allocate_power(
     ship_id: String,
     request: Json<AllocatePowerRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Engineering>,
 ) -> Result<Json<AllocatePowerResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -119,12 +144,40 @@ pub fn allocate_power(
     }))
 }
 
+/// Set which modules keep power longest during a brownout
+#[patch("/v1/ships/<ship_id>/power/priority", data = "<request>")]
+pub fn set_power_priority(
+    ship_id: String,
+    request: Json<PowerPriorityRequest>,
+    world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Engineering>,
+) -> Result<Json<PowerPriorityResponse>, Status> {
+    let mut world = world.write().unwrap();
+
+    // Check if ship exists
+    if !world.ships().contains_key(&ship_id) {
+        return Err(Status::NotFound);
+    }
+
+    let prioritized_count = request.priorities.len();
+
+    world
+        .set_power_priorities(&ship_id, request.priorities.clone())
+        .map_err(|_| Status::BadRequest)?;
+
+    Ok(Json(PowerPriorityResponse {
+        success: true,
+        prioritized_modules: prioritized_count,
+    }))
+}
+
 /// Allocate cooling to modules
 #[patch("/v1/ships/<ship_id>/cooling/allocate", data = "<request>")]
 pub fn allocate_cooling(
     ship_id: String,
     request: Json<AllocateCoolingRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Engineering>,
 ) -> Result<Json<AllocateCoolingResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -144,12 +197,13 @@ pub fn allocate_cooling(
     }))
 }
 
-/// Initiate repair on a module
+/// Queue a damaged module for repair by the ship's damage-control teams
 #[post("/v1/ships/<ship_id>/repair", data = "<request>")]
 pub fn repair_module(
     ship_id: String,
     request: Json<RepairRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Engineering>,
 ) -> Result<Json<RepairResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -158,12 +212,14 @@ pub fn repair_module(
         return Err(Status::NotFound);
     }
 
-    // Record repair command
-    world.add_repair_command(ship_id, request.module_id.clone());
+    world
+        .add_repair_command(ship_id.clone(), request.module_id.clone())
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(RepairResponse {
         success: true,
         module_id: request.module_id.clone(),
+        queue: world.repair_queue(&ship_id),
     }))
 }
 
@@ -172,6 +228,7 @@ pub fn repair_module(
 pub fn get_ship_status(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _member: ShipTeamMember,
 ) -> Result<Json<ShipStatusInfo>, Status> {
     let world = world.read().unwrap();
 
@@ -210,6 +267,7 @@ pub fn get_ship_status(
 pub fn get_modules_status(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _member: ShipTeamMember,
 ) -> Result<Json<ModulesStatusResponse>, Status> {
     let world = world.read().unwrap();
 
@@ -247,30 +305,51 @@ pub fn activate_auxiliary_module(
     module_id: String,
     _request: Json<ActivateModuleRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Engineering>,
 ) -> Result<Json<ActivateModuleResponse>, Status> {
     let mut world = world.write().unwrap();
 
-    // Check if ship exists
+    // Check that the ship has the module
     let ship = world.find_ship(&ship_id)?;
+    if !ship.modules.iter().any(|m| m.instance_id == module_id) {
+        return Err(Status::NotFound);
+    }
 
-    // Find the module
-    let module = ship
-        .modules
-        .iter()
-        .find(|m| m.instance_id == module_id)
-        .ok_or(Status::NotFound)?;
+    let activation = world
+        .add_auxiliary_activation(ship_id, module_id)
+        .map_err(|_| Status::BadRequest)?;
+
+    Ok(Json(ActivateModuleResponse {
+        success: true,
+        message: "Auxiliary module activated".to_string(),
+        duration: Some(activation.duration),
+        remaining_uses: Some(activation.remaining_uses),
+    }))
+}
 
-    // Get the module's duration stat
-    let duration = module.get_stat_f64("duration").unwrap_or(10.0) as f32;
+/// Restore an auxiliary module's uses at the station the ship is docked at
+#[post("/v1/ships/<ship_id>/modules/<module_id>/recharge")]
+pub fn recharge_auxiliary_module(
+    ship_id: String,
+    module_id: String,
+    world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Engineering>,
+) -> Result<Json<RechargeModuleResponse>, Status> {
+    let mut world = world.write().unwrap();
 
-    // Record the activation command
-    world.add_auxiliary_activation(ship_id, module_id.clone(), duration);
+    let ship = world.find_ship(&ship_id)?;
+    if !ship.modules.iter().any(|m| m.instance_id == module_id) {
+        return Err(Status::NotFound);
+    }
 
-    Ok(Json(ActivateModuleResponse {
+    let remaining_uses = world
+        .recharge_auxiliary(&ship_id, &module_id)
+        .map_err(|_| Status::BadRequest)?;
+
+    Ok(Json(RechargeModuleResponse {
         success: true,
-        message: "Auxiliary module activation queued".to_string(),
-        duration: Some(duration),
-        remaining_uses: None, // Will be updated by simulation
+        module_id,
+        remaining_uses,
     }))
 }
 
@@ -278,17 +357,22 @@ pub fn activate_auxiliary_module(
 pub fn routes() -> Vec<Route> {
     routes![
         allocate_power,
+        set_power_priority,
         allocate_cooling,
         repair_module,
         get_ship_status,
         get_modules_status,
-        activate_auxiliary_module
+        activate_auxiliary_module,
+        recharge_auxiliary_module
     ]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::auth::test_utils::{crew, team_member};
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::CompiledModule;
     use crate::models::ship::Ship;
     use crate::models::status::ShipStatus;
     use crate::state::GameWorld;
@@ -324,7 +408,12 @@ mod tests {
 
         let request = Json(AllocatePowerRequest { allocations });
 
-        let result = allocate_power("ship1".to_string(), request, State::from(&world));
+        let result = allocate_power(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -332,6 +421,36 @@ mod tests {
         assert_eq!(response.allocated_modules, 2);
     }
 
+    #[test]
+    fn test_set_power_priority() {
+        let world = setup_test_world();
+        let ship = create_test_ship("ship1", "team1");
+        world.write().unwrap().add_ship(ship);
+
+        // A ship outside the simulation has no modules to prioritize
+        let request = Json(PowerPriorityRequest {
+            priorities: HashMap::from([("shield1".to_string(), 9)]),
+        });
+        let result = set_power_priority(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
+        assert_eq!(result.unwrap_err(), Status::BadRequest);
+
+        let request = Json(PowerPriorityRequest {
+            priorities: HashMap::new(),
+        });
+        let result = set_power_priority(
+            "missing".to_string(),
+            request,
+            State::from(&world),
+            crew("missing"),
+        );
+        assert_eq!(result.unwrap_err(), Status::NotFound);
+    }
+
     #[test]
     fn test_allocate_cooling() {
         let world = setup_test_world();
@@ -343,7 +462,12 @@ mod tests {
 
         let request = Json(AllocateCoolingRequest { allocations });
 
-        let result = allocate_cooling("ship1".to_string(), request, State::from(&world));
+        let result = allocate_cooling(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -354,19 +478,104 @@ mod tests {
     #[test]
     fn test_repair_module() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
-
-        let request = Json(RepairRequest {
-            module_id: "engine1".to_string(),
-        });
+        {
+            let mut world = world.write().unwrap();
+            let mut ship = create_test_ship("ship1", "team1");
+            ship.modules = vec![CompiledModule {
+                instance_id: "engine1".to_string(),
+                module_id: "impulse-engine".to_string(),
+                kind: None,
+                name: "Impulse Engine".to_string(),
+                stats: Default::default(),
+                current_health: 40.0,
+                max_health: 100.0,
+                operational: true,
+                power_allocated: 1.0,
+                cooling_allocated: 1.0,
+            }];
+            world.add_ship(ship);
+            world
+                .spawn_ship_entity("ship1", &create_test_game_config())
+                .unwrap();
+        }
 
-        let result = repair_module("ship1".to_string(), request, State::from(&world));
+        let result = repair_module(
+            "ship1".to_string(),
+            Json(RepairRequest {
+                module_id: "engine1".to_string(),
+            }),
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
         assert!(response.success);
         assert_eq!(response.module_id, "engine1");
+        assert_eq!(response.queue, vec!["engine1".to_string()]);
+
+        let result = repair_module(
+            "ship1".to_string(),
+            Json(RepairRequest {
+                module_id: "missing".to_string(),
+            }),
+            State::from(&world),
+            crew("ship1"),
+        );
+        assert_eq!(result.unwrap_err(), Status::BadRequest);
+    }
+
+    #[test]
+    fn test_activate_auxiliary_module() {
+        let world = setup_test_world();
+        {
+            let mut world = world.write().unwrap();
+            let mut ship = create_test_ship("ship1", "team1");
+            ship.modules = vec![CompiledModule {
+                instance_id: "power-cell".to_string(),
+                module_id: "aux-support-system".to_string(),
+                kind: Some("emergency-power-cell".to_string()),
+                name: "Emergency Power Cell".to_string(),
+                stats: serde_json::from_value(
+                    serde_json::json!({ "energy_restored": 500, "num_uses": 3 }),
+                )
+                .unwrap(),
+                current_health: 100.0,
+                max_health: 100.0,
+                operational: true,
+                power_allocated: 1.0,
+                cooling_allocated: 1.0,
+            }];
+            world.add_ship(ship);
+            world
+                .spawn_ship_entity("ship1", &create_test_game_config())
+                .unwrap();
+        }
+        let activate = || {
+            activate_auxiliary_module(
+                "ship1".to_string(),
+                "power-cell".to_string(),
+                Json(ActivateModuleRequest {}),
+                State::from(&world),
+                crew("ship1"),
+            )
+        };
+
+        let response = activate().unwrap().into_inner();
+        assert!(response.success);
+        assert_eq!(response.remaining_uses, Some(2));
+
+        // Still cooling down
+        assert_eq!(activate().unwrap_err(), Status::BadRequest);
+
+        // Not docked
+        let result = recharge_auxiliary_module(
+            "ship1".to_string(),
+            "power-cell".to_string(),
+            State::from(&world),
+            crew("ship1"),
+        );
+        assert_eq!(result.unwrap_err(), Status::BadRequest);
     }
 
     #[test]
@@ -377,7 +586,11 @@ mod tests {
         ship.status.max_hull = 1000.0;
         world.write().unwrap().add_ship(ship);
 
-        let result = get_ship_status("ship1".to_string(), State::from(&world));
+        let result = get_ship_status(
+            "ship1".to_string(),
+            State::from(&world),
+            team_member("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -392,7 +605,11 @@ mod tests {
         let ship = create_test_ship("ship1", "team1");
         world.write().unwrap().add_ship(ship);
 
-        let result = get_modules_status("ship1".to_string(), State::from(&world));
+        let result = get_modules_status(
+            "ship1".to_string(),
+            State::from(&world),
+            team_member("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
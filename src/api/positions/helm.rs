@@ -6,6 +6,7 @@ use rocket::{Route, State, http::Status, serde::json::Json};
 use rocket::{get, post, routes};
 use serde::{Deserialize, Serialize};
 
+use crate::api::auth::{ShipTeamMember, StationCrew, station};
 use crate::api::lookup::WorldLookup;
 use crate::state::SharedGameWorld;
 
@@ -94,6 +95,7 @@ pub fn set_thrust(
     ship_id: String,
     request: Json<SetThrustRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Helm>,
 ) -> Result<Json<ThrustResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -112,6 +114,7 @@ pub fn set_rotation(
     ship_id: String,
     request: Json<SetRotationRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Helm>,
 ) -> Result<Json<RotationResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -129,6 +132,7 @@ pub fn set_rotation(
 pub fn full_stop(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Helm>,
 ) -> Result<Json<ThrustResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -147,6 +151,7 @@ pub fn engage_warp(
     ship_id: String,
     request: Json<WarpRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Helm>,
 ) -> Result<Json<WarpResponse>, Status> {
     let world_read = world.read().unwrap();
 
@@ -182,6 +187,7 @@ pub fn engage_jump(
     ship_id: String,
     request: Json<JumpRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Helm>,
 ) -> Result<Json<JumpResponse>, Status> {
     let world_read = world.read().unwrap();
 
@@ -217,6 +223,7 @@ pub fn initiate_dock(
     ship_id: String,
     request: Json<DockRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Helm>,
 ) -> Result<Json<DockResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -234,6 +241,7 @@ pub fn initiate_dock(
 pub fn get_helm_status(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _member: ShipTeamMember,
 ) -> Result<Json<HelmStatusResponse>, Status> {
     let world = world.read().unwrap();
 
@@ -265,6 +273,7 @@ pub fn routes() -> Vec<Route> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::auth::test_utils::{crew, team_member};
     use crate::models::ship::Ship;
     use crate::models::status::{ShipStatus, StatusEffect, StatusEffectType};
     use crate::state::GameWorld;
@@ -300,7 +309,12 @@ mod tests {
             z: 0.0,
         });
 
-        let result = set_thrust("ship1".to_string(), request, State::from(&world));
+        let result = set_thrust(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
     }
 
@@ -316,7 +330,12 @@ mod tests {
             roll: 0.0,
         });
 
-        let result = set_rotation("ship1".to_string(), request, State::from(&world));
+        let result = set_rotation(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
     }
 
@@ -326,7 +345,7 @@ mod tests {
         let ship = create_test_ship("ship1", "team1");
         world.write().unwrap().add_ship(ship);
 
-        let result = full_stop("ship1".to_string(), State::from(&world));
+        let result = full_stop("ship1".to_string(), State::from(&world), crew("ship1"));
         assert!(result.is_ok());
     }
 
@@ -342,7 +361,12 @@ mod tests {
             destination_z: 3000.0,
         });
 
-        let result = engage_warp("ship1".to_string(), request, State::from(&world));
+        let result = engage_warp(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -367,7 +391,12 @@ mod tests {
             destination_z: 3000.0,
         });
 
-        let result = engage_warp("ship1".to_string(), request, State::from(&world));
+        let result = engage_warp(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -387,7 +416,12 @@ mod tests {
             destination_z: 3000.0,
         });
 
-        let result = engage_jump("ship1".to_string(), request, State::from(&world));
+        let result = engage_jump(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -405,7 +439,12 @@ mod tests {
             station_id: "station1".to_string(),
         });
 
-        let result = initiate_dock("ship1".to_string(), request, State::from(&world));
+        let result = initiate_dock(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
     }
 
@@ -415,7 +454,11 @@ mod tests {
         let ship = create_test_ship("ship1", "team1");
         world.write().unwrap().add_ship(ship);
 
-        let result = get_helm_status("ship1".to_string(), State::from(&world));
+        let result = get_helm_status(
+            "ship1".to_string(),
+            State::from(&world),
+            team_member("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -6,7 +6,9 @@ use rocket::{Route, State, http::Status, serde::json::Json};
 use rocket::{get, post, routes};
 use serde::{Deserialize, Serialize};
 
+use crate::api::auth::{ShipTeamMember, StationCrew, station};
 use crate::api::lookup::WorldLookup;
+use crate::config::GameConfig;
 use crate::state::SharedGameWorld;
 
 /// Request to set target
@@ -64,6 +66,7 @@ pub fn set_target(
     ship_id: String,
     request: Json<SetTargetRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::KineticWeapons>,
 ) -> Result<Json<KineticResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -71,7 +74,9 @@ pub fn set_target(
         return Err(Status::NotFound);
     }
 
-    world.set_kinetic_weapon_target(ship_id, request.target_id.clone());
+    world
+        .set_kinetic_weapon_target(ship_id, request.target_id.clone())
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(KineticResponse { success: true }))
 }
@@ -86,6 +91,7 @@ pub fn configure_weapon(
     weapon_id: String,
     request: Json<ConfigureWeaponRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::KineticWeapons>,
 ) -> Result<Json<KineticResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -93,7 +99,9 @@ pub fn configure_weapon(
         return Err(Status::NotFound);
     }
 
-    world.configure_kinetic_weapon(ship_id, weapon_id, request.kind.clone());
+    world
+        .configure_kinetic_weapon(ship_id, weapon_id, request.kind.clone())
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(KineticResponse { success: true }))
 }
@@ -108,6 +116,8 @@ pub fn load_ammo(
     weapon_id: String,
     request: Json<LoadAmmoRequest>,
     world: &State<SharedGameWorld>,
+    config: &State<GameConfig>,
+    _crew: StationCrew<station::KineticWeapons>,
 ) -> Result<Json<KineticResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -115,12 +125,15 @@ pub fn load_ammo(
         return Err(Status::NotFound);
     }
 
-    world.load_kinetic_ammo(
-        ship_id,
-        weapon_id,
-        request.ammo_type.clone(),
-        request.quantity,
-    );
+    world
+        .load_kinetic_ammo(
+            ship_id,
+            weapon_id,
+            request.ammo_type.clone(),
+            request.quantity,
+            config,
+        )
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(KineticResponse { success: true }))
 }
@@ -131,6 +144,7 @@ pub fn fire_weapon(
     ship_id: String,
     weapon_id: String,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::KineticWeapons>,
 ) -> Result<Json<KineticResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -138,7 +152,9 @@ pub fn fire_weapon(
         return Err(Status::NotFound);
     }
 
-    world.fire_kinetic_weapon(ship_id, weapon_id);
+    world
+        .fire_kinetic_weapon(ship_id, weapon_id)
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(KineticResponse { success: true }))
 }
@@ -153,6 +169,7 @@ pub fn toggle_auto(
     weapon_id: String,
     request: Json<ToggleAutoRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::KineticWeapons>,
 ) -> Result<Json<KineticResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -160,7 +177,9 @@ pub fn toggle_auto(
         return Err(Status::NotFound);
     }
 
-    world.set_kinetic_auto_fire(ship_id, weapon_id, request.enabled);
+    world
+        .set_kinetic_auto_fire(ship_id, weapon_id, request.enabled)
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(KineticResponse { success: true }))
 }
@@ -170,19 +189,25 @@ pub fn toggle_auto(
 pub fn get_status(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _member: ShipTeamMember,
 ) -> Result<Json<Vec<KineticWeaponStatus>>, Status> {
     let world = world.read().unwrap();
 
     let ship = world.find_ship(&ship_id)?;
 
-    let weapons = ship
-        .weapons
-        .iter()
+    let weapons = world
+        .get_ship_weapons(&ship_id)
+        .into_iter()
+        .filter(|w| w.weapon_type == "kinetic")
         .map(|w| KineticWeaponStatus {
-            weapon_id: w.id.clone(),
-            kind: w.kind.clone(),
-            loaded_ammo: 0,
-            ready: true,
+            kind: ship
+                .weapons
+                .iter()
+                .find(|instance| instance.id == w.id)
+                .and_then(|instance| instance.kind.clone()),
+            loaded_ammo: w.ammo_count,
+            ready: w.can_fire(),
+            weapon_id: w.id,
         })
         .collect();
 
@@ -204,16 +229,56 @@ pub fn routes() -> Vec<Route> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::ship::Ship;
+    use crate::api::auth::test_utils::{crew, team_member};
+    use crate::config::AmmunitionConfig;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::ship::{CompiledModule, Ship};
     use crate::models::status::ShipStatus;
     use crate::state::GameWorld;
     use std::sync::{Arc, RwLock};
 
+    /// World with an armed `ship1` and an `enemy1` target, both simulated
     fn setup_test_world() -> SharedGameWorld {
-        Arc::new(RwLock::new(GameWorld::new()))
+        let world = Arc::new(RwLock::new(GameWorld::new()));
+        let config = create_test_game_config();
+        {
+            let mut world = world.write().unwrap();
+            world.add_ship(create_test_ship("ship1", "team1"));
+            world.add_ship(create_test_ship("enemy1", "team2"));
+            world.spawn_ship_entity("ship1", &config).unwrap();
+            world.spawn_ship_entity("enemy1", &config).unwrap();
+        }
+        world
+    }
+
+    fn create_test_config() -> GameConfig {
+        let mut config = create_test_game_config();
+        config.ammunition_types = vec![AmmunitionConfig {
+            id: "sabot".to_string(),
+            name: "sabot".to_string(),
+            description: String::new(),
+            cost: 10.0,
+            weight: 1.0,
+            impact_damage: 25.0,
+            blast_radius: 0.0,
+            blast_damage: 0.0,
+            velocity: 2000.0,
+            armor_penetration: 0.0,
+            acceleration: 100.0,
+            max_speed: 800.0,
+            max_turn_rate: 30.0,
+            lifetime: 20.0,
+            weapon_tags: vec!["Kinetic".to_string()],
+            category: "kinetic".to_string(),
+            ammo_type: String::new(),
+            ammo_size: String::new(),
+        }];
+        config
     }
 
     fn create_test_ship(id: &str, team_id: &str) -> Ship {
+        let mut inventory = crate::models::Inventory::new();
+        inventory.add_ammunition("sabot".to_string(), 100);
         Ship {
             id: id.to_string(),
             name: format!("Ship {}", id),
@@ -221,31 +286,43 @@ mod tests {
             team_id: team_id.to_string(),
             player_roles: std::collections::HashMap::new(),
             status: ShipStatus::default(),
-            modules: vec![],
+            modules: vec![CompiledModule {
+                instance_id: "kinetic1".to_string(),
+                module_id: "kinetic-weapon".to_string(),
+                kind: None,
+                name: "Cannon".to_string(),
+                stats: serde_json::from_value(serde_json::json!({ "reload_time": 1.0 })).unwrap(),
+                current_health: 50.0,
+                max_health: 50.0,
+                operational: true,
+                power_allocated: 1.0,
+                cooling_allocated: 1.0,
+            }],
             weapons: vec![],
-            inventory: Default::default(),
+            inventory,
         }
     }
 
     #[test]
     fn test_set_target() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
 
         let request = Json(SetTargetRequest {
             target_id: "enemy1".to_string(),
         });
 
-        let result = set_target("ship1".to_string(), request, State::from(&world));
+        let result = set_target(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_configure_weapon() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
 
         let request = Json(ConfigureWeaponRequest {
             weapon_id: "kinetic1".to_string(),
@@ -257,6 +334,7 @@ mod tests {
             "kinetic1".to_string(),
             request,
             State::from(&world),
+            crew("ship1"),
         );
         assert!(result.is_ok());
     }
@@ -264,13 +342,12 @@ mod tests {
     #[test]
     fn test_load_ammo() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
+        let config = create_test_config();
 
         let request = Json(LoadAmmoRequest {
             weapon_id: "kinetic1".to_string(),
             ammo_type: "sabot".to_string(),
-            quantity: 100,
+            quantity: 30,
         });
 
         let result = load_ammo(
@@ -278,29 +355,92 @@ mod tests {
             "kinetic1".to_string(),
             request,
             State::from(&world),
+            State::from(&config),
+            crew("ship1"),
         );
         assert!(result.is_ok());
+
+        let status = get_status(
+            "ship1".to_string(),
+            State::from(&world),
+            team_member("ship1"),
+        )
+        .unwrap()
+        .into_inner();
+        assert_eq!(status[0].loaded_ammo, 30);
+    }
+
+    #[test]
+    fn test_load_ammo_insufficient() {
+        let world = setup_test_world();
+        let config = create_test_config();
+
+        let request = Json(LoadAmmoRequest {
+            weapon_id: "kinetic1".to_string(),
+            ammo_type: "sabot".to_string(),
+            quantity: 500,
+        });
+
+        let result = load_ammo(
+            "ship1".to_string(),
+            "kinetic1".to_string(),
+            request,
+            State::from(&world),
+            State::from(&config),
+            crew("ship1"),
+        );
+        assert_eq!(result.err(), Some(Status::BadRequest));
     }
 
     #[test]
     fn test_fire_weapon() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
+        {
+            let mut world = world.write().unwrap();
+            world
+                .set_kinetic_weapon_target("ship1".to_string(), "enemy1".to_string())
+                .unwrap();
+            world
+                .load_kinetic_ammo(
+                    "ship1".to_string(),
+                    "kinetic1".to_string(),
+                    "sabot".to_string(),
+                    10,
+                    &create_test_config(),
+                )
+                .unwrap();
+        }
 
         let result = fire_weapon(
             "ship1".to_string(),
             "kinetic1".to_string(),
             State::from(&world),
+            crew("ship1"),
         );
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_fire_weapon_empty_magazine() {
+        let world = setup_test_world();
+        world
+            .write()
+            .unwrap()
+            .set_kinetic_weapon_target("ship1".to_string(), "enemy1".to_string())
+            .unwrap();
+
+        let result = fire_weapon(
+            "ship1".to_string(),
+            "kinetic1".to_string(),
+            State::from(&world),
+            crew("ship1"),
+        );
+        assert_eq!(result.err(), Some(Status::BadRequest));
+    }
+
     #[test]
     fn test_toggle_auto() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
 
         let request = Json(ToggleAutoRequest {
             weapon_id: "kinetic1".to_string(),
@@ -312,6 +452,7 @@ mod tests {
             "kinetic1".to_string(),
             request,
             State::from(&world),
+            crew("ship1"),
         );
         assert!(result.is_ok());
     }
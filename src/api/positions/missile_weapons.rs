@@ -6,7 +6,9 @@ use rocket::{Route, State, http::Status, serde::json::Json};
 use rocket::{get, post, routes};
 use serde::{Deserialize, Serialize};
 
+use crate::api::auth::{ShipTeamMember, StationCrew, station};
 use crate::api::lookup::WorldLookup;
+use crate::config::GameConfig;
 use crate::state::SharedGameWorld;
 
 /// Request to set target
@@ -57,6 +59,7 @@ pub fn set_target(
     ship_id: String,
     request: Json<SetTargetRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::MissileWeapons>,
 ) -> Result<Json<MissileResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -64,7 +67,9 @@ pub fn set_target(
         return Err(Status::NotFound);
     }
 
-    world.set_missile_weapon_target(ship_id, request.target_id.clone());
+    world
+        .set_missile_weapon_target(ship_id, request.target_id.clone())
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(MissileResponse { success: true }))
 }
@@ -79,6 +84,8 @@ pub fn load_ordnance(
     weapon_id: String,
     request: Json<LoadOrdnanceRequest>,
     world: &State<SharedGameWorld>,
+    config: &State<GameConfig>,
+    _crew: StationCrew<station::MissileWeapons>,
 ) -> Result<Json<MissileResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -86,12 +93,15 @@ pub fn load_ordnance(
         return Err(Status::NotFound);
     }
 
-    world.load_missile_ordnance(
-        ship_id,
-        weapon_id,
-        request.ordnance_type.clone(),
-        request.quantity,
-    );
+    world
+        .load_missile_ordnance(
+            ship_id,
+            weapon_id,
+            request.ordnance_type.clone(),
+            request.quantity,
+            config,
+        )
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(MissileResponse { success: true }))
 }
@@ -102,6 +112,7 @@ pub fn fire_weapon(
     ship_id: String,
     weapon_id: String,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::MissileWeapons>,
 ) -> Result<Json<MissileResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -109,7 +120,9 @@ pub fn fire_weapon(
         return Err(Status::NotFound);
     }
 
-    world.fire_missile_weapon(ship_id, weapon_id);
+    world
+        .fire_missile_weapon(ship_id, weapon_id)
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(MissileResponse { success: true }))
 }
@@ -124,6 +137,7 @@ pub fn toggle_auto(
     weapon_id: String,
     request: Json<ToggleAutoRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::MissileWeapons>,
 ) -> Result<Json<MissileResponse>, Status> {
     let mut world = world.write().unwrap();
 
@@ -131,7 +145,9 @@ pub fn toggle_auto(
         return Err(Status::NotFound);
     }
 
-    world.set_missile_auto_fire(ship_id, weapon_id, request.enabled);
+    world
+        .set_missile_auto_fire(ship_id, weapon_id, request.enabled)
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(MissileResponse { success: true }))
 }
@@ -141,19 +157,21 @@ pub fn toggle_auto(
 pub fn get_status(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _member: ShipTeamMember,
 ) -> Result<Json<Vec<MissileWeaponStatus>>, Status> {
     let world = world.read().unwrap();
 
-    let ship = world.find_ship(&ship_id)?;
+    world.find_ship(&ship_id)?;
 
-    let weapons = ship
-        .weapons
-        .iter()
+    let weapons = world
+        .get_ship_weapons(&ship_id)
+        .into_iter()
+        .filter(|w| w.weapon_type == "missile")
         .map(|w| MissileWeaponStatus {
-            weapon_id: w.id.clone(),
-            loaded_ordnance: "none".to_string(),
-            quantity: 0,
-            ready: true,
+            loaded_ordnance: w.ammunition.clone().unwrap_or_else(|| "none".to_string()),
+            quantity: w.ammo_count,
+            ready: w.can_fire(),
+            weapon_id: w.id,
         })
         .collect();
 
@@ -174,16 +192,56 @@ pub fn routes() -> Vec<Route> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::ship::Ship;
+    use crate::api::auth::test_utils::{crew, team_member};
+    use crate::config::AmmunitionConfig;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::ship::{CompiledModule, Ship};
     use crate::models::status::ShipStatus;
     use crate::state::GameWorld;
     use std::sync::{Arc, RwLock};
 
+    /// World with an armed `ship1` and an `enemy1` target, both simulated
     fn setup_test_world() -> SharedGameWorld {
-        Arc::new(RwLock::new(GameWorld::new()))
+        let world = Arc::new(RwLock::new(GameWorld::new()));
+        let config = create_test_game_config();
+        {
+            let mut world = world.write().unwrap();
+            world.add_ship(create_test_ship("ship1", "team1"));
+            world.add_ship(create_test_ship("enemy1", "team2"));
+            world.spawn_ship_entity("ship1", &config).unwrap();
+            world.spawn_ship_entity("enemy1", &config).unwrap();
+        }
+        world
+    }
+
+    fn create_test_config() -> GameConfig {
+        let mut config = create_test_game_config();
+        config.ammunition_types = vec![AmmunitionConfig {
+            id: "photon_torpedo".to_string(),
+            name: "photon_torpedo".to_string(),
+            description: String::new(),
+            cost: 10.0,
+            weight: 1.0,
+            impact_damage: 25.0,
+            blast_radius: 0.0,
+            blast_damage: 0.0,
+            velocity: 2000.0,
+            armor_penetration: 0.0,
+            acceleration: 100.0,
+            max_speed: 800.0,
+            max_turn_rate: 30.0,
+            lifetime: 20.0,
+            weapon_tags: vec!["Torpedo".to_string()],
+            category: "torpedos".to_string(),
+            ammo_type: String::new(),
+            ammo_size: String::new(),
+        }];
+        config
     }
 
     fn create_test_ship(id: &str, team_id: &str) -> Ship {
+        let mut inventory = crate::models::Inventory::new();
+        inventory.add_ammunition("photon_torpedo".to_string(), 10);
         Ship {
             id: id.to_string(),
             name: format!("Ship {}", id),
@@ -191,31 +249,47 @@ mod tests {
             team_id: team_id.to_string(),
             player_roles: std::collections::HashMap::new(),
             status: ShipStatus::default(),
-            modules: vec![],
+            modules: vec![CompiledModule {
+                instance_id: "missile1".to_string(),
+                module_id: "torpedo-tube".to_string(),
+                kind: None,
+                name: "Torpedo Tube".to_string(),
+                stats: serde_json::from_value(
+                    serde_json::json!({ "reload_time": 5.0, "ammo_capacity": 4 }),
+                )
+                .unwrap(),
+                current_health: 50.0,
+                max_health: 50.0,
+                operational: true,
+                power_allocated: 1.0,
+                cooling_allocated: 1.0,
+            }],
             weapons: vec![],
-            inventory: Default::default(),
+            inventory,
         }
     }
 
     #[test]
     fn test_set_target() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
 
         let request = Json(SetTargetRequest {
             target_id: "enemy1".to_string(),
         });
 
-        let result = set_target("ship1".to_string(), request, State::from(&world));
+        let result = set_target(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_load_ordnance() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
+        let config = create_test_config();
 
         let request = Json(LoadOrdnanceRequest {
             weapon_id: "missile1".to_string(),
@@ -228,20 +302,47 @@ mod tests {
             "missile1".to_string(),
             request,
             State::from(&world),
+            State::from(&config),
+            crew("ship1"),
         );
         assert!(result.is_ok());
+
+        // Only a magazine's worth is loaded
+        let status = get_status(
+            "ship1".to_string(),
+            State::from(&world),
+            team_member("ship1"),
+        )
+        .unwrap()
+        .into_inner();
+        assert_eq!(status[0].loaded_ordnance, "photon_torpedo");
+        assert_eq!(status[0].quantity, 4);
     }
 
     #[test]
     fn test_fire_weapon() {
         let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
+        {
+            let mut world = world.write().unwrap();
+            world
+                .set_missile_weapon_target("ship1".to_string(), "enemy1".to_string())
+                .unwrap();
+            world
+                .load_missile_ordnance(
+                    "ship1".to_string(),
+                    "missile1".to_string(),
+                    "photon_torpedo".to_string(),
+                    1,
+                    &create_test_config(),
+                )
+                .unwrap();
+        }
 
         let result = fire_weapon(
             "ship1".to_string(),
             "missile1".to_string(),
             State::from(&world),
+            crew("ship1"),
         );
         assert!(result.is_ok());
     }
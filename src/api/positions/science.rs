@@ -6,8 +6,9 @@ use rocket::{Route, State, http::Status, serde::json::Json};
 use rocket::{get, post, routes};
 use serde::{Deserialize, Serialize};
 
+use crate::api::auth::{StationCrew, station};
 use crate::api::lookup::WorldLookup;
-use crate::state::SharedGameWorld;
+use crate::state::{ScanReport, SharedGameWorld};
 
 /// Request to scan a target
 #[derive(Debug, Deserialize)]
@@ -20,6 +21,8 @@ pub struct ScanRequest {
 pub struct ScanResponse {
     pub success: bool,
     pub ion_jammed: bool,
+    /// Time for the scan to reveal every tier it can (seconds)
+    pub duration: f32,
 }
 
 /// Request to analyze a target
@@ -28,14 +31,25 @@ pub struct AnalyzeRequest {
     pub target_id: String,
 }
 
+/// Progress of a scan under way
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanProgressInfo {
+    pub elapsed: f32,
+    pub duration: f32,
+    pub tiers_revealed: u8,
+    pub tiers: u8,
+}
+
 /// Response for deep analysis
+///
+/// Holds only what the ship's scans have revealed about the target.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AnalysisResponse {
     pub target_id: String,
-    pub class: String,
-    pub faction: String,
-    pub hull_percentage: f32,
-    pub shields_percentage: f32,
+    #[serde(flatten)]
+    pub report: ScanReport,
+    /// Progress of the ship's scan of this target, if one is under way
+    pub scan: Option<ScanProgressInfo>,
 }
 
 /// Contact information
@@ -82,6 +96,7 @@ pub fn scan_target(
     ship_id: String,
     request: Json<ScanRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Science>,
 ) -> Result<Json<ScanResponse>, Status> {
     let world_read = world.read().unwrap();
 
@@ -94,23 +109,33 @@ pub fn scan_target(
         return Ok(Json(ScanResponse {
             success: false,
             ion_jammed: true,
+            duration: 0.0,
         }));
     }
 
     let mut world_write = world.write().unwrap();
-    world_write.add_scan_command(ship_id, request.target_id.clone());
+
+    // Ships the crew cannot see are indistinguishable from ones that don't exist
+    if !world_write.can_see(&ship_id, &request.target_id) {
+        return Err(Status::NotFound);
+    }
+    let duration = world_write
+        .add_scan_command(ship_id, request.target_id.clone())
+        .map_err(|_| Status::BadRequest)?;
 
     Ok(Json(ScanResponse {
         success: true,
         ion_jammed: false,
+        duration,
     }))
 }
 
-/// Get detected contacts
+/// Get ships currently held on sensors
 #[get("/v1/ships/<ship_id>/contacts")]
 pub fn get_contacts(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Science>,
 ) -> Result<Json<ContactsResponse>, Status> {
     let world = world.read().unwrap();
 
@@ -118,17 +143,26 @@ pub fn get_contacts(
         return Err(Status::NotFound);
     }
 
-    // In a real implementation, this would query the simulation
-    let contacts = vec![];
+    let contacts = world
+        .get_contacts(&ship_id)
+        .into_iter()
+        .filter(|contact| world.can_see(&ship_id, &contact.ship_id))
+        .map(|contact| Contact {
+            id: contact.ship_id,
+            distance: contact.distance,
+            bearing: contact.bearing,
+        })
+        .collect();
 
     Ok(Json(ContactsResponse { contacts }))
 }
 
-/// Get incoming threats
+/// Get hostile ordnance closing on the ship
 #[get("/v1/ships/<ship_id>/threats")]
 pub fn get_threats(
     ship_id: String,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Science>,
 ) -> Result<Json<ThreatsResponse>, Status> {
     let world = world.read().unwrap();
 
@@ -136,8 +170,16 @@ pub fn get_threats(
         return Err(Status::NotFound);
     }
 
-    // In a real implementation, this would query the simulation
-    let threats = vec![];
+    let threats = world
+        .get_threats(&ship_id)
+        .into_iter()
+        .map(|threat| Threat {
+            id: threat.id,
+            threat_type: threat.threat_type,
+            distance: threat.distance,
+            time_to_impact: threat.time_to_impact,
+        })
+        .collect();
 
     Ok(Json(ThreatsResponse { threats }))
 }
@@ -148,12 +190,17 @@ pub fn get_navigation(
     ship_id: String,
     target_id: String,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Science>,
 ) -> Result<Json<NavigationInfo>, Status> {
     let world = world.read().unwrap();
 
     if !world.ships().contains_key(&ship_id) {
         return Err(Status::NotFound);
     }
+    // Ships the crew cannot see are indistinguishable from ones that don't exist
+    if !world.can_see(&ship_id, &target_id) {
+        return Err(Status::NotFound);
+    }
 
     // In a real implementation, this would calculate actual navigation data
     Ok(Json(NavigationInfo {
@@ -170,6 +217,7 @@ pub fn analyze_target(
     ship_id: String,
     request: Json<AnalyzeRequest>,
     world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Science>,
 ) -> Result<Json<AnalysisResponse>, Status> {
     let world_read = world.read().unwrap();
 
@@ -181,27 +229,27 @@ pub fn analyze_target(
         return Err(Status::BadRequest);
     }
 
-    // Try to find target ship
-    let target = world_read.find_ship(&request.target_id)?;
-
-    let hull_pct = if target.status.max_hull > 0.0 {
-        (target.status.hull / target.status.max_hull) * 100.0
-    } else {
-        0.0
-    };
+    // Ships the crew cannot see, and has never scanned, are
+    // indistinguishable from ones that don't exist
+    let report = world_read.scan_report(&ship_id, &request.target_id);
+    if report.is_none() && !world_read.can_see(&ship_id, &request.target_id) {
+        return Err(Status::NotFound);
+    }
 
-    let shields_pct = if target.status.max_shields > 0.0 {
-        (target.status.shields / target.status.max_shields) * 100.0
-    } else {
-        0.0
-    };
+    let scan = world_read
+        .scan_status(&ship_id)
+        .filter(|scan| scan.target_id == request.target_id)
+        .map(|scan| ScanProgressInfo {
+            elapsed: scan.elapsed,
+            duration: scan.duration,
+            tiers_revealed: scan.revealed,
+            tiers: scan.tiers,
+        });
 
     Ok(Json(AnalysisResponse {
-        target_id: target.id.clone(),
-        class: target.class.clone(),
-        faction: target.team_id.clone(),
-        hull_percentage: hull_pct,
-        shields_percentage: shields_pct,
+        target_id: request.target_id.clone(),
+        report: report.cloned().unwrap_or_default(),
+        scan,
     }))
 }
 
@@ -219,6 +267,9 @@ pub fn routes() -> Vec<Route> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::auth::test_utils::crew;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::CompiledModule;
     use crate::models::ship::Ship;
     use crate::models::status::{ShipStatus, StatusEffect, StatusEffectType};
     use crate::state::GameWorld;
@@ -242,22 +293,82 @@ mod tests {
         }
     }
 
+    /// A world where ship1 holds ship2 on sensors
+    fn setup_sensor_world() -> SharedGameWorld {
+        let world = setup_test_world();
+        let mut ship1 = create_test_ship("ship1", "team1");
+        ship1.modules.push(CompiledModule {
+            instance_id: "sensors".to_string(),
+            module_id: "sensor-array".to_string(),
+            kind: None,
+            name: "Sensors".to_string(),
+            stats: serde_json::from_value(serde_json::json!({
+                "scan_range": 5000.0,
+                "scan_time": 1.0,
+                "detail_level": 10,
+            }))
+            .unwrap(),
+            current_health: 100.0,
+            max_health: 100.0,
+            operational: true,
+            power_allocated: 1.0,
+            cooling_allocated: 1.0,
+        });
+        let mut ship2 = create_test_ship("ship2", "team2");
+        ship2.status.position = [1000.0, 0.0, 0.0];
+        {
+            let mut world = world.write().unwrap();
+            let config = create_test_game_config();
+            world.add_ship(ship1);
+            world.add_ship(ship2);
+            world.spawn_ship_entity("ship1", &config).unwrap();
+            world.spawn_ship_entity("ship2", &config).unwrap();
+            world.step_simulation();
+        }
+        world
+    }
+
     #[test]
     fn test_scan_target() {
-        let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
+        let world = setup_sensor_world();
 
         let request = Json(ScanRequest {
-            target_id: "enemy1".to_string(),
+            target_id: "ship2".to_string(),
         });
 
-        let result = scan_target("ship1".to_string(), request, State::from(&world));
+        let result = scan_target(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
         assert!(response.success);
         assert!(!response.ion_jammed);
+        assert!((response.duration - 6.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_scan_target_not_on_sensors() {
+        let world = setup_test_world();
+        world
+            .write()
+            .unwrap()
+            .add_ship(create_test_ship("ship1", "team1"));
+
+        let request = Json(ScanRequest {
+            target_id: "enemy1".to_string(),
+        });
+
+        let result = scan_target(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
+        assert_eq!(result.err(), Some(Status::NotFound));
     }
 
     #[test]
@@ -275,7 +386,12 @@ mod tests {
             target_id: "enemy1".to_string(),
         });
 
-        let result = scan_target("ship1".to_string(), request, State::from(&world));
+        let result = scan_target(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert!(result.is_ok());
 
         let response = result.unwrap().into_inner();
@@ -289,51 +405,118 @@ mod tests {
         let ship = create_test_ship("ship1", "team1");
         world.write().unwrap().add_ship(ship);
 
-        let result = get_contacts("ship1".to_string(), State::from(&world));
+        let result = get_contacts("ship1".to_string(), State::from(&world), crew("ship1"));
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_get_contacts_lists_ships_on_sensors() {
+        let world = setup_sensor_world();
+
+        let contacts = get_contacts("ship1".to_string(), State::from(&world), crew("ship1"))
+            .unwrap()
+            .into_inner()
+            .contacts;
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].id, "ship2");
+
+        // ship2 carries no sensors, so it sees nothing
+        let contacts = get_contacts("ship2".to_string(), State::from(&world), crew("ship2"))
+            .unwrap()
+            .into_inner()
+            .contacts;
+        assert!(contacts.is_empty());
+    }
+
     #[test]
     fn test_get_threats() {
         let world = setup_test_world();
         let ship = create_test_ship("ship1", "team1");
         world.write().unwrap().add_ship(ship);
 
-        let result = get_threats("ship1".to_string(), State::from(&world));
+        let result = get_threats("ship1".to_string(), State::from(&world), crew("ship1"));
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_get_navigation() {
-        let world = setup_test_world();
-        let ship = create_test_ship("ship1", "team1");
-        world.write().unwrap().add_ship(ship);
+        let world = setup_sensor_world();
 
         let result = get_navigation(
             "ship1".to_string(),
-            "target1".to_string(),
+            "ship2".to_string(),
             State::from(&world),
+            crew("ship1"),
         );
         assert!(result.is_ok());
+
+        // ship1 is not on ship2's sensors
+        let result = get_navigation(
+            "ship2".to_string(),
+            "ship1".to_string(),
+            State::from(&world),
+            crew("ship2"),
+        );
+        assert_eq!(result.err(), Some(Status::NotFound));
     }
 
     #[test]
     fn test_analyze_target() {
+        let world = setup_sensor_world();
+        let analyze = || {
+            analyze_target(
+                "ship1".to_string(),
+                Json(AnalyzeRequest {
+                    target_id: "ship2".to_string(),
+                }),
+                State::from(&world),
+                crew("ship1"),
+            )
+            .unwrap()
+            .into_inner()
+        };
+
+        // Nothing is known before a scan
+        let response = analyze();
+        assert_eq!(response.target_id, "ship2");
+        assert!(response.report.class.is_none());
+        assert!(response.scan.is_none());
+
+        world.write().unwrap().start_scan("ship1", "ship2").unwrap();
+        for _ in 0..150 {
+            world.write().unwrap().step_simulation();
+        }
+
+        let response = analyze();
+        assert_eq!(response.report.class.as_deref(), Some("corvette"));
+        assert_eq!(response.report.team_id.as_deref(), Some("team2"));
+        assert!(response.report.hull_percentage.is_none());
+        assert!(response.scan.is_some_and(|scan| scan.tiers_revealed == 2));
+    }
+
+    #[test]
+    fn test_analyze_target_not_on_sensors() {
         let world = setup_test_world();
-        let ship1 = create_test_ship("ship1", "team1");
-        let ship2 = create_test_ship("ship2", "team2");
-        world.write().unwrap().add_ship(ship1);
-        world.write().unwrap().add_ship(ship2);
+        world
+            .write()
+            .unwrap()
+            .add_ship(create_test_ship("ship1", "team1"));
+        world
+            .write()
+            .unwrap()
+            .add_ship(create_test_ship("ship2", "team2"));
 
         let request = Json(AnalyzeRequest {
             target_id: "ship2".to_string(),
         });
 
-        let result = analyze_target("ship1".to_string(), request, State::from(&world));
-        assert!(result.is_ok());
-
-        let response = result.unwrap().into_inner();
-        assert_eq!(response.target_id, "ship2");
+        let result = analyze_target(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
+        assert_eq!(result.err(), Some(Status::NotFound));
     }
 
     #[test]
@@ -353,7 +536,12 @@ mod tests {
             target_id: "ship2".to_string(),
         });
 
-        let result = analyze_target("ship1".to_string(), request, State::from(&world));
+        let result = analyze_target(
+            "ship1".to_string(),
+            request,
+            State::from(&world),
+            crew("ship1"),
+        );
         assert_eq!(result.err(), Some(Status::BadRequest));
     }
 }
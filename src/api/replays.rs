@@ -0,0 +1,226 @@
+//! Session recording API endpoints
+//!
+//! This module provides REST API endpoints for recording the running session
+//! so it can be re-run with `hyperion replay <file>`. Only the server
+//! [`Operator`] may use them, so players cannot start or cut short the record
+//! of a session.
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{State, get, post};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::api::auth::Operator;
+use crate::config::GameConfig;
+use crate::replay::ReplayStore;
+use crate::state::SharedGameWorld;
+
+/// Request to start recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartRecordingRequest {
+    pub name: String,
+}
+
+/// Whether a recording is in progress
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingStatusResponse {
+    pub recording: bool,
+    pub tick: u64,
+    /// Commands recorded so far
+    pub commands: usize,
+}
+
+/// A finished recording written to the replay directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingSavedResponse {
+    pub name: String,
+    /// File to pass to `hyperion replay`
+    pub path: String,
+    pub start_tick: u64,
+    pub end_tick: u64,
+    pub commands: usize,
+}
+
+/// Get the recording status
+#[get("/v1/replays/status")]
+pub fn recording_status(
+    world: &State<SharedGameWorld>,
+    _operator: Operator,
+) -> Json<RecordingStatusResponse> {
+    let world = world.read().unwrap();
+    let commands = world.recorded_command_count();
+    Json(RecordingStatusResponse {
+        recording: commands.is_some(),
+        tick: world.simulation.tick,
+        commands: commands.unwrap_or(0),
+    })
+}
+
+/// Start recording the running session
+#[post("/v1/replays/start", data = "<request>")]
+pub fn start_recording(
+    request: Json<StartRecordingRequest>,
+    config: &State<GameConfig>,
+    world: &State<SharedGameWorld>,
+    _operator: Operator,
+) -> Result<Json<RecordingStatusResponse>, Status> {
+    crate::save::check_name(&request.name).map_err(|_| Status::BadRequest)?;
+    let mut world = world.write().unwrap();
+    world
+        .start_recording(&request.name, config)
+        .map_err(|_| Status::Conflict)?;
+
+    Ok(Json(RecordingStatusResponse {
+        recording: true,
+        tick: world.simulation.tick,
+        commands: 0,
+    }))
+}
+
+/// Stop recording and write the recording to the replay directory
+#[post("/v1/replays/stop")]
+pub fn stop_recording(
+    world: &State<SharedGameWorld>,
+    store: &State<Arc<ReplayStore>>,
+    _operator: Operator,
+) -> Result<Json<RecordingSavedResponse>, Status> {
+    let recording = world
+        .write()
+        .unwrap()
+        .stop_recording()
+        .ok_or(Status::NotFound)?;
+    let path = store
+        .write(&recording)
+        .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Json(RecordingSavedResponse {
+        name: recording.name.clone(),
+        path: path.display().to_string(),
+        start_tick: recording.start_tick(),
+        end_tick: recording.end_tick,
+        commands: recording.commands.len(),
+    }))
+}
+
+/// Get all session recording routes
+pub fn routes() -> Vec<rocket::Route> {
+    rocket::routes![recording_status, start_recording, stop_recording]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::auth::test_utils::{operator, operator_token};
+    use crate::config::test_utils::create_test_game_config;
+    use crate::replay::Recording;
+    use crate::replay::test_utils::temp_replay_store;
+    use crate::state::GameWorld;
+    use rocket::Build;
+    use rocket::http::{ContentType, Header};
+    use rocket::local::blocking::Client;
+    use std::path::Path;
+
+    fn create_test_rocket(world: SharedGameWorld) -> rocket::Rocket<Build> {
+        rocket::build()
+            .manage(world)
+            .manage(create_test_game_config())
+            .manage(Arc::new(temp_replay_store()))
+            .manage(operator_token())
+            .mount("/", routes())
+    }
+
+    #[test]
+    fn test_start_and_stop_recording() {
+        let world = GameWorld::new_shared();
+        let client = Client::tracked(create_test_rocket(world.clone())).unwrap();
+
+        let response = client
+            .post("/v1/replays/start")
+            .header(ContentType::JSON)
+            .header(operator())
+            .body(r#"{"name":"duel"}"#)
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        // Only one recording at a time
+        let response = client
+            .post("/v1/replays/start")
+            .header(ContentType::JSON)
+            .header(operator())
+            .body(r#"{"name":"other"}"#)
+            .dispatch();
+        assert_eq!(response.status(), Status::Conflict);
+
+        for _ in 0..5 {
+            world.write().unwrap().step_simulation();
+        }
+        let status: RecordingStatusResponse = client
+            .get("/v1/replays/status")
+            .header(operator())
+            .dispatch()
+            .into_json()
+            .unwrap();
+        assert!(status.recording);
+        assert_eq!(status.tick, 5);
+
+        let saved: RecordingSavedResponse = client
+            .post("/v1/replays/stop")
+            .header(operator())
+            .dispatch()
+            .into_json()
+            .unwrap();
+        assert_eq!(saved.end_tick - saved.start_tick, 5);
+        let recording = Recording::read(Path::new(&saved.path)).unwrap();
+        assert_eq!(recording.name, "duel");
+
+        let response = client
+            .post("/v1/replays/stop")
+            .header(operator())
+            .dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn test_recording_requires_the_operator() {
+        let world = GameWorld::new_shared();
+        let token = {
+            let mut world = world.write().unwrap();
+            let player_id = world.register_player("alice".to_string()).unwrap();
+            world.issue_player_token(&player_id).unwrap()
+        };
+        let client = Client::tracked(create_test_rocket(world.clone())).unwrap();
+
+        let response = client
+            .post("/v1/replays/start")
+            .header(ContentType::JSON)
+            .body(r#"{"name":"duel"}"#)
+            .dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        let response = client
+            .post("/v1/replays/start")
+            .header(ContentType::JSON)
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
+            .body(r#"{"name":"duel"}"#)
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+        assert!(world.read().unwrap().recorded_command_count().is_none());
+
+        let response = client.post("/v1/replays/stop").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        let response = client.get("/v1/replays/status").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn test_invalid_recording_name() {
+        let client = Client::tracked(create_test_rocket(GameWorld::new_shared())).unwrap();
+        let response = client
+            .post("/v1/replays/start")
+            .header(ContentType::JSON)
+            .header(operator())
+            .body(r#"{"name":"../../etc"}"#)
+            .dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+}
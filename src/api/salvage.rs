@@ -0,0 +1,141 @@
+//! Wreck and salvage API endpoints
+//!
+//! Lists the wrecks destroyed ships leave behind and lets a ship's Science
+//! officer lock a tractor beam onto one and salvage it into the hold.
+
+use rocket::{Route, State, get, http::Status, post, routes, serde::json::Json};
+use serde::{Deserialize, Serialize};
+
+use crate::api::auth::{StationCrew, station};
+use crate::api::lookup::WorldLookup;
+use crate::config::GameConfig;
+use crate::salvage::Wreck;
+use crate::state::{SalvageReceipt, SharedGameWorld};
+
+/// Response for listing wrecks
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListWrecksResponse {
+    pub wrecks: Vec<Wreck>,
+    pub count: usize,
+}
+
+/// Request to lock or release a tractor beam
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TractorRequest {
+    pub wreck_id: String,
+    /// Lock on (true) or let go (false)
+    pub engaged: bool,
+}
+
+/// Outcome of a tractor beam order
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TractorResponse {
+    pub success: bool,
+    pub message: String,
+}
+
+/// Request to salvage a wreck
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SalvageRequest {
+    pub wreck_id: String,
+}
+
+/// Outcome of a salvage order
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SalvageResponse {
+    pub success: bool,
+    pub message: String,
+    pub receipt: Option<SalvageReceipt>,
+}
+
+/// GET /v1/wrecks - List all wrecks
+#[get("/v1/wrecks")]
+pub fn list_wrecks(world: &State<SharedGameWorld>) -> Json<ListWrecksResponse> {
+    let world = world.read().unwrap();
+    let mut wrecks: Vec<Wreck> = world.wrecks().values().cloned().collect();
+    wrecks.sort_by(|a, b| a.id.cmp(&b.id));
+
+    Json(ListWrecksResponse {
+        count: wrecks.len(),
+        wrecks,
+    })
+}
+
+/// GET /v1/wrecks/<id> - Get a wreck and what is left in it
+#[get("/v1/wrecks/<id>")]
+pub fn get_wreck(id: &str, world: &State<SharedGameWorld>) -> Result<Json<Wreck>, Status> {
+    let world = world.read().unwrap();
+    Ok(Json(world.find_wreck(id)?.clone()))
+}
+
+/// POST /v1/ships/<ship_id>/tractor - Lock or release a tractor beam
+///
+/// An order that cannot be carried out comes back with `success: false`
+/// and the reason.
+#[post("/v1/ships/<ship_id>/tractor", data = "<request>")]
+pub fn set_tractor(
+    ship_id: String,
+    request: Json<TractorRequest>,
+    world: &State<SharedGameWorld>,
+    _crew: StationCrew<station::Science>,
+) -> Result<Json<TractorResponse>, Status> {
+    let mut world = world.write().unwrap();
+    world.find_wreck(&request.wreck_id)?;
+
+    let response = match world.set_tractor(&ship_id, &request.wreck_id, request.engaged) {
+        Ok(()) => TractorResponse {
+            success: true,
+            message: if request.engaged {
+                "Tractor beam locked".to_string()
+            } else {
+                "Tractor beam released".to_string()
+            },
+        },
+        Err(message) => TractorResponse {
+            success: false,
+            message,
+        },
+    };
+    Ok(Json(response))
+}
+
+/// POST /v1/ships/<ship_id>/salvage - Salvage a wreck held in the tractor
+/// beam
+///
+/// A salvage that cannot go through comes back with `success: false` and
+/// the reason.
+#[post("/v1/ships/<ship_id>/salvage", data = "<request>")]
+pub fn salvage_wreck(
+    ship_id: String,
+    request: Json<SalvageRequest>,
+    world: &State<SharedGameWorld>,
+    config: &State<GameConfig>,
+    _crew: StationCrew<station::Science>,
+) -> Result<Json<SalvageResponse>, Status> {
+    let mut world = world.write().unwrap();
+    world.find_wreck(&request.wreck_id)?;
+
+    let response = match world.salvage_wreck(&ship_id, &request.wreck_id, config) {
+        Ok(receipt) => SalvageResponse {
+            success: true,
+            message: format!(
+                "Salvaged {} cargo, {} rounds, and {} modules",
+                receipt.cargo.values().sum::<u32>(),
+                receipt.ammunition.values().sum::<u32>(),
+                receipt.modules.len()
+            ),
+            receipt: Some(receipt),
+        },
+        Err(message) => SalvageResponse {
+            success: false,
+            message,
+            receipt: None,
+        },
+    };
+    Ok(Json(response))
+}
+
+/// Returns all salvage API routes
+pub fn routes() -> Vec<Route> {
+    routes![list_wrecks, get_wreck, set_tractor, salvage_wreck]
+}
@@ -0,0 +1,183 @@
+//! Saved session API endpoints
+//!
+//! This module provides REST API endpoints for saving the running session to
+//! the server's save directory and loading it back. Only the server
+//! [`Operator`] may use them.
+
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{State, get, post};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+use crate::api::auth::Operator;
+use crate::api::generation::UniverseState;
+use crate::config::GameConfig;
+use crate::save::{SaveFile, SaveStore, SaveSummary};
+use crate::state::SharedGameWorld;
+
+/// Request to save the running session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveRequest {
+    pub name: String,
+}
+
+/// List saved sessions, newest first
+#[get("/v1/saves")]
+pub fn list_saves(store: &State<Arc<SaveStore>>, _operator: Operator) -> Json<Vec<SaveSummary>> {
+    Json(store.list())
+}
+
+/// Save the running session, replacing any save with the same name
+#[post("/v1/saves", data = "<request>")]
+pub fn create_save(
+    request: Json<SaveRequest>,
+    world: &State<SharedGameWorld>,
+    universe: &State<Arc<RwLock<UniverseState>>>,
+    store: &State<Arc<SaveStore>>,
+    _operator: Operator,
+) -> Result<Json<SaveSummary>, Status> {
+    let save = {
+        let world = world.read().unwrap();
+        let universe = universe.read().unwrap();
+        SaveFile::capture(&request.name, &world, &universe)
+    };
+    store.write(&save).map_err(|_| Status::BadRequest)?;
+
+    Ok(Json(save.summary()))
+}
+
+/// Replace the running session with a saved one
+#[post("/v1/saves/<name>/load")]
+pub fn load_save(
+    name: &str,
+    config: &State<GameConfig>,
+    world: &State<SharedGameWorld>,
+    universe: &State<Arc<RwLock<UniverseState>>>,
+    store: &State<Arc<SaveStore>>,
+    _operator: Operator,
+) -> Result<Json<SaveSummary>, Status> {
+    let save = store.read(name).map_err(|_| Status::NotFound)?;
+    let summary = save.summary();
+    save.restore(config, world, universe)
+        .map_err(|_| Status::UnprocessableEntity)?;
+
+    Ok(Json(summary))
+}
+
+/// Get all saved session routes
+pub fn routes() -> Vec<rocket::Route> {
+    rocket::routes![list_saves, create_save, load_save]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::auth::test_utils::{operator, operator_token};
+    use crate::config::test_utils::create_test_game_config;
+    use crate::save::test_utils::temp_store;
+    use crate::state::GameWorld;
+    use rocket::Build;
+    use rocket::http::{ContentType, Header};
+    use rocket::local::blocking::Client;
+
+    fn create_test_rocket(world: SharedGameWorld) -> rocket::Rocket<Build> {
+        rocket::build()
+            .manage(world)
+            .manage(create_test_game_config())
+            .manage(Arc::new(RwLock::new(UniverseState::new())))
+            .manage(Arc::new(temp_store()))
+            .manage(operator_token())
+            .mount("/", routes())
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let world = GameWorld::new_shared();
+        world
+            .write()
+            .unwrap()
+            .register_player("alice".to_string())
+            .unwrap();
+        let client = Client::tracked(create_test_rocket(world.clone())).unwrap();
+
+        let response = client
+            .post("/v1/saves")
+            .header(ContentType::JSON)
+            .header(operator())
+            .body(r#"{"name":"before"}"#)
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        world
+            .write()
+            .unwrap()
+            .register_player("bob".to_string())
+            .unwrap();
+        let response = client
+            .post("/v1/saves/before/load")
+            .header(operator())
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let world = world.read().unwrap();
+        assert!(world.get_player_by_name("alice").is_some());
+        assert!(world.get_player_by_name("bob").is_none());
+
+        let saves: Vec<SaveSummary> = client
+            .get("/v1/saves")
+            .header(operator())
+            .dispatch()
+            .into_json()
+            .unwrap();
+        assert_eq!(saves.len(), 1);
+        assert_eq!(saves[0].name, "before");
+    }
+
+    #[test]
+    fn test_saves_require_the_operator() {
+        let world = GameWorld::new_shared();
+        let token = {
+            let mut world = world.write().unwrap();
+            let player_id = world.register_player("alice".to_string()).unwrap();
+            world.issue_player_token(&player_id).unwrap()
+        };
+        let client = Client::tracked(create_test_rocket(world)).unwrap();
+
+        let response = client.get("/v1/saves").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        let response = client
+            .post("/v1/saves/before/load")
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+        let response = client
+            .post("/v1/saves")
+            .header(ContentType::JSON)
+            .body(r#"{"name":"before"}"#)
+            .dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[test]
+    fn test_load_missing_save() {
+        let client = Client::tracked(create_test_rocket(GameWorld::new_shared())).unwrap();
+        let response = client
+            .post("/v1/saves/nothing/load")
+            .header(operator())
+            .dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn test_invalid_save_name() {
+        let client = Client::tracked(create_test_rocket(GameWorld::new_shared())).unwrap();
+        let response = client
+            .post("/v1/saves")
+            .header(ContentType::JSON)
+            .header(operator())
+            .body(r#"{"name":"../../etc"}"#)
+            .dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+}
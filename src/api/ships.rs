@@ -1,11 +1,14 @@
 //! Ship compilation API endpoints
 //!
-//! Provides REST API endpoints for compiling blueprints into active ships.
+//! Provides REST API endpoints for compiling blueprints into active ships
+//! and refitting them while docked.
 
+use crate::api::auth::{AuthenticatedPlayer, ShipTeamMember, require_team_member};
 use crate::api::lookup::WorldLookup;
 use crate::compiler;
 use crate::config::GameConfig;
-use crate::state::SharedGameWorld;
+use crate::models::ModuleInstance;
+use crate::state::{RefitReceipt, SharedGameWorld};
 use rocket::{State, get, http::Status, post, routes, serde::json::Json};
 use serde::{Deserialize, Serialize};
 
@@ -26,6 +29,21 @@ pub struct CompileResponse {
     pub team_id: String,
 }
 
+/// Request to refit a docked ship
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RefitRequest {
+    /// The ship's complete module list after the refit
+    pub modules: Vec<ModuleInstance>,
+}
+
+/// Outcome of a refit
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RefitResponse {
+    pub success: bool,
+    pub message: String,
+    pub receipt: Option<RefitReceipt>,
+}
+
 /// Response for ship list
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ListShipsResponse {
@@ -52,14 +70,20 @@ pub struct ShipResponse {
 // ==================== API Endpoints ====================
 
 /// POST /v1/ships/compile - Compile a blueprint into an active ship
+///
+/// Compiling spends the owning team's credits, so only its members may do it.
 #[post("/v1/ships/compile", data = "<request>")]
 pub fn compile_ship(
     request: Json<CompileRequest>,
     world: &State<SharedGameWorld>,
     config: &State<GameConfig>,
+    player: AuthenticatedPlayer,
 ) -> Result<Json<CompileResponse>, Status> {
     let mut world = world.write().unwrap();
 
+    let team_id = world.find_blueprint(&request.blueprint_id)?.team_id.clone();
+    require_team_member(&world, &team_id, &player.0).map_err(|error| error.status)?;
+
     // Compile and spawn ship
     let ship_id = compiler::compile_and_spawn(&request.blueprint_id, &mut world, config)
         .map_err(|_| Status::BadRequest)?;
@@ -77,22 +101,72 @@ pub fn compile_ship(
     }))
 }
 
-/// GET /v1/ships - List all active ships
+/// POST /v1/ships/<id>/refit - Swap the modules of a docked ship
+///
+/// Refitting spends the owning team's credits, so only its members may do
+/// it. A refit that cannot go through comes back with `success: false` and
+/// the reason.
+#[post("/v1/ships/<id>/refit", data = "<request>")]
+pub fn refit_ship(
+    id: &str,
+    request: Json<RefitRequest>,
+    world: &State<SharedGameWorld>,
+    config: &State<GameConfig>,
+    player: AuthenticatedPlayer,
+) -> Result<Json<RefitResponse>, Status> {
+    let mut world = world.write().unwrap();
+
+    let team_id = world.find_ship(id)?.team_id.clone();
+    require_team_member(&world, &team_id, &player.0).map_err(|error| error.status)?;
+
+    let response = match world.refit_ship(id, request.into_inner().modules, config) {
+        Ok(receipt) => RefitResponse {
+            success: true,
+            message: format!(
+                "Installed {} modules, removed {}",
+                receipt.installed.len(),
+                receipt.removed.len()
+            ),
+            receipt: Some(receipt),
+        },
+        Err(message) => RefitResponse {
+            success: false,
+            message,
+            receipt: None,
+        },
+    };
+    Ok(Json(response))
+}
+
+/// GET /v1/ships - List the active ships of the caller's teams
+///
+/// Other teams' ships are only seen through a ship's sensors and scans.
 #[get("/v1/ships")]
-pub fn list_ships(world: &State<SharedGameWorld>) -> Json<ListShipsResponse> {
+pub fn list_ships(
+    world: &State<SharedGameWorld>,
+    player: AuthenticatedPlayer,
+) -> Json<ListShipsResponse> {
     let world = world.read().unwrap();
     let ships = world
         .get_all_ships()
         .iter()
+        .filter(|ship| world.is_team_member(&ship.team_id, &player.0.id))
         .map(|ship| ship_to_response(ship))
         .collect();
 
     Json(ListShipsResponse { ships })
 }
 
-/// GET /v1/ships/<id> - Get details of a specific ship
+/// GET /v1/ships/<id> - Get details of one of the caller's teams' ships
+///
+/// Other teams' ships are only revealed through the Science station's scan
+/// reports.
 #[get("/v1/ships/<id>")]
-pub fn get_ship(id: &str, world: &State<SharedGameWorld>) -> Result<Json<ShipResponse>, Status> {
+pub fn get_ship(
+    id: &str,
+    world: &State<SharedGameWorld>,
+    _member: ShipTeamMember,
+) -> Result<Json<ShipResponse>, Status> {
     let world = world.read().unwrap();
 
     let ship = world.find_ship(id)?;
@@ -121,7 +195,7 @@ fn ship_to_response(ship: &crate::models::Ship) -> ShipResponse {
 
 /// Aggregate all ship routes
 pub fn routes() -> Vec<rocket::Route> {
-    routes![compile_ship, list_ships, get_ship,]
+    routes![compile_ship, refit_ship, list_ships, get_ship,]
 }
 
 #[cfg(test)]
@@ -131,6 +205,7 @@ mod tests {
     use crate::config::{Faction, GameConfig};
     use crate::state::GameWorld;
     use rocket::Build;
+    use rocket::http::Header;
     use rocket::local::blocking::Client;
 
     // API tests exercise the "test_cruiser" ship class id and "alliance" faction.
@@ -156,11 +231,31 @@ mod tests {
             .mount("/", crate::api::blueprints::routes())
     }
 
+    /// Register a player and return their bearer token header
+    fn register(client: &Client, name: &str) -> Header<'static> {
+        let player: serde_json::Value = client
+            .post("/v1/players")
+            .json(&serde_json::json!({ "name": name }))
+            .dispatch()
+            .into_json()
+            .unwrap();
+        Header::new(
+            "Authorization",
+            format!("Bearer {}", player["token"].as_str().unwrap()),
+        )
+    }
+
     #[test]
     fn test_list_ships_empty() {
         let client = Client::tracked(create_test_rocket()).expect("valid rocket instance");
 
         let response = client.get("/v1/ships").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client
+            .get("/v1/ships")
+            .header(register(&client, "Lurker"))
+            .dispatch();
         assert_eq!(response.status(), Status::Ok);
 
         let ships: ListShipsResponse = response.into_json().unwrap();
@@ -202,10 +297,12 @@ mod tests {
             .dispatch();
         let player_json: serde_json::Value = player_response.into_json().unwrap();
         let player_id = player_json["id"].as_str().unwrap();
+        let token = player_json["token"].as_str().unwrap();
 
         // Add player to team
         client
             .patch(format!("/v1/teams/{}", team_id))
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
             .json(&serde_json::json!({"player_id": player_id}))
             .dispatch();
 
@@ -224,12 +321,14 @@ mod tests {
         // Join blueprint
         client
             .post(format!("/v1/blueprints/{}/join", blueprint_id))
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
             .json(&serde_json::json!({"player_id": player_id}))
             .dispatch();
 
         // Assign roles
         client
             .patch(format!("/v1/blueprints/{}/roles", blueprint_id))
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
             .json(&serde_json::json!({
                 "player_id": player_id,
                 "roles": ["captain"]
@@ -239,12 +338,14 @@ mod tests {
         // Mark ready
         client
             .post(format!("/v1/blueprints/{}/ready", blueprint_id))
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
             .json(&serde_json::json!({"player_id": player_id}))
             .dispatch();
 
         // Compile ship
         let compile_response = client
             .post("/v1/ships/compile")
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
             .json(&CompileRequest {
                 blueprint_id: blueprint_id.to_string(),
             })
@@ -255,11 +356,22 @@ mod tests {
         assert_eq!(ship.name, "Enterprise");
         assert_eq!(ship.class, "test_cruiser");
 
-        // Verify ship appears in list
-        let list_response = client.get("/v1/ships").dispatch();
+        // Verify ship appears in the team's list
+        let list_response = client
+            .get("/v1/ships")
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
+            .dispatch();
         let ships: ListShipsResponse = list_response.into_json().unwrap();
         assert_eq!(ships.ships.len(), 1);
         assert_eq!(ships.ships[0].name, "Enterprise");
+
+        // ...and not in anyone else's
+        let list_response = client
+            .get("/v1/ships")
+            .header(register(&client, "Spy"))
+            .dispatch();
+        let ships: ListShipsResponse = list_response.into_json().unwrap();
+        assert!(ships.ships.is_empty());
     }
 
     #[test]
@@ -280,9 +392,11 @@ mod tests {
             .dispatch();
         let player_json: serde_json::Value = player_response.into_json().unwrap();
         let player_id = player_json["id"].as_str().unwrap();
+        let token = player_json["token"].as_str().unwrap();
 
         client
             .patch(format!("/v1/teams/{}", team_id))
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
             .json(&serde_json::json!({"player_id": player_id}))
             .dispatch();
 
@@ -299,11 +413,13 @@ mod tests {
 
         client
             .post(format!("/v1/blueprints/{}/join", blueprint_id))
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
             .json(&serde_json::json!({"player_id": player_id}))
             .dispatch();
 
         client
             .patch(format!("/v1/blueprints/{}/roles", blueprint_id))
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
             .json(&serde_json::json!({
                 "player_id": player_id,
                 "roles": ["captain"]
@@ -312,11 +428,13 @@ mod tests {
 
         client
             .post(format!("/v1/blueprints/{}/ready", blueprint_id))
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
             .json(&serde_json::json!({"player_id": player_id}))
             .dispatch();
 
         let compile_response = client
             .post("/v1/ships/compile")
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
             .json(&CompileRequest {
                 blueprint_id: blueprint_id.to_string(),
             })
@@ -324,13 +442,23 @@ mod tests {
         let ship: CompileResponse = compile_response.into_json().unwrap();
 
         // Get ship by ID
-        let get_response = client.get(format!("/v1/ships/{}", ship.ship_id)).dispatch();
+        let get_response = client
+            .get(format!("/v1/ships/{}", ship.ship_id))
+            .header(Header::new("Authorization", format!("Bearer {}", token)))
+            .dispatch();
         assert_eq!(get_response.status(), Status::Ok);
 
         let ship_details: ShipResponse = get_response.into_json().unwrap();
         assert_eq!(ship_details.name, "Voyager");
         assert_eq!(ship_details.max_hull, 1000.0);
         assert_eq!(ship_details.max_shields, 500.0);
+
+        // Other teams only learn about it through scans
+        let get_response = client
+            .get(format!("/v1/ships/{}", ship.ship_id))
+            .header(register(&client, "Spy"))
+            .dispatch();
+        assert_eq!(get_response.status(), Status::Forbidden);
     }
 
     #[test]
@@ -5,11 +5,19 @@
 use rocket::serde::json::Json;
 use rocket::{Route, State, delete, get, post, routes};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::{Arc, RwLock};
 use uuid::Uuid;
 
-use crate::state::GameWorld;
-use crate::stations::{DockingStatus, ServiceRequest, ServiceResponse, Station, StationSize};
+use crate::api::auth::{AuthenticatedPlayer, Operator, require_team_member};
+use crate::api::lookup::WorldLookup;
+use crate::config::GameConfig;
+use crate::generation::StationType;
+use crate::market::{MarketQuote, TradeReceipt, TradeSide};
+use crate::state::{GameWorld, SalvageSale};
+use crate::stations::{
+    DockingStatus, ServiceRequest, ServiceResponse, Station, StationSize, StockedModule,
+};
 
 /// Request to create a new station
 #[derive(Debug, Serialize, Deserialize)]
@@ -18,6 +26,8 @@ pub struct CreateStationRequest {
     pub position: [f64; 3],
     pub faction: String,
     pub size: Option<StationSize>,
+    #[serde(default)]
+    pub station_type: Option<StationType>,
 }
 
 /// Response with station details
@@ -28,6 +38,7 @@ pub struct StationResponse {
     pub position: [f64; 3],
     pub faction: String,
     pub size: StationSize,
+    pub station_type: StationType,
     pub max_docked_ships: usize,
     pub docked_ships: Vec<String>,
     pub available_bays: usize,
@@ -50,6 +61,7 @@ impl From<&Station> for StationResponse {
             position: station.position,
             faction: station.faction.clone(),
             size: station.size,
+            station_type: station.station_type,
             max_docked_ships: station.max_docked_ships,
             docked_ships: station
                 .docked_ships
@@ -101,7 +113,7 @@ fn create_station(
 ) -> Json<StationResponse> {
     let mut world = game_world.write().unwrap();
 
-    let station = if let Some(size) = request.size {
+    let mut station = if let Some(size) = request.size {
         Station::with_size(
             request.name.clone(),
             request.position,
@@ -115,6 +127,9 @@ fn create_station(
             request.faction.clone(),
         )
     };
+    if let Some(station_type) = request.station_type {
+        station.station_type = station_type;
+    }
 
     let response = StationResponse::from(&station);
     world.register_station(station);
@@ -122,11 +137,12 @@ fn create_station(
     Json(response)
 }
 
-/// Delete a station
+/// Delete a station; reserved for the server operator
 #[delete("/v1/stations/<id>")]
 fn delete_station(
     id: &str,
     game_world: &State<Arc<RwLock<GameWorld>>>,
+    _operator: Operator,
 ) -> Result<Json<serde_json::Value>, rocket::http::Status> {
     let mut world = game_world.write().unwrap();
 
@@ -139,10 +155,11 @@ fn delete_station(
 }
 
 /// Docking request
+///
+/// The station judges the request by the faction of the ship's team.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DockingRequest {
     pub ship_id: String,
-    pub ship_faction: String,
 }
 
 /// Docking response
@@ -154,85 +171,100 @@ pub struct DockingResponse {
 }
 
 /// Request docking at a station
+///
+/// Only members of the ship's team may ask, and only within request range.
 #[post("/v1/stations/<station_id>/dock", data = "<request>")]
 fn request_docking(
     station_id: &str,
     request: Json<DockingRequest>,
     game_world: &State<Arc<RwLock<GameWorld>>>,
+    player: AuthenticatedPlayer,
 ) -> Result<Json<DockingResponse>, rocket::http::Status> {
     let mut world = game_world.write().unwrap();
 
-    let station = world
-        .get_station_mut(station_id)
-        .ok_or(rocket::http::Status::NotFound)?;
-
     let ship_id =
         Uuid::parse_str(&request.ship_id).map_err(|_| rocket::http::Status::BadRequest)?;
+    require_ship_team(&world, &request.ship_id, &player)?;
 
-    let approved = station.request_docking(ship_id, &request.ship_faction);
-    let status = station.get_docking_status(ship_id);
-
-    let message = if approved {
-        "Docking request approved".to_string()
-    } else {
-        match status {
-            Some(DockingStatus::Denied) => {
-                if station.is_hostile_to(&request.ship_faction) {
-                    "Docking denied: Hostile faction".to_string()
-                } else {
-                    "Docking denied: Station is full".to_string()
-                }
-            }
-            _ => "Docking denied".to_string(),
-        }
-    };
+    request_docking_at(&mut world, station_id, ship_id)
+        .map(Json)
+        .ok_or(rocket::http::Status::NotFound)
+}
 
-    Ok(Json(DockingResponse {
-        approved,
-        status,
-        message,
-    }))
+/// Ask a station for docking clearance and describe the outcome
+///
+/// See [`GameWorld::request_docking`]. Shared by the REST and GraphQL
+/// docking endpoints; `None` if the station does not exist.
+pub fn request_docking_at(
+    world: &mut GameWorld,
+    station_id: &str,
+    ship_id: Uuid,
+) -> Option<DockingResponse> {
+    world.get_station(station_id)?;
+    let outcome = world.request_docking(&ship_id.to_string(), station_id);
+    let status = world.get_station(station_id)?.get_docking_status(ship_id);
+
+    Some(match outcome {
+        Ok(()) => DockingResponse {
+            approved: true,
+            status,
+            message: "Docking request approved".to_string(),
+        },
+        Err(message) => DockingResponse {
+            approved: false,
+            status,
+            message,
+        },
+    })
 }
 
 /// Complete docking (ship has arrived)
+///
+/// Only members of the ship's team may confirm arrival, and only once the
+/// ship is inside final range and slow enough; the simulation also docks
+/// such ships on its own.
 #[post("/v1/stations/<station_id>/dock/<ship_id>/complete")]
 fn complete_docking(
     station_id: &str,
     ship_id: &str,
     game_world: &State<Arc<RwLock<GameWorld>>>,
+    player: AuthenticatedPlayer,
 ) -> Result<Json<serde_json::Value>, rocket::http::Status> {
     let mut world = game_world.write().unwrap();
 
-    let station = world
-        .get_station_mut(station_id)
-        .ok_or(rocket::http::Status::NotFound)?;
-
-    let ship_uuid = Uuid::parse_str(ship_id).map_err(|_| rocket::http::Status::BadRequest)?;
+    if world.get_station(station_id).is_none() {
+        return Err(rocket::http::Status::NotFound);
+    }
+    Uuid::parse_str(ship_id).map_err(|_| rocket::http::Status::BadRequest)?;
+    require_ship_team(&world, ship_id, &player)?;
 
-    if station.complete_docking(ship_uuid) {
-        Ok(Json(serde_json::json!({
+    match world.complete_docking(ship_id, station_id) {
+        Ok(()) => Ok(Json(serde_json::json!({
             "message": "Docking completed successfully"
-        })))
-    } else {
-        Err(rocket::http::Status::BadRequest)
+        }))),
+        Err(_) => Err(rocket::http::Status::BadRequest),
     }
 }
 
 /// Undock from a station
+///
+/// Only members of the ship's team may undock it.
 #[post("/v1/stations/<station_id>/undock/<ship_id>")]
 fn undock_ship(
     station_id: &str,
     ship_id: &str,
     game_world: &State<Arc<RwLock<GameWorld>>>,
+    player: AuthenticatedPlayer,
 ) -> Result<Json<serde_json::Value>, rocket::http::Status> {
     let mut world = game_world.write().unwrap();
 
-    let station = world
-        .get_station_mut(station_id)
-        .ok_or(rocket::http::Status::NotFound)?;
-
+    if world.get_station(station_id).is_none() {
+        return Err(rocket::http::Status::NotFound);
+    }
     let ship_uuid = Uuid::parse_str(ship_id).map_err(|_| rocket::http::Status::BadRequest)?;
+    require_ship_team(&world, ship_id, &player)?;
 
+    let station = world.get_station_mut(station_id).unwrap();
     if station.undock_ship(ship_uuid) {
         Ok(Json(serde_json::json!({
             "message": "Undocking initiated"
@@ -243,119 +275,291 @@ fn undock_ship(
 }
 
 /// Request a service at a station
+///
+/// Services are billed to the ship's team, so only its members may order
+/// them.
 #[post("/v1/stations/<station_id>/services/<ship_id>", data = "<request>")]
 fn request_service(
     station_id: &str,
     ship_id: &str,
     request: Json<ServiceRequest>,
     game_world: &State<Arc<RwLock<GameWorld>>>,
+    config: &State<GameConfig>,
+    player: AuthenticatedPlayer,
 ) -> Result<Json<ServiceResponse>, rocket::http::Status> {
-    let world = game_world.read().unwrap();
+    let mut world = game_world.write().unwrap();
 
-    let station = world
-        .get_station(station_id)
-        .ok_or(rocket::http::Status::NotFound)?;
+    if world.get_station(station_id).is_none() {
+        return Err(rocket::http::Status::NotFound);
+    }
 
     let ship_uuid = Uuid::parse_str(ship_id).map_err(|_| rocket::http::Status::BadRequest)?;
+    require_ship_team(&world, ship_id, &player)?;
+
+    Ok(Json(process_service(
+        &mut world,
+        station_id,
+        ship_uuid,
+        request.into_inner(),
+        config,
+    )))
+}
+
+/// Check that the caller is a member of the team flying a ship
+///
+/// Docking acts for that team, and station services, trades and salvage
+/// sales bill or pay it.
+fn require_ship_team(
+    world: &GameWorld,
+    ship_id: &str,
+    player: &AuthenticatedPlayer,
+) -> Result<(), rocket::http::Status> {
+    let team_id = world.find_ship(ship_id)?.team_id.clone();
+    require_team_member(world, &team_id, &player.0).map_err(|error| error.status)
+}
 
-    // Check if ship is docked
-    if !station.is_ship_docked(ship_uuid) {
-        return Ok(Json(ServiceResponse {
+/// Perform a service for a ship docked at a station and bill its team
+///
+/// Shared by the REST and GraphQL service endpoints. A service that cannot
+/// be performed comes back with `success: false` and the reason.
+pub fn process_service(
+    world: &mut GameWorld,
+    station_id: &str,
+    ship_id: Uuid,
+    request: ServiceRequest,
+    config: &GameConfig,
+) -> ServiceResponse {
+    world
+        .perform_station_service(station_id, &ship_id.to_string(), request, config)
+        .unwrap_or_else(|message| ServiceResponse {
             success: false,
-            message: "Ship must be docked to request services".to_string(),
+            message,
             cost: 0.0,
-        }));
+        })
+}
+
+/// A station's market
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarketResponse {
+    pub station_id: String,
+    pub listings: Vec<MarketQuote>,
+}
+
+/// Order to buy or sell a commodity
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TradeRequest {
+    pub ship_id: String,
+    pub commodity_id: String,
+    pub quantity: u32,
+}
+
+/// Outcome of a trade order
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TradeResponse {
+    pub success: bool,
+    pub message: String,
+    pub receipt: Option<TradeReceipt>,
+}
+
+/// Get prices and stock at a station's market
+#[get("/v1/stations/<station_id>/market")]
+fn get_market(
+    station_id: &str,
+    game_world: &State<Arc<RwLock<GameWorld>>>,
+) -> Result<Json<MarketResponse>, rocket::http::Status> {
+    let world = game_world.read().unwrap();
+
+    if world.get_station(station_id).is_none() {
+        return Err(rocket::http::Status::NotFound);
     }
 
-    // Process service request (simplified for now)
-    let response = match request.into_inner() {
-        ServiceRequest::RepairModule { module_id } => {
-            if !station.services.repair {
-                ServiceResponse {
-                    success: false,
-                    message: "Repair services not available".to_string(),
-                    cost: 0.0,
-                }
-            } else {
-                ServiceResponse {
-                    success: true,
-                    message: format!("Repaired module {}", module_id),
-                    cost: 100.0 * station.services.repair_cost,
-                }
-            }
-        }
-        ServiceRequest::RepairAll => {
-            if !station.services.repair {
-                ServiceResponse {
-                    success: false,
-                    message: "Repair services not available".to_string(),
-                    cost: 0.0,
-                }
-            } else {
-                ServiceResponse {
-                    success: true,
-                    message: "Repaired all modules".to_string(),
-                    cost: 500.0 * station.services.repair_cost,
-                }
-            }
-        }
-        ServiceRequest::Refuel { amount } => {
-            if !station.services.refuel {
-                ServiceResponse {
-                    success: false,
-                    message: "Refuel services not available".to_string(),
-                    cost: 0.0,
-                }
-            } else {
-                ServiceResponse {
-                    success: true,
-                    message: format!("Refueled {} units", amount),
-                    cost: amount * station.services.refuel_cost,
-                }
-            }
-        }
-        ServiceRequest::RearmWeapon {
-            weapon_id,
-            ammunition_type,
-            quantity,
-        } => {
-            if !station.services.rearm {
-                ServiceResponse {
-                    success: false,
-                    message: "Rearm services not available".to_string(),
-                    cost: 0.0,
-                }
-            } else {
-                ServiceResponse {
-                    success: true,
-                    message: format!(
-                        "Rearmed {} with {} x {}",
-                        weapon_id, quantity, ammunition_type
-                    ),
-                    cost: (quantity as f32) * 10.0 * station.services.rearm_cost,
-                }
-            }
-        }
-        ServiceRequest::RearmAll => {
-            if !station.services.rearm {
-                ServiceResponse {
-                    success: false,
-                    message: "Rearm services not available".to_string(),
-                    cost: 0.0,
-                }
-            } else {
-                ServiceResponse {
-                    success: true,
-                    message: "Rearmed all weapons".to_string(),
-                    cost: 1000.0 * station.services.rearm_cost,
-                }
-            }
-        }
+    let listings = world
+        .market_quotes(station_id)
+        .map_err(|_| rocket::http::Status::BadRequest)?;
+
+    Ok(Json(MarketResponse {
+        station_id: station_id.to_string(),
+        listings,
+    }))
+}
+
+/// Buy a commodity from a station's market
+#[post("/v1/stations/<station_id>/market/buy", data = "<request>")]
+fn buy_commodity(
+    station_id: &str,
+    request: Json<TradeRequest>,
+    game_world: &State<Arc<RwLock<GameWorld>>>,
+    config: &State<GameConfig>,
+    player: AuthenticatedPlayer,
+) -> Result<Json<TradeResponse>, rocket::http::Status> {
+    trade(
+        station_id,
+        TradeSide::Buy,
+        request,
+        game_world,
+        config,
+        player,
+    )
+}
+
+/// Sell a commodity to a station's market
+#[post("/v1/stations/<station_id>/market/sell", data = "<request>")]
+fn sell_commodity(
+    station_id: &str,
+    request: Json<TradeRequest>,
+    game_world: &State<Arc<RwLock<GameWorld>>>,
+    config: &State<GameConfig>,
+    player: AuthenticatedPlayer,
+) -> Result<Json<TradeResponse>, rocket::http::Status> {
+    trade(
+        station_id,
+        TradeSide::Sell,
+        request,
+        game_world,
+        config,
+        player,
+    )
+}
+
+/// Place a trade order for a ship docked at a station
+///
+/// Only members of the ship's team may trade, since the team pays or is
+/// paid. A trade that cannot go through comes back with `success: false` and
+/// the reason.
+fn trade(
+    station_id: &str,
+    side: TradeSide,
+    request: Json<TradeRequest>,
+    game_world: &State<Arc<RwLock<GameWorld>>>,
+    config: &State<GameConfig>,
+    player: AuthenticatedPlayer,
+) -> Result<Json<TradeResponse>, rocket::http::Status> {
+    let mut world = game_world.write().unwrap();
+
+    if world.get_station(station_id).is_none() {
+        return Err(rocket::http::Status::NotFound);
+    }
+
+    let ship_uuid =
+        Uuid::parse_str(&request.ship_id).map_err(|_| rocket::http::Status::BadRequest)?;
+    require_ship_team(&world, &request.ship_id, &player)?;
+
+    let response = match world.trade_commodity(
+        station_id,
+        &ship_uuid.to_string(),
+        side,
+        &request.commodity_id,
+        request.quantity,
+        config,
+    ) {
+        Ok(receipt) => TradeResponse {
+            success: true,
+            message: match side {
+                TradeSide::Buy => format!("Bought {} {}", receipt.quantity, receipt.commodity_id),
+                TradeSide::Sell => format!("Sold {} {}", receipt.quantity, receipt.commodity_id),
+            },
+            receipt: Some(receipt),
+        },
+        Err(message) => TradeResponse {
+            success: false,
+            message,
+            receipt: None,
+        },
     };
+    Ok(Json(response))
+}
+
+/// Order to sell a salvaged module
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SalvageSaleRequest {
+    pub ship_id: String,
+    /// Instance ID of the salvaged module
+    pub module_id: String,
+}
 
+/// Outcome of a salvage sale
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SalvageSaleResponse {
+    pub success: bool,
+    pub message: String,
+    pub sale: Option<SalvageSale>,
+}
+
+/// Sell a salvaged module to a station
+///
+/// Only members of the ship's team may sell, since the team is paid. A sale
+/// that cannot go through comes back with `success: false` and the reason.
+#[post("/v1/stations/<station_id>/salvage/sell", data = "<request>")]
+fn sell_salvage(
+    station_id: &str,
+    request: Json<SalvageSaleRequest>,
+    game_world: &State<Arc<RwLock<GameWorld>>>,
+    config: &State<GameConfig>,
+    player: AuthenticatedPlayer,
+) -> Result<Json<SalvageSaleResponse>, rocket::http::Status> {
+    let mut world = game_world.write().unwrap();
+
+    if world.get_station(station_id).is_none() {
+        return Err(rocket::http::Status::NotFound);
+    }
+
+    let ship_uuid =
+        Uuid::parse_str(&request.ship_id).map_err(|_| rocket::http::Status::BadRequest)?;
+    require_ship_team(&world, &request.ship_id, &player)?;
+
+    let response = match world.sell_salvage(
+        station_id,
+        &ship_uuid.to_string(),
+        &request.module_id,
+        config,
+    ) {
+        Ok(sale) => SalvageSaleResponse {
+            success: true,
+            message: format!("Sold {} for {} credits", sale.name, sale.credits),
+            sale: Some(sale),
+        },
+        Err(message) => SalvageSaleResponse {
+            success: false,
+            message,
+            sale: None,
+        },
+    };
     Ok(Json(response))
 }
 
+/// Modules and ammunition a station has for sale
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StationInventoryResponse {
+    pub station_id: String,
+    pub modules: Vec<StockedModule>,
+    /// Rounds in stock (ammo_type -> rounds)
+    pub ammunition: BTreeMap<String, u32>,
+}
+
+/// Get the modules and ammunition a station has for sale
+#[get("/v1/stations/<station_id>/inventory")]
+fn get_inventory(
+    station_id: &str,
+    game_world: &State<Arc<RwLock<GameWorld>>>,
+    config: &State<GameConfig>,
+) -> Result<Json<StationInventoryResponse>, rocket::http::Status> {
+    let world = game_world.read().unwrap();
+
+    let inventory = world
+        .station_inventory(station_id, config)
+        .map_err(|_| rocket::http::Status::NotFound)?;
+
+    Ok(Json(StationInventoryResponse {
+        station_id: station_id.to_string(),
+        modules: inventory
+            .modules
+            .into_iter()
+            .filter(|stocked| stocked.quantity > 0)
+            .collect(),
+        ammunition: inventory.ammunition,
+    }))
+}
+
 /// Get docking status for a ship at a station
 #[get("/v1/stations/<station_id>/dock/<ship_id>")]
 fn get_docking_status(
@@ -398,6 +602,11 @@ pub fn routes() -> Vec<Route> {
         complete_docking,
         undock_ship,
         request_service,
+        get_market,
+        buy_commodity,
+        sell_commodity,
+        sell_salvage,
+        get_inventory,
         get_docking_status,
     ]
 }
@@ -405,17 +614,105 @@ pub fn routes() -> Vec<Route> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rocket::http::Status;
+    use crate::api::auth::test_utils::{operator, operator_token};
+    use crate::config::test_utils::create_test_game_config;
+    use rocket::http::{Header, Status};
     use rocket::local::blocking::Client;
 
     fn create_test_client() -> Client {
         let game_world = GameWorld::new_shared();
 
-        let rocket = rocket::build().manage(game_world).mount("/", routes());
+        let rocket = rocket::build()
+            .manage(game_world)
+            .manage(create_test_game_config())
+            .manage(operator_token())
+            .mount("/", routes());
 
         Client::tracked(rocket).expect("valid rocket instance")
     }
 
+    /// Tokens for a member of the team flying a test ship and for a player
+    /// outside it
+    struct Tokens {
+        member: String,
+        stranger: String,
+    }
+
+    /// Register a team with a ship and a member, plus an outsider; returns
+    /// the ship ID and both players' tokens
+    fn setup_team_ship(world: &mut GameWorld) -> (String, Tokens) {
+        let member = world.register_player("member".to_string()).unwrap();
+        let stranger = world.register_player("stranger".to_string()).unwrap();
+        let team_id = world
+            .create_team("Alpha".to_string(), "Federation".to_string())
+            .unwrap();
+        world.add_player_to_team(&team_id, &member).unwrap();
+        let ship_id = Uuid::new_v4().to_string();
+        world.add_ship(crate::models::Ship {
+            id: ship_id.clone(),
+            name: "Trader".to_string(),
+            class: "freighter".to_string(),
+            team_id,
+            player_roles: Default::default(),
+            status: Default::default(),
+            modules: vec![],
+            weapons: vec![],
+            inventory: Default::default(),
+        });
+        let tokens = Tokens {
+            member: world.issue_player_token(&member).unwrap(),
+            stranger: world.issue_player_token(&stranger).unwrap(),
+        };
+        (ship_id, tokens)
+    }
+
+    fn bearer(token: &str) -> Header<'static> {
+        Header::new("Authorization", format!("Bearer {}", token))
+    }
+
+    #[test]
+    fn test_service_requires_team_member() {
+        let game_world = GameWorld::new_shared();
+        let (station_id, ship_id, tokens) = {
+            let mut world = game_world.write().unwrap();
+            let station_id = world.register_station(Station::new(
+                "Service Station".to_string(),
+                [0.0, 0.0, 0.0],
+                "Federation".to_string(),
+            ));
+            let (ship_id, tokens) = setup_team_ship(&mut world);
+            (station_id, ship_id, tokens)
+        };
+        let rocket = rocket::build()
+            .manage(game_world)
+            .manage(create_test_game_config())
+            .mount("/", routes());
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let url = format!("/v1/stations/{}/services/{}", station_id, ship_id);
+
+        let response = client
+            .post(&url)
+            .json(&ServiceRequest::RepairAll)
+            .dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client
+            .post(&url)
+            .header(bearer(&tokens.stranger))
+            .json(&ServiceRequest::RepairAll)
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client
+            .post(&url)
+            .header(bearer(&tokens.member))
+            .json(&ServiceRequest::RepairAll)
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let service: ServiceResponse = response.into_json().unwrap();
+        assert!(!service.success);
+    }
+
     #[test]
     fn test_list_stations_empty() {
         let client = create_test_client();
@@ -451,6 +748,7 @@ mod tests {
             position: [100.0, 200.0, 300.0],
             faction: "Federation".to_string(),
             size: Some(StationSize::Large),
+            station_type: None,
         };
 
         let response = client.post("/v1/stations").json(&request).dispatch();
@@ -462,6 +760,32 @@ mod tests {
         assert_eq!(station.max_docked_ships, 10);
     }
 
+    #[test]
+    fn test_delete_station_requires_the_operator() {
+        let client = create_test_client();
+        let station: StationResponse = client
+            .post("/v1/stations")
+            .json(&CreateStationRequest {
+                name: "Beta Station".to_string(),
+                position: [0.0, 0.0, 0.0],
+                faction: "Federation".to_string(),
+                size: None,
+                station_type: None,
+            })
+            .dispatch()
+            .into_json()
+            .unwrap();
+        let url = format!("/v1/stations/{}", station.id);
+
+        let response = client.delete(&url).dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client.delete(&url).header(operator()).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let response = client.get(&url).dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
     #[test]
     fn test_get_station() {
         let client = create_test_client();
@@ -472,6 +796,7 @@ mod tests {
             position: [0.0, 0.0, 0.0],
             faction: "Federation".to_string(),
             size: None,
+            station_type: None,
         };
 
         let create_response = client.post("/v1/stations").json(&create_req).dispatch();
@@ -491,65 +816,289 @@ mod tests {
 
     #[test]
     fn test_request_docking() {
-        let client = create_test_client();
-
-        // Create a station
-        let create_req = CreateStationRequest {
-            name: "Docking Station".to_string(),
-            position: [0.0, 0.0, 0.0],
-            faction: "Federation".to_string(),
-            size: None,
+        let game_world = GameWorld::new_shared();
+        let (station_id, ship_id, tokens) = {
+            let mut world = game_world.write().unwrap();
+            let station_id = world.register_station(Station::new(
+                "Docking Station".to_string(),
+                [0.0, 0.0, 0.0],
+                "Federation".to_string(),
+            ));
+            let (ship_id, tokens) = setup_team_ship(&mut world);
+            (station_id, ship_id, tokens)
+        };
+        let rocket = rocket::build()
+            .manage(game_world.clone())
+            .manage(create_test_game_config())
+            .mount("/", routes());
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let dock_url = format!("/v1/stations/{}/dock", station_id);
+        let dock_req = DockingRequest {
+            ship_id: ship_id.clone(),
         };
 
-        let create_response = client.post("/v1/stations").json(&create_req).dispatch();
+        let response = client.post(&dock_url).json(&dock_req).dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        let response = client
+            .post(&dock_url)
+            .header(bearer(&tokens.stranger))
+            .json(&dock_req)
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+
+        // Clearance is only given within request range
+        game_world
+            .write()
+            .unwrap()
+            .ships_mut()
+            .get_mut(&ship_id)
+            .unwrap()
+            .status
+            .position = [1.0e6, 0.0, 0.0];
+        let response = client
+            .post(&dock_url)
+            .header(bearer(&tokens.member))
+            .json(&dock_req)
+            .dispatch();
+        let dock_resp: DockingResponse = response.into_json().unwrap();
+        assert!(!dock_resp.approved);
+        assert!(dock_resp.message.contains("out of range"));
+
+        game_world
+            .write()
+            .unwrap()
+            .ships_mut()
+            .get_mut(&ship_id)
+            .unwrap()
+            .status
+            .position = [0.0, 0.0, 0.0];
+        let response = client
+            .post(&dock_url)
+            .header(bearer(&tokens.member))
+            .json(&dock_req)
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let dock_resp: DockingResponse = response.into_json().unwrap();
+        assert!(dock_resp.approved);
+        assert_eq!(dock_resp.status, Some(DockingStatus::Approaching));
+
+        // Arrival is only confirmed for a ship slow enough to dock
+        let complete_url = format!("/v1/stations/{}/dock/{}/complete", station_id, ship_id);
+        game_world
+            .write()
+            .unwrap()
+            .ships_mut()
+            .get_mut(&ship_id)
+            .unwrap()
+            .status
+            .velocity = [500.0, 0.0, 0.0];
+        let response = client
+            .post(&complete_url)
+            .header(bearer(&tokens.member))
+            .dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+
+        game_world
+            .write()
+            .unwrap()
+            .ships_mut()
+            .get_mut(&ship_id)
+            .unwrap()
+            .status
+            .velocity = [0.0; 3];
+        let response = client
+            .post(&complete_url)
+            .header(bearer(&tokens.stranger))
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+        let response = client
+            .post(&complete_url)
+            .header(bearer(&tokens.member))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
 
-        let station: StationResponse = create_response.into_json().unwrap();
+        let undock_url = format!("/v1/stations/{}/undock/{}", station_id, ship_id);
+        let response = client
+            .post(&undock_url)
+            .header(bearer(&tokens.stranger))
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+        let response = client
+            .post(&undock_url)
+            .header(bearer(&tokens.member))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+    }
 
-        // Request docking
-        let dock_req = DockingRequest {
-            ship_id: Uuid::new_v4().to_string(),
-            ship_faction: "Federation".to_string(),
+    #[test]
+    fn test_market_quotes_and_trade_orders() {
+        let game_world = GameWorld::new_shared();
+        let mut config = create_test_game_config();
+        config.commodities = vec![crate::config::CommodityConfig {
+            id: "water".to_string(),
+            name: "Water".to_string(),
+            description: String::new(),
+            base_price: 4.0,
+            volume: 1.0,
+            stock: 2000.0,
+        }];
+        let (station_id, ship_id, tokens) = {
+            let mut world = game_world.write().unwrap();
+            world.configure_markets(&config.commodities);
+            let station_id = world.register_station(Station::new(
+                "Market Station".to_string(),
+                [0.0, 0.0, 0.0],
+                "Federation".to_string(),
+            ));
+            let (ship_id, tokens) = setup_team_ship(&mut world);
+            (station_id, ship_id, tokens)
         };
+        let rocket = rocket::build()
+            .manage(game_world)
+            .manage(config)
+            .mount("/", routes());
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+
+        let response = client
+            .get(format!("/v1/stations/{}/market", station_id))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let market: MarketResponse = response.into_json().unwrap();
+        assert_eq!(market.listings.len(), 1);
+        assert_eq!(market.listings[0].commodity_id, "water");
+        assert!(market.listings[0].sell_price < market.listings[0].buy_price);
+
+        let order = TradeRequest {
+            ship_id,
+            commodity_id: "water".to_string(),
+            quantity: 10,
+        };
+        for side in ["buy", "sell"] {
+            let url = format!("/v1/stations/{}/market/{}", station_id, side);
+            let response = client.post(&url).json(&order).dispatch();
+            assert_eq!(response.status(), Status::Unauthorized);
+            let response = client
+                .post(&url)
+                .header(bearer(&tokens.stranger))
+                .json(&order)
+                .dispatch();
+            assert_eq!(response.status(), Status::Forbidden);
+        }
 
-        let dock_response = client
-            .post(format!("/v1/stations/{}/dock", station.id))
-            .json(&dock_req)
+        let response = client
+            .post(format!("/v1/stations/{}/market/buy", station_id))
+            .header(bearer(&tokens.member))
+            .json(&order)
             .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let trade: TradeResponse = response.into_json().unwrap();
+        assert!(!trade.success);
+        assert_eq!(trade.message, "Ship must be docked to trade");
+    }
 
-        assert_eq!(dock_response.status(), Status::Ok);
-        let dock_resp: DockingResponse = dock_response.into_json().unwrap();
-        assert!(dock_resp.approved);
+    #[test]
+    fn test_salvage_sale_requires_team_member() {
+        let game_world = GameWorld::new_shared();
+        let (station_id, ship_id, tokens) = {
+            let mut world = game_world.write().unwrap();
+            let station_id = world.register_station(Station::new(
+                "Scrapyard".to_string(),
+                [0.0, 0.0, 0.0],
+                "Federation".to_string(),
+            ));
+            let (ship_id, tokens) = setup_team_ship(&mut world);
+            (station_id, ship_id, tokens)
+        };
+        let rocket = rocket::build()
+            .manage(game_world)
+            .manage(create_test_game_config())
+            .mount("/", routes());
+        let client = Client::tracked(rocket).expect("valid rocket instance");
+        let url = format!("/v1/stations/{}/salvage/sell", station_id);
+        let sale = SalvageSaleRequest {
+            ship_id,
+            module_id: "missing".to_string(),
+        };
+
+        let response = client.post(&url).json(&sale).dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client
+            .post(&url)
+            .header(bearer(&tokens.stranger))
+            .json(&sale)
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client
+            .post(&url)
+            .header(bearer(&tokens.member))
+            .json(&sale)
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let sale: SalvageSaleResponse = response.into_json().unwrap();
+        assert!(!sale.success);
     }
 
     #[test]
-    fn test_hostile_faction_docking() {
+    fn test_station_inventory() {
         let client = create_test_client();
 
-        // Create a station
         let create_req = CreateStationRequest {
-            name: "Hostile Station".to_string(),
+            name: "Arsenal".to_string(),
             position: [0.0, 0.0, 0.0],
             faction: "Federation".to_string(),
             size: None,
+            station_type: Some(StationType::Military),
         };
+        let response = client.post("/v1/stations").json(&create_req).dispatch();
+        let station: StationResponse = response.into_json().unwrap();
+        assert_eq!(station.station_type, StationType::Military);
 
-        let create_response = client.post("/v1/stations").json(&create_req).dispatch();
-
-        let station: StationResponse = create_response.into_json().unwrap();
+        let response = client
+            .get(format!("/v1/stations/{}/inventory", station.id))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let inventory: StationInventoryResponse = response.into_json().unwrap();
+        assert_eq!(inventory.station_id, station.id);
 
-        // Mark Empire as hostile (this would need to be done via API in real scenario)
-        // For now, we'll just test the request and expect denial
+        let response = client
+            .get(format!("/v1/stations/{}/inventory", Uuid::new_v4()))
+            .dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
 
-        let dock_req = DockingRequest {
-            ship_id: Uuid::new_v4().to_string(),
-            ship_faction: "Empire".to_string(), // Not in hostile list yet, so should succeed
+    #[test]
+    fn test_hostile_faction_docking() {
+        let game_world = GameWorld::new_shared();
+        let (station_id, ship_id, tokens) = {
+            let mut world = game_world.write().unwrap();
+            let mut station = Station::new(
+                "Hostile Station".to_string(),
+                [0.0, 0.0, 0.0],
+                "Empire".to_string(),
+            );
+            station.hostile_factions.push("Federation".to_string());
+            let station_id = world.register_station(station);
+            let (ship_id, tokens) = setup_team_ship(&mut world);
+            (station_id, ship_id, tokens)
         };
+        let rocket = rocket::build()
+            .manage(game_world)
+            .manage(create_test_game_config())
+            .mount("/", routes());
+        let client = Client::tracked(rocket).expect("valid rocket instance");
 
+        // The ship's team flies for the Federation, whatever the client says
         let dock_response = client
-            .post(format!("/v1/stations/{}/dock", station.id))
-            .json(&dock_req)
+            .post(format!("/v1/stations/{}/dock", station_id))
+            .header(bearer(&tokens.member))
+            .json(&serde_json::json!({ "ship_id": ship_id, "ship_faction": "Empire" }))
             .dispatch();
 
         assert_eq!(dock_response.status(), Status::Ok);
+        let dock_resp: DockingResponse = dock_response.into_json().unwrap();
+        assert!(!dock_resp.approved);
+        assert_eq!(dock_resp.message, "Docking denied: Hostile faction");
     }
 }
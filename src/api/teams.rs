@@ -7,8 +7,12 @@ use rocket::serde::json::Json;
 use rocket::{Route, State, delete, get, patch, post, routes};
 use serde::{Deserialize, Serialize};
 
+use crate::api::auth::{
+    AuthError, AuthenticatedPlayer, TeamMember, require_self, require_team_admission,
+    require_team_member,
+};
 use crate::config::GameConfig;
-use crate::state::SharedGameWorld;
+use crate::state::{FactionStanding, SharedGameWorld};
 
 /// Request body for creating a new team
 #[derive(Debug, Serialize, Deserialize)]
@@ -89,6 +93,15 @@ pub struct CreditBalanceResponse {
     pub credits: i64,
 }
 
+/// Response for reputation queries
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReputationResponse {
+    /// Team ID
+    pub team_id: String,
+    /// Standing with each known faction
+    pub standings: Vec<FactionStanding>,
+}
+
 /// GET /v1/teams - List all teams
 ///
 /// Returns a list of all teams currently in the game.
@@ -116,16 +129,27 @@ pub fn list_teams(world: &State<SharedGameWorld>) -> Json<ListTeamsResponse> {
     })
 }
 
+/// Render a failed authorization check as an error response
+fn auth_error(error: AuthError) -> (Status, Json<ErrorResponse>) {
+    (
+        error.status,
+        Json(ErrorResponse {
+            error: error.message,
+        }),
+    )
+}
+
 /// POST /v1/teams - Create a new team
 ///
 /// Creates a new team with the given name and faction. The team name
 /// must be unique and 1-50 characters long. Optionally adds the creating
-/// player as the first team member.
+/// player as the first team member, which requires that player's token.
 #[post("/v1/teams", data = "<request>")]
 pub fn create_team(
     world: &State<SharedGameWorld>,
     config: &State<GameConfig>,
     request: Json<CreateTeamRequest>,
+    player: Option<AuthenticatedPlayer>,
 ) -> Result<Json<CreateTeamResponse>, (Status, Json<ErrorResponse>)> {
     // Validate faction exists in config
     let faction_exists = config
@@ -146,6 +170,13 @@ pub fn create_team(
         ));
     }
 
+    // Only the player themselves may join them to the new team
+    if let Some(ref player_id) = request.player_id {
+        let player =
+            player.ok_or_else(|| auth_error(AuthError::unauthorized("Authentication required")))?;
+        require_self(&player.0, player_id).map_err(auth_error)?;
+    }
+
     let mut world = world.write().unwrap();
 
     // Validate player exists if player_id provided
@@ -223,14 +254,17 @@ pub fn get_team(
 /// PATCH /v1/teams/<id> - Add player to team
 ///
 /// Adds a player to an existing team. The player must exist and not
-/// already be a member of the team.
+/// already be a member of the team. Members may add anyone; other players
+/// may only add themselves to a team with no members yet.
 #[patch("/v1/teams/<id>", data = "<request>")]
 pub fn add_player_to_team(
     world: &State<SharedGameWorld>,
     id: String,
     request: Json<AddPlayerRequest>,
+    player: AuthenticatedPlayer,
 ) -> Result<Json<TeamResponse>, (Status, Json<ErrorResponse>)> {
     let mut world = world.write().unwrap();
+    require_team_admission(&world, &id, &player.0, &request.player_id).map_err(auth_error)?;
 
     match world.add_player_to_team(&id, &request.player_id) {
         Ok(_) => {
@@ -250,13 +284,18 @@ pub fn add_player_to_team(
 /// DELETE /v1/teams/<team_id>/players/<player_id> - Remove player from team
 ///
 /// Removes a player from a team. The player and team must both exist.
+/// Players may leave on their own; removing anyone else takes a member.
 #[delete("/v1/teams/<team_id>/players/<player_id>")]
 pub fn remove_player_from_team(
     world: &State<SharedGameWorld>,
     team_id: String,
     player_id: String,
+    player: AuthenticatedPlayer,
 ) -> Result<Status, (Status, Json<ErrorResponse>)> {
     let mut world = world.write().unwrap();
+    if player.0.id != player_id && world.get_team(&team_id).is_some() {
+        require_team_member(&world, &team_id, &player.0).map_err(auth_error)?;
+    }
 
     match world.remove_player_from_team(&team_id, &player_id) {
         Ok(_) => Ok(Status::NoContent),
@@ -330,12 +369,14 @@ pub fn add_team_credits(
 ///
 /// Deducts credits from a team's balance. Used for purchases.
 /// Returns an error if the team has insufficient credits.
-/// The amount must be positive.
+/// The amount must be positive. Only members of the team may spend its
+/// credits.
 #[post("/v1/teams/<id>/credits/deduct", data = "<request>")]
 pub fn deduct_team_credits(
     world: &State<SharedGameWorld>,
     id: String,
     request: Json<CreditTransactionRequest>,
+    _member: TeamMember,
 ) -> Result<Json<CreditBalanceResponse>, (Status, Json<ErrorResponse>)> {
     if request.amount <= 0 {
         return Err((
@@ -365,6 +406,26 @@ pub fn deduct_team_credits(
     }
 }
 
+/// GET /v1/teams/<id>/reputation - Get team's standing with factions
+///
+/// Returns the team's standing with every known faction, along with
+/// whether their stations allow docking and how they scale prices.
+#[get("/v1/teams/<id>/reputation")]
+pub fn get_team_reputation(
+    world: &State<SharedGameWorld>,
+    id: String,
+) -> Result<Json<ReputationResponse>, (Status, Json<ErrorResponse>)> {
+    let world = world.read().unwrap();
+
+    match world.reputation(&id) {
+        Ok(standings) => Ok(Json(ReputationResponse {
+            team_id: id,
+            standings,
+        })),
+        Err(err) => Err((Status::NotFound, Json(ErrorResponse { error: err }))),
+    }
+}
+
 /// Returns all team API routes
 pub fn routes() -> Vec<Route> {
     routes![
@@ -375,7 +436,8 @@ pub fn routes() -> Vec<Route> {
         remove_player_from_team,
         get_team_credits,
         add_team_credits,
-        deduct_team_credits
+        deduct_team_credits,
+        get_team_reputation
     ]
 }
 
@@ -387,6 +449,7 @@ mod tests {
     use crate::config::{Faction, GameConfig};
     use crate::state::GameWorld;
     use rocket::Build;
+    use rocket::http::Header;
     use rocket::local::blocking::Client;
 
     // Capitalized faction ids ("Federation", "Empire") are intentional — team
@@ -418,6 +481,21 @@ mod tests {
             .mount("/", crate::api::players::routes())
     }
 
+    fn bearer(token: &str) -> Header<'static> {
+        Header::new("Authorization", format!("Bearer {}", token))
+    }
+
+    fn register(client: &Client, name: &str) -> crate::api::players::CreatePlayerResponse {
+        client
+            .post("/v1/players")
+            .json(&CreatePlayerRequest {
+                name: name.to_string(),
+            })
+            .dispatch()
+            .into_json()
+            .unwrap()
+    }
+
     #[test]
     fn returns_404_for_missing_team() {
         // Lock in the current 404 body shape for team lookups: a JSON
@@ -568,7 +646,7 @@ mod tests {
             .dispatch();
         let player_body: crate::api::players::CreatePlayerResponse =
             player_response.into_json().unwrap();
-        let player_id = player_body.id;
+        let player_id = player_body.id.clone();
 
         // Create team
         let team_response = client
@@ -585,6 +663,7 @@ mod tests {
         // Add player to team
         let response = client
             .patch(format!("/v1/teams/{}", team_id))
+            .header(bearer(&player_body.token))
             .json(&AddPlayerRequest {
                 player_id: player_id.clone(),
             })
@@ -600,14 +679,16 @@ mod tests {
     #[test]
     fn test_add_player_to_team_player_not_found() {
         let client = Client::tracked(create_test_rocket()).unwrap();
+        let alice = register(&client, "Alice");
 
         // Create team
         let team_response = client
             .post("/v1/teams")
+            .header(bearer(&alice.token))
             .json(&CreateTeamRequest {
                 name: "Alpha Team".to_string(),
                 faction: "Federation".to_string(),
-                player_id: None,
+                player_id: Some(alice.id.clone()),
             })
             .dispatch();
         let team_body: CreateTeamResponse = team_response.into_json().unwrap();
@@ -616,6 +697,7 @@ mod tests {
         // Try to add non-existent player
         let response = client
             .patch(format!("/v1/teams/{}", team_id))
+            .header(bearer(&alice.token))
             .json(&AddPlayerRequest {
                 player_id: "nonexistent-player".to_string(),
             })
@@ -645,6 +727,7 @@ mod tests {
         // Try to add to non-existent team
         let response = client
             .patch("/v1/teams/nonexistent-team")
+            .header(bearer(&player_body.token))
             .json(&AddPlayerRequest { player_id })
             .dispatch();
 
@@ -681,6 +764,7 @@ mod tests {
         // Add player to team
         client
             .patch(format!("/v1/teams/{}", team_id))
+            .header(bearer(&player_body.token))
             .json(&AddPlayerRequest {
                 player_id: player_id.clone(),
             })
@@ -689,6 +773,7 @@ mod tests {
         // Remove player from team
         let response = client
             .delete(format!("/v1/teams/{}/players/{}", team_id, player_id))
+            .header(bearer(&player_body.token))
             .dispatch();
 
         assert_eq!(response.status(), Status::NoContent);
@@ -702,9 +787,11 @@ mod tests {
     #[test]
     fn test_remove_player_from_team_not_found() {
         let client = Client::tracked(create_test_rocket()).unwrap();
+        let alice = register(&client, "Alice");
 
         let response = client
             .delete("/v1/teams/nonexistent-team/players/nonexistent-player")
+            .header(bearer(&alice.token))
             .dispatch();
 
         assert_eq!(response.status(), Status::NotFound);
@@ -777,9 +864,10 @@ mod tests {
         let team_body: CreateTeamResponse = team_response.into_json().unwrap();
         let team_id = team_body.id;
 
-        // Add both players
+        // Alice founds the team, then adds Bob
         client
             .patch(format!("/v1/teams/{}", team_id))
+            .header(bearer(&alice_body.token))
             .json(&AddPlayerRequest {
                 player_id: alice_body.id.clone(),
             })
@@ -787,6 +875,7 @@ mod tests {
 
         client
             .patch(format!("/v1/teams/{}", team_id))
+            .header(bearer(&alice_body.token))
             .json(&AddPlayerRequest {
                 player_id: bob_body.id.clone(),
             })
@@ -819,6 +908,7 @@ mod tests {
         // Create team with player_id
         let response = client
             .post("/v1/teams")
+            .header(bearer(&player_body.token))
             .json(&CreateTeamRequest {
                 name: "Alpha Team".to_string(),
                 faction: "Federation".to_string(),
@@ -879,21 +969,122 @@ mod tests {
     }
 
     #[test]
-    fn test_create_team_with_invalid_player() {
+    fn test_create_team_for_another_player() {
         let client = Client::tracked(create_test_rocket()).unwrap();
+        let alice = register(&client, "Alice");
+        let request = CreateTeamRequest {
+            name: "Alpha Team".to_string(),
+            faction: "Federation".to_string(),
+            player_id: Some("nonexistent-player".to_string()),
+        };
+
+        let response = client.post("/v1/teams").json(&request).dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
 
         let response = client
             .post("/v1/teams")
+            .header(bearer(&alice.token))
+            .json(&request)
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let body: ErrorResponse = response.into_json().unwrap();
+        assert!(body.error.contains("may not act for"));
+    }
+
+    #[test]
+    fn test_team_membership_is_granted_by_members() {
+        let client = Client::tracked(create_test_rocket()).unwrap();
+        let alice = register(&client, "Alice");
+        let bob = register(&client, "Bob");
+        let team: CreateTeamResponse = client
+            .post("/v1/teams")
+            .header(bearer(&alice.token))
             .json(&CreateTeamRequest {
                 name: "Alpha Team".to_string(),
                 faction: "Federation".to_string(),
-                player_id: Some("nonexistent-player".to_string()),
+                player_id: Some(alice.id.clone()),
             })
+            .dispatch()
+            .into_json()
+            .unwrap();
+        let join = AddPlayerRequest {
+            player_id: bob.id.clone(),
+        };
+
+        let response = client
+            .patch(format!("/v1/teams/{}", team.id))
+            .json(&join)
             .dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
 
-        assert_eq!(response.status(), Status::BadRequest);
+        // Bob cannot let himself into a team that already has members
+        let response = client
+            .patch(format!("/v1/teams/{}", team.id))
+            .header(bearer(&bob.token))
+            .json(&join)
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
 
-        let body: ErrorResponse = response.into_json().unwrap();
-        assert!(body.error.contains("not found"));
+        let response = client
+            .delete(format!("/v1/teams/{}/players/{}", team.id, alice.id))
+            .header(bearer(&bob.token))
+            .dispatch();
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client
+            .patch(format!("/v1/teams/{}", team.id))
+            .header(bearer(&alice.token))
+            .json(&join)
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let body: TeamResponse = response.into_json().unwrap();
+        assert_eq!(body.members.len(), 2);
+    }
+
+    #[test]
+    fn test_get_team_reputation() {
+        let world = GameWorld::new_shared();
+        let team_id = {
+            let mut world = world.write().unwrap();
+            let team_id = world
+                .create_team("Alpha Team".to_string(), "Federation".to_string())
+                .unwrap();
+            // Contracts are awarded by the server, never reported by clients
+            world.complete_contract(&team_id, "Empire").unwrap();
+            team_id
+        };
+        let rocket = rocket::build()
+            .manage(world)
+            .manage(create_test_config())
+            .mount("/", routes());
+        let client = Client::tracked(rocket).unwrap();
+
+        let response = client
+            .get(format!("/v1/teams/{}/reputation", team_id))
+            .dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let body: ReputationResponse = response.into_json().unwrap();
+        let empire = body
+            .standings
+            .iter()
+            .find(|standing| standing.faction == "Empire")
+            .unwrap();
+        assert_eq!(empire.standing, 10.0);
+        assert!(empire.docking_allowed);
+        assert!(empire.price_factor < 1.0);
+        assert!(
+            body.standings
+                .iter()
+                .any(|standing| standing.faction == "Federation")
+        );
+
+        let response = client
+            .post(format!("/v1/teams/{}/reputation/contract", team_id))
+            .dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.get("/v1/teams/nonexistent-id/reputation").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
     }
 }
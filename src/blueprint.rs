@@ -512,6 +512,7 @@ mod tests {
             faction: "alliance".to_string(),
             members: vec![],
             credits: 1_000_000,
+            reputation: Default::default(),
         }
     }
 
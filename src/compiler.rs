@@ -199,6 +199,18 @@ impl<'a> ShipCompiler<'a> {
             }
         };
 
+        // Heat comes from the slot's base plus the variant's additional heat
+        let mut stats = stats;
+        if !stats.has("heat_generation") {
+            let heat_generation = self.module_heat_generation(module);
+            if heat_generation > 0.0 {
+                stats.stats.insert(
+                    "heat_generation".to_string(),
+                    serde_json::json!(heat_generation),
+                );
+            }
+        }
+
         // Get max health from module slot template or default to 100
         let max_health = self
             .config
@@ -222,6 +234,36 @@ impl<'a> ShipCompiler<'a> {
         })
     }
 
+    /// Heat generated by a module at full power (K/s)
+    ///
+    /// Sums the slot's `base_heat_generation` and the variant's
+    /// `additional_heat_generation`, falling back to the legacy module
+    /// template when the slot is not configured.
+    fn module_heat_generation(&self, module: &ModuleInstance) -> f32 {
+        let base = self
+            .config
+            .get_module_slot(&module.module_slot_id)
+            .map(|slot| slot.base_heat_generation)
+            .or_else(|| {
+                self.config
+                    .modules
+                    .modules
+                    .get(&module.module_slot_id)
+                    .and_then(|template| template.base_heat_generation)
+            })
+            .unwrap_or(0.0);
+        let additional = module
+            .variant_id
+            .as_ref()
+            .and_then(|variant_id| {
+                self.config
+                    .get_module_variant(&module.module_slot_id, variant_id)
+            })
+            .map_or(0.0, |variant| variant.additional_heat_generation);
+
+        base + additional
+    }
+
     /// Get ship class configuration
     fn get_ship_class(&self, class_id: &str) -> Result<&ShipClassConfig, CompilationError> {
         self.config
@@ -269,11 +311,16 @@ impl<'a> ShipCompiler<'a> {
             power_capacity,
             power_usage: 0.0,
             cooling_capacity,
-            heat_generation: 0.0,
+            heat_generation: compiled_modules
+                .iter()
+                .filter_map(|m| m.get_stat_f64("heat_generation"))
+                .sum::<f64>() as f32,
             base_weight,
             effective_weight: base_weight,
             status_effects: Vec::new(),
             module_health,
+            position: [0.0; 3],
+            velocity: [0.0; 3],
         };
 
         status.update_effective_weight();
@@ -321,7 +368,10 @@ impl<'a> ShipCompiler<'a> {
     fn calculate_cooling_capacity_from_compiled(&self, modules: &[CompiledModule]) -> f32 {
         let total_cooling: f64 = modules
             .iter()
-            .filter_map(|m| m.get_stat_f64("cooling_capacity"))
+            .filter_map(|m| {
+                m.get_stat_f64("cooling_capacity")
+                    .or_else(|| m.get_stat_f64("generated_cooling"))
+            })
             .sum();
 
         // If no cooling modules found, provide minimum default
@@ -394,10 +444,11 @@ pub fn compile_and_spawn(
         .deduct_team_credits(&blueprint.team_id, total_cost)
         .map_err(CompilationError::SystemCalculationFailed)?;
 
-    // Add ship to world
+    // Add ship to world and spawn its simulation entities
     world.register_ship(ship);
-
-    // TODO: Trigger ship spawn event in simulation
+    world
+        .spawn_ship_entity(&ship_id, config)
+        .map_err(CompilationError::SystemCalculationFailed)?;
 
     Ok(ship_id)
 }
@@ -468,24 +519,33 @@ pub fn calculate_ship_value(ship: &Ship, config: &GameConfig) -> i64 {
         .map(|sc| sc.cost)
         .unwrap_or(0);
 
-    let mut total_value: i64 = ship_class_cost;
+    ship_class_cost
+        + ship
+            .modules
+            .iter()
+            .map(|module| calculate_module_value(module, config))
+            .sum::<i64>()
+}
 
-    // Add module slot and variant costs
-    for module in &ship.modules {
-        // Add module slot cost (module_id is the slot type)
-        if let Some(slot) = config.get_module_slot(&module.module_id) {
-            total_value += slot.credit_cost;
-        }
+/// Calculate the credit value of one compiled module
+///
+/// The module slot's `credit_cost` plus its variant's, if it has one.
+pub fn calculate_module_value(module: &CompiledModule, config: &GameConfig) -> i64 {
+    let mut value = 0;
 
-        // Add variant cost if present (kind is the variant_id)
-        if let Some(ref variant_id) = module.kind
-            && let Some(variant) = config.get_module_variant(&module.module_id, variant_id)
-        {
-            total_value += variant.credit_cost;
-        }
+    // Add module slot cost (module_id is the slot type)
+    if let Some(slot) = config.get_module_slot(&module.module_id) {
+        value += slot.credit_cost;
     }
 
-    total_value
+    // Add variant cost if present (kind is the variant_id)
+    if let Some(ref variant_id) = module.kind
+        && let Some(variant) = config.get_module_variant(&module.module_id, variant_id)
+    {
+        value += variant.credit_cost;
+    }
+
+    value
 }
 
 /// Remove a ship and refund its credit value to the team
@@ -637,6 +697,11 @@ mod tests {
         let ship = world.get_ship(&ship_id);
         assert!(ship.is_some());
         assert_eq!(ship.unwrap().name, "Test Ship");
+
+        // Verify the ship entered the simulation
+        let entity = world.get_ship_entity(&ship_id).unwrap();
+        let ship_data = world.world.get::<crate::simulation::ShipData>(entity);
+        assert_eq!(ship_data.unwrap().id, ship_id);
     }
 
     #[test]
@@ -671,6 +736,62 @@ mod tests {
         assert!(ship.inventory.ammunition.is_empty());
     }
 
+    #[test]
+    fn test_module_heat_generation_from_slot_and_variant() {
+        let mut config = create_test_config();
+        let slot: crate::config::ModuleSlot = serde_yaml::from_str(
+            r#"
+id: power-core
+name: "Power Core"
+desc: "Test"
+extended_desc: "Test"
+groups: ["Essential"]
+required: true
+has_varients: true
+base_cost: 10
+max_slots: 2
+base_hp: 10
+base_power_consumption: 0.0
+base_heat_generation: 5.0
+base_weight: 100
+"#,
+        )
+        .unwrap();
+        let variant: crate::config::ModuleVariant = serde_yaml::from_str(
+            r#"
+id: test-reactor
+type: power-core
+name: "Test Reactor"
+model: "Test Model"
+manufacturer: "Test Corp"
+desc: "A test power core"
+lore: "Test lore"
+cost: 100
+additional_hp: 10
+additional_power_consumption: 0.0
+additional_heat_generation: 10.0
+additional_weight: 50
+energy_production: 1000
+"#,
+        )
+        .unwrap();
+        config.module_slots.insert("power-core".to_string(), slot);
+        config
+            .module_variants
+            .insert("power-core".to_string(), vec![variant]);
+
+        let compiler = ShipCompiler::new(&config);
+        let module = compiler
+            .compile_module(&ModuleInstance {
+                id: "core-1".to_string(),
+                module_slot_id: "power-core".to_string(),
+                variant_id: Some("test-reactor".to_string()),
+            })
+            .unwrap();
+
+        assert_eq!(module.get_stat_f64("heat_generation"), Some(15.0));
+    }
+
     #[test]
     fn test_calculate_blueprint_cost() {
         let config = create_test_config();
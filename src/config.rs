@@ -10,13 +10,17 @@
 //! - `map` - Galaxy and procedural generation configurations
 //! - `simulation` - Physics and combat simulation configurations
 //! - `faction_gen` - Faction generation and relationship configurations
+//! - `commodity` - Trade goods bought and sold at stations
+//! - `outfitting` - Module and ammunition stock at stations
 
 pub mod ai;
 pub mod bonus;
+pub mod commodity;
 pub mod faction_gen;
 pub mod game_settings;
 pub mod map;
 pub mod module;
+pub mod outfitting;
 pub mod ship_class;
 pub mod simulation;
 pub mod weapon;
@@ -30,6 +34,7 @@ use std::path::{Path, PathBuf};
 // Re-export config types
 pub use ai::AIConfig;
 pub use bonus::{BonusConfig, BonusFormat, BonusMetadata, CategoryMetadata, FormattedBonus};
+pub use commodity::{CommoditiesConfig, CommodityConfig};
 pub use faction_gen::FactionGenConfig;
 pub use game_settings::GameSettings;
 pub use map::MapConfig as ProceduralMapConfig;
@@ -39,6 +44,7 @@ pub use module::{
     PowerCoreFields, SensorArrayFields, ShieldGeneratorFields, StealthSystemFields,
     WarpJumpCoreFields, WarpType,
 };
+pub use outfitting::OutfittingConfig;
 pub use ship_class::{ShipClassConfig, ShipClassRole, ShipSize};
 pub use simulation::SimulationConfig as ProceduralSimConfig;
 pub use weapon::{
@@ -60,6 +66,10 @@ pub struct GameConfig {
     pub module_definitions: Vec<ModuleConfig>,
     pub ammunition_types: Vec<AmmunitionConfig>,
     pub kinetic_weapon_kinds: Vec<KineticWeaponKind>,
+    #[serde(default)]
+    pub commodities: Vec<CommodityConfig>,
+    #[serde(default)]
+    pub outfitting: OutfittingConfig,
 
     // New Phase 7.5 configurations
     #[serde(default)]
@@ -260,6 +270,18 @@ impl GameConfig {
         .map(|c| c.kinds)
         .unwrap_or_default();
 
+        // Load the commodity catalog for station markets
+        let commodities =
+            Self::load_yaml_optional::<CommoditiesConfig>(data_dir.join("commodities.yaml"))
+                .unwrap_or_default()
+                .commodities;
+        info!("Loaded {} commodities", commodities.len());
+
+        // Load station outfitting stock levels
+        let outfitting =
+            Self::load_yaml_optional::<OutfittingConfig>(data_dir.join("outfitting.yaml"))
+                .unwrap_or_default();
+
         // Load module slot definitions first (needed for variant validation)
         let module_slots = Self::load_module_slots(data_dir.to_path_buf())?;
         info!("Loaded {} module slot definitions", module_slots.len());
@@ -280,6 +302,8 @@ impl GameConfig {
             module_definitions,
             ammunition_types,
             kinetic_weapon_kinds,
+            commodities,
+            outfitting,
             ai_behavior,
             procedural_map,
             simulation_params,
@@ -308,6 +332,11 @@ impl GameConfig {
             module.validate()?;
         }
 
+        // Validate commodities
+        for commodity in &self.commodities {
+            commodity.validate()?;
+        }
+
         // Validate module slots
         for slot in self.module_slots.values() {
             slot.validate()?;
@@ -345,6 +374,13 @@ impl GameConfig {
             }
         }
 
+        let mut commodity_ids = HashMap::new();
+        for c in &self.commodities {
+            if commodity_ids.insert(&c.id, &c.name).is_some() {
+                return Err(format!("Duplicate commodity ID '{}' found", c.id));
+            }
+        }
+
         // Check for duplicate module slot IDs (already done during loading via HashMap)
         // But we can validate they're reasonable
         if self.module_slots.is_empty() {
@@ -387,6 +423,11 @@ impl GameConfig {
         self.kinetic_weapon_kinds.iter().find(|k| k.id == id)
     }
 
+    /// Find a commodity by ID
+    pub fn get_commodity(&self, id: &str) -> Option<&CommodityConfig> {
+        self.commodities.iter().find(|c| c.id == id)
+    }
+
     /// Get all variants for a module type
     pub fn get_module_variants(&self, module_type: &str) -> Option<&Vec<ModuleVariant>> {
         self.module_variants.get(module_type)
@@ -842,6 +883,8 @@ pub mod test_utils {
             module_definitions: vec![],
             ammunition_types: vec![],
             kinetic_weapon_kinds: vec![],
+            commodities: vec![],
+            outfitting: OutfittingConfig::default(),
             game_settings: GameSettings::default(),
         }
     }
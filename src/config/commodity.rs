@@ -0,0 +1,90 @@
+//! Commodity configuration
+//!
+//! This module defines the trade goods stations buy and sell, loaded from
+//! `data/commodities.yaml`.
+
+use serde::{Deserialize, Serialize};
+
+/// Commodity catalog file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommoditiesConfig {
+    /// All tradeable commodities
+    pub commodities: Vec<CommodityConfig>,
+}
+
+/// A tradeable commodity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommodityConfig {
+    /// Unique identifier
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Description
+    #[serde(default)]
+    pub description: String,
+    /// Price per unit when a station's supply meets its demand (credits)
+    pub base_price: f32,
+    /// Cargo space taken by one unit (cubic meters)
+    pub volume: f32,
+    /// Units a station typically keeps on hand
+    pub stock: f32,
+}
+
+impl CommodityConfig {
+    /// Validate the commodity
+    pub fn validate(&self) -> Result<(), String> {
+        if self.base_price <= 0.0 {
+            return Err(format!(
+                "Commodity '{}' must have a positive base_price",
+                self.id
+            ));
+        }
+        if self.volume <= 0.0 {
+            return Err(format!(
+                "Commodity '{}' must have a positive volume",
+                self.id
+            ));
+        }
+        if self.stock < 0.0 {
+            return Err(format!(
+                "Commodity '{}' cannot have negative stock",
+                self.id
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commodities() {
+        let yaml = r#"
+commodities:
+  - id: water
+    name: "Water"
+    base_price: 5
+    volume: 1.0
+    stock: 1000
+"#;
+        let config: CommoditiesConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.commodities.len(), 1);
+        assert_eq!(config.commodities[0].base_price, 5.0);
+        assert!(config.commodities[0].validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_free_commodity() {
+        let commodity = CommodityConfig {
+            id: "air".to_string(),
+            name: "Air".to_string(),
+            description: String::new(),
+            base_price: 0.0,
+            volume: 1.0,
+            stock: 100.0,
+        };
+        assert!(commodity.validate().is_err());
+    }
+}
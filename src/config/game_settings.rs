@@ -1,7 +1,8 @@
 //! Game settings configuration
 //!
 //! This module defines global game settings loaded from `data/game.yaml`,
-//! including economy parameters like team starting credits.
+//! including economy parameters like team starting credits and the session
+//! autosave interval.
 
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +17,12 @@ pub struct GameSettings {
     /// Default: 1,000,000 credits
     #[serde(default = "default_team_starting_credits")]
     pub team_starting_credits: i64,
+
+    /// Seconds between automatic session saves; `0` disables autosave
+    ///
+    /// Default: 300 seconds
+    #[serde(default = "default_autosave_interval")]
+    pub autosave_interval: u64,
 }
 
 /// Default starting credits for teams (1,000,000)
@@ -23,10 +30,16 @@ fn default_team_starting_credits() -> i64 {
     1_000_000
 }
 
+/// Default autosave interval (5 minutes)
+fn default_autosave_interval() -> u64 {
+    300
+}
+
 impl Default for GameSettings {
     fn default() -> Self {
         Self {
             team_starting_credits: default_team_starting_credits(),
+            autosave_interval: default_autosave_interval(),
         }
     }
 }
@@ -51,12 +64,14 @@ mod tests {
     fn test_default_game_settings() {
         let settings = GameSettings::default();
         assert_eq!(settings.team_starting_credits, 1_000_000);
+        assert_eq!(settings.autosave_interval, 300);
     }
 
     #[test]
     fn test_validate_valid_settings() {
         let settings = GameSettings {
             team_starting_credits: 500_000,
+            ..Default::default()
         };
         assert!(settings.validate().is_ok());
     }
@@ -65,6 +80,7 @@ mod tests {
     fn test_validate_zero_credits() {
         let settings = GameSettings {
             team_starting_credits: 0,
+            ..Default::default()
         };
         assert!(settings.validate().is_ok());
     }
@@ -73,6 +89,7 @@ mod tests {
     fn test_validate_negative_credits() {
         let settings = GameSettings {
             team_starting_credits: -100,
+            ..Default::default()
         };
         let result = settings.validate();
         assert!(result.is_err());
@@ -83,9 +100,11 @@ mod tests {
     fn test_deserialize_from_yaml() {
         let yaml = r#"
 team_starting_credits: 2000000
+autosave_interval: 60
 "#;
         let settings: GameSettings = serde_yaml::from_str(yaml).unwrap();
         assert_eq!(settings.team_starting_credits, 2_000_000);
+        assert_eq!(settings.autosave_interval, 60);
     }
 
     #[test]
@@ -95,12 +114,14 @@ team_starting_credits: 2000000
 "#;
         let settings: GameSettings = serde_yaml::from_str(yaml).unwrap();
         assert_eq!(settings.team_starting_credits, 1_000_000);
+        assert_eq!(settings.autosave_interval, 300);
     }
 
     #[test]
     fn test_serialize_to_yaml() {
         let settings = GameSettings {
             team_starting_credits: 1_500_000,
+            ..Default::default()
         };
         let yaml = serde_yaml::to_string(&settings).unwrap();
         assert!(yaml.contains("team_starting_credits: 1500000"));
@@ -0,0 +1,153 @@
+//! Outfitting configuration
+//!
+//! This module defines how stations stock modules and ammunition for docked
+//! refits, loaded from `data/outfitting.yaml`. Each station's stock is
+//! scaled by its station type and its owning faction.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::generation::StationType;
+
+/// Outfitting stock configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OutfittingConfig {
+    /// Stock levels by station type; types not listed use the defaults
+    #[serde(default)]
+    pub station_types: HashMap<StationType, StationStockConfig>,
+    /// Stock multipliers by faction ID; factions not listed use 1.0
+    #[serde(default)]
+    pub factions: HashMap<String, FactionStockConfig>,
+}
+
+/// Stock levels for one station type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StationStockConfig {
+    /// Units of each module or variant a station typically stocks
+    #[serde(default = "default_module_stock")]
+    pub module_stock: f32,
+    /// Rounds of each ammunition type a station typically stocks
+    #[serde(default = "default_ammunition_stock")]
+    pub ammunition_stock: f32,
+    /// Stock multiplier per module group (`Offense`, `Defense`, `Support`)
+    #[serde(default)]
+    pub groups: HashMap<String, f32>,
+}
+
+/// Stock multipliers for one faction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactionStockConfig {
+    /// Stock multiplier per module slot ID
+    #[serde(default)]
+    pub slots: HashMap<String, f32>,
+    /// Stock multiplier for ammunition
+    #[serde(default = "default_multiplier")]
+    pub ammunition: f32,
+}
+
+fn default_module_stock() -> f32 {
+    2.0
+}
+
+fn default_ammunition_stock() -> f32 {
+    200.0
+}
+
+fn default_multiplier() -> f32 {
+    1.0
+}
+
+impl Default for StationStockConfig {
+    fn default() -> Self {
+        Self {
+            module_stock: default_module_stock(),
+            ammunition_stock: default_ammunition_stock(),
+            groups: HashMap::new(),
+        }
+    }
+}
+
+impl OutfittingConfig {
+    /// Stock levels for a station type
+    pub fn station_type(&self, station_type: StationType) -> StationStockConfig {
+        self.station_types
+            .get(&station_type)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Units of a module slot's modules a station typically stocks
+    ///
+    /// The station type's `module_stock` scaled by its weight for the
+    /// slot's groups and the faction's weight for the slot.
+    pub fn module_stock(
+        &self,
+        station_type: StationType,
+        faction: &str,
+        slot_id: &str,
+        groups: &[String],
+    ) -> f32 {
+        let stock = self.station_type(station_type);
+        let group_weight: f32 = groups
+            .iter()
+            .map(|group| stock.groups.get(group).copied().unwrap_or(1.0))
+            .product();
+        let faction_weight = self
+            .factions
+            .get(faction)
+            .and_then(|faction| faction.slots.get(slot_id))
+            .copied()
+            .unwrap_or(1.0);
+        stock.module_stock * group_weight * faction_weight
+    }
+
+    /// Rounds of each ammunition type a station typically stocks
+    pub fn ammunition_stock(&self, station_type: StationType, faction: &str) -> f32 {
+        let faction_weight = self
+            .factions
+            .get(faction)
+            .map_or(1.0, |faction| faction.ammunition);
+        self.station_type(station_type).ammunition_stock * faction_weight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stock_weights() {
+        let yaml = r#"
+station_types:
+  military:
+    module_stock: 4
+    ammunition_stock: 500
+    groups:
+      Offense: 2.0
+factions:
+  mars-coalition:
+    slots:
+      kinetic-weapon: 1.5
+    ammunition: 2.0
+"#;
+        let config: OutfittingConfig = serde_yaml::from_str(yaml).unwrap();
+        let offense = vec!["Offense".to_string()];
+        assert_eq!(
+            config.module_stock(
+                StationType::Military,
+                "mars-coalition",
+                "kinetic-weapon",
+                &offense
+            ),
+            12.0
+        );
+        assert_eq!(
+            config.module_stock(StationType::Trade, "mars-coalition", "cargo-bay", &[]),
+            2.0
+        );
+        assert_eq!(
+            config.ammunition_stock(StationType::Military, "mars-coalition"),
+            1000.0
+        );
+    }
+}
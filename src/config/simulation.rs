@@ -15,6 +15,14 @@ pub struct SimulationConfig {
     pub systems: ShipSystemsConfig,
     /// Docking and stations
     pub docking: DockingConfig,
+    /// Ship-to-ship communications
+    pub comms: CommsConfig,
+    /// Station markets and cargo
+    pub trade: TradeConfig,
+    /// Team standing with factions
+    pub reputation: ReputationConfig,
+    /// Wrecks left by destroyed ships
+    pub salvage: SalvageConfig,
 }
 
 /// Physics simulation configuration
@@ -199,6 +207,8 @@ pub struct ShipSystemsConfig {
     pub cooling: CoolingConfig,
     /// Repair system settings
     pub repair: RepairConfig,
+    /// Auxiliary support module settings
+    pub auxiliary: AuxiliaryConfig,
 }
 
 /// Power system configuration
@@ -238,6 +248,16 @@ pub struct RepairConfig {
     pub power_cost: f64,
 }
 
+/// Auxiliary support module configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuxiliaryConfig {
+    /// Activation duration for modules without a `duration` stat (seconds)
+    pub activation_duration: f64,
+    /// Cooldown between activations for modules without a `recharge_time`
+    /// stat (seconds)
+    pub cooldown: f64,
+}
+
 /// Docking configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DockingConfig {
@@ -253,6 +273,96 @@ pub struct DockingConfig {
     pub undock_distance: f64,
 }
 
+/// Communications configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommsConfig {
+    /// Range at which friendly stations relay messages (meters)
+    pub station_relay_range: f64,
+    /// Most relays a message may pass through on its way
+    pub max_relay_hops: u32,
+}
+
+/// Trade configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeConfig {
+    /// Fraction of the gap between a market's supply and its usual stock
+    /// closed per second
+    pub restock_rate: f32,
+    /// Largest relative change in a market's demand per second
+    pub demand_volatility: f32,
+    /// How strongly prices follow the demand to supply ratio
+    pub price_elasticity: f32,
+    /// Lowest price as a fraction of the base price
+    pub min_price_factor: f32,
+    /// Highest price as a fraction of the base price
+    pub max_price_factor: f32,
+    /// Fraction of the asking price a station pays for goods it buys
+    pub sell_ratio: f32,
+    /// Spread of each station's stock and demand around the catalog stock
+    /// (0.5 = ±50%)
+    pub stock_variance: f32,
+    /// Cargo space added by a cargo bay without a `cargo_capacity` stat
+    /// (cubic meters)
+    pub cargo_bay_capacity: f32,
+}
+
+/// Reputation configuration
+///
+/// Standings run from `min_standing` to `max_standing`, with 0 neutral.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReputationConfig {
+    /// Starting standing per step of the relationship between the team's
+    /// faction and another (allied = 3 steps, at war = -3)
+    pub standing_per_relationship: f32,
+    /// Lowest standing
+    pub min_standing: f32,
+    /// Highest standing
+    pub max_standing: f32,
+    /// Standing below which a faction's stations deny docking
+    pub docking_threshold: f32,
+    /// Standing at or below which a faction's AI ships treat the team as
+    /// hostile
+    pub hostile_threshold: f32,
+    /// Largest change in station prices from standing (0.25 = ±25% at the
+    /// ends of the range)
+    pub price_spread: f32,
+    /// Standing lost with a faction per point of damage dealt to its ships
+    pub combat_loss_per_damage: f32,
+    /// Multiplier on combat losses with factions not hostile to the team
+    pub neutral_attack_multiplier: f32,
+    /// Fraction of a combat loss gained with factions hostile to the
+    /// victim's
+    pub enemy_gain_ratio: f32,
+    /// Standing gained per credit traded at a faction's stations
+    pub trade_gain_per_credit: f32,
+    /// Standing gained for completing a contract for a faction
+    pub contract_gain: f32,
+}
+
+/// Salvage configuration
+///
+/// A destroyed ship leaves a wreck holding part of its hold and the modules
+/// that survived.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SalvageConfig {
+    /// Smallest fraction of each cargo and ammunition stack left in a wreck
+    pub min_loot_fraction: f32,
+    /// Largest fraction of each cargo and ammunition stack left in a wreck
+    pub max_loot_fraction: f32,
+    /// Chance each intact module survives in the wreck
+    pub module_survival_chance: f32,
+    /// Farthest a ship can lock a tractor beam onto a wreck (meters)
+    pub tractor_range: f64,
+    /// Speed a tractor beam pulls a wreck in at (meters/second)
+    pub tractor_speed: f64,
+    /// Farthest a wreck can be from a ship to salvage it (meters)
+    pub salvage_range: f64,
+    /// Cargo space a salvaged module takes (cubic meters)
+    pub module_volume: f32,
+    /// Fraction of a module's value a station pays for it, before wear
+    pub sale_fraction: f32,
+}
+
 impl Default for SimulationConfig {
     fn default() -> Self {
         Self {
@@ -353,6 +463,10 @@ impl Default for SimulationConfig {
                     module_repair_time: 2.0,
                     power_cost: 0.5,
                 },
+                auxiliary: AuxiliaryConfig {
+                    activation_duration: 10.0,
+                    cooldown: 30.0,
+                },
             },
             docking: DockingConfig {
                 request_range: 10000.0,
@@ -361,6 +475,43 @@ impl Default for SimulationConfig {
                 completion_time: 5.0,
                 undock_distance: 500.0,
             },
+            comms: CommsConfig {
+                station_relay_range: 50000.0,
+                max_relay_hops: 4,
+            },
+            trade: TradeConfig {
+                restock_rate: 0.01,
+                demand_volatility: 0.05,
+                price_elasticity: 1.0,
+                min_price_factor: 0.25,
+                max_price_factor: 4.0,
+                sell_ratio: 0.9,
+                stock_variance: 0.5,
+                cargo_bay_capacity: 100.0,
+            },
+            reputation: ReputationConfig {
+                standing_per_relationship: 20.0,
+                min_standing: -100.0,
+                max_standing: 100.0,
+                docking_threshold: -30.0,
+                hostile_threshold: -50.0,
+                price_spread: 0.25,
+                combat_loss_per_damage: 0.02,
+                neutral_attack_multiplier: 3.0,
+                enemy_gain_ratio: 0.5,
+                trade_gain_per_credit: 0.001,
+                contract_gain: 10.0,
+            },
+            salvage: SalvageConfig {
+                min_loot_fraction: 0.25,
+                max_loot_fraction: 0.75,
+                module_survival_chance: 0.5,
+                tractor_range: 2000.0,
+                tractor_speed: 50.0,
+                salvage_range: 100.0,
+                module_volume: 5.0,
+                sale_fraction: 0.4,
+            },
         }
     }
 }
@@ -55,12 +55,23 @@ pub enum GameEvent {
     /// Status effect removed from ship
     StatusEffectRemoved { ship_id: Uuid, effect_type: String },
 
-    /// Module damaged or repaired
+    /// Module damaged, repaired, overheated, or shut down
     ModuleStatusChanged {
         ship_id: Uuid,
         module_id: String,
         health_percent: f32,
         operational: bool,
+        #[serde(default)]
+        overheated: bool,
+    },
+
+    /// A module was shed or restored because power demand exceeded supply
+    ModuleBrownout {
+        ship_id: Uuid,
+        module_id: String,
+        /// Share of its allocated power the module now receives
+        power_percent: f32,
+        browned_out: bool,
     },
 
     /// Power allocation changed
@@ -75,11 +86,25 @@ pub enum GameEvent {
         allocations: Vec<ModuleAllocation>,
     },
 
-    /// Communication message sent
+    /// Communication message received by a ship
     MessageSent {
+        message_id: String,
         from_ship_id: Uuid,
         to_ship_id: Option<Uuid>, // None for broadcasts
+        /// Ship whose crew received this copy
+        receiver_id: Uuid,
         message: String,
+        /// Whether the receiver could not decrypt the message
+        garbled: bool,
+        /// Ships and stations the message passed through, in order
+        relays: Vec<String>,
+    },
+
+    /// Communication message that could not be delivered
+    MessageUndelivered {
+        ship_id: Uuid,
+        message_id: String,
+        to_ship_id: Option<Uuid>,
     },
 
     /// Ship docked at station
@@ -101,22 +126,43 @@ pub enum GameEvent {
         contact_id: Uuid,
     },
 
+    /// A scan revealed another detail tier of its target
+    ScanTierRevealed {
+        ship_id: Uuid,
+        target_id: Uuid,
+        tier: String,
+        /// Whether this was the scan's last tier
+        complete: bool,
+    },
+
     /// Ship destroyed
     ShipDestroyed {
         ship_id: Uuid,
         destroyed_by: Option<Uuid>,
     },
 
+    /// A destroyed ship left a wreck
+    WreckSpawned {
+        wreck_id: String,
+        /// Ship the wreck was
+        ship_id: Uuid,
+        position: [f64; 3],
+    },
+
     /// Countermeasure activated
     CountermeasureActivated {
         ship_id: Uuid,
         countermeasure_type: String,
     },
 
-    /// Point defense engaged
+    /// Point defense fired at incoming ordnance
     PointDefenseEngaged {
         ship_id: Uuid,
-        target_id: Uuid,
+        weapon_id: String,
+        /// Ship that fired the ordnance, if it still exists
+        target_id: Option<Uuid>,
+        damage_type: DamageType,
+        /// Whether the ordnance was destroyed
         success: bool,
     },
 
@@ -133,12 +179,90 @@ pub enum GameEvent {
         drive_type: FtlDriveType,
     },
 
+    /// A missile or torpedo warhead exploded
+    Explosion {
+        /// Ship that fired the warhead, if it still exists
+        source_ship_id: Option<Uuid>,
+        damage_type: DamageType,
+        position: [f64; 3],
+        /// Blast radius (meters)
+        radius: f32,
+        /// Damage at the center of the blast
+        damage: f32,
+    },
+
+    /// A team's standing with a faction changed
+    ReputationChanged {
+        team_id: String,
+        faction: String,
+        /// Standing after the change
+        standing: f32,
+        /// Change in standing
+        change: f32,
+        cause: ReputationCause,
+        /// Whether the faction's AI ships now treat the team as hostile
+        hostile: bool,
+    },
+
     /// Simulation tick completed
     SimulationTick { tick: u64, time: f64 },
 }
 
+impl GameEvent {
+    /// The ship this event concerns, if any
+    ///
+    /// Contact events belong to the detecting ship, received messages to the
+    /// receiving ship, and undelivered messages to the sender.
+    /// Explosions and wrecks are broadcast to every simulation subscriber;
+    /// reputation changes belong to a team instead (see [`Self::team_id`]).
+    pub fn ship_id(&self) -> Option<Uuid> {
+        match self {
+            GameEvent::ShipMoved { ship_id, .. }
+            | GameEvent::WeaponFired { ship_id, .. }
+            | GameEvent::DamageTaken { ship_id, .. }
+            | GameEvent::ShieldChanged { ship_id, .. }
+            | GameEvent::StatusEffectApplied { ship_id, .. }
+            | GameEvent::StatusEffectRemoved { ship_id, .. }
+            | GameEvent::ModuleStatusChanged { ship_id, .. }
+            | GameEvent::ModuleBrownout { ship_id, .. }
+            | GameEvent::PowerAllocationChanged { ship_id, .. }
+            | GameEvent::CoolingAllocationChanged { ship_id, .. }
+            | GameEvent::ShipDocked { ship_id, .. }
+            | GameEvent::ShipUndocked { ship_id, .. }
+            | GameEvent::ShipDestroyed { ship_id, .. }
+            | GameEvent::CountermeasureActivated { ship_id, .. }
+            | GameEvent::PointDefenseEngaged { ship_id, .. }
+            | GameEvent::ScanTierRevealed { ship_id, .. }
+            | GameEvent::MessageUndelivered { ship_id, .. }
+            | GameEvent::FtlEngaged { ship_id, .. }
+            | GameEvent::FtlDisengaged { ship_id, .. } => Some(*ship_id),
+            GameEvent::MessageSent { receiver_id, .. } => Some(*receiver_id),
+            GameEvent::ContactDetected {
+                detecting_ship_id, ..
+            }
+            | GameEvent::ContactLost {
+                detecting_ship_id, ..
+            } => Some(*detecting_ship_id),
+            GameEvent::Explosion { .. }
+            | GameEvent::WreckSpawned { .. }
+            | GameEvent::ReputationChanged { .. }
+            | GameEvent::SimulationTick { .. } => None,
+        }
+    }
+
+    /// The team this event is private to, if any
+    ///
+    /// Only the team's members receive a change in its standing.
+    pub fn team_id(&self) -> Option<&str> {
+        match self {
+            GameEvent::ReputationChanged { team_id, .. } => Some(team_id),
+            _ => None,
+        }
+    }
+}
+
 /// Type of damage dealt
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, juniper::GraphQLEnum)]
 #[serde(rename_all = "snake_case")]
 pub enum DamageType {
     Energy,
@@ -147,6 +271,20 @@ pub enum DamageType {
     Torpedo,
 }
 
+/// What changed a team's standing with a faction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReputationCause {
+    /// Damage dealt to the faction's ships
+    Combat,
+    /// Damage dealt to ships of a faction the faction is hostile to
+    EnemyCombat,
+    /// Trade at the faction's stations
+    Trade,
+    /// A contract completed for the faction
+    Contract,
+}
+
 /// Module allocation info
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleAllocation {
@@ -201,6 +339,11 @@ impl EventQueue {
         self.events.len()
     }
 
+    /// Events pushed since the queue held `start` events
+    pub fn since(&self, start: usize) -> &[GameEvent] {
+        self.events.get(start..).unwrap_or_default()
+    }
+
     /// Check if the queue is empty
     pub fn is_empty(&self) -> bool {
         self.events.is_empty()
@@ -306,14 +449,19 @@ mod tests {
         let to_ship = Uuid::new_v4();
 
         let event = GameEvent::MessageSent {
+            message_id: "msg1".to_string(),
             from_ship_id: from_ship,
             to_ship_id: Some(to_ship),
+            receiver_id: to_ship,
             message: "Hello!".to_string(),
+            garbled: false,
+            relays: vec![],
         };
 
         let json = serde_json::to_string(&event).unwrap();
         assert!(json.contains("message_sent"));
         assert!(json.contains("Hello!"));
+        assert_eq!(event.ship_id(), Some(to_ship));
     }
 
     #[test]
@@ -38,6 +38,30 @@ pub struct ProceduralUniverse {
     pub history: Vec<HistoricalEvent>,
 }
 
+/// Parameters a universe is generated from
+///
+/// Generation is deterministic, so these are enough to rebuild the same
+/// universe.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UniverseSettings {
+    pub name: String,
+    pub seed: u64,
+    pub num_stars: usize,
+    pub num_factions: usize,
+}
+
+impl UniverseSettings {
+    /// Generate the universe these settings describe
+    pub fn generate(&self) -> ProceduralUniverse {
+        ProceduralUniverse::generate(
+            self.name.clone(),
+            self.seed,
+            self.num_stars,
+            self.num_factions,
+        )
+    }
+}
+
 impl ProceduralUniverse {
     /// Generate a complete universe
     pub fn generate(name: String, seed: u64, num_stars: usize, num_factions: usize) -> Self {
@@ -118,10 +118,11 @@ pub struct StationInfo {
 }
 
 /// Type of space station
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum StationType {
     /// Trading hub
+    #[default]
     Trade,
     /// Military base
     Military,
@@ -11,7 +11,11 @@ pub mod config;
 pub mod event_broadcaster;
 pub mod events;
 pub mod generation;
+pub mod market;
 pub mod models;
+pub mod replay;
+pub mod salvage;
+pub mod save;
 pub mod server;
 pub mod simulation;
 pub mod state;
@@ -5,8 +5,11 @@
 
 use clap::{Parser, Subcommand};
 use hyperion::config::GameConfig;
+use hyperion::replay::{self, Recording};
 use hyperion::server;
 use log::{LevelFilter, error, info};
+use std::io::{Read, Write};
+use std::net::TcpStream;
 use std::path::PathBuf;
 
 /// HYPERION - Spaceship Bridge Simulation Game Server
@@ -29,9 +32,90 @@ enum Commands {
         /// Log level (error, warn, info, debug, trace)
         #[arg(short, long, default_value = "info")]
         log_level: String,
+
+        /// Resume a saved session by name
+        #[arg(long)]
+        load: Option<String>,
+
+        /// Record the session under this name from the start
+        #[arg(long)]
+        record: Option<String>,
+    },
+
+    /// Save the session of a running server
+    Save {
+        /// Name to save the session under
+        name: String,
+
+        /// Address of the running server
+        #[arg(short, long, default_value = "127.0.0.1:8000")]
+        address: String,
+
+        /// Operator token of the running server
+        #[arg(short, long, env = "HYPERION_OPERATOR_TOKEN")]
+        token: String,
+    },
+
+    /// Replace the session of a running server with a saved one
+    Load {
+        /// Name of the saved session
+        name: String,
+
+        /// Address of the running server
+        #[arg(short, long, default_value = "127.0.0.1:8000")]
+        address: String,
+
+        /// Operator token of the running server
+        #[arg(short, long, env = "HYPERION_OPERATOR_TOKEN")]
+        token: String,
+    },
+
+    /// Re-run a recorded session headlessly, printing its events as JSON lines
+    Replay {
+        /// Recording file written by `POST /v1/replays/stop`
+        file: PathBuf,
+
+        /// Path to the data directory the session was recorded with
+        #[arg(short, long, default_value = "./data")]
+        data_dir: PathBuf,
+
+        /// Only report whether the replay matched the recording
+        #[arg(short, long)]
+        quiet: bool,
     },
 }
 
+/// Send a POST request to a running server as its operator and return the
+/// response body
+///
+/// Fails if the server cannot be reached or does not answer `200 OK`.
+fn post(
+    address: &str,
+    token: &str,
+    path: &str,
+    body: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut stream = TcpStream::connect(address)?;
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nAuthorization: Bearer {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        address,
+        token,
+        body.len(),
+        body
+    )?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let status = head.lines().next().unwrap_or_default();
+    if !status.contains(" 200 ") {
+        return Err(format!("Server responded with '{}'", status).into());
+    }
+    Ok(body.to_string())
+}
+
 #[rocket::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
@@ -40,6 +124,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Commands::Start {
             data_dir,
             log_level,
+            load,
+            record,
         } => {
             // Initialize logging
             let level_filter = match log_level.to_lowercase().as_str() {
@@ -73,8 +159,55 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             // Start the server
             info!("Launching Rocket server...");
-            server::launch(config).await?;
+            server::launch(config, load, record).await?;
+
+            Ok(())
+        }
+        Commands::Save {
+            name,
+            address,
+            token,
+        } => {
+            let body = serde_json::json!({ "name": name }).to_string();
+            post(&address, &token, "/v1/saves", &body)?;
+            println!("Saved session '{}'", name);
+            Ok(())
+        }
+        Commands::Load {
+            name,
+            address,
+            token,
+        } => {
+            post(&address, &token, &format!("/v1/saves/{}/load", name), "")?;
+            println!("Loaded session '{}'", name);
+            Ok(())
+        }
+        Commands::Replay {
+            file,
+            data_dir,
+            quiet,
+        } => {
+            let config = GameConfig::load_from_directory(&data_dir)?;
+            let recording = Recording::read(&file)?;
+
+            let mut stdout = std::io::stdout().lock();
+            let outcome = replay::replay(&recording, &config, |event| {
+                if !quiet && let Ok(line) = serde_json::to_string(event) {
+                    let _ = writeln!(stdout, "{}", line);
+                }
+            })?;
 
+            if !outcome.matched {
+                return Err(format!(
+                    "Replay of '{}' diverged: event digest {:016x}, recorded {:016x}",
+                    recording.name, outcome.event_digest, recording.event_digest
+                )
+                .into());
+            }
+            eprintln!(
+                "Replayed '{}' ({} ticks): events match the recording",
+                recording.name, outcome.ticks
+            );
             Ok(())
         }
     }
@@ -104,8 +237,70 @@ mod tests {
         let Commands::Start {
             data_dir,
             log_level,
-        } = cli.command;
+            load,
+            record,
+        } = cli.command
+        else {
+            panic!("expected start command");
+        };
         assert_eq!(data_dir, PathBuf::from("/custom/path"));
         assert_eq!(log_level, "debug");
+        assert_eq!(load, None);
+        assert_eq!(record, None);
+    }
+
+    #[test]
+    fn test_cli_save_and_load() {
+        let cli = Cli::parse_from(["hyperion", "start", "--load", "autosave"]);
+        assert!(
+            matches!(cli.command, Commands::Start { load: Some(name), .. } if name == "autosave")
+        );
+
+        let cli = Cli::parse_from([
+            "hyperion",
+            "save",
+            "evening",
+            "--address",
+            "10.0.0.2:8000",
+            "--token",
+            "secret",
+        ]);
+        let Commands::Save {
+            name,
+            address,
+            token,
+        } = cli.command
+        else {
+            panic!("expected save command");
+        };
+        assert_eq!(name, "evening");
+        assert_eq!(address, "10.0.0.2:8000");
+        assert_eq!(token, "secret");
+
+        let cli = Cli::parse_from(["hyperion", "load", "evening", "--token", "secret"]);
+        assert!(
+            matches!(cli.command, Commands::Load { address, .. } if address == "127.0.0.1:8000")
+        );
+    }
+
+    #[test]
+    fn test_cli_replay() {
+        let cli = Cli::parse_from(["hyperion", "replay", "duel.json", "--quiet"]);
+        let Commands::Replay {
+            file,
+            data_dir,
+            quiet,
+        } = cli.command
+        else {
+            panic!("expected replay command");
+        };
+        assert_eq!(file, PathBuf::from("duel.json"));
+        assert_eq!(data_dir, PathBuf::from("./data"));
+        assert!(quiet);
+
+        let cli = Cli::parse_from(["hyperion", "start", "--record", "duel"]);
+        assert!(
+            matches!(cli.command, Commands::Start { record: Some(name), .. } if name == "duel")
+        );
     }
 }
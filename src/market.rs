@@ -0,0 +1,261 @@
+//! Station commodity markets
+//!
+//! Every station with a trade service keeps a market listing each commodity
+//! in the catalog. A listing tracks the station's supply of the commodity
+//! and its demand for it; the price is the commodity's base price scaled by
+//! the demand to supply ratio. Buying draws down supply and raises the price,
+//! selling does the opposite. Over time supply settles back toward the
+//! station's usual stock while demand wanders.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::config::CommodityConfig;
+use crate::config::simulation::TradeConfig;
+
+/// Direction of a trade, from the ship's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeSide {
+    /// The ship buys from the station
+    Buy,
+    /// The ship sells to the station
+    Sell,
+}
+
+/// A station's supply of and demand for one commodity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketListing {
+    /// Commodity ID
+    pub commodity_id: String,
+    /// Commodity base price (credits per unit)
+    pub base_price: f32,
+    /// Units on hand
+    pub supply: f32,
+    /// Units the station wants on hand
+    pub demand: f32,
+    /// Units the station usually keeps on hand
+    pub stock: f32,
+}
+
+impl MarketListing {
+    /// Price per unit the station asks
+    pub fn price(&self, config: &TradeConfig) -> f32 {
+        let ratio = (self.demand / self.supply.max(1.0)).powf(config.price_elasticity);
+        self.base_price * ratio.clamp(config.min_price_factor, config.max_price_factor)
+    }
+
+    /// Price per unit the station pays
+    pub fn bid(&self, config: &TradeConfig) -> f32 {
+        self.price(config) * config.sell_ratio
+    }
+
+    /// Whole units available to buy
+    pub fn available(&self) -> u32 {
+        self.supply.max(0.0).floor() as u32
+    }
+
+    /// Total price of trading `quantity` units, one unit at a time
+    ///
+    /// Each unit moves supply by one, so large orders pay for the price
+    /// movement they cause.
+    pub fn quote(&self, side: TradeSide, quantity: u32, config: &TradeConfig) -> f32 {
+        let mut listing = self.clone();
+        (0..quantity)
+            .map(|_| match side {
+                TradeSide::Buy => {
+                    let price = listing.price(config);
+                    listing.supply -= 1.0;
+                    price
+                }
+                TradeSide::Sell => {
+                    listing.supply += 1.0;
+                    listing.bid(config)
+                }
+            })
+            .sum()
+    }
+
+    /// Apply a trade of `quantity` units to supply
+    pub fn settle(&mut self, side: TradeSide, quantity: u32) {
+        match side {
+            TradeSide::Buy => self.supply = (self.supply - quantity as f32).max(0.0),
+            TradeSide::Sell => self.supply += quantity as f32,
+        }
+    }
+
+    /// Let supply settle toward the usual stock and demand wander for `dt`
+    /// seconds
+    pub fn drift(&mut self, dt: f32, config: &TradeConfig, rng: &mut impl Rng) {
+        self.supply += (self.stock - self.supply) * (config.restock_rate * dt).min(1.0);
+        let swing = rng.random_range(-1.0..=1.0) * config.demand_volatility * dt;
+        self.demand = (self.demand * (1.0 + swing)).clamp(
+            self.stock * config.min_price_factor,
+            self.stock * config.max_price_factor,
+        );
+    }
+}
+
+/// A station's commodity market
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StationMarket {
+    /// One listing per commodity, in catalog order
+    pub listings: Vec<MarketListing>,
+}
+
+impl StationMarket {
+    /// Open a market for every commodity in the catalog
+    ///
+    /// Each station's usual stock, current supply, and demand are spread
+    /// around the catalog stock by `stock_variance`.
+    pub fn open(commodities: &[CommodityConfig], config: &TradeConfig, rng: &mut impl Rng) -> Self {
+        let variance = config.stock_variance.clamp(0.0, 1.0);
+        let mut spread = |value: f32| value * rng.random_range(1.0 - variance..=1.0 + variance);
+        let listings = commodities
+            .iter()
+            .map(|commodity| {
+                let stock = spread(commodity.stock);
+                MarketListing {
+                    commodity_id: commodity.id.clone(),
+                    base_price: commodity.base_price,
+                    supply: spread(stock),
+                    demand: spread(stock),
+                    stock,
+                }
+            })
+            .collect();
+        Self { listings }
+    }
+
+    /// Whether the market has been opened
+    pub fn is_open(&self) -> bool {
+        !self.listings.is_empty()
+    }
+
+    /// The listing for a commodity
+    pub fn listing(&self, commodity_id: &str) -> Option<&MarketListing> {
+        self.listings
+            .iter()
+            .find(|listing| listing.commodity_id == commodity_id)
+    }
+
+    /// The listing for a commodity, mutably
+    pub fn listing_mut(&mut self, commodity_id: &str) -> Option<&mut MarketListing> {
+        self.listings
+            .iter_mut()
+            .find(|listing| listing.commodity_id == commodity_id)
+    }
+
+    /// Let every listing drift for `dt` seconds
+    pub fn drift(&mut self, dt: f32, config: &TradeConfig, rng: &mut impl Rng) {
+        for listing in &mut self.listings {
+            listing.drift(dt, config, rng);
+        }
+    }
+}
+
+/// Prices and stock of one commodity at a station
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketQuote {
+    pub commodity_id: String,
+    pub name: String,
+    /// Price per unit to buy from the station
+    pub buy_price: f32,
+    /// Price per unit the station pays
+    pub sell_price: f32,
+    /// Whole units available to buy
+    pub supply: u32,
+    /// Units the station wants on hand
+    pub demand: u32,
+    /// Cargo space taken by one unit (cubic meters)
+    pub volume: f32,
+}
+
+/// Outcome of a completed trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeReceipt {
+    pub side: TradeSide,
+    pub commodity_id: String,
+    pub quantity: u32,
+    /// Credits paid (buy) or received (sell)
+    pub total: i64,
+    /// Team credits after the trade
+    pub credits: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::simulation::SimulationConfig;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn listing(supply: f32, demand: f32) -> MarketListing {
+        MarketListing {
+            commodity_id: "ore".to_string(),
+            base_price: 10.0,
+            supply,
+            demand,
+            stock: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_price_follows_demand_and_supply() {
+        let config = SimulationConfig::default().trade;
+        assert_eq!(listing(100.0, 100.0).price(&config), 10.0);
+        assert_eq!(listing(50.0, 100.0).price(&config), 20.0);
+        assert_eq!(listing(200.0, 100.0).price(&config), 5.0);
+        // Clamped to the configured range
+        assert_eq!(listing(1.0, 100.0).price(&config), 40.0);
+        assert_eq!(listing(100.0, 100.0).bid(&config), 9.0);
+    }
+
+    #[test]
+    fn test_trades_move_the_price() {
+        let config = SimulationConfig::default().trade;
+        let mut market = listing(100.0, 100.0);
+
+        // Each unit bought costs more than the last
+        let total = market.quote(TradeSide::Buy, 10, &config);
+        assert!(total > 100.0);
+        market.settle(TradeSide::Buy, 10);
+        assert_eq!(market.supply, 90.0);
+        assert!(market.price(&config) > 10.0);
+
+        market.settle(TradeSide::Sell, 30);
+        assert_eq!(market.supply, 120.0);
+        assert!(market.price(&config) < 10.0);
+        assert!(market.quote(TradeSide::Sell, 10, &config) < 10.0 * 9.0);
+    }
+
+    #[test]
+    fn test_drift_restocks_and_bounds_demand() {
+        let config = SimulationConfig::default().trade;
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut market = listing(20.0, 100.0);
+        for _ in 0..1000 {
+            market.drift(1.0, &config, &mut rng);
+        }
+        assert!(market.supply > 99.0);
+        assert!(market.demand >= 25.0 && market.demand <= 400.0);
+    }
+
+    #[test]
+    fn test_open_lists_every_commodity() {
+        let config = SimulationConfig::default().trade;
+        let commodities = vec![CommodityConfig {
+            id: "ore".to_string(),
+            name: "Ore".to_string(),
+            description: String::new(),
+            base_price: 8.0,
+            volume: 2.0,
+            stock: 1000.0,
+        }];
+        let market = StationMarket::open(&commodities, &config, &mut StdRng::seed_from_u64(3));
+        let ore = market.listing("ore").unwrap();
+        assert!(market.is_open());
+        assert!(ore.stock >= 500.0 && ore.stock <= 1500.0);
+        assert_eq!(ore.base_price, 8.0);
+    }
+}
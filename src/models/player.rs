@@ -3,6 +3,7 @@
 //! Defines structures for players and teams in the game.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Represents a player in the game
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -27,6 +28,11 @@ pub struct Team {
     /// Team's current credit balance
     #[serde(default)]
     pub credits: i64,
+    /// Standing with each faction it has dealt with (faction ID -> standing)
+    ///
+    /// Factions not listed are at the standing the team started with.
+    #[serde(default)]
+    pub reputation: BTreeMap<String, f32>,
 }
 
 impl Player {
@@ -53,6 +59,7 @@ impl Team {
             faction,
             members: Vec::new(),
             credits: starting_credits,
+            reputation: BTreeMap::new(),
         }
     }
 
@@ -5,7 +5,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Enumeration of all ship crew positions
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, juniper::GraphQLEnum)]
 #[serde(rename_all = "snake_case")]
 pub enum ShipRole {
     /// Captain - Command authority, crew reassignment, captain's log
@@ -5,6 +5,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::ship::CompiledModule;
+
 /// Represents the current status of a ship
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShipStatus {
@@ -36,6 +38,12 @@ pub struct ShipStatus {
     pub status_effects: Vec<StatusEffect>,
     /// Module health tracking (module_id -> health percentage)
     pub module_health: HashMap<String, f32>,
+    /// Position in space (synced from the simulation)
+    #[serde(default)]
+    pub position: [f64; 3],
+    /// Velocity in m/s (synced from the simulation)
+    #[serde(default)]
+    pub velocity: [f64; 3],
 }
 
 /// Represents a temporary status effect on a ship
@@ -67,6 +75,9 @@ pub struct Inventory {
     pub ammunition: HashMap<String, u32>,
     /// Cargo storage (item_id -> quantity)
     pub cargo: HashMap<String, u32>,
+    /// Modules salvaged from wrecks, carried as cargo
+    #[serde(default)]
+    pub salvage: Vec<CompiledModule>,
 }
 
 impl Default for ShipStatus {
@@ -93,6 +104,8 @@ impl ShipStatus {
             effective_weight: base_weight,
             status_effects: Vec::new(),
             module_health: HashMap::new(),
+            position: [0.0; 3],
+            velocity: [0.0; 3],
         }
     }
 
@@ -139,6 +152,7 @@ impl Inventory {
         Self {
             ammunition: HashMap::new(),
             cargo: HashMap::new(),
+            salvage: Vec::new(),
         }
     }
 
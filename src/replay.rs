@@ -0,0 +1,597 @@
+//! Deterministic session recordings
+//!
+//! A [`Recording`] holds the [`WorldSnapshot`] a session started from, the
+//! seed its [`SimulationRng`](crate::simulation::SimulationRng) is derived
+//! from, and every command applied while recording, stamped with the tick it
+//! was applied before. [`replay`] rebuilds the world from the snapshot and
+//! feeds the commands back in at their ticks; because the simulation's only
+//! source of randomness is the seeded generator, the replayed session
+//! produces the same `GameEvent`s as the original.
+//!
+//! To check that it did, the recording carries an [`EventDigest`] of every
+//! event emitted by the recorded ticks. Events within a tick are hashed in
+//! sorted order, since the order registries are walked in is not stable
+//! between processes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::ai::AICommand;
+use crate::config::GameConfig;
+use crate::events::GameEvent;
+use crate::market::TradeSide;
+use crate::models::{ModuleInstance, Ship};
+use crate::save;
+use crate::simulation::{HelmCommand, Transmission};
+use crate::state::{GameWorld, WorldSnapshot};
+use crate::stations::ServiceRequest;
+
+/// Current recording format version
+pub const REPLAY_VERSION: u32 = 1;
+
+/// A command applied to the world while recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplayCommand {
+    /// Helm order queued for the next tick
+    Helm {
+        ship_id: String,
+        command: HelmCommand,
+    },
+    /// Weapons of one type pointed at a target ship
+    WeaponTarget {
+        ship_id: String,
+        weapon_type: String,
+        target_id: String,
+    },
+    /// Weapon fired manually
+    FireWeapon {
+        ship_id: String,
+        weapon_type: String,
+        weapon_id: String,
+    },
+    /// Weapon auto-fire toggled
+    AutoFire {
+        ship_id: String,
+        weapon_type: String,
+        weapon_id: String,
+        enabled: bool,
+    },
+    /// Rounds moved from the hold into a weapon's magazine
+    LoadWeapon {
+        ship_id: String,
+        weapon_type: String,
+        weapon_id: String,
+        ammo_type: String,
+        quantity: u32,
+    },
+    /// Engineering power allocations (module instance ID -> MW)
+    PowerAllocations {
+        ship_id: String,
+        allocations: HashMap<String, f32>,
+    },
+    /// Engineering cooling allocations (module instance ID -> K/s)
+    CoolingAllocations {
+        ship_id: String,
+        allocations: HashMap<String, f32>,
+    },
+    /// Engineering power priorities (module instance ID -> priority)
+    PowerPriorities {
+        ship_id: String,
+        priorities: HashMap<String, u8>,
+    },
+    /// Module queued for repair
+    Repair { ship_id: String, module_id: String },
+    /// Auxiliary module activated
+    ActivateAuxiliary { ship_id: String, module_id: String },
+    /// Auxiliary module uses restocked at a station
+    RechargeAuxiliary { ship_id: String, module_id: String },
+    /// Service bought at a station by a docked ship
+    StationService {
+        station_id: String,
+        ship_id: String,
+        request: ServiceRequest,
+    },
+    /// Commodity bought or sold at a station by a docked ship
+    Trade {
+        station_id: String,
+        ship_id: String,
+        side: TradeSide,
+        commodity_id: String,
+        quantity: u32,
+    },
+    /// Modules of a docked ship swapped at a station
+    Refit {
+        ship_id: String,
+        modules: Vec<ModuleInstance>,
+    },
+    /// Tractor beam locked onto or released from a wreck
+    Tractor {
+        ship_id: String,
+        wreck_id: String,
+        engaged: bool,
+    },
+    /// Wreck salvaged into a ship's hold
+    Salvage { ship_id: String, wreck_id: String },
+    /// Salvaged module sold to a station by a docked ship
+    SellSalvage {
+        station_id: String,
+        ship_id: String,
+        module_id: String,
+    },
+    /// Contract for a faction completed by a team
+    CompleteContract { team_id: String, faction: String },
+    /// Shields raised or lowered
+    Shields { ship_id: String, raised: bool },
+    /// Chaff cloud deployed around a ship
+    DeployChaff { ship_id: String },
+    /// Point defense enabled or disabled
+    PointDefense { ship_id: String, enabled: bool },
+    /// Interceptor rounds loaded into point-defense magazines
+    RestockPointDefense { ship_id: String, quantity: u32 },
+    /// Message queued for transmission
+    SendMessage {
+        ship_id: String,
+        transmission: Transmission,
+    },
+    /// Science scan of another ship started
+    Scan { ship_id: String, target_id: String },
+    /// Orders issued by a ship's AI
+    Ai {
+        ship_id: String,
+        commands: Vec<AICommand>,
+    },
+    /// Ship registered and spawned into the simulation
+    SpawnShip { ship: Box<Ship> },
+    /// Ship removed from the game
+    RemoveShip { ship_id: String },
+}
+
+/// A command and the tick it was applied before
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedCommand {
+    pub tick: u64,
+    pub command: ReplayCommand,
+}
+
+/// A recorded session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recording {
+    /// Recording format version
+    pub version: u32,
+    /// Recording name
+    pub name: String,
+    /// Seconds since the Unix epoch when recording started
+    pub recorded_at: u64,
+    /// Seed the simulation's random rolls are derived from
+    pub seed: u64,
+    /// The world when recording started
+    pub initial: WorldSnapshot,
+    /// Commands in the order they were applied
+    pub commands: Vec<RecordedCommand>,
+    /// Tick the simulation had reached when recording stopped
+    pub end_tick: u64,
+    /// Digest of every event emitted by the recorded ticks
+    pub event_digest: u64,
+}
+
+impl Recording {
+    /// First recorded tick
+    pub fn start_tick(&self) -> u64 {
+        self.initial.simulation.tick
+    }
+
+    /// Parse a recording
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let recording: Self =
+            serde_json::from_str(json).map_err(|e| format!("Invalid recording: {}", e))?;
+        if recording.version != REPLAY_VERSION {
+            return Err(format!(
+                "Unsupported recording version {} (this server reads {})",
+                recording.version, REPLAY_VERSION
+            ));
+        }
+        Ok(recording)
+    }
+
+    /// Read a recording from a file
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_json(&json)
+    }
+}
+
+/// Order-insensitive (within a tick) FNV-1a hash of emitted events
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventDigest(u64);
+
+impl EventDigest {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    /// Digest of no ticks
+    pub fn new() -> Self {
+        Self(Self::OFFSET)
+    }
+
+    /// Fold in the events emitted by one tick
+    pub fn add_tick(&mut self, events: &[GameEvent]) {
+        let mut encoded: Vec<String> = events
+            .iter()
+            .map(|event| serde_json::to_string(event).unwrap_or_default())
+            .collect();
+        encoded.sort();
+        for event in encoded {
+            self.write(event.as_bytes());
+            self.write(b"\n");
+        }
+        // Record separator, so events cannot slide between ticks unnoticed
+        self.write(&[0x1e]);
+    }
+
+    /// Current digest value
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+}
+
+impl Default for EventDigest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Recording in progress, owned by the `GameWorld` being recorded
+#[derive(Debug)]
+pub struct Recorder {
+    name: String,
+    recorded_at: u64,
+    initial: WorldSnapshot,
+    commands: Vec<RecordedCommand>,
+    digest: EventDigest,
+}
+
+impl Recorder {
+    /// Start recording from a snapshot of the world
+    pub fn new(name: &str, initial: WorldSnapshot) -> Self {
+        Self {
+            name: name.to_string(),
+            recorded_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+            initial,
+            commands: Vec::new(),
+            digest: EventDigest::new(),
+        }
+    }
+
+    /// Append a command applied before `tick`
+    pub fn record(&mut self, tick: u64, command: ReplayCommand) {
+        self.commands.push(RecordedCommand { tick, command });
+    }
+
+    /// Fold in the events emitted by one tick
+    pub fn record_tick(&mut self, events: &[GameEvent]) {
+        self.digest.add_tick(events);
+    }
+
+    /// Number of commands recorded so far
+    pub fn command_count(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Finish the recording at `end_tick`
+    pub fn finish(self, end_tick: u64) -> Recording {
+        Recording {
+            version: REPLAY_VERSION,
+            name: self.name,
+            recorded_at: self.recorded_at,
+            seed: self.initial.simulation.seed,
+            initial: self.initial,
+            commands: self.commands,
+            end_tick,
+            event_digest: self.digest.value(),
+        }
+    }
+}
+
+/// Result of replaying a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayOutcome {
+    /// Ticks simulated
+    pub ticks: u64,
+    /// Digest of the events the replay emitted
+    pub event_digest: u64,
+    /// Whether the replay emitted the same events as the recorded session
+    pub matched: bool,
+}
+
+/// Re-run a recording headlessly
+///
+/// `on_event` is called with every event the replay emits, tick by tick.
+/// Fails if a recorded command cannot be applied, which means the replay has
+/// already diverged from the original session.
+pub fn replay(
+    recording: &Recording,
+    config: &GameConfig,
+    mut on_event: impl FnMut(&GameEvent),
+) -> Result<ReplayOutcome, String> {
+    let mut world = GameWorld::from_snapshot(recording.initial.clone(), config)?;
+    world.simulation.seed = recording.seed;
+    // Only ticks that ran were recorded, whatever the pause state at the start
+    world.simulation.resume();
+    // Re-recording computes the replay's digest exactly as the original's was
+    world.start_recording(&recording.name, config)?;
+
+    let mut commands = recording.commands.iter().peekable();
+    while world.simulation.tick < recording.end_tick {
+        let tick = world.simulation.tick;
+        while let Some(recorded) = commands.next_if(|recorded| recorded.tick <= tick) {
+            world
+                .apply_replay_command(recorded.command.clone(), config)
+                .map_err(|e| format!("Replay diverged at tick {}: {}", recorded.tick, e))?;
+        }
+        world.step_simulation();
+        world.drain_events().iter().for_each(&mut on_event);
+    }
+
+    let replayed = world
+        .stop_recording()
+        .ok_or("Replay stopped recording unexpectedly")?;
+    Ok(ReplayOutcome {
+        ticks: recording.end_tick - recording.start_tick(),
+        event_digest: replayed.event_digest,
+        matched: replayed.event_digest == recording.event_digest,
+    })
+}
+
+/// Directory of recordings
+pub struct ReplayStore {
+    dir: PathBuf,
+}
+
+impl ReplayStore {
+    /// Create a store over `dir` (created on first write)
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Store in the platform data directory, falling back to `./replays`
+    pub fn default_location() -> Self {
+        let dir = directories::ProjectDirs::from("dev", "totty", "hyperion")
+            .map(|dirs| dirs.data_dir().join("replays"))
+            .unwrap_or_else(|| PathBuf::from("replays"));
+        Self::new(dir)
+    }
+
+    /// Write a recording, replacing any with the same name
+    ///
+    /// Returns the path written, for `hyperion replay`.
+    pub fn write(&self, recording: &Recording) -> Result<PathBuf, String> {
+        save::check_name(&recording.name)?;
+        let path = self.dir.join(format!("{}.json", recording.name));
+        let json = serde_json::to_vec(recording)
+            .map_err(|e| format!("Failed to encode recording: {}", e))?;
+        save::write_atomic(&self.dir, &path, &json)?;
+        Ok(path)
+    }
+}
+
+/// Stores for unit tests
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use super::ReplayStore;
+
+    /// A store in a fresh directory under the system temp dir
+    pub fn temp_replay_store() -> ReplayStore {
+        ReplayStore::new(
+            std::env::temp_dir()
+                .join("hyperion-tests")
+                .join(uuid::Uuid::new_v4().to_string()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_utils::temp_replay_store;
+    use super::*;
+    use crate::config::AmmunitionConfig;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::{CompiledModule, Inventory, ShipStatus};
+    use uuid::Uuid;
+
+    fn test_config() -> GameConfig {
+        let mut config = create_test_game_config();
+        config.ammunition_types = vec![AmmunitionConfig {
+            id: "slug".to_string(),
+            name: "Slug".to_string(),
+            description: String::new(),
+            cost: 1.0,
+            weight: 1.0,
+            impact_damage: 15.0,
+            blast_radius: 0.0,
+            blast_damage: 0.0,
+            velocity: 300.0,
+            armor_penetration: 0.0,
+            acceleration: 0.0,
+            max_speed: 0.0,
+            max_turn_rate: 0.0,
+            lifetime: 5.0,
+            weapon_tags: vec![],
+            category: "kinetic".to_string(),
+            ammo_type: String::new(),
+            ammo_size: String::new(),
+        }];
+        config
+    }
+
+    fn weapon(instance_id: &str, slot: &str, stats: serde_json::Value) -> CompiledModule {
+        CompiledModule {
+            instance_id: instance_id.to_string(),
+            module_id: slot.to_string(),
+            kind: None,
+            name: instance_id.to_string(),
+            stats: serde_json::from_value(stats).unwrap(),
+            current_health: 100.0,
+            max_health: 100.0,
+            operational: true,
+            power_allocated: 1.0,
+            cooling_allocated: 1.0,
+        }
+    }
+
+    fn ship(name: &str, position: [f64; 3]) -> Ship {
+        let mut status = ShipStatus::new(1000.0, 100.0, 1000.0);
+        status.position = position;
+        let mut inventory = Inventory::new();
+        inventory.add_ammunition("slug".to_string(), 50);
+        Ship {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            class: "cruiser".to_string(),
+            team_id: "team1".to_string(),
+            player_roles: HashMap::new(),
+            status,
+            modules: vec![
+                weapon(
+                    "laser",
+                    "de-weapon",
+                    serde_json::json!({ "damage": 20, "recharge_time": 0.5, "weapon_tags": ["Beam", "Ion"] }),
+                ),
+                weapon(
+                    "cannon",
+                    "kinetic-weapon",
+                    serde_json::json!({ "reload_time": 0.1, "accuracy": 0.5, "ammo_capacity": 50 }),
+                ),
+            ],
+            weapons: vec![],
+            inventory,
+        }
+    }
+
+    /// Record a short engagement: one ship closes on another while its AI
+    /// locks on (three seconds) and opens fire with a coin-flip cannon and
+    /// an Ion beam
+    fn record_engagement(config: &GameConfig) -> (Recording, Vec<GameEvent>) {
+        let mut world = GameWorld::new();
+        world.configure_simulation(&config.simulation_params);
+        let attacker = world.register_ship(ship("Attacker", [0.0, 0.0, 0.0]));
+        let target = world.register_ship(ship("Target", [0.0, 0.0, 40.0]));
+        world.spawn_ship_entity(&attacker, config).unwrap();
+        world.spawn_ship_entity(&target, config).unwrap();
+
+        world.start_recording("engagement", config).unwrap();
+        world
+            .load_kinetic_ammo(
+                attacker.clone(),
+                "cannon".to_string(),
+                "slug".to_string(),
+                50,
+                config,
+            )
+            .unwrap();
+        world.add_thrust_command(attacker.clone(), 0.0, 0.0, 1.0);
+        world
+            .apply_ai_commands(
+                &attacker,
+                vec![
+                    AICommand::SetTarget {
+                        target_id: Uuid::parse_str(&target).unwrap(),
+                    },
+                    AICommand::FireWeapons,
+                ],
+            )
+            .unwrap();
+
+        let mut events = Vec::new();
+        for tick in 0..420 {
+            if tick == 60 {
+                world.add_stop_command(attacker.clone());
+            }
+            world.step_simulation();
+            events.extend(world.drain_events());
+        }
+        (world.stop_recording().unwrap(), events)
+    }
+
+    #[test]
+    fn test_replay_reproduces_events() {
+        let config = test_config();
+        let (recording, events) = record_engagement(&config);
+        assert_eq!(recording.end_tick - recording.start_tick(), 420);
+        assert!(
+            events
+                .iter()
+                .any(|event| matches!(event, GameEvent::DamageTaken { .. }))
+        );
+
+        let mut replayed = Vec::new();
+        let outcome = replay(&recording, &config, |event| replayed.push(event.clone())).unwrap();
+
+        assert!(outcome.matched);
+        assert_eq!(outcome.ticks, 420);
+        assert_eq!(outcome.event_digest, recording.event_digest);
+        assert_eq!(replayed.len(), events.len());
+    }
+
+    #[test]
+    fn test_replay_detects_divergence() {
+        let config = test_config();
+        let (mut recording, _) = record_engagement(&config);
+
+        recording.seed = recording.seed.wrapping_add(1);
+        let outcome = replay(&recording, &config, |_| {}).unwrap();
+        assert!(!outcome.matched);
+    }
+
+    #[test]
+    fn test_write_and_read() {
+        let config = test_config();
+        let (recording, _) = record_engagement(&config);
+        let store = temp_replay_store();
+
+        let path = store.write(&recording).unwrap();
+        let read = Recording::read(&path).unwrap();
+        assert_eq!(read.commands.len(), recording.commands.len());
+        assert_eq!(read.event_digest, recording.event_digest);
+
+        let mut invalid = recording;
+        invalid.name = "../escape".to_string();
+        assert!(store.write(&invalid).is_err());
+        invalid.version = REPLAY_VERSION + 1;
+        assert!(
+            Recording::from_json(&serde_json::to_string(&invalid).unwrap())
+                .unwrap_err()
+                .contains("Unsupported recording version")
+        );
+    }
+
+    #[test]
+    fn test_digest_ignores_order_within_tick() {
+        let a = GameEvent::SimulationTick { tick: 1, time: 0.1 };
+        let b = GameEvent::SimulationTick { tick: 2, time: 0.2 };
+
+        let mut forward = EventDigest::new();
+        forward.add_tick(&[a.clone(), b.clone()]);
+        let mut backward = EventDigest::new();
+        backward.add_tick(&[b.clone(), a.clone()]);
+        assert_eq!(forward, backward);
+
+        let mut split = EventDigest::new();
+        split.add_tick(&[a]);
+        split.add_tick(&[b]);
+        assert_ne!(forward, split);
+    }
+}
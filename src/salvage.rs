@@ -0,0 +1,206 @@
+//! Wrecks left by destroyed ships
+//!
+//! A destroyed ship leaves a wreck where it died, drifting on at its last
+//! velocity. The wreck holds a random fraction of each cargo and ammunition
+//! stack the ship carried and each intact module that survived the
+//! destruction. Ships lock a tractor beam onto a wreck to pull it alongside,
+//! then salvage what fits into their hold.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::config::simulation::SalvageConfig;
+use crate::models::{CompiledModule, Inventory, Ship};
+
+/// What is left of a destroyed ship
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Wreck {
+    /// Unique wreck identifier
+    pub id: String,
+    /// ID of the ship destroyed
+    pub ship_id: String,
+    /// Name of the ship destroyed
+    pub name: String,
+    /// Class of the ship destroyed
+    pub class: String,
+    /// Position in space
+    pub position: [f64; 3],
+    /// Drift velocity
+    pub velocity: [f64; 3],
+    /// Ammunition and cargo left in the wreck
+    pub inventory: Inventory,
+    /// Modules that survived
+    pub modules: Vec<CompiledModule>,
+    /// Ship holding the wreck in its tractor beam, if any
+    pub tractored_by: Option<String>,
+}
+
+impl Wreck {
+    /// Leave a wreck of a ship
+    ///
+    /// Each cargo and ammunition stack keeps a fraction between
+    /// `min_loot_fraction` and `max_loot_fraction`, rounded down, and each
+    /// module that was not destroyed survives with `module_survival_chance`.
+    pub fn from_ship(id: String, ship: &Ship, config: &SalvageConfig, rng: &mut impl Rng) -> Self {
+        let mut inventory = Inventory::new();
+        inventory.ammunition = loot(&ship.inventory.ammunition, config, rng);
+        inventory.cargo = loot(&ship.inventory.cargo, config, rng);
+        inventory.salvage = ship
+            .inventory
+            .salvage
+            .iter()
+            .filter(|_| rng.random_bool(config.module_survival_chance.clamp(0.0, 1.0) as f64))
+            .cloned()
+            .collect();
+
+        let modules = ship
+            .modules
+            .iter()
+            .filter(|module| !module.is_destroyed())
+            .filter(|_| rng.random_bool(config.module_survival_chance.clamp(0.0, 1.0) as f64))
+            .cloned()
+            .map(|mut module| {
+                module.operational = false;
+                module.power_allocated = 0.0;
+                module.cooling_allocated = 0.0;
+                module
+            })
+            .collect();
+
+        Self {
+            id,
+            ship_id: ship.id.clone(),
+            name: ship.name.clone(),
+            class: ship.class.clone(),
+            position: ship.status.position,
+            velocity: ship.status.velocity,
+            inventory,
+            modules,
+            tractored_by: None,
+        }
+    }
+
+    /// Whether nothing is left to salvage
+    pub fn is_empty(&self) -> bool {
+        self.inventory.ammunition.is_empty()
+            && self.inventory.cargo.is_empty()
+            && self.inventory.salvage.is_empty()
+            && self.modules.is_empty()
+    }
+}
+
+/// Roll the part of each stack left in a wreck, dropping empty stacks
+fn loot(
+    stacks: &HashMap<String, u32>,
+    config: &SalvageConfig,
+    rng: &mut impl Rng,
+) -> HashMap<String, u32> {
+    // Walk the stacks in order so the same seed leaves the same wreck
+    let mut items: Vec<(&String, &u32)> = stacks.iter().collect();
+    items.sort();
+
+    let (min, max) = (config.min_loot_fraction, config.max_loot_fraction);
+    items
+        .into_iter()
+        .filter_map(|(item_id, quantity)| {
+            let fraction = if max > min {
+                rng.random_range(min..=max)
+            } else {
+                min
+            };
+            let left = (*quantity as f32 * fraction.clamp(0.0, 1.0)).floor() as u32;
+            (left > 0).then(|| (item_id.clone(), left))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::simulation::SimulationConfig;
+    use crate::models::ShipStatus;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn module(instance_id: &str, health: f32) -> CompiledModule {
+        CompiledModule {
+            instance_id: instance_id.to_string(),
+            module_id: "cargo-bay".to_string(),
+            kind: None,
+            name: "Cargo Bay".to_string(),
+            stats: Default::default(),
+            current_health: health,
+            max_health: 10.0,
+            operational: true,
+            power_allocated: 1.0,
+            cooling_allocated: 1.0,
+        }
+    }
+
+    fn ship() -> Ship {
+        let mut inventory = Inventory::new();
+        inventory.add_ammunition("slug".to_string(), 100);
+        inventory.cargo.insert("ore".to_string(), 40);
+        inventory.cargo.insert("gems".to_string(), 1);
+        Ship {
+            id: "ship-1".to_string(),
+            name: "Doomed".to_string(),
+            class: "cruiser".to_string(),
+            team_id: "team-1".to_string(),
+            player_roles: HashMap::new(),
+            status: ShipStatus {
+                position: [10.0, 20.0, 30.0],
+                velocity: [1.0, 0.0, 0.0],
+                ..ShipStatus::default()
+            },
+            modules: vec![module("intact", 10.0), module("wrecked", 0.0)],
+            weapons: vec![],
+            inventory,
+        }
+    }
+
+    #[test]
+    fn test_wreck_keeps_a_fraction_of_the_hold() {
+        let mut config = SimulationConfig::default().salvage;
+        config.min_loot_fraction = 0.5;
+        config.max_loot_fraction = 0.5;
+        config.module_survival_chance = 1.0;
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let wreck = Wreck::from_ship("wreck-1".to_string(), &ship(), &config, &mut rng);
+
+        assert_eq!(wreck.ship_id, "ship-1");
+        assert_eq!(wreck.position, [10.0, 20.0, 30.0]);
+        assert_eq!(wreck.velocity, [1.0, 0.0, 0.0]);
+        assert_eq!(wreck.inventory.ammunition["slug"], 50);
+        assert_eq!(wreck.inventory.cargo["ore"], 20);
+        // Half a unit rounds down to nothing
+        assert!(!wreck.inventory.cargo.contains_key("gems"));
+        // Destroyed modules never survive, and survivors are powered down
+        assert_eq!(wreck.modules.len(), 1);
+        assert_eq!(wreck.modules[0].instance_id, "intact");
+        assert!(!wreck.modules[0].operational);
+        assert!(!wreck.is_empty());
+    }
+
+    #[test]
+    fn test_wreck_loot_is_random_within_bounds() {
+        let config = SimulationConfig::default().salvage;
+
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let wreck = Wreck::from_ship("wreck".to_string(), &ship(), &config, &mut rng);
+            let slugs = wreck.inventory.ammunition["slug"];
+            assert!((25..=75).contains(&slugs));
+            assert!(wreck.modules.len() <= 1);
+
+            // The same seed leaves the same wreck
+            let mut rng = StdRng::seed_from_u64(seed);
+            let again = Wreck::from_ship("wreck".to_string(), &ship(), &config, &mut rng);
+            assert_eq!(again.inventory.ammunition, wreck.inventory.ammunition);
+            assert_eq!(again.inventory.cargo, wreck.inventory.cargo);
+            assert_eq!(again.modules.len(), wreck.modules.len());
+        }
+    }
+}
@@ -0,0 +1,419 @@
+//! Saved game sessions
+//!
+//! A [`SaveFile`] bundles a [`WorldSnapshot`] with the settings the current
+//! procedural universe was generated from (the universe itself is rebuilt
+//! from its seed on load). Saves are JSON files in a [`SaveStore`] directory,
+//! by default the platform data directory from the `directories` crate.
+//!
+//! Every file carries a `version` field. When the format changes, bump
+//! [`SAVE_VERSION`] and append a step to `MIGRATIONS` that upgrades a save
+//! from the previous version, so older files keep loading.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time;
+
+use crate::api::generation::UniverseState;
+use crate::config::GameConfig;
+use crate::generation::UniverseSettings;
+use crate::state::{GameWorld, SharedGameWorld, WorldSnapshot, token_digest};
+
+/// Current save format version
+pub const SAVE_VERSION: u32 = 2;
+
+/// Name autosaves are written under
+pub const AUTOSAVE_NAME: &str = "autosave";
+
+/// Upgrade steps; entry `n` converts a version `n + 1` save to version `n + 2`
+const MIGRATIONS: &[fn(serde_json::Value) -> serde_json::Value] = &[digest_player_tokens];
+
+/// Version 1 saved session tokens as they were issued; version 2 keeps only
+/// their digests
+fn digest_player_tokens(mut value: serde_json::Value) -> serde_json::Value {
+    if let Some(tokens) = value["world"]["player_tokens"].as_object_mut() {
+        *tokens = std::mem::take(tokens)
+            .into_iter()
+            .map(|(token, player_id)| (token_digest(&token), player_id))
+            .collect();
+    }
+    value
+}
+
+/// A complete saved game session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveFile {
+    /// Save format version
+    pub version: u32,
+    /// Save name
+    pub name: String,
+    /// Seconds since the Unix epoch when the save was written
+    pub saved_at: u64,
+    /// Settings of the procedural universe, if one was generated
+    pub universe: Option<UniverseSettings>,
+    /// The game world
+    pub world: WorldSnapshot,
+}
+
+impl SaveFile {
+    /// Capture the current session
+    pub fn capture(name: &str, world: &GameWorld, universe: &UniverseState) -> Self {
+        Self {
+            version: SAVE_VERSION,
+            name: name.to_string(),
+            saved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+            universe: universe.settings.clone(),
+            world: world.snapshot(),
+        }
+    }
+
+    /// Replace the running session with this save
+    ///
+    /// The world is rebuilt before anything is swapped, so a save that fails
+    /// to restore leaves the running session untouched.
+    pub fn restore(
+        self,
+        config: &GameConfig,
+        game_world: &SharedGameWorld,
+        universe: &RwLock<UniverseState>,
+    ) -> Result<(), String> {
+        let mut restored = GameWorld::from_snapshot(self.world, config)?;
+
+        let mut world = game_world.write().unwrap();
+        // The runner's configured timestep outlives the session it ticks
+        restored.simulation.timestep = world.simulation.timestep;
+        *world = restored;
+
+        let mut universe = universe.write().unwrap();
+        match self.universe {
+            Some(settings) => {
+                universe.generate(settings);
+            }
+            None => *universe = UniverseState::new(),
+        }
+
+        Ok(())
+    }
+
+    /// Listing summary of this save
+    pub fn summary(&self) -> SaveSummary {
+        SaveSummary {
+            name: self.name.clone(),
+            version: self.version,
+            saved_at: self.saved_at,
+            tick: self.world.simulation.tick,
+        }
+    }
+
+    /// Parse a save, migrating it from an older version if needed
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| format!("Invalid save file: {}", e))?;
+        serde_json::from_value(migrate(value)?).map_err(|e| format!("Invalid save file: {}", e))
+    }
+}
+
+/// Bring a raw save up to [`SAVE_VERSION`]
+fn migrate(mut value: serde_json::Value) -> Result<serde_json::Value, String> {
+    let mut version = value
+        .get("version")
+        .and_then(|version| version.as_u64())
+        .ok_or("Save file has no version")? as u32;
+
+    if version == 0 || version > SAVE_VERSION {
+        return Err(format!(
+            "Unsupported save version {} (this server reads up to {})",
+            version, SAVE_VERSION
+        ));
+    }
+
+    while version < SAVE_VERSION {
+        value = MIGRATIONS[(version - 1) as usize](value);
+        version += 1;
+        value["version"] = version.into();
+    }
+
+    Ok(value)
+}
+
+/// Summary of a save for listings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveSummary {
+    pub name: String,
+    pub version: u32,
+    pub saved_at: u64,
+    pub tick: u64,
+}
+
+/// Directory of save files
+pub struct SaveStore {
+    dir: PathBuf,
+}
+
+impl SaveStore {
+    /// Create a store over `dir` (created on first write)
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Store in the platform data directory, falling back to `./saves`
+    pub fn default_location() -> Self {
+        let dir = directories::ProjectDirs::from("dev", "totty", "hyperion")
+            .map(|dirs| dirs.data_dir().join("saves"))
+            .unwrap_or_else(|| PathBuf::from("saves"));
+        Self::new(dir)
+    }
+
+    /// Directory saves are written to
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write a save, replacing any save with the same name
+    ///
+    /// The file is written beside its destination and renamed into place, so
+    /// a crash mid-write never corrupts an existing save.
+    pub fn write(&self, save: &SaveFile) -> Result<(), String> {
+        let path = self.path(&save.name)?;
+        let json = serde_json::to_vec(save).map_err(|e| format!("Failed to encode save: {}", e))?;
+        write_atomic(&self.dir, &path, &json)
+    }
+
+    /// Read a save by name
+    pub fn read(&self, name: &str) -> Result<SaveFile, String> {
+        let path = self.path(name)?;
+        let json = fs::read_to_string(&path).map_err(|_| format!("Save '{}' not found", name))?;
+        SaveFile::from_json(&json)
+    }
+
+    /// Summaries of every readable save, newest first
+    pub fn list(&self) -> Vec<SaveSummary> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+
+        let mut saves: Vec<SaveSummary> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                let save = SaveFile::from_json(&fs::read_to_string(path).ok()?).ok()?;
+                Some(save.summary())
+            })
+            .collect();
+        saves.sort_by(|a, b| b.saved_at.cmp(&a.saved_at).then(a.name.cmp(&b.name)));
+        saves
+    }
+
+    /// File path for a save name
+    ///
+    /// Names are limited to letters, digits, `-` and `_` so they cannot
+    /// escape the save directory.
+    fn path(&self, name: &str) -> Result<PathBuf, String> {
+        check_name(name)?;
+        Ok(self.dir.join(format!("{}.json", name)))
+    }
+}
+
+/// Check a save or recording name is 1-64 letters, digits, `-` or `_`
+pub(crate) fn check_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!(
+            "Invalid name '{}': use 1-64 letters, digits, '-' or '_'",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// Write `contents` to `path` inside `dir`, creating `dir` if needed
+///
+/// The file is written beside its destination and renamed into place, so a
+/// crash mid-write never corrupts an existing file.
+pub(crate) fn write_atomic(dir: &Path, path: &Path, contents: &[u8]) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let partial = path.with_extension("json.tmp");
+    fs::write(&partial, contents)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    fs::rename(&partial, path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Background service that periodically saves the session
+pub struct Autosaver {
+    game_world: SharedGameWorld,
+    universe: Arc<RwLock<UniverseState>>,
+    store: Arc<SaveStore>,
+    interval: Duration,
+}
+
+impl Autosaver {
+    /// Create an autosaver writing every `interval_secs` seconds
+    pub fn new(
+        game_world: SharedGameWorld,
+        universe: Arc<RwLock<UniverseState>>,
+        store: Arc<SaveStore>,
+        interval_secs: u64,
+    ) -> Self {
+        Self {
+            game_world,
+            universe,
+            store,
+            interval: Duration::from_secs(interval_secs),
+        }
+    }
+
+    /// Start the autosave loop
+    ///
+    /// Runs forever. The first save happens one interval after start.
+    pub async fn run(self) {
+        let mut interval = time::interval_at(time::Instant::now() + self.interval, self.interval);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            match self.save() {
+                Ok(()) => log::info!("Autosaved session to {}", self.store.dir().display()),
+                Err(e) => log::error!("Autosave failed: {}", e),
+            }
+        }
+    }
+
+    /// Write the autosave now
+    pub fn save(&self) -> Result<(), String> {
+        let save = {
+            let world = self.game_world.read().unwrap();
+            let universe = self.universe.read().unwrap();
+            SaveFile::capture(AUTOSAVE_NAME, &world, &universe)
+        };
+        self.store.write(&save)
+    }
+}
+
+/// Stores for unit tests
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use super::SaveStore;
+
+    /// A store in a fresh directory under the system temp dir
+    pub fn temp_store() -> SaveStore {
+        SaveStore::new(
+            std::env::temp_dir()
+                .join("hyperion-tests")
+                .join(uuid::Uuid::new_v4().to_string()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_utils::temp_store;
+    use super::*;
+    use crate::config::test_utils::create_test_game_config;
+
+    fn settings() -> UniverseSettings {
+        UniverseSettings {
+            name: "Saved".to_string(),
+            seed: 42,
+            num_stars: 20,
+            num_factions: 3,
+        }
+    }
+
+    #[test]
+    fn test_write_and_read() {
+        let store = temp_store();
+        let mut world = GameWorld::new();
+        world.register_player("alice".to_string()).unwrap();
+        let mut universe = UniverseState::new();
+        universe.generate(settings());
+
+        store
+            .write(&SaveFile::capture("session-1", &world, &universe))
+            .unwrap();
+        let save = store.read("session-1").unwrap();
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.universe.unwrap().seed, 42);
+        assert_eq!(save.world.players.len(), 1);
+        assert_eq!(store.list().len(), 1);
+        assert_eq!(store.list()[0].name, "session-1");
+    }
+
+    #[test]
+    fn test_restore_replaces_session() {
+        let config = create_test_game_config();
+        let mut saved_world = GameWorld::new();
+        saved_world.register_player("alice".to_string()).unwrap();
+        let mut saved_universe = UniverseState::new();
+        saved_universe.generate(settings());
+        let save = SaveFile::capture("s", &saved_world, &saved_universe);
+
+        let world = GameWorld::new_shared();
+        world
+            .write()
+            .unwrap()
+            .register_player("bob".to_string())
+            .unwrap();
+        let universe = RwLock::new(UniverseState::new());
+        save.restore(&config, &world, &universe).unwrap();
+
+        let world = world.read().unwrap();
+        assert!(world.get_player_by_name("alice").is_some());
+        assert!(world.get_player_by_name("bob").is_none());
+        let universe = universe.read().unwrap();
+        assert_eq!(
+            universe.universe.as_ref().unwrap().galaxy.stars.len(),
+            saved_universe.universe.as_ref().unwrap().galaxy.stars.len()
+        );
+    }
+
+    #[test]
+    fn test_invalid_names_rejected() {
+        let store = temp_store();
+        let save = SaveFile::capture("../escape", &GameWorld::new(), &UniverseState::new());
+        assert!(store.write(&save).is_err());
+        assert!(store.read("").is_err());
+        assert!(store.read("missing").unwrap_err().contains("not found"));
+    }
+
+    #[test]
+    fn test_version_checks() {
+        let save = SaveFile::capture("s", &GameWorld::new(), &UniverseState::new());
+        let mut value = serde_json::to_value(&save).unwrap();
+
+        value["version"] = (SAVE_VERSION + 1).into();
+        assert!(
+            SaveFile::from_json(&value.to_string())
+                .unwrap_err()
+                .contains("Unsupported save version")
+        );
+
+        value.as_object_mut().unwrap().remove("version");
+        assert!(SaveFile::from_json(&value.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_version_1_tokens_are_digested() {
+        let mut world = GameWorld::new();
+        let player_id = world.register_player("alice".to_string()).unwrap();
+        let save = SaveFile::capture("s", &world, &UniverseState::new());
+        let mut value = serde_json::to_value(&save).unwrap();
+        value["version"] = 1.into();
+        value["world"]["player_tokens"] = serde_json::json!({ "old-token": player_id });
+
+        let save = SaveFile::from_json(&value.to_string()).unwrap();
+        assert_eq!(save.version, SAVE_VERSION);
+        let world = GameWorld::from_snapshot(save.world, &create_test_game_config()).unwrap();
+        assert_eq!(world.authenticate_token("old-token").unwrap().id, player_id);
+    }
+}
@@ -3,10 +3,14 @@
 //! Handles the Rocket web server, GraphQL API, and client connections.
 
 use crate::api;
+use crate::api::auth::{OPERATOR_TOKEN_VAR, OperatorToken};
 use crate::api::generation::UniverseState;
 use crate::config::GameConfig;
 use crate::event_broadcaster::EventBroadcaster;
-use crate::state::GameWorld;
+use crate::replay::ReplayStore;
+use crate::save::{Autosaver, SaveStore};
+use crate::simulation::SimulationRunner;
+use crate::state::{GameWorld, generate_token};
 use crate::websocket::WebSocketManager;
 use log::info;
 use rocket_cors::{AllowedOrigins, CorsOptions};
@@ -18,15 +22,26 @@ use std::time::Duration;
 /// # Arguments
 ///
 /// * `config` - The loaded game configuration
+/// * `load` - Name of a saved session to resume, if any
+/// * `record` - Name to record the session under from the start, if any
 ///
 /// # Returns
 ///
 /// Returns a `Result` indicating success or failure
-pub async fn launch(config: GameConfig) -> Result<(), rocket::Error> {
+pub async fn launch(
+    config: GameConfig,
+    load: Option<String>,
+    record: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
     info!("Configuring Rocket server");
 
     // Initialize game world state
     let game_world = GameWorld::new_shared();
+    {
+        let mut world = game_world.write().unwrap();
+        world.configure_simulation(&config.simulation_params);
+        world.configure_markets(&config.commodities);
+    }
     info!("Game world initialized");
 
     // Initialize WebSocket manager
@@ -37,6 +52,27 @@ pub async fn launch(config: GameConfig) -> Result<(), rocket::Error> {
     let universe_state = Arc::new(RwLock::new(UniverseState::new()));
     info!("Procedural generation state initialized");
 
+    // Resume a saved session before anything starts ticking
+    let save_store = Arc::new(SaveStore::default_location());
+    info!("Save directory: {}", save_store.dir().display());
+    if let Some(name) = load {
+        save_store
+            .read(&name)?
+            .restore(&config, &game_world, &universe_state)?;
+        info!("Loaded saved session '{}'", name);
+    }
+
+    // Recordings are finished with `POST /v1/replays/stop`
+    let replay_store = Arc::new(ReplayStore::default_location());
+    if let Some(name) = record {
+        crate::save::check_name(&name)?;
+        game_world
+            .write()
+            .unwrap()
+            .start_recording(&name, &config)?;
+        info!("Recording session as '{}'", name);
+    }
+
     // Start event broadcaster in background
     let broadcaster = EventBroadcaster::with_interval(
         game_world.clone(),
@@ -49,6 +85,44 @@ pub async fn launch(config: GameConfig) -> Result<(), rocket::Error> {
     });
     info!("Event broadcaster started");
 
+    // Start the fixed-timestep simulation loop in background
+    let tick_rate = config.simulation_params.physics.tick_rate;
+    let runner = SimulationRunner::new(game_world.clone(), tick_rate);
+
+    tokio::spawn(async move {
+        runner.run().await;
+    });
+    info!("Simulation runner started ({} Hz)", tick_rate);
+
+    // Periodically save the session (an interval of 0 disables autosave)
+    let autosave_interval = config.game_settings.autosave_interval;
+    if autosave_interval > 0 {
+        let autosaver = Autosaver::new(
+            game_world.clone(),
+            universe_state.clone(),
+            save_store.clone(),
+            autosave_interval,
+        );
+
+        tokio::spawn(async move {
+            autosaver.run().await;
+        });
+        info!("Autosave enabled (every {} s)", autosave_interval);
+    }
+
+    // Saves and recordings are left to the operator
+    let operator_token = match std::env::var(OPERATOR_TOKEN_VAR) {
+        Ok(token) if !token.is_empty() => token,
+        _ => {
+            let token = generate_token();
+            info!(
+                "Operator token: {} (set {} to choose one)",
+                token, OPERATOR_TOKEN_VAR
+            );
+            token
+        }
+    };
+
     // Configure CORS to allow all origins for development
     let cors = CorsOptions::default()
         .allowed_origins(AllowedOrigins::all())
@@ -56,14 +130,23 @@ pub async fn launch(config: GameConfig) -> Result<(), rocket::Error> {
         .to_cors()
         .expect("Failed to create CORS configuration");
 
+    // GraphQL resolvers hold their own handle to the configuration
+    let graphql_config = Arc::new(config.clone());
+
     let rocket = rocket::build()
         .manage(config)
+        .manage(graphql_config)
+        .manage(api::graphql::schema())
         .manage(game_world)
         .manage(ws_manager)
         .manage(universe_state)
+        .manage(save_store)
+        .manage(replay_store)
+        .manage(OperatorToken::new(&operator_token))
         .attach(cors)
         .mount("/", api::routes())
-        .mount("/graphql", api::graphql_routes());
+        .mount("/graphql", api::graphql_routes())
+        .register("/", api::auth::catchers());
 
     info!("Server ready to launch");
     rocket.launch().await?;
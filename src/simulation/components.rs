@@ -4,11 +4,13 @@
 //! These components represent various aspects of ships, weapons, modules,
 //! and other entities in the game world.
 
+use super::module_state::ModuleStateTracker;
 use crate::models::WeaponTag;
 use crate::weapons::StatusEffectType;
 use bevy_ecs::prelude::*;
 use nalgebra::{UnitQuaternion, Vector3};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
 /// 3D position, rotation, and velocity
 #[derive(Component, Debug, Clone)]
@@ -62,6 +64,8 @@ pub struct ShipData {
     pub class_id: String,
     /// Team identifier
     pub team_id: String,
+    /// Faction of the ship's team (empty if unaffiliated)
+    pub faction: String,
     /// Current hull integrity (0.0 = destroyed)
     pub hull: f32,
     /// Maximum hull integrity
@@ -100,6 +104,7 @@ impl ShipData {
             name,
             class_id,
             team_id,
+            faction: String::new(),
             hull: max_hull,
             max_hull,
             shields: max_shields,
@@ -113,6 +118,11 @@ impl ShipData {
         }
     }
 
+    /// Whether `other` flies for the same team or faction
+    pub fn is_friendly(&self, other: &ShipData) -> bool {
+        self.team_id == other.team_id || (!self.faction.is_empty() && self.faction == other.faction)
+    }
+
     /// Check if ship is destroyed
     pub fn is_destroyed(&self) -> bool {
         self.hull <= 0.0
@@ -196,6 +206,9 @@ impl ModuleComponent {
     }
 }
 
+/// Default muzzle velocity for weapons without a `projectile_speed` stat (m/s)
+pub const DEFAULT_PROJECTILE_SPEED: f32 = 1000.0;
+
 /// Weapon state and configuration
 #[derive(Component, Debug, Clone)]
 pub struct WeaponComponent {
@@ -221,6 +234,41 @@ pub struct WeaponComponent {
     pub is_automatic: bool,
     /// Whether weapon is currently active/enabled
     pub is_active: bool,
+    /// Muzzle velocity when no ordnance overrides it (meters/second)
+    pub projectile_speed: f32,
+    /// Magazine capacity in rounds (0 = unlimited)
+    pub ammo_capacity: u32,
+    /// Ballistic profile of the loaded ammunition
+    pub ordnance: Option<Ordnance>,
+    /// Chance each round or beam hits its target (0.0 to 1.0)
+    pub accuracy: f32,
+    /// Engagement range (meters, 0 = unlimited)
+    pub range: f32,
+}
+
+/// Ballistic profile of a loaded ammunition type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ordnance {
+    /// Ammunition category (kinetic, missiles, torpedos)
+    pub category: String,
+    /// Impact damage per round
+    pub damage: f32,
+    /// Projectile speed (meters/second)
+    pub speed: f32,
+    /// Guided ordnance acceleration (meters/second²)
+    pub acceleration: f32,
+    /// Guided ordnance turn rate (radians/second)
+    pub turn_rate: f32,
+    /// Flight time before the round self-destructs (seconds, 0 = weapon default)
+    pub lifetime: f32,
+    /// Blast radius of the warhead (meters, 0 = no explosion)
+    #[serde(default)]
+    pub blast_radius: f32,
+    /// Blast damage at the center of the explosion
+    #[serde(default)]
+    pub blast_damage: f32,
+    /// Tags contributed by the ammunition
+    pub tags: Vec<WeaponTag>,
 }
 
 impl WeaponComponent {
@@ -247,6 +295,11 @@ impl WeaponComponent {
             ammo_count: 0,
             is_automatic,
             is_active: true,
+            projectile_speed: DEFAULT_PROJECTILE_SPEED,
+            ammo_capacity: 0,
+            ordnance: None,
+            accuracy: 1.0,
+            range: 0.0,
         }
     }
 
@@ -255,9 +308,22 @@ impl WeaponComponent {
         self.is_active && self.cooldown <= 0.0 && self.has_ammunition()
     }
 
+    /// Whether this weapon draws rounds from a magazine
+    pub fn uses_ammunition(&self) -> bool {
+        matches!(
+            self.weapon_type.as_str(),
+            "kinetic" | "missile" | "countermeasure"
+        )
+    }
+
+    /// Whether a target `distance` meters away is within engagement range
+    pub fn in_range(&self, distance: f32) -> bool {
+        self.range <= 0.0 || distance <= self.range
+    }
+
     /// Check if weapon has ammunition (always true for energy weapons)
     pub fn has_ammunition(&self) -> bool {
-        if self.weapon_type == "kinetic" || self.weapon_type == "missile" {
+        if self.uses_ammunition() {
             self.ammo_count > 0
         } else {
             true // Energy weapons don't need ammo
@@ -274,7 +340,7 @@ impl WeaponComponent {
     /// Fire the weapon (sets cooldown and consumes ammo)
     pub fn fire(&mut self) {
         self.cooldown = self.max_cooldown;
-        if self.weapon_type == "kinetic" || self.weapon_type == "missile" {
+        if self.uses_ammunition() {
             self.ammo_count = self.ammo_count.saturating_sub(1);
         }
     }
@@ -312,6 +378,28 @@ impl TargetingComponent {
         !self.disabled && self.target.is_some() && self.is_locked
     }
 
+    /// Acquire a new target, restarting the lock
+    pub fn set_target(&mut self, target: Entity) {
+        if self.target != Some(target) {
+            self.target = Some(target);
+            self.is_locked = false;
+            self.lock_progress = 0.0;
+        }
+    }
+
+    /// Advance the lock on the current target
+    pub fn update_lock(&mut self, delta_time: f32) {
+        if self.disabled || self.target.is_none() || self.is_locked {
+            return;
+        }
+        self.lock_progress = if self.lock_time > 0.0 {
+            (self.lock_progress + delta_time / self.lock_time).min(1.0)
+        } else {
+            1.0
+        };
+        self.is_locked = self.lock_progress >= 1.0;
+    }
+
     /// Clear target
     pub fn clear_target(&mut self) {
         self.target = None;
@@ -387,22 +475,72 @@ impl ShieldComponent {
 pub struct PowerGrid {
     /// Total power generation (units/second)
     pub generation: f32,
-    /// Power distribution to modules
-    pub distribution: HashMap<String, f32>,
+    /// Power distribution to modules (ordered for deterministic totals)
+    pub distribution: BTreeMap<String, f32>,
     /// Current power capacity
     pub capacity: f32,
+    /// Energy held in reserve (up to `capacity`)
+    pub stored: f32,
 }
 
 impl PowerGrid {
-    /// Create new power grid
+    /// Create new power grid with full reserves
     pub fn new(generation: f32, capacity: f32) -> Self {
         Self {
             generation,
-            distribution: HashMap::new(),
+            distribution: BTreeMap::new(),
             capacity,
+            stored: capacity,
         }
     }
 
+    /// Deliver one tick of power, shedding load if demand exceeds supply
+    ///
+    /// Supply is generation plus whatever the reserve can deliver over
+    /// `delta_time`. When allocations exceed it, modules are served in order
+    /// of descending `priority` (ties broken by module ID): the module where
+    /// supply runs out is throttled and everything below it gets nothing.
+    /// Surplus generation recharges the reserve; a deficit drains it.
+    ///
+    /// Returns the fraction of its allocation each module received.
+    pub fn supply(
+        &mut self,
+        priority: impl Fn(&str) -> u8,
+        delta_time: f32,
+    ) -> HashMap<String, f32> {
+        let demand = self.total_allocated();
+        let reserve = if delta_time > 0.0 {
+            self.stored / delta_time
+        } else {
+            0.0
+        };
+        let supply = self.generation + reserve;
+
+        let mut requests: Vec<(&String, f32)> = self
+            .distribution
+            .iter()
+            .map(|(module_id, amount)| (module_id, *amount))
+            .collect();
+        requests.sort_by(|(a, _), (b, _)| priority(b).cmp(&priority(a)).then(a.cmp(b)));
+
+        let mut remaining = supply;
+        let supplied = requests
+            .into_iter()
+            .map(|(module_id, amount)| {
+                let granted = amount.min(remaining).max(0.0);
+                remaining -= granted;
+                let fraction = if amount > 0.0 { granted / amount } else { 1.0 };
+                (module_id.clone(), fraction)
+            })
+            .collect();
+
+        let drawn = demand.min(supply);
+        self.stored =
+            (self.stored + (self.generation - drawn) * delta_time).clamp(0.0, self.capacity);
+
+        supplied
+    }
+
     /// Get total allocated power
     pub fn total_allocated(&self) -> f32 {
         self.distribution.values().sum()
@@ -425,7 +563,7 @@ pub struct CoolingSystem {
     /// Heat dissipation rate (units/second)
     pub dissipation: f32,
     /// Cooling distribution to modules
-    pub distribution: HashMap<String, f32>,
+    pub distribution: BTreeMap<String, f32>,
     /// Current cooling capacity
     pub capacity: f32,
 }
@@ -435,7 +573,7 @@ impl CoolingSystem {
     pub fn new(dissipation: f32, capacity: f32) -> Self {
         Self {
             dissipation,
-            distribution: HashMap::new(),
+            distribution: BTreeMap::new(),
             capacity,
         }
     }
@@ -454,6 +592,20 @@ impl CoolingSystem {
     pub fn allocate(&mut self, module_id: String, amount: f32) {
         self.distribution.insert(module_id, amount);
     }
+
+    /// Cooling actually delivered to a module (K/s)
+    ///
+    /// When allocations exceed the ship's dissipation, every module's share
+    /// is scaled down proportionally.
+    pub fn delivered(&self, module_id: &str) -> f32 {
+        let allocated = self.distribution.get(module_id).copied().unwrap_or(0.0);
+        let total = self.total_allocated();
+        if total > self.dissipation {
+            allocated * self.dissipation / total
+        } else {
+            allocated
+        }
+    }
 }
 
 /// Damage tracking per module
@@ -487,6 +639,40 @@ impl DamageComponent {
     }
 }
 
+/// Damage-control teams and the module repairs queued for them
+///
+/// Each team works on one queued module at a time, in queue order.
+#[derive(Component, Debug, Clone, Default)]
+pub struct RepairComponent {
+    /// Number of damage-control teams aboard
+    pub teams: u8,
+    /// Module instance IDs awaiting repair
+    pub queue: VecDeque<String>,
+}
+
+impl RepairComponent {
+    /// Create a repair component with the given number of teams
+    pub fn new(teams: u8) -> Self {
+        Self {
+            teams,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Whether a module is already queued for repair
+    pub fn is_queued(&self, module_id: &str) -> bool {
+        self.queue.iter().any(|queued| queued == module_id)
+    }
+}
+
+/// Marks a ship docked at a station, refreshed from the station registry
+/// before each tick
+#[derive(Component, Debug, Clone)]
+pub struct Docked {
+    /// Station ID
+    pub station_id: String,
+}
+
 /// Inventory for ammunition and cargo
 #[derive(Component, Debug, Clone, Default)]
 pub struct InventoryComponent {
@@ -580,6 +766,36 @@ pub struct ProjectileComponent {
     pub tags: Vec<WeaponTag>,
     /// Remaining lifetime (seconds)
     pub lifetime: f32,
+    /// Chance to hit when it reaches its target (0.0 to 1.0)
+    pub accuracy: f32,
+    /// Blast radius of the warhead (meters, 0 = no explosion)
+    pub blast_radius: f32,
+    /// Blast damage at the center of the explosion
+    pub blast_damage: f32,
+    /// Homing state (used by guided missiles only)
+    pub guidance: Guidance,
+}
+
+/// Homing state of a guided missile
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Guidance {
+    /// Speed the missile accelerates up to (meters/second, 0 = hold launch speed)
+    pub max_speed: f32,
+    /// Decoy or chaff cloud the missile was seduced by, chased instead of its target
+    pub lure: Option<Entity>,
+    /// Lures the missile has already rolled against and ignored
+    pub resisted: Vec<Entity>,
+}
+
+/// A cloud of chaff that can seduce guided missiles passing through it
+#[derive(Component, Debug, Clone)]
+pub struct ChaffCloud {
+    /// Ship that deployed the chaff (its own missiles ignore the cloud)
+    pub owner: Entity,
+    /// Radius within which missiles can be seduced (meters)
+    pub radius: f32,
+    /// Time until the cloud disperses (seconds)
+    pub remaining: f32,
 }
 
 /// Type of projectile
@@ -610,6 +826,10 @@ impl ProjectileComponent {
             damage,
             tags,
             lifetime,
+            accuracy: 1.0,
+            blast_radius: 0.0,
+            blast_damage: 0.0,
+            guidance: Guidance::default(),
         }
     }
 
@@ -630,6 +850,10 @@ impl ProjectileComponent {
             damage,
             tags,
             lifetime,
+            accuracy: 1.0,
+            blast_radius: 0.0,
+            blast_damage: 0.0,
+            guidance: Guidance::default(),
         }
     }
 
@@ -642,6 +866,196 @@ impl ProjectileComponent {
     pub fn update(&mut self, delta_time: f32) {
         self.lifetime -= delta_time;
     }
+
+    /// Whether the projectile carries a warhead that explodes
+    pub fn is_explosive(&self) -> bool {
+        self.blast_radius > 0.0 && self.blast_damage > 0.0
+    }
+}
+
+/// Number of detail tiers a full scan can reveal
+pub const SCAN_TIERS: u8 = 6;
+
+/// Highest sensor `detail_level`
+const MAX_DETAIL_LEVEL: f32 = 10.0;
+
+/// A sensor-array module's capabilities
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorArray {
+    /// Detection range (meters)
+    pub scan_range: f32,
+    /// Time to reveal each detail tier of a scan (seconds)
+    pub scan_time: f32,
+    /// Scan resolution (1 to 10)
+    pub detail_level: u8,
+}
+
+impl SensorArray {
+    /// Number of detail tiers a scan with this array reveals
+    ///
+    /// Scales with `detail_level`, from one tier up to all [`SCAN_TIERS`].
+    pub fn scan_tiers(&self) -> u8 {
+        let tiers = (self.detail_level as f32 / MAX_DETAIL_LEVEL * SCAN_TIERS as f32).ceil();
+        (tiers as u8).clamp(1, SCAN_TIERS)
+    }
+}
+
+/// Detail tiers revealed by a scan, in the order they are revealed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScanTier {
+    /// Ship class
+    Class,
+    /// Team and faction
+    Faction,
+    /// Installed modules and weapons
+    Loadout,
+    /// Health of each module
+    ModuleHealth,
+    /// Ammunition in the hold and magazines
+    Ammunition,
+    /// Active status effects
+    StatusEffects,
+}
+
+impl ScanTier {
+    /// Every tier, in reveal order
+    pub const ALL: [ScanTier; SCAN_TIERS as usize] = [
+        ScanTier::Class,
+        ScanTier::Faction,
+        ScanTier::Loadout,
+        ScanTier::ModuleHealth,
+        ScanTier::Ammunition,
+        ScanTier::StatusEffects,
+    ];
+
+    /// Snake-case name of the tier
+    pub fn name(self) -> &'static str {
+        match self {
+            ScanTier::Class => "class",
+            ScanTier::Faction => "faction",
+            ScanTier::Loadout => "loadout",
+            ScanTier::ModuleHealth => "module_health",
+            ScanTier::Ammunition => "ammunition",
+            ScanTier::StatusEffects => "status_effects",
+        }
+    }
+}
+
+/// A science scan of another ship under way
+#[derive(Component, Debug, Clone)]
+pub struct ScanProgress {
+    /// Ship being scanned
+    pub target: Entity,
+    /// Ship ID of the target
+    pub target_id: String,
+    /// Time to reveal each tier (seconds)
+    pub tier_time: f32,
+    /// Number of tiers the scan will reveal
+    pub tiers: u8,
+    /// Scanning time accumulated so far (seconds)
+    pub elapsed: f32,
+    /// Number of tiers revealed so far
+    pub revealed: u8,
+}
+
+impl ScanProgress {
+    /// Total time for the scan to complete (seconds)
+    pub fn duration(&self) -> f32 {
+        self.tier_time * self.tiers as f32
+    }
+
+    /// Whether every tier has been revealed
+    pub fn is_complete(&self) -> bool {
+        self.revealed >= self.tiers
+    }
+}
+
+/// Sensor arrays and the contacts they currently hold
+#[derive(Component, Debug, Clone, Default)]
+pub struct SensorComponent {
+    /// Sensor-array modules by instance ID
+    pub arrays: BTreeMap<String, SensorArray>,
+    /// Detection range this tick, after power and damage (meters)
+    pub range: f32,
+    /// Ships currently detected, with their ship IDs
+    pub contacts: BTreeMap<Entity, String>,
+}
+
+impl SensorComponent {
+    /// Detection range of the best sensor array, scaled by its efficiency
+    pub fn effective_range(&self, modules: &ModuleStateTracker) -> f32 {
+        self.arrays
+            .iter()
+            .map(|(instance_id, array)| {
+                array.scan_range * modules.get(instance_id).map_or(0.0, |m| m.efficiency)
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// The working array with the finest detail level, for scanning
+    pub fn best_scanner(&self, modules: &ModuleStateTracker) -> Option<&SensorArray> {
+        self.arrays
+            .iter()
+            .filter(|(instance_id, _)| modules.get(instance_id).is_some_and(|m| m.efficiency > 0.0))
+            .map(|(_, array)| array)
+            .max_by_key(|array| array.detail_level)
+    }
+
+    /// Whether `target` is currently detected
+    pub fn has_contact(&self, target: Entity) -> bool {
+        self.contacts.contains_key(&target)
+    }
+}
+
+/// A stealth-system module's capabilities
+#[derive(Debug, Clone, PartialEq)]
+pub struct StealthSystem {
+    /// Fraction of enemy sensor range the ship is hidden from (0.0 to 1.0)
+    pub detectability_reduction: f32,
+    /// Extra time enemy scans need per detail tier (seconds)
+    pub scan_time_increase: f32,
+}
+
+/// Stealth systems hiding a ship from enemy sensors
+#[derive(Component, Debug, Clone, Default)]
+pub struct StealthComponent {
+    /// Stealth-system modules by instance ID
+    pub systems: BTreeMap<String, StealthSystem>,
+}
+
+impl StealthComponent {
+    /// Fraction of enemy sensor range the ship is hidden from
+    ///
+    /// The best stealth system counts, scaled by its efficiency.
+    pub fn reduction(&self, modules: Option<&ModuleStateTracker>) -> f32 {
+        self.systems
+            .iter()
+            .map(|(instance_id, system)| {
+                system.detectability_reduction * module_efficiency(modules, instance_id)
+            })
+            .fold(0.0, f32::max)
+            .clamp(0.0, 1.0)
+    }
+
+    /// Extra time enemy scans need per detail tier (seconds)
+    ///
+    /// The best working stealth system counts.
+    pub fn scan_time_increase(&self, modules: Option<&ModuleStateTracker>) -> f32 {
+        self.systems
+            .iter()
+            .filter(|(instance_id, _)| module_efficiency(modules, instance_id) > 0.0)
+            .map(|(_, system)| system.scan_time_increase)
+            .fold(0.0, f32::max)
+    }
+}
+
+/// Efficiency of a module, treating every module as fully working when the
+/// ship has no module tracker
+fn module_efficiency(modules: Option<&ModuleStateTracker>, instance_id: &str) -> f32 {
+    modules
+        .map_or(Some(1.0), |m| m.get(instance_id).map(|m| m.efficiency))
+        .unwrap_or(0.0)
 }
 
 /// Communication state
@@ -691,6 +1105,74 @@ impl Default for CommunicationState {
     }
 }
 
+/// A comms-system module's capabilities
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommsSystem {
+    /// Transmission range at full efficiency (meters)
+    pub comm_range: f32,
+    /// Encryption strength (1 to 10)
+    pub encryption_lvl: u8,
+}
+
+/// A message waiting to be transmitted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transmission {
+    /// Message ID
+    pub id: String,
+    /// Ship ID of the addressee, or `None` for a broadcast
+    pub recipient_id: Option<String>,
+    pub text: String,
+    /// Message ID of the hail this answers, if any
+    pub in_response_to: Option<String>,
+}
+
+/// Comms systems and the messages queued for transmission
+#[derive(Component, Debug, Clone, Default)]
+pub struct CommsComponent {
+    /// Comms-system modules by instance ID
+    pub systems: BTreeMap<String, CommsSystem>,
+    /// Messages to transmit on the next tick
+    pub outbox: Vec<Transmission>,
+}
+
+impl CommsComponent {
+    /// Range of the best comms system, scaled by its efficiency
+    pub fn effective_range(&self, modules: Option<&ModuleStateTracker>) -> f32 {
+        self.systems
+            .iter()
+            .map(|(instance_id, system)| {
+                system.comm_range * module_efficiency(modules, instance_id)
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// Strongest encryption among working comms systems (0 if none work)
+    pub fn encryption_lvl(&self, modules: Option<&ModuleStateTracker>) -> u8 {
+        self.systems
+            .iter()
+            .filter(|(instance_id, _)| module_efficiency(modules, instance_id) > 0.0)
+            .map(|(_, system)| system.encryption_lvl)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// A station able to relay messages for ships of its faction
+#[derive(Debug, Clone)]
+pub struct RelayStation {
+    /// Station ID
+    pub id: String,
+    pub faction: String,
+    pub position: Vector3<f32>,
+}
+
+/// Stations that relay messages, refreshed from the station registry
+/// before each tick
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CommRelays {
+    pub stations: Vec<RelayStation>,
+}
+
 /// Shared startup/cooldown/disabled machinery for FTL drive components.
 ///
 /// Warp and jump drives both track the same three-phase state — startup
@@ -782,6 +1264,8 @@ pub struct WarpDriveComponent {
     pub timer: DriveTimer,
     /// Whether warp drive is currently active
     pub active: bool,
+    /// Warp destination (drive drops out of warp on arrival)
+    pub destination: Option<Vector3<f32>>,
 }
 
 impl WarpDriveComponent {
@@ -792,6 +1276,7 @@ impl WarpDriveComponent {
             current_warp_factor: 1.0,
             timer: DriveTimer::new(startup_time, cooldown_time),
             active: false,
+            destination: None,
         }
     }
 
@@ -937,6 +1422,27 @@ mod tests {
         assert!(!targeting.can_engage());
     }
 
+    #[test]
+    fn test_targeting_lock() {
+        let mut targeting = TargetingComponent::new(2.0);
+        targeting.set_target(Entity::from_raw_u32(7).unwrap());
+
+        targeting.update_lock(1.0);
+        assert!(!targeting.is_locked);
+        assert_eq!(targeting.lock_progress, 0.5);
+
+        targeting.update_lock(1.0);
+        assert!(targeting.can_engage());
+
+        // Retargeting restarts the lock
+        targeting.set_target(Entity::from_raw_u32(8).unwrap());
+        assert!(!targeting.is_locked);
+
+        targeting.disabled = true;
+        targeting.update_lock(5.0);
+        assert_eq!(targeting.lock_progress, 0.0);
+    }
+
     #[test]
     fn test_shield_component() {
         let mut shields = ShieldComponent::new(1000.0, 50.0, 10.0);
@@ -972,6 +1478,44 @@ mod tests {
         assert_eq!(grid.available_power(), 500.0);
     }
 
+    #[test]
+    fn test_power_grid_supply_within_generation() {
+        let mut grid = PowerGrid::new(100.0, 50.0);
+        grid.stored = 0.0;
+        grid.allocate("engine".to_string(), 60.0);
+
+        let supplied = grid.supply(|_| 5, 1.0);
+        assert_eq!(supplied["engine"], 1.0);
+        // Surplus generation recharges the reserve
+        assert_eq!(grid.stored, 40.0);
+    }
+
+    #[test]
+    fn test_power_grid_sheds_lowest_priority() {
+        let mut grid = PowerGrid::new(100.0, 20.0);
+        grid.allocate("shields".to_string(), 60.0);
+        grid.allocate("engine".to_string(), 50.0);
+        grid.allocate("lights".to_string(), 30.0);
+        let priority = |module_id: &str| match module_id {
+            "shields" => 9,
+            "engine" => 5,
+            _ => 1,
+        };
+
+        // 100 generated + 20 from reserve against 140 demanded
+        let supplied = grid.supply(priority, 1.0);
+        assert_eq!(supplied["shields"], 1.0);
+        assert_eq!(supplied["engine"], 1.0);
+        assert!((supplied["lights"] - 10.0 / 30.0).abs() < 1e-6);
+        assert_eq!(grid.stored, 0.0);
+
+        // With the reserve drained, the engine is throttled too
+        let supplied = grid.supply(priority, 1.0);
+        assert_eq!(supplied["shields"], 1.0);
+        assert!((supplied["engine"] - 0.8).abs() < 1e-6);
+        assert_eq!(supplied["lights"], 0.0);
+    }
+
     #[test]
     fn test_cooling_system() {
         let mut cooling = CoolingSystem::new(500.0, 500.0);
@@ -0,0 +1,316 @@
+//! Helm command processing for the HYPERION simulation.
+//!
+//! Helm orders arrive through the REST API, are queued per ship on the
+//! `GameWorld`, and are handed to each ship's [`HelmComponent`] at the start
+//! of a tick. [`helm_system`] drains that queue, applies the orders within
+//! the limits in `PhysicsConfig`, and turns the resulting engine state into
+//! forces on the ship's `ForceAccumulator`.
+
+use bevy_ecs::prelude::*;
+use nalgebra::Vector3;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use super::components::*;
+use super::params::SimulationParams;
+use super::physics::{Force, ForceAccumulator, calculate_effective_weight};
+
+/// Speed below which a full stop is considered complete (m/s)
+const STOP_THRESHOLD: f32 = 0.01;
+
+/// A navigation order issued by the helm officer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HelmCommand {
+    /// Set engine thrust as an acceleration in the ship's local frame (m/s²)
+    Thrust { x: f64, y: f64, z: f64 },
+    /// Set angular velocity about the ship's local axes (rad/s)
+    Rotate { pitch: f64, yaw: f64, roll: f64 },
+    /// Cut engines and brake to a halt
+    Stop,
+    /// Spool up the warp drive toward a destination
+    Warp { destination: [f64; 3] },
+    /// Charge the jump drive for a destination
+    Jump { destination: [f64; 3] },
+    /// Begin a docking approach to a station
+    Dock { station_id: String },
+}
+
+/// Engine state and pending orders for a ship's helm
+#[derive(Component, Debug, Clone, Default)]
+pub struct HelmComponent {
+    /// Orders waiting to be applied on the next tick
+    pub queue: VecDeque<HelmCommand>,
+    /// Commanded acceleration in the ship's local frame (m/s²)
+    pub thrust: Vector3<f32>,
+    /// Whether a full stop is in progress
+    pub braking: bool,
+}
+
+impl HelmComponent {
+    /// Create an idle helm
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Components read and written by [`helm_system`]
+pub type HelmShip = (
+    &'static mut HelmComponent,
+    &'static mut Transform,
+    &'static mut ForceAccumulator,
+    &'static ShipData,
+    &'static StatusEffects,
+    Option<&'static mut WarpDriveComponent>,
+    Option<&'static mut JumpDriveComponent>,
+);
+
+/// System that applies queued helm orders and engine thrust.
+///
+/// Thrust is clamped to `movement.max_acceleration`, braking to
+/// `movement.max_deceleration`, and rotation to
+/// `rotation.max_angular_velocity`. Warp and jump orders arm the ship's
+/// drives if they are present and ready; otherwise they are dropped.
+/// Docking is resolved by the `GameWorld`, which owns the station registry.
+pub fn helm_system(mut query: Query<HelmShip>, params: Res<SimulationParams>, delta_time: f32) {
+    let max_acceleration = params.physics.movement.max_acceleration as f32;
+    let max_deceleration = params.physics.movement.max_deceleration as f32;
+    let max_angular_velocity = params.physics.rotation.max_angular_velocity as f32;
+
+    for (mut helm, mut transform, mut accumulator, ship_data, effects, mut warp, mut jump) in
+        query.iter_mut()
+    {
+        while let Some(command) = helm.queue.pop_front() {
+            match command {
+                HelmCommand::Thrust { x, y, z } => {
+                    helm.thrust = to_vector([x, y, z]).cap_magnitude(max_acceleration);
+                    helm.braking = false;
+                }
+                HelmCommand::Rotate { pitch, yaw, roll } => {
+                    let local = to_vector([pitch, yaw, roll]).cap_magnitude(max_angular_velocity);
+                    transform.angular_velocity = transform.rotation * local;
+                }
+                HelmCommand::Stop => {
+                    helm.thrust = Vector3::zeros();
+                    helm.braking = true;
+                    transform.angular_velocity = Vector3::zeros();
+                }
+                HelmCommand::Warp { destination } => {
+                    if let Some(warp) = warp.as_deref_mut()
+                        && warp.can_engage()
+                        && !warp.timer.is_starting_up()
+                    {
+                        warp.destination = Some(to_vector(destination));
+                        warp.current_warp_factor = warp.max_warp_factor;
+                        warp.timer.startup_progress = warp.timer.startup_time;
+                    }
+                }
+                HelmCommand::Jump { destination } => {
+                    let destination = to_vector(destination);
+                    if let Some(jump) = jump.as_deref_mut()
+                        && jump.can_jump()
+                        && (destination - transform.position).magnitude() <= jump.max_range
+                    {
+                        jump.target_destination = Some(destination);
+                        jump.timer.startup_progress = jump.timer.startup_time;
+                    }
+                }
+                HelmCommand::Dock { .. } => {}
+            }
+        }
+
+        let mass = calculate_effective_weight(ship_data.base_weight, effects);
+        if mass <= 0.0 {
+            continue;
+        }
+
+        if helm.braking {
+            let speed = transform.velocity.magnitude();
+            if speed <= STOP_THRESHOLD {
+                transform.velocity = Vector3::zeros();
+                helm.braking = false;
+            } else {
+                let deceleration = max_deceleration.min(speed / delta_time);
+                let direction = -transform.velocity / speed;
+                accumulator.add_force(Force::new(direction * deceleration * mass));
+            }
+        } else if helm.thrust != Vector3::zeros() {
+            let acceleration = transform.rotation * helm.thrust;
+            accumulator.add_force(Force::new(acceleration * mass));
+        }
+    }
+}
+
+fn to_vector(v: [f64; 3]) -> Vector3<f32> {
+    Vector3::new(v[0] as f32, v[1] as f32, v[2] as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    fn spawn_helm_ship(world: &mut World) -> Entity {
+        world
+            .spawn((
+                HelmComponent::new(),
+                Transform::new(),
+                ForceAccumulator::new(),
+                ShipData::new(
+                    "ship1".to_string(),
+                    "Test Ship".to_string(),
+                    "cruiser".to_string(),
+                    "team1".to_string(),
+                    1000.0,
+                    100.0,
+                    500.0,
+                ),
+                StatusEffects::new(),
+            ))
+            .id()
+    }
+
+    fn run_helm(world: &mut World, delta_time: f32) {
+        world
+            .run_system_once(
+                move |query: Query<HelmShip>, params: Res<SimulationParams>| {
+                    helm_system(query, params, delta_time);
+                },
+            )
+            .unwrap();
+    }
+
+    fn queue(world: &mut World, entity: Entity, command: HelmCommand) {
+        world
+            .get_mut::<HelmComponent>(entity)
+            .unwrap()
+            .queue
+            .push_back(command);
+    }
+
+    #[test]
+    fn test_thrust_clamped_to_max_acceleration() {
+        let mut world = World::new();
+        world.init_resource::<SimulationParams>();
+        let ship = spawn_helm_ship(&mut world);
+        let max = SimulationParams::default()
+            .physics
+            .movement
+            .max_acceleration as f32;
+
+        queue(
+            &mut world,
+            ship,
+            HelmCommand::Thrust {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0e6,
+            },
+        );
+        run_helm(&mut world, 0.1);
+
+        let helm = world.get::<HelmComponent>(ship).unwrap();
+        assert!(helm.queue.is_empty());
+        assert!((helm.thrust.magnitude() - max).abs() < 1e-3);
+
+        // Force is applied every tick until thrust changes
+        let force = world.get::<ForceAccumulator>(ship).unwrap().total_force();
+        assert!((force.z - max * 500.0).abs() < 1e-1);
+    }
+
+    #[test]
+    fn test_rotation_clamped_to_max_angular_velocity() {
+        let mut world = World::new();
+        world.init_resource::<SimulationParams>();
+        let ship = spawn_helm_ship(&mut world);
+        let max = SimulationParams::default()
+            .physics
+            .rotation
+            .max_angular_velocity as f32;
+
+        queue(
+            &mut world,
+            ship,
+            HelmCommand::Rotate {
+                pitch: 0.0,
+                yaw: 50.0,
+                roll: 0.0,
+            },
+        );
+        run_helm(&mut world, 0.1);
+
+        let transform = world.get::<Transform>(ship).unwrap();
+        assert!((transform.angular_velocity.y - max).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_stop_brakes_to_halt() {
+        let mut world = World::new();
+        world.init_resource::<SimulationParams>();
+        let ship = spawn_helm_ship(&mut world);
+        world.get_mut::<Transform>(ship).unwrap().velocity = Vector3::new(1.0, 0.0, 0.0);
+
+        queue(&mut world, ship, HelmCommand::Stop);
+        run_helm(&mut world, 1.0);
+
+        // Braking force never exceeds what is needed to stop this tick
+        let force = world.get::<ForceAccumulator>(ship).unwrap().total_force();
+        assert!((force.x + 500.0).abs() < 1e-3);
+        assert!(world.get::<HelmComponent>(ship).unwrap().braking);
+    }
+
+    #[test]
+    fn test_warp_and_jump_arm_drives() {
+        let mut world = World::new();
+        world.init_resource::<SimulationParams>();
+        let ship = spawn_helm_ship(&mut world);
+        world.entity_mut(ship).insert((
+            WarpDriveComponent::new(8.0, 5.0, 10.0),
+            JumpDriveComponent::new(1000.0, 3.0, 10.0),
+        ));
+
+        queue(
+            &mut world,
+            ship,
+            HelmCommand::Warp {
+                destination: [1.0e6, 0.0, 0.0],
+            },
+        );
+        queue(
+            &mut world,
+            ship,
+            HelmCommand::Jump {
+                destination: [500.0, 0.0, 0.0],
+            },
+        );
+        run_helm(&mut world, 0.1);
+
+        let warp = world.get::<WarpDriveComponent>(ship).unwrap();
+        assert!(warp.timer.is_starting_up());
+        assert_eq!(warp.destination, Some(Vector3::new(1.0e6, 0.0, 0.0)));
+        let jump = world.get::<JumpDriveComponent>(ship).unwrap();
+        assert_eq!(jump.target_destination, Some(Vector3::new(500.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_jump_out_of_range_ignored() {
+        let mut world = World::new();
+        world.init_resource::<SimulationParams>();
+        let ship = spawn_helm_ship(&mut world);
+        world
+            .entity_mut(ship)
+            .insert(JumpDriveComponent::new(1000.0, 3.0, 10.0));
+
+        queue(
+            &mut world,
+            ship,
+            HelmCommand::Jump {
+                destination: [5000.0, 0.0, 0.0],
+            },
+        );
+        run_helm(&mut world, 0.1);
+
+        let jump = world.get::<JumpDriveComponent>(ship).unwrap();
+        assert!(jump.target_destination.is_none());
+    }
+}
@@ -6,14 +6,19 @@
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::RunSystemOnce;
 use bevy_ecs::world::World;
+use serde::{Deserialize, Serialize};
 
 use super::components::*;
+use super::helm::*;
+use super::messages::*;
 use super::module_state::*;
+use super::params::*;
 use super::physics::*;
+use super::rng::*;
 use super::systems::*;
 
 /// Simulation state tracking
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimulationState {
     /// Current simulation tick
     pub tick: u64,
@@ -23,6 +28,9 @@ pub struct SimulationState {
     pub timestep: f32,
     /// Whether simulation is paused
     pub paused: bool,
+    /// Seed the per-tick [`SimulationRng`](super::SimulationRng) is derived from
+    #[serde(default)]
+    pub seed: u64,
 }
 
 impl SimulationState {
@@ -33,6 +41,7 @@ impl SimulationState {
             time: 0.0,
             timestep: 1.0 / 60.0,
             paused: false,
+            seed: 0,
         }
     }
 
@@ -43,6 +52,7 @@ impl SimulationState {
             time: 0.0,
             timestep,
             paused: false,
+            seed: 0,
         }
     }
 
@@ -81,7 +91,7 @@ impl Default for SimulationState {
 /// Executes all systems in the correct order for one simulation tick.
 ///
 /// Systems are organized into phases to ensure proper execution order:
-/// 1. Physics forces (engines, drag)
+/// 1. Helm orders and physics forces (thrust, engines, drag)
 /// 2. Physics integration (F=ma)
 /// 3. Movement (position updates)
 /// 4. Weapon systems (cooldown, firing, projectiles)
@@ -93,6 +103,13 @@ impl Default for SimulationState {
 /// 10. Collision detection, repair, explosions, momentum
 pub fn run_simulation_tick(world: &mut World, delta_time: f32) {
     // Phase 1: Physics Forces
+    // Apply queued helm orders and commanded thrust
+    let _ = world.run_system_once(
+        move |query: Query<HelmShip>, params: Res<SimulationParams>| {
+            helm_system(query, params, delta_time);
+        },
+    );
+
     // Apply engine thrust and drag forces
     let _ = world.run_system_once(engine_force_system);
     let _ = world.run_system_once(drag_force_system);
@@ -116,30 +133,67 @@ pub fn run_simulation_tick(world: &mut World, delta_time: f32) {
         movement_system(query, delta_time);
     });
 
+    // Keep weapon mounts attached to their ships
+    let _ = world.run_system_once(weapon_mount_system);
+
     // Phase 4: Weapon Systems
     // Update weapon cooldowns
     let _ = world.run_system_once(move |query: Query<&mut WeaponComponent>| {
         weapon_cooldown_system(query, delta_time);
     });
 
+    // Advance target locks (jammed by Ion)
+    let _ = world.run_system_once(
+        move |weapons: Query<(&mut TargetingComponent, &ChildOf)>, ships: Query<&StatusEffects>| {
+            targeting_system(weapons, ships, delta_time);
+        },
+    );
+
     // Handle weapon firing
     let _ = world.run_system_once(weapon_fire_system);
 
     // Update projectile positions
     let _ = world.run_system_once(
-        move |commands: Commands, query: Query<(Entity, &mut ProjectileComponent)>| {
-            projectile_system(commands, query, delta_time);
+        move |commands: Commands,
+              projectiles: Query<(Entity, &mut ProjectileComponent, &mut Transform)>,
+              bodies: Query<&Transform, Without<ProjectileComponent>>,
+              chaff: Query<(Entity, &ChaffCloud, &Transform), Without<ProjectileComponent>>,
+              params: Res<SimulationParams>,
+              explosions: Option<MessageWriter<Explosion>>,
+              rng: Option<ResMut<SimulationRng>>| {
+            projectile_system(
+                commands,
+                projectiles,
+                bodies,
+                chaff,
+                params,
+                explosions,
+                rng,
+                delta_time,
+            );
         },
     );
 
     // Phase 5: Combat
     // Apply damage from projectile hits
     let _ = world.run_system_once(
-        |commands: Commands,
-         projectiles: Query<(Entity, &ProjectileComponent, &Transform)>,
-         ships: Query<(Entity, &mut ShipData, &mut ShieldComponent, &Transform)>,
-         status_effects: Query<&mut StatusEffects>| {
-            damage_system(commands, projectiles, ships, status_effects);
+        move |commands: Commands,
+              projectiles: Query<(Entity, &ProjectileComponent, &Transform)>,
+              ships: Query<(Entity, &mut ShipData, &mut ShieldComponent, &Transform)>,
+              status_effects: Query<&mut StatusEffects>,
+              damaged: Option<MessageWriter<ShipDamaged>>,
+              explosions: Option<MessageWriter<Explosion>>,
+              rng: Option<ResMut<SimulationRng>>| {
+            damage_system(
+                commands,
+                projectiles,
+                ships,
+                status_effects,
+                damaged,
+                explosions,
+                rng,
+                delta_time,
+            );
         },
     );
 
@@ -147,35 +201,37 @@ pub fn run_simulation_tick(world: &mut World, delta_time: f32) {
     let _ = world.run_system_once(
         move |beams: Query<(&ProjectileComponent, &Transform)>,
               ships: Query<(Entity, &mut ShipData, &mut ShieldComponent, &Transform)>,
-              status_effects: Query<&mut StatusEffects>| {
-            beam_weapon_system(beams, ships, status_effects, delta_time);
+              status_effects: Query<&mut StatusEffects>,
+              damaged: Option<MessageWriter<ShipDamaged>>,
+              rng: Option<ResMut<SimulationRng>>| {
+            beam_weapon_system(beams, ships, status_effects, damaged, rng, delta_time);
         },
     );
 
     // Handle countermeasures vs projectiles
+    let _ = world.run_system_once(countermeasure_system);
+
+    // Phase 6: Ship Systems
+    // Deliver power, shedding low-priority modules on a deficit
     let _ = world.run_system_once(
-        |commands: Commands,
-         countermeasures: Query<(&WeaponComponent, &Transform), With<PointDefenseMarker>>,
-         projectiles: Query<(Entity, &ProjectileComponent, &Transform)>| {
-            countermeasure_system(commands, countermeasures, projectiles);
+        move |ships: Query<(Entity, &mut PowerGrid, Option<&mut ModuleStateTracker>)>,
+              brownouts: Option<MessageWriter<ModuleBrownout>>| {
+            power_system(ships, brownouts, delta_time);
         },
     );
 
-    // Phase 6: Ship Systems
-    // Update power generation/consumption
-    let _ = world.run_system_once(power_system);
-
     // Update heat generation/dissipation
-    let _ = world.run_system_once(cooling_system);
+    let _ = world.run_system_once(
+        move |ships: Query<(Entity, &CoolingSystem, &mut ModuleStateTracker)>,
+              params: Res<SimulationParams>,
+              status_changes: Option<MessageWriter<ModuleStatusChanged>>| {
+            cooling_system(ships, params, status_changes, delta_time);
+        },
+    );
 
-    // Update module states (power/cooling allocation, heat, efficiency)
+    // Update module states (power allocation, efficiency, auxiliary timers)
     let _ = world.run_system_once(
-        move |query: Query<(
-            &mut ModuleStateTracker,
-            &PowerGrid,
-            &CoolingSystem,
-            &ShipData,
-        )>| {
+        move |query: Query<(&mut ModuleStateTracker, &PowerGrid, &ShipData)>| {
             module_state_system(query, delta_time);
         },
     );
@@ -210,8 +266,28 @@ pub fn run_simulation_tick(world: &mut World, delta_time: f32) {
     );
 
     // Phase 9: Communication & Scanning
-    let _ = world.run_system_once(communication_system);
-    let _ = world.run_system_once(scanning_system);
+    let _ = world.run_system_once(
+        move |ships: Query<CommsShip>,
+              relays: Option<Res<CommRelays>>,
+              params: Res<SimulationParams>,
+              received: Option<MessageWriter<MessageReceived>>,
+              undelivered: Option<MessageWriter<MessageUndelivered>>| {
+            communication_system(ships, relays, params, received, undelivered, delta_time);
+        },
+    );
+    let _ = world.run_system_once(sensor_system);
+    let _ = world.run_system_once(
+        move |commands: Commands,
+              scanners: Query<(
+            Entity,
+            &mut ScanProgress,
+            &SensorComponent,
+            Option<&StatusEffects>,
+        )>,
+              revealed: Option<MessageWriter<ScanTierRevealed>>| {
+            scanning_system(commands, scanners, revealed, delta_time);
+        },
+    );
 
     // Phase 10: Collision & Cleanup
     let _ =
@@ -225,9 +301,24 @@ pub fn run_simulation_tick(world: &mut World, delta_time: f32) {
             },
         );
 
-    // Repair damaged ships
-    let _ = world.run_system_once(repair_system);
+    // Repair damaged modules
+    let _ = world.run_system_once(
+        move |ships: Query<RepairShip>,
+              params: Res<SimulationParams>,
+              status_changes: Option<MessageWriter<ModuleStatusChanged>>| {
+            repair_system(ships, params, status_changes, delta_time);
+        },
+    );
+
+    // Apply area damage from warheads detonated this tick
     let _ = world.run_system_once(explosion_system);
+
+    // Disperse expired chaff clouds
+    let _ = world.run_system_once(
+        move |commands: Commands, clouds: Query<(Entity, &mut ChaffCloud)>| {
+            chaff_system(commands, clouds, delta_time);
+        },
+    );
     let _ = world.run_system_once(
         |ships: Query<
             (&mut Transform, &ShipData, &StatusEffects),
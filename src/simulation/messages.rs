@@ -0,0 +1,260 @@
+//! Buffered ECS messages for the HYPERION simulation.
+//!
+//! Systems write these messages during a tick; the server drains them after
+//! the tick completes and translates them into [`GameEvent`](crate::events::GameEvent)s
+//! for WebSocket clients.
+
+use bevy_ecs::prelude::*;
+use nalgebra::Vector3;
+
+use super::components::{ProjectileComponent, ProjectileType, ScanTier};
+use crate::events::DamageType;
+use crate::models::WeaponTag;
+
+/// A ship took damage from a projectile or beam this tick.
+#[derive(Message, Debug, Clone)]
+pub struct ShipDamaged {
+    /// The ship entity that was hit
+    pub ship: Entity,
+    /// Category of the damage source
+    pub damage_type: DamageType,
+    /// Damage dealt: what the shields absorbed plus what reached the hull
+    pub amount: f32,
+    /// The ship that fired the round, beam, or warhead
+    pub attacker: Entity,
+}
+
+/// A module entered or left brownout this tick.
+#[derive(Message, Debug, Clone)]
+pub struct ModuleBrownout {
+    /// The ship entity the module belongs to
+    pub ship: Entity,
+    /// Module instance ID
+    pub module_id: String,
+    /// Fraction of its allocated power the module now receives
+    pub power_supplied: f32,
+    /// Whether the module is now browned out
+    pub browned_out: bool,
+}
+
+/// A module started or stopped overheating, shut down, restarted, or was
+/// repaired this tick.
+#[derive(Message, Debug, Clone)]
+pub struct ModuleStatusChanged {
+    /// The ship entity the module belongs to
+    pub ship: Entity,
+    /// Module instance ID
+    pub module_id: String,
+    /// Module health as a percentage of its maximum
+    pub health_percent: f32,
+    /// Whether the module is now operational
+    pub operational: bool,
+    /// Whether the module is now overheated
+    pub overheated: bool,
+}
+
+/// A missile or torpedo warhead detonated this tick.
+///
+/// Written by `damage_system` on impact and by `projectile_system` when a
+/// warhead's lifetime runs out; `explosion_system` applies the blast.
+#[derive(Message, Debug, Clone)]
+pub struct Explosion {
+    /// The ship that fired the warhead
+    pub owner: Entity,
+    /// Category of the warhead
+    pub damage_type: DamageType,
+    /// Where the warhead detonated
+    pub position: Vector3<f32>,
+    /// Blast radius (meters)
+    pub radius: f32,
+    /// Damage at the center of the blast, falling off to zero at `radius`
+    pub damage: f32,
+    /// Weapon tags carried by the warhead
+    pub tags: Vec<WeaponTag>,
+}
+
+/// A point-defense weapon fired at incoming ordnance this tick.
+#[derive(Message, Debug, Clone)]
+pub struct PointDefenseEngaged {
+    /// The ship defending itself
+    pub ship: Entity,
+    /// Point-defense weapon instance ID
+    pub weapon_id: String,
+    /// The ship that fired the ordnance
+    pub attacker: Entity,
+    /// Category of the ordnance engaged
+    pub damage_type: DamageType,
+    /// Whether the ordnance was destroyed
+    pub success: bool,
+}
+
+/// A ship gained or lost a sensor contact this tick.
+#[derive(Message, Debug, Clone)]
+pub struct ContactChanged {
+    /// The detecting ship
+    pub ship: Entity,
+    /// Ship ID of the contact
+    pub contact_id: String,
+    /// Whether the contact was gained (true) or lost (false)
+    pub detected: bool,
+}
+
+/// A transmitted message reached a ship this tick.
+#[derive(Message, Debug, Clone)]
+pub struct MessageReceived {
+    pub message_id: String,
+    /// The transmitting ship
+    pub sender: Entity,
+    /// The ship that received this copy
+    pub receiver: Entity,
+    /// Ship ID of the addressee, or `None` for a broadcast
+    pub recipient_id: Option<String>,
+    /// Message text, garbled if the receiver could not decrypt it
+    pub text: String,
+    pub in_response_to: Option<String>,
+    /// Whether the receiver could not decrypt the message
+    pub garbled: bool,
+    /// Ships and stations the message passed through, in order
+    pub relays: Vec<String>,
+}
+
+/// A message could not be transmitted, or could not reach its addressee,
+/// this tick.
+#[derive(Message, Debug, Clone)]
+pub struct MessageUndelivered {
+    pub message_id: String,
+    /// The transmitting ship
+    pub sender: Entity,
+    /// Ship ID of the addressee, or `None` for a broadcast
+    pub recipient_id: Option<String>,
+}
+
+/// A scan revealed another detail tier of its target this tick.
+#[derive(Message, Debug, Clone)]
+pub struct ScanTierRevealed {
+    /// The scanning ship
+    pub ship: Entity,
+    /// Ship ID of the target
+    pub target_id: String,
+    /// The tier revealed
+    pub tier: ScanTier,
+    /// Whether this was the scan's last tier
+    pub complete: bool,
+}
+
+impl Explosion {
+    /// The explosion of `projectile`'s warhead at `position`
+    pub fn from_projectile(projectile: &ProjectileComponent, position: Vector3<f32>) -> Self {
+        Self {
+            owner: projectile.owner,
+            damage_type: projectile.projectile_type.into(),
+            position,
+            radius: projectile.blast_radius,
+            damage: projectile.blast_damage,
+            tags: projectile.tags.clone(),
+        }
+    }
+
+    /// Blast damage dealt at `distance` meters from the center
+    ///
+    /// Falls off linearly from `damage` at the center to zero at `radius`.
+    pub fn damage_at(&self, distance: f32) -> f32 {
+        if distance >= self.radius {
+            0.0
+        } else {
+            self.damage * (1.0 - distance / self.radius)
+        }
+    }
+}
+
+impl From<ProjectileType> for DamageType {
+    fn from(projectile_type: ProjectileType) -> Self {
+        match projectile_type {
+            ProjectileType::Kinetic => DamageType::Kinetic,
+            ProjectileType::Missile { .. } => DamageType::Missile,
+            ProjectileType::Torpedo => DamageType::Torpedo,
+            ProjectileType::Beam => DamageType::Energy,
+        }
+    }
+}
+
+/// Register every simulation message channel on `world`.
+///
+/// Systems take their writers as `Option<MessageWriter<_>>`, so a world
+/// without registered channels still runs — messages are simply dropped.
+pub fn register_messages(world: &mut World) {
+    world.init_resource::<Messages<ShipDamaged>>();
+    world.init_resource::<Messages<ModuleBrownout>>();
+    world.init_resource::<Messages<ModuleStatusChanged>>();
+    world.init_resource::<Messages<Explosion>>();
+    world.init_resource::<Messages<PointDefenseEngaged>>();
+    world.init_resource::<Messages<ContactChanged>>();
+    world.init_resource::<Messages<ScanTierRevealed>>();
+    world.init_resource::<Messages<MessageReceived>>();
+    world.init_resource::<Messages<MessageUndelivered>>();
+}
+
+/// Drain all pending messages of type `M` from `world`.
+///
+/// Returns an empty vector if the channel was never registered.
+pub fn drain_messages<M: Message>(world: &mut World) -> Vec<M> {
+    world
+        .get_resource_mut::<Messages<M>>()
+        .map(|mut messages| messages.drain().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_projectile_type_to_damage_type() {
+        assert_eq!(
+            DamageType::from(ProjectileType::Kinetic),
+            DamageType::Kinetic
+        );
+        assert_eq!(
+            DamageType::from(ProjectileType::Missile {
+                thrust: 1,
+                turn_rate: 1
+            }),
+            DamageType::Missile
+        );
+        assert_eq!(DamageType::from(ProjectileType::Beam), DamageType::Energy);
+    }
+
+    #[test]
+    fn test_explosion_falloff() {
+        let explosion = Explosion {
+            owner: Entity::from_raw_u32(1).unwrap(),
+            damage_type: DamageType::Missile,
+            position: Vector3::zeros(),
+            radius: 100.0,
+            damage: 80.0,
+            tags: vec![],
+        };
+        assert_eq!(explosion.damage_at(0.0), 80.0);
+        assert_eq!(explosion.damage_at(25.0), 60.0);
+        assert_eq!(explosion.damage_at(100.0), 0.0);
+        assert_eq!(explosion.damage_at(150.0), 0.0);
+    }
+
+    #[test]
+    fn test_drain_messages() {
+        let mut world = World::new();
+        assert!(drain_messages::<ShipDamaged>(&mut world).is_empty());
+
+        register_messages(&mut world);
+        world.write_message(ShipDamaged {
+            ship: Entity::from_raw_u32(1).unwrap(),
+            damage_type: DamageType::Kinetic,
+            amount: 10.0,
+            attacker: Entity::from_raw_u32(2).unwrap(),
+        });
+
+        let drained = drain_messages::<ShipDamaged>(&mut world);
+        assert_eq!(drained.len(), 1);
+        assert!(drain_messages::<ShipDamaged>(&mut world).is_empty());
+    }
+}
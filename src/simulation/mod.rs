@@ -5,24 +5,42 @@
 //! as well as systems that operate on those components.
 
 pub mod components;
+pub mod helm;
 pub mod r#loop;
+pub mod messages;
 pub mod module_state;
+pub mod params;
 pub mod physics;
+pub mod rng;
+pub mod runner;
+pub mod spawn;
 pub mod systems;
 
 pub use components::*;
+pub use helm::*;
 pub use r#loop::*;
+pub use messages::*;
 pub use module_state::*;
+pub use params::*;
 pub use physics::*;
+pub use rng::*;
+pub use runner::*;
+pub use spawn::*;
 pub use systems::*;
 
 use bevy_ecs::world::World;
 
 /// Initialize the simulation world.
 ///
-/// Creates a new Bevy ECS World for managing game entities and components.
+/// Creates a new Bevy ECS World for managing game entities and components,
+/// with all simulation message channels registered and default
+/// [`SimulationParams`] and [`SimulationRng`] installed.
 pub fn init_simulation() -> World {
-    World::new()
+    let mut world = World::new();
+    register_messages(&mut world);
+    world.init_resource::<SimulationParams>();
+    world.init_resource::<SimulationRng>();
+    world
 }
 
 #[cfg(test)]
@@ -5,16 +5,47 @@
 //! It bridges the gap between CompiledModule (ship data) and the ECS simulation.
 
 use bevy_ecs::prelude::*;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 use super::components::{CoolingSystem, PowerGrid, ShipData};
+use crate::config::simulation::CoolingConfig;
 use crate::models::CompiledModule;
 
+/// Module type IDs for power cores (module slot ID and legacy plural form)
+const POWER_CORE_TYPES: [&str; 2] = ["power-core", "power-cores"];
+
+/// Module type IDs for cooling systems (module slot ID and legacy plural form)
+const COOLING_SYSTEM_TYPES: [&str; 2] = ["cooling-system", "cooling-systems"];
+
+/// Power priority of modules Engineering has not prioritized
+pub const DEFAULT_POWER_PRIORITY: u8 = 5;
+
+/// Highest power priority (modules at this priority are shed last)
+pub const MAX_POWER_PRIORITY: u8 = 10;
+
+/// Heat a module can hold (K); `cooling.overheat_threshold` is a fraction of this
+pub const MAX_MODULE_HEAT: f32 = 1000.0;
+
+/// Heat (K) at which an overheating module shuts itself down
+pub const CRITICAL_MODULE_HEAT: f32 = 2000.0;
+
+/// Fraction of `MAX_MODULE_HEAT` a shut-down module must cool to before restarting
+const THERMAL_RESTART_FRACTION: f32 = 0.5;
+
+fn default_power_priority() -> u8 {
+    DEFAULT_POWER_PRIORITY
+}
+
+fn default_power_supplied() -> f32 {
+    1.0
+}
+
 /// Runtime state for a single module
 ///
 /// Tracks dynamic state that changes during gameplay. This complements
 /// the CompiledModule structure which contains static stats and configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModuleRuntimeState {
     /// Module instance ID
     pub instance_id: String,
@@ -46,6 +77,15 @@ pub struct ModuleRuntimeState {
     pub heat_generation: f32,
     /// Maximum uses before recharge (for auxiliary modules)
     pub max_uses: u32,
+    /// Hull restored per activation (for auxiliary modules)
+    #[serde(default)]
+    pub hp_regained: f32,
+    /// Energy added to the power reserve per activation (for auxiliary modules)
+    #[serde(default)]
+    pub energy_restored: f32,
+    /// Heat (K) drawn out of every module per activation (for auxiliary modules)
+    #[serde(default)]
+    pub heat_dissipated: f32,
     /// Remaining uses available
     pub remaining_uses: u32,
     /// Cooldown time between activations (seconds)
@@ -56,20 +96,44 @@ pub struct ModuleRuntimeState {
     pub is_active: bool,
     /// Time remaining for current activation (seconds)
     pub activation_time_remaining: f32,
+    /// Power priority (0 = shed first, `MAX_POWER_PRIORITY` = shed last)
+    #[serde(default = "default_power_priority")]
+    pub power_priority: u8,
+    /// Fraction of the allocated power the grid actually delivered (0.0 to 1.0)
+    #[serde(default = "default_power_supplied")]
+    pub power_supplied: f32,
+    /// Whether the module is being shed because demand exceeds supply
+    #[serde(default)]
+    pub browned_out: bool,
+    /// Whether the module shut itself down after reaching `CRITICAL_MODULE_HEAT`
+    #[serde(default)]
+    pub thermal_shutdown: bool,
 }
 
 impl ModuleRuntimeState {
     /// Create new runtime state from a compiled module
     pub fn from_compiled(module: &CompiledModule) -> Self {
-        // Extract power stats from module
+        // Extract power stats from module (legacy names first, then variant YAML names)
         let power_requirement = module.get_stat_f64("power_consumption").unwrap_or(0.0) as f32;
-        let power_generation = module.get_stat_f64("production").unwrap_or(0.0) as f32;
-        let cooling_capacity = module.get_stat_f64("cooling_capacity").unwrap_or(0.0) as f32;
+        let power_generation = module
+            .get_stat_f64("production")
+            .or_else(|| module.get_stat_f64("energy_production"))
+            .unwrap_or(0.0) as f32;
+        let cooling_capacity = module
+            .get_stat_f64("cooling_capacity")
+            .or_else(|| module.get_stat_f64("generated_cooling"))
+            .unwrap_or(0.0) as f32;
         let heat_generation = module.get_stat_f64("heat_generation").unwrap_or(0.0) as f32;
 
-        // Extract auxiliary module stats
-        let max_uses = module.get_stat_f64("max_uses").unwrap_or(0.0) as u32;
+        // Extract auxiliary module stats (variant YAML names first, then legacy)
+        let max_uses = module
+            .get_stat_f64("num_uses")
+            .or_else(|| module.get_stat_f64("max_uses"))
+            .unwrap_or(0.0) as u32;
         let cooldown_time = module.get_stat_f64("recharge_time").unwrap_or(0.0) as f32;
+        let hp_regained = module.get_stat_f64("hp_regained").unwrap_or(0.0) as f32;
+        let energy_restored = module.get_stat_f64("energy_restored").unwrap_or(0.0) as f32;
+        let heat_dissipated = module.get_stat_f64("heat_dissipated").unwrap_or(0.0) as f32;
 
         Self {
             instance_id: module.instance_id.clone(),
@@ -87,11 +151,18 @@ impl ModuleRuntimeState {
             cooling_capacity,
             heat_generation,
             max_uses,
+            hp_regained,
+            energy_restored,
+            heat_dissipated,
             remaining_uses: max_uses, // Start with full charges
             cooldown_time,
             current_cooldown: 0.0,
             is_active: false,
             activation_time_remaining: 0.0,
+            power_priority: DEFAULT_POWER_PRIORITY,
+            power_supplied: 1.0,
+            browned_out: false,
+            thermal_shutdown: false,
         }
     }
 
@@ -99,7 +170,7 @@ impl ModuleRuntimeState {
     ///
     /// Efficiency is affected by:
     /// - Health: Damaged modules are less efficient
-    /// - Power: Low power reduces performance
+    /// - Power: Low power reduces performance, as does brownout shedding
     /// - Heat: Overheated modules are less efficient
     pub fn update_efficiency(&mut self) {
         if !self.operational || self.current_health <= 0.0 {
@@ -110,29 +181,21 @@ impl ModuleRuntimeState {
         // Health factor: Linear scaling from damage
         let health_factor = self.current_health / self.max_health;
 
-        // Power factor: Power allocation directly affects efficiency
-        let power_factor = self.power_allocated;
+        // Power factor: Power allocation directly affects efficiency, scaled
+        // by how much of that allocation survived brownout shedding
+        let power_factor = self.power_allocated * self.power_supplied;
 
         // Heat factor: Graduated penalties based on heat level
         let heat_factor = if !self.overheated {
-            1.0 // Normal operation below 1000 K
+            1.0 // Normal operation below the overheat threshold
         } else {
             // Overheated: efficiency degrades with temperature
-            // 1000 K: 100% efficiency
-            // 1500 K: 50% efficiency
-            // 2000 K: 10% efficiency (minimum)
-            let overheat_threshold = 1000.0;
-            let critical_threshold = 2000.0;
-
-            if self.heat < critical_threshold {
-                // Linear degradation from 100% to 10%
-                let overheat_amount =
-                    (self.heat - overheat_threshold) / (critical_threshold - overheat_threshold);
-                (1.0 - overheat_amount * 0.9).max(0.1)
-            } else {
-                // Critical overheat: 10% minimum efficiency
-                0.1
-            }
+            // MAX_MODULE_HEAT (1000 K): 100% efficiency
+            // 1500 K: 55% efficiency
+            // CRITICAL_MODULE_HEAT (2000 K): 10% efficiency (minimum)
+            let overheat_amount =
+                (self.heat - MAX_MODULE_HEAT) / (CRITICAL_MODULE_HEAT - MAX_MODULE_HEAT);
+            (1.0 - overheat_amount * 0.9).clamp(0.1, 1.0)
         };
 
         // Combined efficiency
@@ -161,8 +224,9 @@ impl ModuleRuntimeState {
     pub fn repair(&mut self, amount: f32) {
         self.current_health = (self.current_health + amount).min(self.max_health);
 
-        // Module becomes operational again if repaired above 0
-        if self.current_health > 0.0 && !self.operational {
+        // Module becomes operational again if repaired above 0, unless it
+        // is still cooling down from a thermal shutdown
+        if self.current_health > 0.0 && !self.operational && !self.thermal_shutdown {
             self.operational = true;
         }
 
@@ -175,6 +239,18 @@ impl ModuleRuntimeState {
         self.update_efficiency();
     }
 
+    /// Record how much of the allocated power was delivered this tick
+    ///
+    /// Returns true if the brownout flag changed.
+    pub fn set_power_supplied(&mut self, fraction: f32) -> bool {
+        let browned_out = fraction < 1.0;
+        let changed = browned_out != self.browned_out;
+        self.power_supplied = fraction.clamp(0.0, 1.0);
+        self.browned_out = browned_out;
+        self.update_efficiency();
+        changed
+    }
+
     /// Set cooling allocation (0.0 to 1.0)
     pub fn set_cooling_allocation(&mut self, allocation: f32) {
         self.cooling_allocated = allocation.clamp(0.0, 1.0);
@@ -185,22 +261,23 @@ impl ModuleRuntimeState {
     /// # Arguments
     /// * `heat_generated` - Heat generated this tick (K/s)
     /// * `cooling_applied` - Cooling applied this tick (K/s)
+    /// * `overheat_threshold` - Fraction of `MAX_MODULE_HEAT` above which the module is overheated
     /// * `delta_time` - Time step in seconds
-    pub fn update_heat(&mut self, heat_generated: f32, cooling_applied: f32, delta_time: f32) {
+    pub fn update_heat(
+        &mut self,
+        heat_generated: f32,
+        cooling_applied: f32,
+        overheat_threshold: f32,
+        delta_time: f32,
+    ) {
         // Add generated heat
         self.heat += heat_generated * delta_time;
 
         // Apply cooling
         self.heat = (self.heat - cooling_applied * delta_time).max(0.0);
 
-        // Overheating threshold: heat > 1000 K is considered overheated
-        // This is a normalized value where:
-        // - 0-1000 K: Normal operation
-        // - 1000-2000 K: Overheated (reduced efficiency)
-        // - >2000 K: Critical overheat (severe penalties)
-        let overheat_threshold = 1000.0;
         let was_overheated = self.overheated;
-        self.overheated = self.heat > overheat_threshold;
+        self.overheated = self.heat > overheat_threshold * MAX_MODULE_HEAT;
 
         // Update efficiency if overheat state changed or if already overheated
         if self.overheated || was_overheated != self.overheated {
@@ -208,6 +285,55 @@ impl ModuleRuntimeState {
         }
     }
 
+    /// Advance the module's heat by one tick and apply its consequences
+    ///
+    /// A running module generates `heat_generation` scaled by the power it
+    /// actually receives; `cooling_applied` (K/s) is what Engineering's
+    /// cooling allocation delivers. Above the overheat threshold the module
+    /// takes `overheat_damage` per second, and at `CRITICAL_MODULE_HEAT` it
+    /// shuts down until it has cooled to half of `MAX_MODULE_HEAT`.
+    ///
+    /// Returns true if the module's overheated or operational state changed.
+    pub fn update_thermal(
+        &mut self,
+        cooling_applied: f32,
+        cooling: &CoolingConfig,
+        delta_time: f32,
+    ) -> bool {
+        if self.is_destroyed() {
+            return false;
+        }
+        let was = (self.overheated, self.operational);
+
+        let heat_generated = if self.operational {
+            self.heat_generation * self.power_allocated * self.power_supplied
+        } else {
+            0.0
+        };
+        self.update_heat(
+            heat_generated,
+            cooling_applied,
+            cooling.overheat_threshold as f32,
+            delta_time,
+        );
+
+        if self.overheated && self.operational {
+            self.apply_damage(cooling.overheat_damage as f32 * delta_time);
+        }
+
+        if self.operational && self.heat >= CRITICAL_MODULE_HEAT {
+            self.thermal_shutdown = true;
+            self.operational = false;
+            self.efficiency = 0.0;
+        } else if self.thermal_shutdown && self.heat <= MAX_MODULE_HEAT * THERMAL_RESTART_FRACTION {
+            self.thermal_shutdown = false;
+            self.operational = !self.is_destroyed();
+            self.update_efficiency();
+        }
+
+        was != (self.overheated, self.operational)
+    }
+
     /// Check if module is damaged
     pub fn is_damaged(&self) -> bool {
         self.current_health < self.max_health
@@ -320,8 +446,9 @@ impl ModuleRuntimeState {
 /// Component that tracks module states for a ship
 #[derive(Component, Debug, Clone)]
 pub struct ModuleStateTracker {
-    /// Map of instance_id to runtime state
-    pub states: HashMap<String, ModuleRuntimeState>,
+    /// Map of instance_id to runtime state, ordered so per-ship totals are
+    /// summed the same way every run
+    pub states: BTreeMap<String, ModuleRuntimeState>,
 }
 
 impl ModuleStateTracker {
@@ -353,6 +480,14 @@ impl ModuleStateTracker {
             .collect()
     }
 
+    /// Get all modules matching any of the given types
+    pub fn get_modules_by_types(&self, module_ids: &[&str]) -> Vec<&ModuleRuntimeState> {
+        self.states
+            .values()
+            .filter(|m| module_ids.contains(&m.module_id.as_str()))
+            .collect()
+    }
+
     /// Get all operational modules
     pub fn get_operational_modules(&self) -> Vec<&ModuleRuntimeState> {
         self.states
@@ -376,19 +511,16 @@ impl ModuleStateTracker {
 /// System that updates module states each tick
 ///
 /// This system handles:
-/// - Heat generation and dissipation
+/// - Power allocation as a fraction of each module's requirement
 /// - Efficiency recalculation
-/// - Power/cooling distribution
+/// - Auxiliary module timers
+///
+/// Heat is handled separately by `cooling_system`.
 pub fn module_state_system(
-    mut query: Query<(
-        &mut ModuleStateTracker,
-        &PowerGrid,
-        &CoolingSystem,
-        &ShipData,
-    )>,
+    mut query: Query<(&mut ModuleStateTracker, &PowerGrid, &ShipData)>,
     delta_time: f32,
 ) {
-    for (mut tracker, power_grid, cooling_system, _ship_data) in query.iter_mut() {
+    for (mut tracker, power_grid, _ship_data) in query.iter_mut() {
         // Update each module's state
         for state in tracker.states.values_mut() {
             if !state.operational || state.is_destroyed() {
@@ -402,13 +534,6 @@ pub fn module_state_system(
                 .copied()
                 .unwrap_or(0.0);
 
-            // Get cooling allocated to this module
-            let _cooling_allocated = cooling_system
-                .distribution
-                .get(&state.instance_id)
-                .copied()
-                .unwrap_or(0.0);
-
             // Calculate power allocation as fraction of requirement
             // If module requires 100 MW and gets 50 MW, allocation is 0.5
             state.power_allocated = if state.power_requirement > 0.0 {
@@ -417,31 +542,6 @@ pub fn module_state_system(
                 1.0 // Modules with no power requirement are always at 100%
             };
 
-            // Get cooling allocated to this module (in K/s)
-            let cooling_allocated_ks = cooling_system
-                .distribution
-                .get(&state.instance_id)
-                .copied()
-                .unwrap_or(0.0);
-
-            // Calculate cooling allocation as fraction of heat generation
-            // Module generates 100 K/s heat, gets 50 K/s cooling → 0.5 allocation
-            state.cooling_allocated = if state.heat_generation > 0.0 {
-                (cooling_allocated_ks / state.heat_generation).min(1.0)
-            } else {
-                1.0 // Modules with no heat generation always at 100%
-            };
-
-            // Calculate actual heat generation based on power usage and operational state
-            // Heat generation scales with power allocation (module only generates heat when powered)
-            let actual_heat_generation = state.heat_generation * state.power_allocated;
-
-            // Calculate cooling effectiveness
-            let cooling_effectiveness = cooling_allocated_ks;
-
-            // Update heat
-            state.update_heat(actual_heat_generation, cooling_effectiveness, delta_time);
-
             // Update efficiency
             state.update_efficiency();
 
@@ -467,7 +567,7 @@ pub fn module_damage_propagation_system(
 ) {
     for (tracker, mut power_grid, mut cooling_system, mut ship_data) in query.iter_mut() {
         // Recalculate power generation from operational power cores
-        let power_cores = tracker.get_modules_by_type("power-cores");
+        let power_cores = tracker.get_modules_by_types(&POWER_CORE_TYPES);
         let total_power_generation: f32 = power_cores
             .iter()
             .filter_map(|m| {
@@ -484,7 +584,7 @@ pub fn module_damage_propagation_system(
         power_grid.generation = total_power_generation;
 
         // Recalculate cooling from operational cooling systems
-        let cooling_modules = tracker.get_modules_by_type("cooling-systems");
+        let cooling_modules = tracker.get_modules_by_types(&COOLING_SYSTEM_TYPES);
         let total_cooling: f32 = cooling_modules
             .iter()
             .filter_map(|m| {
@@ -601,6 +701,24 @@ mod tests {
         assert_eq!(state.efficiency, 0.0);
     }
 
+    #[test]
+    fn test_brownout_scales_efficiency() {
+        let module = create_test_module("mod1", "shield-generators", 100.0);
+        let mut state = ModuleRuntimeState::from_compiled(&module);
+        assert_eq!(state.power_priority, DEFAULT_POWER_PRIORITY);
+
+        assert!(state.set_power_supplied(0.25));
+        assert!(state.browned_out);
+        assert_eq!(state.efficiency, 0.25);
+
+        // Staying browned out is not a change
+        assert!(!state.set_power_supplied(0.5));
+
+        assert!(state.set_power_supplied(1.0));
+        assert!(!state.browned_out);
+        assert_eq!(state.efficiency, 1.0);
+    }
+
     #[test]
     fn test_heat_and_overheating() {
         let module = create_test_module("mod1", "impulse-engines", 100.0);
@@ -608,7 +726,7 @@ mod tests {
 
         // Generate heat without cooling (using realistic Kelvin values)
         // Heat generation of 1200 K/s for 1 second exceeds threshold of 1000 K
-        state.update_heat(1200.0, 0.0, 1.0); // 1200 heat/sec for 1 second
+        state.update_heat(1200.0, 0.0, 1.0, 1.0); // 1200 heat/sec for 1 second
         assert_eq!(state.heat, 1200.0);
         assert!(state.overheated); // Should be overheated above 1000 K
 
@@ -616,11 +734,51 @@ mod tests {
         assert!(state.efficiency < 1.0);
 
         // Apply sufficient cooling to bring below threshold
-        state.update_heat(0.0, 1500.0, 1.0); // 1500 cooling/sec for 1 second
+        state.update_heat(0.0, 1500.0, 1.0, 1.0); // 1500 cooling/sec for 1 second
         assert!(state.heat < 1000.0); // Should be below overheat threshold
         assert!(!state.overheated);
     }
 
+    #[test]
+    fn test_thermal_overheat_damage_and_shutdown() {
+        let cooling = CoolingConfig {
+            module_cooling_rate: 50.0,
+            heat_per_module: 10.0,
+            overheat_threshold: 0.9,
+            overheat_damage: 10.0,
+        };
+        let module = create_test_module("mod1", "impulse-engines", 100.0);
+        let mut state = ModuleRuntimeState::from_compiled(&module);
+        state.heat_generation = 500.0;
+
+        // Fully cooled: no heat builds up
+        assert!(!state.update_thermal(500.0, &cooling, 1.0));
+        assert_eq!(state.heat, 0.0);
+
+        // Uncooled: 1000 K crosses the 900 K threshold
+        state.update_thermal(0.0, &cooling, 1.0);
+        assert!(state.update_thermal(0.0, &cooling, 1.0));
+        assert!(state.overheated);
+        assert_eq!(state.current_health, 90.0);
+
+        // 2000 K: thermal shutdown stops heat generation
+        state.update_thermal(0.0, &cooling, 1.0);
+        assert!(state.update_thermal(0.0, &cooling, 1.0));
+        assert!(state.thermal_shutdown);
+        assert!(!state.operational);
+        assert_eq!(state.efficiency, 0.0);
+
+        // Repairs do not restart a module that is still too hot
+        state.repair(5.0);
+        assert!(!state.operational);
+
+        // Cooled to half of MAX_MODULE_HEAT, it restarts
+        assert!(state.update_thermal(1500.0, &cooling, 1.0));
+        assert!(state.operational);
+        assert!(!state.overheated);
+        assert!(!state.thermal_shutdown);
+    }
+
     #[test]
     fn test_combined_efficiency_factors() {
         let module = create_test_module("mod1", "warp-cores", 100.0);
@@ -635,7 +793,7 @@ mod tests {
         assert!((state.efficiency - 0.5).abs() < 0.01);
 
         // Add heat beyond threshold (using realistic values in Kelvin)
-        state.update_heat(1500.0, 0.0, 1.0); // Generate 1500 K (overheated)
+        state.update_heat(1500.0, 0.0, 1.0, 1.0); // Generate 1500 K (overheated)
         assert!(state.overheated);
 
         // Efficiency should be reduced by both damage and heat
@@ -659,7 +817,7 @@ mod tests {
         assert_eq!(tracker.states.len(), 3);
         assert!(tracker.get("mod1").is_some());
 
-        let power_cores = tracker.get_modules_by_type("power-cores");
+        let power_cores = tracker.get_modules_by_types(&POWER_CORE_TYPES);
         assert_eq!(power_cores.len(), 2);
 
         let operational = tracker.get_operational_modules();
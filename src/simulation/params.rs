@@ -0,0 +1,22 @@
+//! Tuning parameters for the HYPERION simulation.
+//!
+//! Systems read gameplay constants (acceleration limits, combat multipliers,
+//! docking ranges) from the [`SimulationParams`] resource, which wraps the
+//! `SimulationConfig` loaded from `data/simulation.yaml`.
+
+use bevy_ecs::prelude::*;
+use std::ops::Deref;
+
+use crate::config::simulation::SimulationConfig;
+
+/// ECS resource holding the active simulation configuration
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SimulationParams(pub SimulationConfig);
+
+impl Deref for SimulationParams {
+    type Target = SimulationConfig;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
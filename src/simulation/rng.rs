@@ -0,0 +1,63 @@
+//! Seeded randomness for the HYPERION simulation.
+//!
+//! Systems that roll dice (accuracy, status effect application) draw from the
+//! [`SimulationRng`] resource instead of a thread-local generator. The
+//! resource is re-derived from the session seed and the tick number before
+//! every tick, so a session started from the same snapshot with the same
+//! commands rolls exactly the same numbers, and there is no generator state
+//! to save.
+
+use bevy_ecs::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::ops::{Deref, DerefMut};
+
+/// ECS resource holding the random number generator for the current tick
+#[derive(Resource, Debug, Clone)]
+pub struct SimulationRng(pub StdRng);
+
+impl SimulationRng {
+    /// Generator for one tick of a session seeded with `seed`
+    pub fn for_tick(seed: u64, tick: u64) -> Self {
+        // Spread consecutive ticks across the seed space (golden ratio step)
+        let mixed = seed ^ tick.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        Self(StdRng::seed_from_u64(mixed))
+    }
+}
+
+impl Default for SimulationRng {
+    fn default() -> Self {
+        Self::for_tick(0, 0)
+    }
+}
+
+impl Deref for SimulationRng {
+    type Target = StdRng;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for SimulationRng {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_same_seed_and_tick_roll_the_same() {
+        let mut a = SimulationRng::for_tick(7, 42);
+        let mut b = SimulationRng::for_tick(7, 42);
+        let mut c = SimulationRng::for_tick(7, 43);
+
+        let rolls: Vec<u32> = (0..4).map(|_| a.random()).collect();
+        assert_eq!(rolls, (0..4).map(|_| b.random()).collect::<Vec<u32>>());
+        assert_ne!(rolls, (0..4).map(|_| c.random()).collect::<Vec<u32>>());
+    }
+}
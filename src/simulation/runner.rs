@@ -0,0 +1,104 @@
+//! Fixed-timestep simulation runner.
+//!
+//! This module drives [`GameWorld::step_simulation`] from a background task
+//! at the tick rate configured in `data/simulation.yaml`, so the ECS world
+//! advances in real time alongside the API and WebSocket services.
+
+use std::time::Duration;
+use tokio::time;
+
+use crate::state::SharedGameWorld;
+
+/// Background service that ticks the ECS simulation at a fixed rate
+pub struct SimulationRunner {
+    game_world: SharedGameWorld,
+    timestep: f32,
+}
+
+impl SimulationRunner {
+    /// Create a runner ticking at `tick_rate` Hz
+    ///
+    /// Non-positive or non-finite rates fall back to 60 Hz.
+    pub fn new(game_world: SharedGameWorld, tick_rate: f64) -> Self {
+        let tick_rate = if tick_rate.is_finite() && tick_rate > 0.0 {
+            tick_rate
+        } else {
+            60.0
+        };
+
+        Self {
+            game_world,
+            timestep: (1.0 / tick_rate) as f32,
+        }
+    }
+
+    /// Fixed timestep in seconds
+    pub fn timestep(&self) -> f32 {
+        self.timestep
+    }
+
+    /// Start the simulation loop
+    ///
+    /// Runs forever, stepping the simulation once per timestep. Missed ticks
+    /// are caught up in a burst so simulation time keeps pace with wall time.
+    pub async fn run(self) {
+        let mut interval = self.start();
+
+        loop {
+            interval.tick().await;
+            self.tick();
+        }
+    }
+
+    /// Run for a limited number of ticks (useful for testing)
+    pub async fn run_limited(self, ticks: u64) {
+        let mut interval = self.start();
+
+        for _ in 0..ticks {
+            interval.tick().await;
+            self.tick();
+        }
+    }
+
+    /// Apply the configured timestep to the world clock and create the timer
+    fn start(&self) -> time::Interval {
+        self.game_world.write().unwrap().simulation.timestep = self.timestep;
+        time::interval(Duration::from_secs_f32(self.timestep))
+    }
+
+    fn tick(&self) {
+        let mut world = self.game_world.write().unwrap();
+        world.step_simulation();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::GameWorld;
+
+    #[test]
+    fn test_runner_timestep_from_tick_rate() {
+        let runner = SimulationRunner::new(GameWorld::new_shared(), 50.0);
+        assert!((runner.timestep() - 0.02).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_runner_invalid_tick_rate_defaults() {
+        let runner = SimulationRunner::new(GameWorld::new_shared(), 0.0);
+        assert!((runner.timestep() - 1.0 / 60.0).abs() < f32::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_runner_advances_world() {
+        let game_world = GameWorld::new_shared();
+        let runner = SimulationRunner::new(game_world.clone(), 200.0);
+
+        runner.run_limited(3).await;
+
+        let world = game_world.read().unwrap();
+        assert_eq!(world.simulation.tick, 3);
+        assert!((world.simulation.timestep - 0.005).abs() < f32::EPSILON);
+        assert_eq!(world.event_count(), 3);
+    }
+}
@@ -0,0 +1,546 @@
+//! Spawning ECS entities for compiled ships.
+//!
+//! Translates a [`Ship`] (the registry model produced by the compiler) into a
+//! ship entity carrying physics, combat, and system components, with one
+//! child entity per weapon.
+
+use bevy_ecs::prelude::*;
+use nalgebra::Vector3;
+
+use super::components::*;
+use super::helm::HelmComponent;
+use super::module_state::ModuleStateTracker;
+use super::physics::{CollisionShape, ForceAccumulator};
+use super::systems::PointDefenseMarker;
+use crate::config::{AmmunitionConfig, GameConfig, ModuleStats};
+use crate::models::{CompiledModule, Ship, WeaponTag};
+
+/// Module slot types that mount weapons, with the weapon type they produce
+const WEAPON_SLOTS: [(&str, &str); 6] = [
+    ("de-weapon", "energy"),
+    ("kinetic-weapon", "kinetic"),
+    ("missile-launcher", "missile"),
+    ("torpedo-tube", "missile"),
+    ("countermeasure-system", "countermeasure"),
+    ("radial-emission-system", "radial"),
+];
+
+/// Default cooldown for weapons without a recharge or reload time (seconds)
+const DEFAULT_WEAPON_COOLDOWN: f32 = 1.0;
+
+/// Engagement range of point defense without a `range` stat (meters)
+const DEFAULT_POINT_DEFENSE_RANGE: f32 = 1000.0;
+
+/// Interceptor rounds carried by point defense without an `ammo_capacity` stat
+const DEFAULT_POINT_DEFENSE_MAGAZINE: u32 = 20;
+
+/// Warp factor reached by warp cores (core variants don't specify one)
+const DEFAULT_MAX_WARP_FACTOR: f32 = 10.0;
+
+/// Meters per kilometer, for converting `jump_distance`
+const METERS_PER_KM: f32 = 1000.0;
+
+/// Crew members that make up one damage-control team
+const CREW_PER_DAMAGE_CONTROL_TEAM: u32 = 20;
+
+/// Number of damage-control teams a ship of the given class carries
+///
+/// One team per `CREW_PER_DAMAGE_CONTROL_TEAM` of the class's typical crew
+/// (the midpoint of `crew_min` and `crew_max`), and never fewer than one.
+pub fn damage_control_teams(config: &GameConfig, class_id: &str) -> u8 {
+    let crew = config
+        .get_ship_class(class_id)
+        .and_then(|class| match (class.crew_min, class.crew_max) {
+            (Some(min), Some(max)) => Some((min + max) / 2),
+            (min, max) => min.or(max),
+        })
+        .unwrap_or(0);
+    (crew / CREW_PER_DAMAGE_CONTROL_TEAM).clamp(1, u8::MAX as u32) as u8
+}
+
+/// Spawn the entity hierarchy for a compiled ship.
+///
+/// The ship entity is seeded from `ship.status` (so a restored ship resumes
+/// with its saved hull, shields, and position) and gets a `ModuleStateTracker`
+/// built from its compiled modules, a `PowerGrid` with each powered module's
+/// allocated draw, a `CoolingSystem` with each heat-generating module's
+/// allocated cooling, a `SensorComponent`, `StealthComponent`, and
+/// `CommsComponent` built from its sensor arrays, stealth systems, and comms
+/// systems, a `CommunicationState`, a `RepairComponent` crewed by
+/// `damage_control_teams`, an `InventoryComponent` mirroring the
+/// ship's hold, a `HelmComponent`, and a warp or jump
+/// drive for each installed warp/jump core. Every weapon-slot module and
+/// every `WeaponInstance` becomes a child entity with a `WeaponComponent`
+/// and `TargetingComponent`.
+///
+/// Returns the ship entity.
+pub fn spawn_ship(world: &mut World, ship: &Ship, config: &GameConfig) -> Entity {
+    let status = &ship.status;
+
+    let mut ship_data = ShipData::new(
+        ship.id.clone(),
+        ship.name.clone(),
+        ship.class.clone(),
+        ship.team_id.clone(),
+        status.max_hull,
+        status.max_shields,
+        status.base_weight,
+    );
+    ship_data.hull = status.hull;
+    ship_data.shields = status.shields;
+    ship_data.power = status.power_generation;
+    ship_data.max_power = status.power_generation;
+    ship_data.cooling = status.cooling_capacity;
+    ship_data.max_cooling = status.cooling_capacity;
+    ship_data.effective_weight = status.effective_weight;
+
+    let shield_regen: f32 = ship
+        .modules
+        .iter()
+        .filter(|m| m.module_id == "shield-generator")
+        .filter_map(|m| m.get_stat_f64("shield_recharge_rate"))
+        .sum::<f64>() as f32;
+    let mut shield = ShieldComponent::new(status.max_shields, shield_regen, 0.0);
+    shield.strength = status.shields;
+    shield.raised = status.shields_raised;
+
+    let mut sensors = SensorComponent::default();
+    let mut stealth = StealthComponent::default();
+    let mut comms = CommsComponent::default();
+    for module in &ship.modules {
+        match module.module_id.as_str() {
+            "sensor-array" => {
+                let array = SensorArray {
+                    scan_range: module.get_stat_f64("scan_range").unwrap_or(0.0) as f32,
+                    scan_time: module.get_stat_f64("scan_time").unwrap_or(0.0) as f32,
+                    detail_level: module.get_stat_f64("detail_level").unwrap_or(1.0) as u8,
+                };
+                sensors.arrays.insert(module.instance_id.clone(), array);
+            }
+            "stealth-system" => {
+                let system = StealthSystem {
+                    detectability_reduction: module
+                        .get_stat_f64("detectability_reduction")
+                        .unwrap_or(0.0) as f32,
+                    scan_time_increase: module.get_stat_f64("scan_time_increase").unwrap_or(0.0)
+                        as f32,
+                };
+                stealth.systems.insert(module.instance_id.clone(), system);
+            }
+            "comms-system" => {
+                let system = CommsSystem {
+                    comm_range: module.get_stat_f64("comm_range").unwrap_or(0.0) as f32,
+                    encryption_lvl: module.get_stat_f64("encryption_lvl").unwrap_or(1.0) as u8,
+                };
+                comms.systems.insert(module.instance_id.clone(), system);
+            }
+            _ => {}
+        }
+    }
+
+    let mut transform = Transform::at_position(array_to_vector(status.position));
+    transform.velocity = array_to_vector(status.velocity);
+
+    // Every powered module starts drawing its allocated share of its requirement
+    let modules = ModuleStateTracker::from_compiled_modules(&ship.modules);
+    let mut power_grid = PowerGrid::new(status.power_generation, status.power_capacity);
+    for state in modules.states.values() {
+        if state.power_requirement > 0.0 {
+            power_grid.allocate(
+                state.instance_id.clone(),
+                state.power_requirement * state.power_allocated,
+            );
+        }
+    }
+
+    // Every heat-generating module starts cooled by its allocated share of its heat
+    let mut cooling = CoolingSystem::new(status.cooling_capacity, status.cooling_capacity);
+    for state in modules.states.values() {
+        if state.heat_generation > 0.0 {
+            cooling.allocate(
+                state.instance_id.clone(),
+                state.heat_generation * state.cooling_allocated,
+            );
+        }
+    }
+
+    let ship_entity = world
+        .spawn((
+            transform.clone(),
+            ship_data,
+            shield,
+            power_grid,
+            cooling,
+            modules,
+            StatusEffects::new(),
+            ForceAccumulator::new(),
+            CollisionShape::ship(),
+            HelmComponent::new(),
+            sensors,
+            stealth,
+            InventoryComponent {
+                ammunition: ship.inventory.ammunition.clone(),
+                cargo: ship.inventory.cargo.clone(),
+            },
+        ))
+        .insert((
+            comms,
+            CommunicationState::new(),
+            RepairComponent::new(damage_control_teams(config, &ship.class)),
+        ))
+        .id();
+
+    for module in ship
+        .modules
+        .iter()
+        .filter(|m| m.module_id == "warp-jump-core")
+    {
+        let delay = module.get_stat_f64("warp_delay").unwrap_or(0.0) as f32;
+        match module.stats.get_string("warp_type").as_deref() {
+            Some("warp") => {
+                world
+                    .entity_mut(ship_entity)
+                    .insert(WarpDriveComponent::new(
+                        DEFAULT_MAX_WARP_FACTOR,
+                        delay,
+                        delay,
+                    ));
+            }
+            Some("jump") => {
+                let range = module.get_stat_f64("jump_distance").unwrap_or(0.0) as f32;
+                world
+                    .entity_mut(ship_entity)
+                    .insert(JumpDriveComponent::new(range * METERS_PER_KM, delay, delay));
+            }
+            _ => {}
+        }
+    }
+
+    for module in &ship.modules {
+        if let Some((weapon, point_defense)) = weapon_from_module(module) {
+            spawn_weapon(world, ship_entity, &transform, weapon, point_defense);
+        }
+    }
+
+    for instance in &ship.weapons {
+        let (weapon, point_defense) = find_weapon_variant(config, &instance.weapon_id)
+            .and_then(|(slot_id, stats)| {
+                weapon_component(&instance.id, &instance.weapon_id, slot_id, stats)
+            })
+            .unwrap_or_else(|| {
+                (
+                    WeaponComponent::new(
+                        instance.id.clone(),
+                        instance.weapon_id.clone(),
+                        "kinetic".to_string(),
+                        Vec::new(),
+                        0.0,
+                        DEFAULT_WEAPON_COOLDOWN,
+                    ),
+                    false,
+                )
+            });
+        let mut weapon = weapon;
+        weapon.ammunition = instance.loaded_ammunition.clone();
+        weapon.ordnance = weapon
+            .ammunition
+            .as_deref()
+            .and_then(|ammo_id| config.get_ammunition(ammo_id))
+            .map(ordnance_from_config);
+        spawn_weapon(world, ship_entity, &transform, weapon, point_defense);
+    }
+
+    ship_entity
+}
+
+/// Build a weapon for a compiled module, if it occupies a weapon slot.
+///
+/// Returns the weapon and whether it is a point-defense system.
+pub fn weapon_from_module(module: &CompiledModule) -> Option<(WeaponComponent, bool)> {
+    let config_id = module.kind.as_deref().unwrap_or(&module.module_id);
+    weapon_component(
+        &module.instance_id,
+        config_id,
+        &module.module_id,
+        &module.stats,
+    )
+}
+
+fn weapon_component(
+    id: &str,
+    config_id: &str,
+    slot_id: &str,
+    stats: &ModuleStats,
+) -> Option<(WeaponComponent, bool)> {
+    let (_, weapon_type) = WEAPON_SLOTS.iter().find(|(slot, _)| *slot == slot_id)?;
+
+    let mut tags = parse_weapon_tags(stats);
+    if tags.is_empty() {
+        match slot_id {
+            "missile-launcher" => tags.push(WeaponTag::Missile),
+            "torpedo-tube" => tags.push(WeaponTag::Torpedo),
+            _ => {}
+        }
+    }
+
+    let cooldown = stats
+        .get_f64("recharge_time")
+        .or_else(|| stats.get_f64("reload_time"))
+        .map(|t| t as f32)
+        .unwrap_or(DEFAULT_WEAPON_COOLDOWN);
+    let damage = stats.get_f64("damage").unwrap_or(0.0) as f32;
+
+    let mut weapon = WeaponComponent::new(
+        id.to_string(),
+        config_id.to_string(),
+        weapon_type.to_string(),
+        tags,
+        damage,
+        cooldown,
+    );
+    if let Some(speed) = stats.get_f64("projectile_speed") {
+        weapon.projectile_speed = speed as f32;
+    }
+    weapon.ammo_capacity = stats.get_f64("ammo_capacity").unwrap_or(0.0) as u32;
+    if let Some(accuracy) = stats.get_f64("accuracy") {
+        weapon.accuracy = (accuracy as f32).clamp(0.0, 1.0);
+    }
+    weapon.range = stats.get_f64("range").unwrap_or(0.0) as f32;
+
+    // Point defense launches with a full magazine of interceptors
+    let point_defense = slot_id == "countermeasure-system";
+    if point_defense {
+        if weapon.range <= 0.0 {
+            weapon.range = DEFAULT_POINT_DEFENSE_RANGE;
+        }
+        if weapon.ammo_capacity == 0 {
+            weapon.ammo_capacity = DEFAULT_POINT_DEFENSE_MAGAZINE;
+        }
+        weapon.ammo_count = weapon.ammo_capacity;
+    }
+
+    Some((weapon, point_defense))
+}
+
+/// Build the ballistic profile for an ammunition type
+pub fn ordnance_from_config(ammo: &AmmunitionConfig) -> Ordnance {
+    let speed = if ammo.velocity > 0.0 {
+        ammo.velocity
+    } else {
+        ammo.max_speed
+    };
+    Ordnance {
+        category: ammo.category.clone(),
+        damage: ammo.impact_damage,
+        speed,
+        acceleration: ammo.acceleration,
+        turn_rate: ammo.max_turn_rate,
+        lifetime: ammo.lifetime,
+        blast_radius: ammo.blast_radius,
+        blast_damage: ammo.blast_damage,
+        tags: ammo
+            .weapon_tags
+            .iter()
+            .filter_map(|tag| parse_weapon_tag(tag))
+            .collect(),
+    }
+}
+
+/// Parse the `weapon_tags` stat, skipping tags the simulation doesn't model
+fn parse_weapon_tags(stats: &ModuleStats) -> Vec<WeaponTag> {
+    stats
+        .stats
+        .get("weapon_tags")
+        .and_then(|v| v.as_array())
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|tag| serde_json::from_value(tag.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_weapon_tag(tag: &str) -> Option<WeaponTag> {
+    serde_json::from_value(serde_json::Value::String(tag.to_string())).ok()
+}
+
+/// Find a weapon variant by ID across all weapon slots
+fn find_weapon_variant<'a>(
+    config: &'a GameConfig,
+    variant_id: &str,
+) -> Option<(&'static str, &'a ModuleStats)> {
+    WEAPON_SLOTS.iter().find_map(|(slot_id, _)| {
+        config
+            .get_module_variant(slot_id, variant_id)
+            .map(|variant| (*slot_id, &variant.stats))
+    })
+}
+
+fn spawn_weapon(
+    world: &mut World,
+    ship_entity: Entity,
+    ship_transform: &Transform,
+    weapon: WeaponComponent,
+    point_defense: bool,
+) {
+    let mut entity = world.spawn((
+        weapon,
+        ship_transform.clone(),
+        TargetingComponent::default(),
+        ChildOf(ship_entity),
+    ));
+    if point_defense {
+        entity.insert(PointDefenseMarker);
+    }
+}
+
+fn array_to_vector(a: [f64; 3]) -> Vector3<f32> {
+    Vector3::new(a[0] as f32, a[1] as f32, a[2] as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::ShipStatus;
+    use crate::models::blueprint::WeaponInstance;
+    use std::collections::HashMap;
+
+    fn module(instance_id: &str, module_id: &str, stats: serde_json::Value) -> CompiledModule {
+        CompiledModule {
+            instance_id: instance_id.to_string(),
+            module_id: module_id.to_string(),
+            kind: Some(format!("{}-variant", module_id)),
+            name: instance_id.to_string(),
+            stats: serde_json::from_value(stats).unwrap(),
+            current_health: 50.0,
+            max_health: 50.0,
+            operational: true,
+            power_allocated: 1.0,
+            cooling_allocated: 1.0,
+        }
+    }
+
+    fn test_ship() -> Ship {
+        let mut status = ShipStatus::new(1000.0, 400.0, 5000.0);
+        status.hull = 750.0;
+        status.position = [100.0, 0.0, -50.0];
+        Ship {
+            id: "ship-1".to_string(),
+            name: "Test Ship".to_string(),
+            class: "cruiser".to_string(),
+            team_id: "team-1".to_string(),
+            player_roles: HashMap::new(),
+            status,
+            modules: vec![
+                module(
+                    "laser-1",
+                    "de-weapon",
+                    serde_json::json!({
+                        "damage": 25, "recharge_time": 2.0,
+                        "weapon_tags": ["Beam", "Photon", "Long-Range"]
+                    }),
+                ),
+                module(
+                    "shield-1",
+                    "shield-generator",
+                    serde_json::json!({ "shield_recharge_rate": 40 }),
+                ),
+                module("pd-1", "countermeasure-system", serde_json::json!({})),
+                module(
+                    "ftl-1",
+                    "warp-jump-core",
+                    serde_json::json!({
+                        "warp_type": "jump", "warp_delay": 5.0, "jump_distance": 50
+                    }),
+                ),
+            ],
+            weapons: vec![WeaponInstance {
+                id: "extra-1".to_string(),
+                weapon_id: "unknown-weapon".to_string(),
+                kind: None,
+                loaded_ammunition: Some("slug-20mm-st".to_string()),
+            }],
+            inventory: crate::models::Inventory::new(),
+        }
+    }
+
+    #[test]
+    fn test_spawn_ship_components() {
+        let mut world = World::new();
+        let config = create_test_game_config();
+
+        let entity = spawn_ship(&mut world, &test_ship(), &config);
+
+        let ship_data = world.get::<ShipData>(entity).unwrap();
+        assert_eq!(ship_data.id, "ship-1");
+        assert_eq!(ship_data.hull, 750.0);
+        let shield = world.get::<ShieldComponent>(entity).unwrap();
+        assert_eq!(shield.regen_rate, 40.0);
+        let transform = world.get::<Transform>(entity).unwrap();
+        assert_eq!(transform.position, Vector3::new(100.0, 0.0, -50.0));
+        assert_eq!(
+            world
+                .get::<ModuleStateTracker>(entity)
+                .unwrap()
+                .states
+                .len(),
+            4
+        );
+        assert!(world.get::<PowerGrid>(entity).is_some());
+        assert!(world.get::<CoolingSystem>(entity).is_some());
+        assert!(world.get::<HelmComponent>(entity).is_some());
+
+        let jump = world.get::<JumpDriveComponent>(entity).unwrap();
+        assert_eq!(jump.max_range, 50_000.0);
+        assert_eq!(jump.timer.startup_time, 5.0);
+        assert!(world.get::<WarpDriveComponent>(entity).is_none());
+    }
+
+    #[test]
+    fn test_spawn_ship_weapon_children() {
+        let mut world = World::new();
+        let config = create_test_game_config();
+
+        let entity = spawn_ship(&mut world, &test_ship(), &config);
+
+        let children: Vec<Entity> = world.get::<Children>(entity).unwrap().to_vec();
+        assert_eq!(children.len(), 3);
+
+        let weapons: HashMap<String, Entity> = children
+            .iter()
+            .map(|e| (world.get::<WeaponComponent>(*e).unwrap().id.clone(), *e))
+            .collect();
+
+        let laser = world.get::<WeaponComponent>(weapons["laser-1"]).unwrap();
+        assert_eq!(laser.weapon_type, "energy");
+        assert_eq!(laser.base_damage, 25.0);
+        assert_eq!(laser.max_cooldown, 2.0);
+        assert_eq!(laser.tags, vec![WeaponTag::Beam, WeaponTag::Photon]);
+
+        assert!(world.get::<PointDefenseMarker>(weapons["pd-1"]).is_some());
+        let pd = world.get::<WeaponComponent>(weapons["pd-1"]).unwrap();
+        assert_eq!(pd.range, DEFAULT_POINT_DEFENSE_RANGE);
+        assert_eq!(pd.ammo_count, DEFAULT_POINT_DEFENSE_MAGAZINE);
+        assert!(
+            world
+                .get::<PointDefenseMarker>(weapons["laser-1"])
+                .is_none()
+        );
+
+        let extra = world.get::<WeaponComponent>(weapons["extra-1"]).unwrap();
+        assert_eq!(extra.ammunition.as_deref(), Some("slug-20mm-st"));
+    }
+
+    #[test]
+    fn test_despawn_ship_removes_weapons() {
+        let mut world = World::new();
+        let config = create_test_game_config();
+
+        let entity = spawn_ship(&mut world, &test_ship(), &config);
+        world.despawn(entity);
+
+        let mut weapons = world.query::<&WeaponComponent>();
+        assert_eq!(weapons.iter(&world).count(), 0);
+    }
+}
@@ -6,9 +6,19 @@
 use bevy_ecs::entity::Entity;
 use bevy_ecs::prelude::*;
 use bevy_ecs::system::{Commands, Query};
-use nalgebra::Vector3;
+use nalgebra::{UnitQuaternion, Vector3};
+use rand::Rng;
+use std::collections::BTreeMap;
 
 use super::components::*;
+use super::messages::{
+    ContactChanged, Explosion, MessageReceived, MessageUndelivered, ModuleBrownout,
+    ModuleStatusChanged, PointDefenseEngaged, ScanTierRevealed, ShipDamaged,
+};
+use super::module_state::{DEFAULT_POWER_PRIORITY, ModuleStateTracker};
+use super::params::SimulationParams;
+use super::rng::SimulationRng;
+use crate::models::WeaponTag;
 use crate::weapons::{DamageResult, StatusEffectType, WeaponTagCalculator};
 
 /// Apply a computed [`DamageResult`] to a target ship.
@@ -20,12 +30,16 @@ use crate::weapons::{DamageResult, StatusEffectType, WeaponTagCalculator};
 /// Damage scaling (e.g. `damage * delta_time` for continuous weapons) must be
 /// applied by the caller before invoking [`WeaponTagCalculator::calculate_damage`];
 /// this helper applies `result.hull_damage` verbatim.
+///
+/// Returns the damage dealt: what the shields absorbed plus what reached the
+/// hull.
 fn apply_damage_result(
     shield: &mut ShieldComponent,
     ship_data: &mut ShipData,
     effects: Option<&mut StatusEffects>,
     result: &DamageResult,
-) {
+) -> f32 {
+    let shield_before = shield.strength;
     let remaining_damage = shield.apply_damage(result.hull_damage);
     if remaining_damage > 0.0 {
         ship_data.hull -= remaining_damage;
@@ -34,11 +48,9 @@ fn apply_damage_result(
     if let (Some(effects), Some(status_effect)) = (effects, result.status_effect.as_ref()) {
         effects.apply(status_effect.effect_type, status_effect.duration);
     }
+    (shield_before - shield.strength).max(0.0) + remaining_damage.max(0.0)
 }
 
-#[cfg(test)]
-use crate::models::WeaponTag;
-
 /// System that updates ship positions based on velocity and effective weight.
 ///
 /// This system runs each tick and updates the `Transform` component based on
@@ -58,30 +70,102 @@ pub fn movement_system(mut query: Query<&mut Transform>, delta_time: f32) {
     }
 }
 
+/// System that keeps weapon mounts positioned on their parent ship.
+///
+/// Weapons are spawned as children of their ship entity; this copies the
+/// ship's position, orientation, and velocity onto each weapon after movement.
+pub fn weapon_mount_system(
+    mut weapons: Query<(&ChildOf, &mut Transform), With<WeaponComponent>>,
+    ships: Query<&Transform, Without<WeaponComponent>>,
+) {
+    for (child_of, mut transform) in weapons.iter_mut() {
+        if let Ok(ship_transform) = ships.get(child_of.parent()) {
+            transform.position = ship_transform.position;
+            transform.rotation = ship_transform.rotation;
+            transform.velocity = ship_transform.velocity;
+        }
+    }
+}
+
 /// System that manages power generation and distribution to modules.
 ///
-/// This system calculates total power generation from power cores and ensures
-/// that the allocated power doesn't exceed available power.
-pub fn power_system(mut power_grids: Query<&mut PowerGrid>) {
-    for power_grid in power_grids.iter_mut() {
-        // Calculate available power (capacity minus allocated)
-        // Power generation and distribution is managed elsewhere
-        // This system just tracks and validates
-        let _allocated = power_grid.total_allocated();
+/// Each ship's grid delivers its allocations from generation plus stored
+/// reserves. When demand exceeds that, the lowest-priority modules (per
+/// their `ModuleRuntimeState::power_priority`) are throttled or cut off and
+/// flagged as browned out. A [`ModuleBrownout`] message is written whenever
+/// a module enters or leaves brownout.
+pub fn power_system(
+    mut ships: Query<(Entity, &mut PowerGrid, Option<&mut ModuleStateTracker>)>,
+    mut brownouts: Option<MessageWriter<ModuleBrownout>>,
+    delta_time: f32,
+) {
+    for (ship, mut power_grid, tracker) in ships.iter_mut() {
+        let Some(mut tracker) = tracker else {
+            power_grid.supply(|_| DEFAULT_POWER_PRIORITY, delta_time);
+            continue;
+        };
 
-        // See docs/plans/simulation-completeness.md — power_system
+        let supplied = power_grid.supply(
+            |module_id| {
+                tracker
+                    .get(module_id)
+                    .map_or(DEFAULT_POWER_PRIORITY, |state| state.power_priority)
+            },
+            delta_time,
+        );
+
+        for state in tracker.states.values_mut() {
+            let fraction = supplied.get(&state.instance_id).copied().unwrap_or(1.0);
+            if state.set_power_supplied(fraction)
+                && let Some(brownouts) = brownouts.as_mut()
+            {
+                brownouts.write(ModuleBrownout {
+                    ship,
+                    module_id: state.instance_id.clone(),
+                    power_supplied: state.power_supplied,
+                    browned_out: state.browned_out,
+                });
+            }
+        }
     }
 }
 
 /// System that manages heat generation and cooling distribution.
 ///
-/// This system calculates cooling capacity and ensures modules don't overheat.
-pub fn cooling_system(mut cooling_systems: Query<&mut CoolingSystem>) {
-    for cooling_sys in cooling_systems.iter_mut() {
-        // Calculate total cooling allocated
-        let _allocated = cooling_sys.total_allocated();
+/// Each module heats up by its `heat_generation` (scaled by the power it
+/// receives) and sheds the cooling Engineering allocated to it, scaled down
+/// when the ship's allocations exceed its dissipation. Overheated modules
+/// take `cooling.overheat_damage` per second and shut down at
+/// `CRITICAL_MODULE_HEAT`. A [`ModuleStatusChanged`] message is written
+/// whenever a module starts or stops overheating, shuts down, or restarts.
+pub fn cooling_system(
+    mut ships: Query<(Entity, &CoolingSystem, &mut ModuleStateTracker)>,
+    params: Res<SimulationParams>,
+    mut status_changes: Option<MessageWriter<ModuleStatusChanged>>,
+    delta_time: f32,
+) {
+    let cooling = &params.systems.cooling;
+    for (ship, cooling_system, mut tracker) in ships.iter_mut() {
+        for state in tracker.states.values_mut() {
+            let delivered = cooling_system.delivered(&state.instance_id);
+            state.cooling_allocated = if state.heat_generation > 0.0 {
+                (delivered / state.heat_generation).min(1.0)
+            } else {
+                1.0 // Modules with no heat generation are always fully cooled
+            };
 
-        // See docs/plans/simulation-completeness.md — cooling_system
+            if state.update_thermal(delivered, cooling, delta_time)
+                && let Some(status_changes) = status_changes.as_mut()
+            {
+                status_changes.write(ModuleStatusChanged {
+                    ship,
+                    module_id: state.instance_id.clone(),
+                    health_percent: state.health_percentage() * 100.0,
+                    operational: state.operational,
+                    overheated: state.overheated,
+                });
+            }
+        }
     }
 }
 
@@ -115,48 +199,211 @@ pub fn weapon_cooldown_system(mut query: Query<&mut WeaponComponent>, delta_time
     }
 }
 
+/// How long a fired beam stays on target (seconds)
+const BEAM_DURATION: f32 = 1.0;
+
+/// Lifetime of unguided rounds and ordnance without a configured lifetime (seconds)
+const DEFAULT_PROJECTILE_LIFETIME: f32 = 10.0;
+
+/// Share of its top speed a guided missile leaves the launcher with
+const MISSILE_LAUNCH_SPEED_FRACTION: f32 = 0.5;
+
+/// Distance at which a seduced missile reaches its lure and detonates (meters)
+const LURE_CAPTURE_DISTANCE: f32 = 10.0;
+
+/// Distance from a ship's center at which a round strikes it (meters)
+// See docs/plans/simulation-completeness.md — damage_system (real radii)
+const COLLISION_DISTANCE: f32 = 10.0;
+
+/// Build the projectile a weapon fires at a target.
+///
+/// Energy weapons produce a beam that stays on target for [`BEAM_DURATION`]
+/// and deals `base_damage` over that time. Kinetic and missile weapons
+/// launch a round from `origin`, leading `target_transform` to where it will
+/// be when the round arrives, using the loaded ordnance's damage, speed, and
+/// tags on top of the weapon's own.
+///
+/// `owner` should be the firing ship so countermeasures and scoring can tell
+/// whose round it is.
+pub fn launch_projectile(
+    owner: Entity,
+    weapon: &WeaponComponent,
+    origin: &Transform,
+    target: Entity,
+    target_transform: &Transform,
+) -> (ProjectileComponent, Transform) {
+    let mut tags = weapon.tags.clone();
+    let mut damage = weapon.base_damage;
+    let mut speed = weapon.projectile_speed;
+    let mut lifetime = DEFAULT_PROJECTILE_LIFETIME;
+    let (mut blast_radius, mut blast_damage) = (0.0, 0.0);
+    if let Some(ordnance) = &weapon.ordnance {
+        damage += ordnance.damage;
+        blast_radius = ordnance.blast_radius;
+        blast_damage = ordnance.blast_damage;
+        if ordnance.speed > 0.0 {
+            speed = ordnance.speed;
+        }
+        if ordnance.lifetime > 0.0 {
+            lifetime = ordnance.lifetime;
+        }
+        for tag in &ordnance.tags {
+            if !tags.contains(tag) {
+                tags.push(*tag);
+            }
+        }
+    }
+
+    let projectile_type = match weapon.weapon_type.as_str() {
+        "energy" => ProjectileType::Beam,
+        "missile" if tags.contains(&WeaponTag::Torpedo) => ProjectileType::Torpedo,
+        "missile" => {
+            let (thrust, turn_rate) = weapon
+                .ordnance
+                .as_ref()
+                .map(|o| (o.acceleration as u32, o.turn_rate.to_degrees() as u32))
+                .unwrap_or((0, 0));
+            ProjectileType::Missile { thrust, turn_rate }
+        }
+        _ => ProjectileType::Kinetic,
+    };
+
+    // Guided missiles leave the launcher slow and accelerate to top speed
+    let mut guidance = Guidance::default();
+    if let ProjectileType::Missile { thrust, .. } = projectile_type
+        && thrust > 0
+    {
+        guidance.max_speed = speed;
+        speed *= MISSILE_LAUNCH_SPEED_FRACTION;
+    }
+
+    let mut transform = Transform::at_position(origin.position);
+    transform.rotation = origin.rotation;
+    if projectile_type == ProjectileType::Beam {
+        damage /= BEAM_DURATION;
+        lifetime = BEAM_DURATION;
+        blast_radius = 0.0;
+        blast_damage = 0.0;
+    } else {
+        let offset = intercept_point(origin.position, target_transform, speed) - origin.position;
+        let direction = if offset.magnitude() > 0.0 {
+            offset.normalize()
+        } else {
+            origin.rotation * Vector3::new(0.0, 0.0, 1.0)
+        };
+        transform.velocity = direction * speed;
+    }
+
+    let projectile = ProjectileComponent {
+        projectile_type,
+        owner,
+        target: Some(target),
+        damage,
+        tags,
+        lifetime,
+        accuracy: weapon.accuracy,
+        blast_radius,
+        blast_damage,
+        guidance,
+    };
+    (projectile, transform)
+}
+
+/// Where a round fired from `origin` at `speed` meets a target holding its
+/// course, or the target's current position if the round cannot catch it
+fn intercept_point(origin: Vector3<f32>, target: &Transform, speed: f32) -> Vector3<f32> {
+    // Solve |offset + velocity * t| = speed * t for the earliest t > 0
+    let offset = target.position - origin;
+    let velocity = target.velocity;
+    let a = velocity.dot(&velocity) - speed * speed;
+    let b = 2.0 * offset.dot(&velocity);
+    let c = offset.dot(&offset);
+    let time = if a.abs() < f32::EPSILON {
+        -c / b
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return target.position;
+        }
+        let root = discriminant.sqrt();
+        let (first, second) = ((-b - root) / (2.0 * a), (-b + root) / (2.0 * a));
+        if first > 0.0 && (second <= 0.0 || first < second) {
+            first
+        } else {
+            second
+        }
+    };
+    if time.is_finite() && time > 0.0 {
+        target.position + velocity * time
+    } else {
+        target.position
+    }
+}
+
+/// System that advances target locks and applies Ion targeting jams.
+///
+/// Weapons on a ship affected by Ion jamming lose their lock and cannot
+/// reacquire it until the effect wears off.
+pub fn targeting_system(
+    mut weapons: Query<(&mut TargetingComponent, &ChildOf)>,
+    ships: Query<&StatusEffects>,
+    delta_time: f32,
+) {
+    for (mut targeting, child_of) in weapons.iter_mut() {
+        let jammed = ships
+            .get(child_of.parent())
+            .is_ok_and(|effects| effects.has_effect(StatusEffectType::IonJam));
+        if jammed {
+            targeting.disabled = true;
+            targeting.is_locked = false;
+            targeting.lock_progress = 0.0;
+            continue;
+        }
+        targeting.disabled = false;
+        targeting.update_lock(delta_time);
+    }
+}
+
 /// System that handles automatic weapon firing when target is locked.
 ///
 /// This system fires weapons that are set to automatic when they have a target lock
 /// and are ready to fire (cooldown complete, ammunition available, etc.).
-///
-/// Note: This is a simplified version that doesn't use parent-child relationships.
-/// In a full implementation, weapons would be children of ships.
+/// Projectiles are aimed at the locked target and owned by the parent ship.
 pub fn weapon_fire_system(
     mut commands: Commands,
-    mut weapons: Query<(Entity, &mut WeaponComponent, &Transform)>,
+    mut weapons: Query<(
+        Entity,
+        &mut WeaponComponent,
+        &Transform,
+        &TargetingComponent,
+        Option<&ChildOf>,
+    )>,
+    targets: Query<&Transform, Without<WeaponComponent>>,
 ) {
-    for (weapon_entity, mut weapon, weapon_transform) in weapons.iter_mut() {
+    for (weapon_entity, mut weapon, weapon_transform, targeting, child_of) in weapons.iter_mut() {
         // Skip if not automatic or not active
         if !weapon.is_automatic || !weapon.is_active {
             continue;
         }
 
-        // Check if weapon can fire
-        if !weapon.can_fire() {
+        // Check if weapon can fire at a locked target
+        if !weapon.can_fire() || !targeting.can_engage() {
             continue;
         }
+        let Some(target) = targeting.target else {
+            continue;
+        };
+        let Ok(target_transform) = targets.get(target) else {
+            continue;
+        };
 
-        // See docs/plans/simulation-completeness.md — weapon_fire_system
-        // (targeting from parent ship and ammunition inventory checks)
-
-        // For now, create a simple projectile
-        let projectile = ProjectileComponent::kinetic(
-            weapon_entity, // Use weapon entity as owner for now
-            weapon.base_damage,
-            weapon.tags.clone(),
-            10.0,
-        );
-
-        // Create projectile entity with appropriate transform
-        commands.spawn((
-            projectile,
-            Transform {
-                position: weapon_transform.position,
-                rotation: weapon_transform.rotation,
-                velocity: weapon_transform.rotation * Vector3::new(0.0, 0.0, 1000.0), // Forward
-                angular_velocity: Vector3::zeros(),
-            },
+        let owner = child_of.map(ChildOf::parent).unwrap_or(weapon_entity);
+        commands.spawn(launch_projectile(
+            owner,
+            &weapon,
+            weapon_transform,
+            target,
+            target_transform,
         ));
 
         // Start weapon cooldown
@@ -168,11 +415,22 @@ pub fn weapon_fire_system(
 ///
 /// This system handles collision detection between projectiles and ships,
 /// calculates damage using the weapon tag system, and applies it to shields/hull.
+/// Collisions sweep each round's path over the last `delta_time` relative to
+/// its target, so fast rounds cannot pass through a ship between ticks.
+/// With a [`SimulationRng`] present, each round rolls against its accuracy
+/// and status effects roll against their application chance. Missile and
+/// torpedo warheads detonate on reaching their target, hit or miss, writing
+/// an [`Explosion`] for `explosion_system`.
+#[allow(clippy::too_many_arguments)]
 pub fn damage_system(
     mut commands: Commands,
     projectiles: Query<(Entity, &ProjectileComponent, &Transform)>,
     mut ships: Query<(Entity, &mut ShipData, &mut ShieldComponent, &Transform)>,
     mut status_effects: Query<&mut StatusEffects>,
+    mut damaged: Option<MessageWriter<ShipDamaged>>,
+    mut explosions: Option<MessageWriter<Explosion>>,
+    mut rng: Option<ResMut<SimulationRng>>,
+    delta_time: f32,
 ) {
     let calculator = WeaponTagCalculator::new();
 
@@ -187,15 +445,30 @@ pub fn damage_system(
             && let Ok((ship_entity, mut ship_data, mut shield, ship_transform)) =
                 ships.get_mut(target_entity)
         {
-            // Simple distance-based collision detection
-            let distance = (proj_transform.position - ship_transform.position).magnitude();
-            // See docs/plans/simulation-completeness.md — damage_system (real radii)
-            let collision_distance = 10.0;
+            let (distance, impact) = closest_approach(proj_transform, ship_transform, delta_time);
+
+            if distance < COLLISION_DISTANCE {
+                if projectile.is_explosive()
+                    && let Some(explosions) = explosions.as_mut()
+                {
+                    explosions.write(Explosion::from_projectile(projectile, impact));
+                }
+
+                // Roll to hit; a miss spends the round without impact damage
+                if let Some(rng) = rng.as_deref_mut()
+                    && rng.random::<f32>() >= projectile.accuracy
+                {
+                    commands.entity(projectile_entity).despawn();
+                    continue;
+                }
 
-            if distance < collision_distance {
                 // Calculate damage using weapon tags
-                let damage_result =
-                    calculator.calculate_damage(projectile.damage, &projectile.tags);
+                let damage_result = resolve_damage(
+                    &calculator,
+                    projectile.damage,
+                    &projectile.tags,
+                    rng.as_deref_mut(),
+                );
 
                 // Check for errors
                 let damage_result = match damage_result {
@@ -204,12 +477,20 @@ pub fn damage_system(
                 };
 
                 let mut effects = status_effects.get_mut(ship_entity).ok();
-                apply_damage_result(
+                let dealt = apply_damage_result(
                     &mut shield,
                     &mut ship_data,
                     effects.as_deref_mut(),
                     &damage_result,
                 );
+                if let Some(damaged) = damaged.as_mut() {
+                    damaged.write(ShipDamaged {
+                        ship: ship_entity,
+                        damage_type: projectile.projectile_type.into(),
+                        amount: dealt,
+                        attacker: projectile.owner,
+                    });
+                }
 
                 // Remove projectile
                 commands.entity(projectile_entity).despawn();
@@ -218,41 +499,236 @@ pub fn damage_system(
     }
 }
 
+/// Closest a projectile came to a ship over the last `delta_time`
+///
+/// Both moved in a straight line during the tick, so this sweeps the
+/// projectile's path relative to the ship rather than testing only where it
+/// ended up. Returns the miss distance and the projectile's position at that
+/// moment.
+fn closest_approach(
+    projectile: &Transform,
+    ship: &Transform,
+    delta_time: f32,
+) -> (f32, Vector3<f32>) {
+    let end = projectile.position - ship.position;
+    let travel = (projectile.velocity - ship.velocity) * delta_time;
+    let start = end - travel;
+    let fraction = if travel.magnitude_squared() > 0.0 {
+        (-start.dot(&travel) / travel.magnitude_squared()).clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    let impact = projectile.position - projectile.velocity * delta_time * (1.0 - fraction);
+    ((start + travel * fraction).magnitude(), impact)
+}
+
+/// Calculate a hit's damage, rolling status effects on the seeded generator
+/// when the world has one.
+fn resolve_damage(
+    calculator: &WeaponTagCalculator,
+    damage: f32,
+    tags: &[WeaponTag],
+    rng: Option<&mut SimulationRng>,
+) -> Result<DamageResult, String> {
+    match rng {
+        Some(rng) => calculator.roll_damage(damage, tags, &mut rng.0),
+        None => calculator.calculate_damage(damage, tags),
+    }
+}
+
+/// Something a guided missile can be seduced by
+struct Lure {
+    entity: Entity,
+    owner: Entity,
+    position: Vector3<f32>,
+    velocity: Vector3<f32>,
+    range: f32,
+}
+
 /// System that moves projectiles and updates their lifetime.
 ///
 /// This system updates projectile positions and despawns projectiles that
-/// have exceeded their lifetime.
+/// have exceeded their lifetime. Warheads self-destruct when their lifetime
+/// runs out, writing an [`Explosion`].
+///
+/// Guided missiles steer toward where their target will be when they
+/// arrive, turning at up to their turn rate (scaled by the missile
+/// `tracking_accuracy`) and accelerating at their thrust up to their top
+/// speed. A missile passing within range of an enemy decoy or chaff cloud
+/// rolls once per lure to be seduced, with chance `decoy_effectiveness`
+/// reduced by the missile `evasion_difficulty`; a seduced missile chases
+/// the lure instead and detonates on reaching it.
+#[allow(clippy::too_many_arguments)]
 pub fn projectile_system(
     mut commands: Commands,
-    mut query: Query<(Entity, &mut ProjectileComponent)>,
+    mut projectiles: Query<(Entity, &mut ProjectileComponent, &mut Transform)>,
+    bodies: Query<&Transform, Without<ProjectileComponent>>,
+    chaff: Query<(Entity, &ChaffCloud, &Transform), Without<ProjectileComponent>>,
+    params: Res<SimulationParams>,
+    mut explosions: Option<MessageWriter<Explosion>>,
+    mut rng: Option<ResMut<SimulationRng>>,
     delta_time: f32,
 ) {
-    for (entity, mut projectile) in query.iter_mut() {
-        // Skip beam weapons (they don't move)
-        if projectile.projectile_type == ProjectileType::Beam {
-            continue;
-        }
-
-        // Update lifetime
+    let missile_config = &params.combat.weapons.missile;
+    let countermeasures = &params.combat.countermeasures;
+    let tracking_accuracy = missile_config.tracking_accuracy as f32;
+    let lure_chance =
+        countermeasures.decoy_effectiveness * (1.0 - missile_config.evasion_difficulty);
+
+    let mut lures: Vec<Lure> = projectiles
+        .iter()
+        .filter(|(_, projectile, _)| projectile.tags.contains(&WeaponTag::Decoy))
+        .map(|(entity, projectile, transform)| Lure {
+            entity,
+            owner: projectile.owner,
+            position: transform.position,
+            velocity: transform.velocity,
+            range: countermeasures.chaff_range as f32,
+        })
+        .collect();
+    lures.extend(chaff.iter().map(|(entity, cloud, transform)| Lure {
+        entity,
+        owner: cloud.owner,
+        position: transform.position,
+        velocity: Vector3::zeros(),
+        range: cloud.radius,
+    }));
+
+    for (entity, mut projectile, mut transform) in projectiles.iter_mut() {
+        // Update lifetime (beams expire once their firing window ends)
         projectile.update(delta_time);
 
         // Despawn if lifetime expired
         if projectile.is_expired() {
+            if projectile.is_explosive()
+                && let Some(explosions) = explosions.as_mut()
+            {
+                explosions.write(Explosion::from_projectile(&projectile, transform.position));
+            }
             commands.entity(entity).despawn();
             continue;
         }
 
         // Missiles track their target
-        if let ProjectileType::Missile {
-            thrust: _,
-            turn_rate: _,
-        } = projectile.projectile_type
+        let ProjectileType::Missile { thrust, turn_rate } = projectile.projectile_type else {
+            // Position already updated by movement_system
+            continue;
+        };
+
+        // Decoys are not fooled by lures, including each other
+        if projectile.guidance.lure.is_none() && !projectile.tags.contains(&WeaponTag::Decoy) {
+            for lure in &lures {
+                if lure.owner == projectile.owner
+                    || lure.entity == entity
+                    || projectile.guidance.resisted.contains(&lure.entity)
+                    || (lure.position - transform.position).magnitude() > lure.range
+                {
+                    continue;
+                }
+                let seduced = rng
+                    .as_deref_mut()
+                    .is_some_and(|rng| rng.random::<f64>() < lure_chance);
+                if seduced {
+                    projectile.guidance.lure = Some(lure.entity);
+                    projectile.target = None;
+                    break;
+                }
+                projectile.guidance.resisted.push(lure.entity);
+            }
+        }
+
+        let aim = match projectile.guidance.lure {
+            Some(lure) => lures
+                .iter()
+                .find(|candidate| candidate.entity == lure)
+                .map(|lure| (lure.position, lure.velocity)),
+            None => projectile
+                .target
+                .and_then(|target| bodies.get(target).ok())
+                .map(|target| (target.position, target.velocity)),
+        };
+        // A missile that lost what it was chasing flies on until it expires
+        let Some((aim_position, aim_velocity)) = aim else {
+            continue;
+        };
+
+        if projectile.guidance.lure.is_some()
+            && (aim_position - transform.position).magnitude() < LURE_CAPTURE_DISTANCE
         {
-            // See docs/plans/simulation-completeness.md — projectile_system (missile homing)
-            // For now, just maintain current velocity
+            if projectile.is_explosive()
+                && let Some(explosions) = explosions.as_mut()
+            {
+                explosions.write(Explosion::from_projectile(&projectile, transform.position));
+            }
+            commands.entity(entity).despawn();
+            continue;
         }
 
-        // Position already updated by movement_system
+        steer_missile(
+            &mut transform,
+            aim_position,
+            aim_velocity,
+            thrust as f32,
+            (turn_rate as f32).to_radians() * tracking_accuracy,
+            projectile.guidance.max_speed,
+            delta_time,
+        );
+    }
+}
+
+/// Turn a missile toward the point where it will intercept its aim point
+/// and accelerate it toward `max_speed`
+///
+/// The heading turns by at most `max_turn_rate` (radians/second).
+fn steer_missile(
+    transform: &mut Transform,
+    aim_position: Vector3<f32>,
+    aim_velocity: Vector3<f32>,
+    thrust: f32,
+    max_turn_rate: f32,
+    max_speed: f32,
+    delta_time: f32,
+) {
+    let mut speed = transform.velocity.magnitude();
+    let offset = aim_position - transform.position;
+    let time_to_go = offset.magnitude() / speed.max(1.0);
+    let Some(desired) = (offset + aim_velocity * time_to_go).try_normalize(f32::EPSILON) else {
+        return;
+    };
+    let heading = transform
+        .velocity
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(|| transform.rotation * Vector3::z());
+
+    let angle = heading.angle(&desired);
+    let max_angle = max_turn_rate * delta_time;
+    let heading = if angle <= max_angle {
+        desired
+    } else {
+        UnitQuaternion::rotation_between(&heading, &desired)
+            .map_or(heading, |turn| turn.powf(max_angle / angle) * heading)
+    };
+
+    if speed < max_speed {
+        speed = (speed + thrust * delta_time).min(max_speed);
+    }
+    transform.velocity = heading * speed;
+    if let Some(rotation) = UnitQuaternion::rotation_between(&Vector3::z(), &heading) {
+        transform.rotation = rotation;
+    }
+}
+
+/// System that disperses chaff clouds once their duration runs out.
+pub fn chaff_system(
+    mut commands: Commands,
+    mut clouds: Query<(Entity, &mut ChaffCloud)>,
+    delta_time: f32,
+) {
+    for (entity, mut cloud) in clouds.iter_mut() {
+        cloud.remaining -= delta_time;
+        if cloud.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+        }
     }
 }
 
@@ -263,6 +739,8 @@ pub fn beam_weapon_system(
     beams: Query<(&ProjectileComponent, &Transform)>,
     mut ships: Query<(Entity, &mut ShipData, &mut ShieldComponent, &Transform)>,
     mut status_effects: Query<&mut StatusEffects>,
+    mut damaged: Option<MessageWriter<ShipDamaged>>,
+    mut rng: Option<ResMut<SimulationRng>>,
     delta_time: f32,
 ) {
     let calculator = WeaponTagCalculator::new();
@@ -285,8 +763,12 @@ pub fn beam_weapon_system(
 
             if distance < max_range {
                 // Calculate damage (1x per second = base_damage * delta_time)
-                let damage_result =
-                    calculator.calculate_damage(beam.damage * delta_time, &beam.tags);
+                let damage_result = resolve_damage(
+                    &calculator,
+                    beam.damage * delta_time,
+                    &beam.tags,
+                    rng.as_deref_mut(),
+                );
 
                 let damage_result = match damage_result {
                     Ok(dr) => dr,
@@ -294,12 +776,20 @@ pub fn beam_weapon_system(
                 };
 
                 let mut effects = status_effects.get_mut(ship_entity).ok();
-                apply_damage_result(
+                let dealt = apply_damage_result(
                     &mut shield,
                     &mut ship_data,
                     effects.as_deref_mut(),
                     &damage_result,
                 );
+                if let Some(damaged) = damaged.as_mut() {
+                    damaged.write(ShipDamaged {
+                        ship: ship_entity,
+                        damage_type: beam.projectile_type.into(),
+                        amount: dealt,
+                        attacker: beam.owner,
+                    });
+                }
             }
         }
     }
@@ -307,11 +797,72 @@ pub fn beam_weapon_system(
 
 /// System that handles missile and torpedo explosions.
 ///
-/// When missiles or torpedoes hit their target, they create an explosion
-/// that can damage nearby ships.
-pub fn explosion_system() {
-    // See docs/plans/simulation-completeness.md — explosion_system (AoE damage)
-    // For now, single-target damage is handled by damage_system
+/// Applies every [`Explosion`] written this tick: each ship within the blast
+/// radius, the firer's own included, takes the blast damage with linear
+/// falloff (through the warhead's weapon tags), and every missile, torpedo,
+/// or kinetic round inside it is destroyed. Applied explosions stay in the
+/// channel until drained or the next tick's explosions replace them.
+pub fn explosion_system(
+    mut commands: Commands,
+    explosions: Option<ResMut<Messages<Explosion>>>,
+    mut ships: Query<(Entity, &mut ShipData, &mut ShieldComponent, &Transform)>,
+    mut status_effects: Query<&mut StatusEffects>,
+    projectiles: Query<(Entity, &ProjectileComponent, &Transform)>,
+    mut damaged: Option<MessageWriter<ShipDamaged>>,
+    mut rng: Option<ResMut<SimulationRng>>,
+) {
+    let Some(mut explosions) = explosions else {
+        return;
+    };
+    let calculator = WeaponTagCalculator::new();
+    let mut destroyed = Vec::new();
+
+    for explosion in explosions.iter_current_update_messages() {
+        for (ship_entity, mut ship_data, mut shield, ship_transform) in ships.iter_mut() {
+            let distance = (ship_transform.position - explosion.position).magnitude();
+            let amount = explosion.damage_at(distance);
+            if amount <= 0.0 {
+                continue;
+            }
+
+            let Ok(damage_result) =
+                resolve_damage(&calculator, amount, &explosion.tags, rng.as_deref_mut())
+            else {
+                continue;
+            };
+            let mut effects = status_effects.get_mut(ship_entity).ok();
+            let dealt = apply_damage_result(
+                &mut shield,
+                &mut ship_data,
+                effects.as_deref_mut(),
+                &damage_result,
+            );
+            if let Some(damaged) = damaged.as_mut() {
+                damaged.write(ShipDamaged {
+                    ship: ship_entity,
+                    damage_type: explosion.damage_type,
+                    amount: dealt,
+                    attacker: explosion.owner,
+                });
+            }
+        }
+
+        for (projectile_entity, projectile, proj_transform) in projectiles.iter() {
+            if projectile.projectile_type == ProjectileType::Beam
+                || destroyed.contains(&projectile_entity)
+            {
+                continue;
+            }
+            if (proj_transform.position - explosion.position).magnitude() < explosion.radius {
+                destroyed.push(projectile_entity);
+            }
+        }
+    }
+
+    for projectile_entity in destroyed {
+        commands.entity(projectile_entity).despawn();
+    }
+    explosions.update();
 }
 
 /// System that applies and decays status effects.
@@ -326,70 +877,501 @@ pub fn status_effect_system(mut query: Query<&mut StatusEffects>, delta_time: f3
 
 /// System that handles anti-missile countermeasures.
 ///
-/// This system allows point-defense weapons to intercept incoming missiles
-/// and torpedoes.
+/// Each ready point-defense weapon engages the nearest hostile missile or
+/// torpedo within its range, ignoring ordnance fired by its own team or
+/// faction. Every engagement spends an interceptor round and starts the
+/// weapon's cooldown, and destroys the ordnance with chance equal to the
+/// weapon's tracking accuracy times `antimissile_effectiveness` (or
+/// `antitorpedo_effectiveness` against torpedoes). Each engagement writes a
+/// [`PointDefenseEngaged`] message.
+#[allow(clippy::type_complexity)]
 pub fn countermeasure_system(
     mut commands: Commands,
-    countermeasures: Query<(&WeaponComponent, &Transform), With<PointDefenseMarker>>,
+    mut countermeasures: Query<
+        (&mut WeaponComponent, &Transform, &ChildOf),
+        With<PointDefenseMarker>,
+    >,
+    ships: Query<&ShipData>,
     projectiles: Query<(Entity, &ProjectileComponent, &Transform)>,
+    params: Res<SimulationParams>,
+    mut engagements: Option<MessageWriter<PointDefenseEngaged>>,
+    mut rng: Option<ResMut<SimulationRng>>,
 ) {
-    // See docs/plans/simulation-completeness.md — countermeasure_system
-    // (owner filtering, accuracy, configurable range, ammunition)
+    let config = &params.combat.countermeasures;
+    let mut destroyed: Vec<Entity> = Vec::new();
 
-    for (weapon, weapon_transform) in countermeasures.iter() {
-        // Check for nearby enemy missiles/torpedoes
-        for (projectile_entity, projectile, proj_transform) in projectiles.iter() {
-            // Owner filtering pending parent-child relationships
+    for (mut weapon, weapon_transform, child_of) in countermeasures.iter_mut() {
+        if !weapon.can_fire() {
+            continue;
+        }
+        let defender = child_of.parent();
+        let Ok(defender_data) = ships.get(defender) else {
+            continue;
+        };
 
-            // Skip if not a missile or torpedo
-            let is_missile_or_torpedo = matches!(
-                projectile.projectile_type,
-                ProjectileType::Missile { .. } | ProjectileType::Torpedo
-            );
+        // Engage the nearest hostile missile or torpedo in range
+        let threat = projectiles
+            .iter()
+            .filter(|(entity, projectile, _)| {
+                matches!(
+                    projectile.projectile_type,
+                    ProjectileType::Missile { .. } | ProjectileType::Torpedo
+                ) && !destroyed.contains(entity)
+                    && projectile.owner != defender
+                    && !ships
+                        .get(projectile.owner)
+                        .is_ok_and(|attacker| attacker.is_friendly(defender_data))
+            })
+            .map(|(entity, projectile, transform)| {
+                let distance = (transform.position - weapon_transform.position).magnitude();
+                (entity, projectile, distance)
+            })
+            .filter(|(_, _, distance)| weapon.in_range(*distance))
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        let Some((threat, projectile, _)) = threat else {
+            continue;
+        };
 
-            if !is_missile_or_torpedo {
+        let effectiveness = match projectile.projectile_type {
+            ProjectileType::Torpedo => config.antitorpedo_effectiveness,
+            _ => config.antimissile_effectiveness,
+        } as f32;
+        let success = rng
+            .as_deref_mut()
+            .is_none_or(|rng| rng.random::<f32>() < weapon.accuracy * effectiveness);
+        weapon.fire();
+
+        if success {
+            destroyed.push(threat);
+            commands.entity(threat).despawn();
+        }
+        if let Some(engagements) = engagements.as_mut() {
+            engagements.write(PointDefenseEngaged {
+                ship: defender,
+                weapon_id: weapon.id.clone(),
+                attacker: projectile.owner,
+                damage_type: projectile.projectile_type.into(),
+                success,
+            });
+        }
+    }
+}
+
+/// Ships that damage-control teams work on
+pub type RepairShip = (
+    Entity,
+    &'static mut RepairComponent,
+    &'static mut ModuleStateTracker,
+    Option<&'static mut PowerGrid>,
+    Has<Docked>,
+);
+
+/// System that handles engineering repairs to damaged modules.
+///
+/// Each of a ship's damage-control teams works on one queued module at a
+/// time, restoring 10% of its maximum health every
+/// `repair.module_repair_time` seconds, capped at `repair.repair_rate`
+/// health per second. Every point of health restored draws
+/// `repair.power_cost` energy from the ship's reserve; teams slow down when
+/// the reserve runs short. Destroyed modules are skipped until the ship is
+/// docked, and fully repaired modules leave the queue. A
+/// [`ModuleStatusChanged`] message is written each time a module's health
+/// crosses a 10% step, it comes back online, or its repair completes.
+pub fn repair_system(
+    mut ships: Query<RepairShip>,
+    params: Res<SimulationParams>,
+    mut status_changes: Option<MessageWriter<ModuleStatusChanged>>,
+    delta_time: f32,
+) {
+    let repair = &params.systems.repair;
+    let power_cost = repair.power_cost as f32;
+    for (ship, mut crew, mut tracker, mut power_grid, docked) in ships.iter_mut() {
+        let crew = &mut *crew;
+        crew.queue.retain(|module_id| {
+            tracker
+                .get(module_id)
+                .is_some_and(|state| state.current_health < state.max_health)
+        });
+
+        let mut idle_teams = crew.teams;
+        for module_id in &crew.queue {
+            if idle_teams == 0 {
+                break;
+            }
+            let Some(state) = tracker.get_mut(module_id) else {
+                continue;
+            };
+            if state.is_destroyed() && !docked {
                 continue;
             }
+            idle_teams -= 1;
 
-            // Check if in range
-            let distance = (proj_transform.position - weapon_transform.position).magnitude();
-            let pd_range = 1000.0;
+            let rate = if repair.module_repair_time > 0.0 {
+                (0.1 * state.max_health / repair.module_repair_time as f32)
+                    .min(repair.repair_rate as f32)
+            } else {
+                repair.repair_rate as f32
+            };
+            let mut amount = (rate * delta_time).min(state.max_health - state.current_health);
+            if let Some(power_grid) = power_grid.as_mut()
+                && power_cost > 0.0
+            {
+                amount = amount.min(power_grid.stored / power_cost);
+                power_grid.stored -= amount * power_cost;
+            }
+            if amount <= 0.0 {
+                continue;
+            }
 
-            if distance < pd_range && weapon.can_fire() {
-                // Always-hit interception; accuracy roll pending
-                commands.entity(projectile_entity).despawn();
-                break; // One missile per weapon per tick
+            let step_before = (state.health_percentage() * 10.0).floor();
+            let was_operational = state.operational;
+            state.repair(amount);
+            let complete = state.current_health >= state.max_health;
+            if (complete
+                || was_operational != state.operational
+                || (state.health_percentage() * 10.0).floor() != step_before)
+                && let Some(status_changes) = status_changes.as_mut()
+            {
+                status_changes.write(ModuleStatusChanged {
+                    ship,
+                    module_id: state.instance_id.clone(),
+                    health_percent: state.health_percentage() * 100.0,
+                    operational: state.operational,
+                    overheated: state.overheated,
+                });
             }
         }
+
+        crew.queue.retain(|module_id| {
+            tracker
+                .get(module_id)
+                .is_some_and(|state| state.current_health < state.max_health)
+        });
     }
 }
 
-/// System that handles engineering repairs to damaged modules.
+/// System that maintains each ship's sensor contacts.
 ///
-/// This system allows crew to repair damaged modules over time.
-pub fn repair_system() {
-    // See docs/plans/simulation-completeness.md — repair_system (crew-driven repairs)
+/// A ship's detection range is the `scan_range` of its best sensor array
+/// scaled by that array's efficiency (power, damage, and heat). Another ship
+/// is detected while it is within that range shrunk by its stealth
+/// `detectability_reduction`. Each contact gained or lost writes a
+/// [`ContactChanged`] message.
+#[allow(clippy::type_complexity)]
+pub fn sensor_system(
+    mut sensors: Query<(
+        Entity,
+        &mut SensorComponent,
+        &ModuleStateTracker,
+        &Transform,
+    )>,
+    targets: Query<(
+        Entity,
+        &ShipData,
+        &Transform,
+        Option<&StealthComponent>,
+        Option<&ModuleStateTracker>,
+    )>,
+    mut changes: Option<MessageWriter<ContactChanged>>,
+) {
+    for (entity, mut sensor, modules, transform) in sensors.iter_mut() {
+        sensor.range = sensor.effective_range(modules);
+
+        let mut detected = BTreeMap::new();
+        for (target, ship_data, target_transform, stealth, target_modules) in targets.iter() {
+            if target == entity {
+                continue;
+            }
+            let reduction = stealth.map_or(0.0, |stealth| stealth.reduction(target_modules));
+            let distance = (target_transform.position - transform.position).magnitude();
+            if distance <= sensor.range * (1.0 - reduction) {
+                detected.insert(target, ship_data.id.clone());
+            }
+        }
+
+        if let Some(changes) = changes.as_mut() {
+            for (target, contact_id) in &detected {
+                if !sensor.contacts.contains_key(target) {
+                    changes.write(ContactChanged {
+                        ship: entity,
+                        contact_id: contact_id.clone(),
+                        detected: true,
+                    });
+                }
+            }
+            for (target, contact_id) in &sensor.contacts {
+                if !detected.contains_key(target) {
+                    changes.write(ContactChanged {
+                        ship: entity,
+                        contact_id: contact_id.clone(),
+                        detected: false,
+                    });
+                }
+            }
+        }
+        sensor.contacts = detected;
+    }
 }
 
 /// System that handles science officer scans.
 ///
-/// This system processes scan requests and reveals information about
-/// target ships.
-pub fn scanning_system() {
-    // See docs/plans/simulation-completeness.md — scanning_system
+/// A scan reveals one detail tier of its target every `tier_time` seconds,
+/// writing a [`ScanTierRevealed`] message for each, and ends once every tier
+/// its sensors can resolve is revealed. Scans pause while the scanning ship
+/// is Ion-jammed or has lost the target from its sensors.
+pub fn scanning_system(
+    mut commands: Commands,
+    mut scanners: Query<(
+        Entity,
+        &mut ScanProgress,
+        &SensorComponent,
+        Option<&StatusEffects>,
+    )>,
+    mut revealed: Option<MessageWriter<ScanTierRevealed>>,
+    delta_time: f32,
+) {
+    for (entity, mut scan, sensor, effects) in scanners.iter_mut() {
+        let jammed = effects.is_some_and(|effects| effects.has_effect(StatusEffectType::IonJam));
+        if jammed || !sensor.has_contact(scan.target) {
+            continue;
+        }
+
+        scan.elapsed += delta_time;
+        while !scan.is_complete() && scan.elapsed >= scan.tier_time * (scan.revealed + 1) as f32 {
+            let tier = ScanTier::ALL[scan.revealed as usize];
+            scan.revealed += 1;
+            if let Some(revealed) = revealed.as_mut() {
+                revealed.write(ScanTierRevealed {
+                    ship: entity,
+                    target_id: scan.target_id.clone(),
+                    tier,
+                    complete: scan.is_complete(),
+                });
+            }
+        }
+
+        if scan.is_complete() {
+            commands.entity(entity).remove::<ScanProgress>();
+        }
+    }
+}
+
+/// A ship or relay station in the comms network
+struct CommsNode {
+    entity: Option<Entity>,
+    id: String,
+    team_id: String,
+    faction: String,
+    position: Vector3<f32>,
+    /// Transmission range this tick; 0 while jammed or without working comms
+    range: f32,
+    encryption_lvl: u8,
+}
+
+impl CommsNode {
+    fn linked(&self, other: &CommsNode) -> bool {
+        self.range > 0.0
+            && other.range > 0.0
+            && (other.position - self.position).magnitude() <= self.range.min(other.range)
+    }
+
+    /// Same team, or same non-empty faction, as `other`
+    fn is_friendly(&self, other: &CommsNode) -> bool {
+        (!self.team_id.is_empty() && self.team_id == other.team_id)
+            || (!self.faction.is_empty() && self.faction == other.faction)
+    }
 }
 
+/// Components read and written by [`communication_system`]
+pub type CommsShip = (
+    Entity,
+    &'static mut CommunicationState,
+    &'static mut CommsComponent,
+    &'static ShipData,
+    &'static Transform,
+    Option<&'static StatusEffects>,
+    Option<&'static ModuleStateTracker>,
+);
+
 /// System that handles ship-to-ship communication.
 ///
-/// Communication is blocked when a ship is jammed by Ion weapons.
-pub fn communication_system() {
-    // See docs/plans/simulation-completeness.md — communication_system
-    // For now, CommunicationState::can_communicate() provides the jam check
+/// Ion jamming sets each ship's `CommunicationState`; jammed ships can
+/// neither transmit nor receive. Every queued transmission then spreads from
+/// its sender to every node within range, where two nodes are in range when
+/// each is inside the other's `comm_range`. Ships friendly to the sender, and
+/// stations of its faction, relay it onward for up to `max_relay_hops`
+/// relays. The addressee reads a directed message; any other ship that picks
+/// it up and is not friendly to the sender is an eavesdropper, and receives
+/// garbled text unless its `encryption_lvl` matches the sender's. Broadcasts
+/// are sent in the clear to every ship they reach.
+pub fn communication_system(
+    mut ships: Query<CommsShip>,
+    relays: Option<Res<CommRelays>>,
+    params: Res<SimulationParams>,
+    mut received: Option<MessageWriter<MessageReceived>>,
+    mut undelivered: Option<MessageWriter<MessageUndelivered>>,
+    delta_time: f32,
+) {
+    let mut nodes = Vec::new();
+    let mut outgoing = Vec::new();
+    for (entity, mut state, mut comms, ship_data, transform, effects, modules) in ships.iter_mut() {
+        if let Some(remaining) = effects.and_then(|e| e.get_duration(StatusEffectType::IonJam)) {
+            state.jam(remaining);
+        }
+        state.update(delta_time);
+
+        if !comms.outbox.is_empty() {
+            outgoing.push((nodes.len(), std::mem::take(&mut comms.outbox)));
+        }
+        nodes.push(CommsNode {
+            entity: Some(entity),
+            id: ship_data.id.clone(),
+            team_id: ship_data.team_id.clone(),
+            faction: ship_data.faction.clone(),
+            position: transform.position,
+            range: if state.can_communicate() {
+                comms.effective_range(modules)
+            } else {
+                0.0
+            },
+            encryption_lvl: comms.encryption_lvl(modules),
+        });
+    }
+    for station in relays.iter().flat_map(|relays| &relays.stations) {
+        nodes.push(CommsNode {
+            entity: None,
+            id: station.id.clone(),
+            team_id: String::new(),
+            faction: station.faction.clone(),
+            position: station.position,
+            range: params.comms.station_relay_range as f32,
+            encryption_lvl: 0,
+        });
+    }
+
+    for (sender, transmissions) in outgoing {
+        let parents = propagate(&nodes, sender, params.comms.max_relay_hops);
+        let sender_entity = nodes[sender].entity.expect("senders are ships");
+
+        for transmission in transmissions {
+            let addressee = transmission.recipient_id.as_ref().and_then(|recipient_id| {
+                nodes
+                    .iter()
+                    .position(|node| node.entity.is_some() && &node.id == recipient_id)
+            });
+            let delivered = match addressee {
+                Some(addressee) => parents[addressee].is_some(),
+                None => transmission.recipient_id.is_none() && nodes[sender].range > 0.0,
+            };
+            if !delivered && let Some(undelivered) = undelivered.as_mut() {
+                undelivered.write(MessageUndelivered {
+                    message_id: transmission.id.clone(),
+                    sender: sender_entity,
+                    recipient_id: transmission.recipient_id.clone(),
+                });
+            }
+
+            for (index, node) in nodes.iter().enumerate() {
+                let Some(receiver) = node.entity else {
+                    continue;
+                };
+                if index == sender || parents[index].is_none() {
+                    continue;
+                }
+                let eavesdropping = addressee.is_some_and(|addressee| addressee != index)
+                    && !node.is_friendly(&nodes[sender]);
+                if addressee.is_some_and(|addressee| addressee != index) && !eavesdropping {
+                    continue;
+                }
+
+                let garbled = eavesdropping && node.encryption_lvl < nodes[sender].encryption_lvl;
+                if let Some(received) = received.as_mut() {
+                    received.write(MessageReceived {
+                        message_id: transmission.id.clone(),
+                        sender: sender_entity,
+                        receiver,
+                        recipient_id: transmission.recipient_id.clone(),
+                        text: if garbled {
+                            garble(&transmission.text)
+                        } else {
+                            transmission.text.clone()
+                        },
+                        in_response_to: transmission.in_response_to.clone(),
+                        garbled,
+                        relays: relay_path(&nodes, &parents, sender, index),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Spread a transmission outward from `sender` through the comms network
+///
+/// Returns, for each node, the node it heard the message from (the sender
+/// hears it from itself), or `None` if the message never reaches it. Only
+/// nodes friendly to the sender pass the message on.
+fn propagate(nodes: &[CommsNode], sender: usize, max_relay_hops: u32) -> Vec<Option<usize>> {
+    let mut parents = vec![None; nodes.len()];
+    let mut hops = vec![0u32; nodes.len()];
+    parents[sender] = Some(sender);
+
+    let mut queue = std::collections::VecDeque::from([sender]);
+    while let Some(current) = queue.pop_front() {
+        if current != sender
+            && (hops[current] > max_relay_hops || !nodes[current].is_friendly(&nodes[sender]))
+        {
+            continue;
+        }
+        for (next, node) in nodes.iter().enumerate() {
+            if parents[next].is_none() && nodes[current].linked(node) {
+                parents[next] = Some(current);
+                hops[next] = hops[current] + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+    parents
+}
+
+/// IDs of the relays between the sender and `receiver`, in order
+fn relay_path(
+    nodes: &[CommsNode],
+    parents: &[Option<usize>],
+    sender: usize,
+    receiver: usize,
+) -> Vec<String> {
+    let mut path = Vec::new();
+    let mut current = parents[receiver];
+    while let Some(node) = current.filter(|node| *node != sender) {
+        path.push(nodes[node].id.clone());
+        current = parents[node];
+    }
+    path.reverse();
+    path
+}
+
+/// Scramble a message for a receiver that cannot decrypt it, keeping its
+/// length and word breaks
+fn garble(text: &str) -> String {
+    const NOISE: &[u8] = b"#%&*@$!?";
+    text.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            if c.is_whitespace() {
+                c
+            } else {
+                NOISE[(c as usize + i) % NOISE.len()] as char
+            }
+        })
+        .collect()
 }
 
 /// System that handles warp drive acceleration.
 ///
 /// Warp drives provide continuous acceleration but are disabled by Tachyon weapons.
+/// A drive armed with a destination steers toward it and drops out of warp on
+/// arrival.
 pub fn warp_system(
     mut query: Query<(&mut WarpDriveComponent, &mut Transform, &StatusEffects)>,
     delta_time: f32,
@@ -399,6 +1381,7 @@ pub fn warp_system(
         if effects.has_effect(StatusEffectType::TachyonWarpBlock) {
             warp.timer.disabled = true;
             warp.active = false;
+            warp.destination = None;
             continue;
         }
         warp.timer.disabled = false;
@@ -420,7 +1403,20 @@ pub fn warp_system(
         if warp.active {
             // Warp increases speed based on warp factor
             let speed_multiplier = warp.current_warp_factor;
-            if transform.velocity.magnitude() > 0.1 {
+            if let Some(destination) = warp.destination {
+                let offset = destination - transform.position;
+                let speed = speed_multiplier * 100.0;
+                if offset.magnitude() <= speed * delta_time {
+                    // Drop out of warp at the destination
+                    transform.position = destination;
+                    transform.velocity = Vector3::zeros();
+                    warp.active = false;
+                    warp.destination = None;
+                    warp.timer.trigger_cooldown();
+                } else {
+                    transform.velocity = offset.normalize() * speed;
+                }
+            } else if transform.velocity.magnitude() > 0.1 {
                 let direction = transform.velocity.normalize();
                 transform.velocity = direction * speed_multiplier * 100.0; // Base speed scaled by warp
             }
@@ -477,6 +1473,111 @@ mod tests {
     use super::*;
     use bevy_ecs::system::RunSystemOnce;
 
+    /// Run `projectile_system` for one second
+    #[allow(clippy::too_many_arguments)]
+    fn run_projectile_system(
+        commands: Commands,
+        projectiles: Query<(Entity, &mut ProjectileComponent, &mut Transform)>,
+        bodies: Query<&Transform, Without<ProjectileComponent>>,
+        chaff: Query<(Entity, &ChaffCloud, &Transform), Without<ProjectileComponent>>,
+        params: Res<SimulationParams>,
+        explosions: Option<MessageWriter<Explosion>>,
+        rng: Option<ResMut<SimulationRng>>,
+    ) {
+        projectile_system(
+            commands,
+            projectiles,
+            bodies,
+            chaff,
+            params,
+            explosions,
+            rng,
+            1.0,
+        );
+    }
+
+    /// Run `damage_system` over a one-second tick
+    fn run_damage_system(
+        commands: Commands,
+        projectiles: Query<(Entity, &ProjectileComponent, &Transform)>,
+        ships: Query<(Entity, &mut ShipData, &mut ShieldComponent, &Transform)>,
+        status_effects: Query<&mut StatusEffects>,
+        damaged: Option<MessageWriter<ShipDamaged>>,
+        explosions: Option<MessageWriter<Explosion>>,
+        rng: Option<ResMut<SimulationRng>>,
+    ) {
+        damage_system(
+            commands,
+            projectiles,
+            ships,
+            status_effects,
+            damaged,
+            explosions,
+            rng,
+            1.0,
+        );
+    }
+
+    #[test]
+    fn test_intercept_point_leads_a_moving_target() {
+        let mut target = Transform::at_position(Vector3::new(1000.0, 0.0, 0.0));
+        target.velocity = Vector3::new(0.0, 100.0, 0.0);
+
+        let aim = intercept_point(Vector3::zeros(), &target, 1000.0);
+        let time = aim.magnitude() / 1000.0;
+        assert!((aim - (target.position + target.velocity * time)).magnitude() < 0.01);
+        assert!(aim.y > 0.0);
+
+        // A target that outruns the round is aimed at where it is
+        target.velocity = Vector3::new(2000.0, 0.0, 0.0);
+        assert_eq!(
+            intercept_point(Vector3::zeros(), &target, 1000.0),
+            target.position
+        );
+    }
+
+    #[test]
+    fn test_fast_round_cannot_pass_through_its_target() {
+        let mut world = World::new();
+        let target = world
+            .spawn((
+                ShipData::new(
+                    "target".to_string(),
+                    "Target".to_string(),
+                    "cruiser".to_string(),
+                    "team1".to_string(),
+                    1000.0,
+                    0.0,
+                    100.0,
+                ),
+                ShieldComponent::new(0.0, 0.0, 0.0),
+                Transform::new(),
+            ))
+            .id();
+        let round = ProjectileComponent {
+            projectile_type: ProjectileType::Kinetic,
+            owner: Entity::from_raw_u32(1000).unwrap(),
+            target: Some(target),
+            damage: 100.0,
+            tags: vec![WeaponTag::SingleFire],
+            lifetime: 1.0,
+            accuracy: 1.0,
+            blast_radius: 0.0,
+            blast_damage: 0.0,
+            guidance: Default::default(),
+        };
+
+        // Flew from 40 m short of the ship to 40 m past it during the tick
+        let mut transform = Transform::at_position(Vector3::new(40.0, 0.0, 0.0));
+        transform.velocity = Vector3::new(80.0, 0.0, 0.0);
+        let round = world.spawn((round, transform)).id();
+
+        let _ = world.run_system_once(run_damage_system);
+
+        assert!(world.get_entity(round).is_err());
+        assert!(world.get::<ShipData>(target).unwrap().hull < 1000.0);
+    }
+
     #[test]
     fn test_movement_system() {
         let mut world = World::new();
@@ -637,6 +1738,44 @@ mod tests {
         assert!(warp.timer.disabled);
     }
 
+    #[test]
+    fn test_warp_system_arrives_at_destination() {
+        let mut world = World::new();
+
+        let mut warp = WarpDriveComponent::new(2.0, 5.0, 10.0);
+        warp.active = true;
+        warp.current_warp_factor = 2.0;
+        warp.destination = Some(Vector3::new(300.0, 0.0, 0.0));
+
+        let ship = world
+            .spawn((warp, Transform::new(), StatusEffects::new()))
+            .id();
+
+        let run = |world: &mut World| {
+            let _ = world.run_system_once(
+                |query: Query<(&mut WarpDriveComponent, &mut Transform, &StatusEffects)>| {
+                    warp_system(query, 1.0);
+                },
+            );
+        };
+
+        // Steers toward the destination at warp speed
+        run(&mut world);
+        let transform = world.get::<Transform>(ship).unwrap();
+        assert_eq!(transform.velocity, Vector3::new(200.0, 0.0, 0.0));
+
+        // Drops out of warp once the destination is within one tick
+        world.get_mut::<Transform>(ship).unwrap().position = Vector3::new(150.0, 0.0, 0.0);
+        run(&mut world);
+        let transform = world.get::<Transform>(ship).unwrap();
+        assert_eq!(transform.position, Vector3::new(300.0, 0.0, 0.0));
+        assert_eq!(transform.velocity, Vector3::zeros());
+        let warp = world.get::<WarpDriveComponent>(ship).unwrap();
+        assert!(!warp.active);
+        assert!(warp.destination.is_none());
+        assert!(warp.timer.cooldown_progress > 0.0);
+    }
+
     #[test]
     fn test_jump_system_disabled_by_tachyon() {
         let mut world = World::new();
@@ -672,6 +1811,39 @@ mod tests {
         assert!(jump.timer.disabled);
     }
 
+    #[test]
+    fn apply_damage_result_reports_shield_and_hull_damage() {
+        let mut ship_data = ShipData::new(
+            "target".to_string(),
+            "Target".to_string(),
+            "cruiser".to_string(),
+            "team1".to_string(),
+            1000.0,
+            200.0,
+            100.0,
+        );
+        let mut shield = ShieldComponent::new(50.0, 0.0, 5.0);
+        let hit = DamageResult {
+            hull_damage: 80.0,
+            shield_damage: 80.0,
+            shield_bypass: 0.0,
+            projectile_count: 1,
+            is_continuous: false,
+            status_effect: None,
+        };
+
+        // 50 absorbed by the shields, 30 through to the hull
+        let dealt = apply_damage_result(&mut shield, &mut ship_data, None, &hit);
+        assert_eq!(dealt, 80.0);
+        assert_eq!(shield.strength, 0.0);
+        assert_eq!(ship_data.hull, 970.0);
+
+        // With the shields down it all reaches the hull
+        let dealt = apply_damage_result(&mut shield, &mut ship_data, None, &hit);
+        assert_eq!(dealt, 80.0);
+        assert_eq!(ship_data.hull, 890.0);
+    }
+
     #[test]
     fn damage_and_beam_systems_share_application_logic() {
         // Both damage_system and beam_weapon_system route through
@@ -716,6 +1888,10 @@ mod tests {
                         damage,
                         tags: vec![WeaponTag::Ion],
                         lifetime: 1.0,
+                        accuracy: 1.0,
+                        blast_radius: 0.0,
+                        blast_damage: 0.0,
+                        guidance: Default::default(),
                     },
                     Transform {
                         position: Vector3::zeros(),
@@ -732,12 +1908,12 @@ mod tests {
         spawn_projectile(&mut world, ProjectileType::Kinetic, kinetic_target, 500.0);
         spawn_projectile(&mut world, ProjectileType::Beam, beam_target, 500.0);
 
-        let _ = world.run_system_once(damage_system);
+        let _ = world.run_system_once(run_damage_system);
         let _ = world.run_system_once(
             |beams: Query<(&ProjectileComponent, &Transform)>,
              ships: Query<(Entity, &mut ShipData, &mut ShieldComponent, &Transform)>,
              effects: Query<&mut StatusEffects>| {
-                beam_weapon_system(beams, ships, effects, 1.0);
+                beam_weapon_system(beams, ships, effects, None, None, 1.0);
             },
         );
 
@@ -768,9 +1944,46 @@ mod tests {
         assert!(k_ion);
     }
 
+    #[test]
+    fn test_damage_system_rolls_accuracy() {
+        let mut world = World::new();
+        world.insert_resource(SimulationRng::for_tick(1, 1));
+        let target = world
+            .spawn((
+                ShipData::new(
+                    "target".to_string(),
+                    "Target".to_string(),
+                    "cruiser".to_string(),
+                    "team1".to_string(),
+                    1000.0,
+                    0.0,
+                    100.0,
+                ),
+                ShieldComponent::new(0.0, 0.0, 0.0),
+                Transform::new(),
+            ))
+            .id();
+        let mut round = ProjectileComponent::kinetic(
+            Entity::from_raw_u32(1000).unwrap(),
+            100.0,
+            vec![WeaponTag::SingleFire],
+            1.0,
+        );
+        round.target = Some(target);
+        round.accuracy = 0.0;
+        let round = world.spawn((round, Transform::new())).id();
+
+        let _ = world.run_system_once(run_damage_system);
+
+        // The round missed: it is spent, and the hull is untouched
+        assert!(world.get_entity(round).is_err());
+        assert_eq!(world.get::<ShipData>(target).unwrap().hull, 1000.0);
+    }
+
     #[test]
     fn test_projectile_system_lifetime() {
         let mut world = World::new();
+        world.insert_resource(SimulationParams::default());
 
         let projectile = ProjectileComponent::kinetic(
             Entity::from_raw_u32(0).unwrap(),
@@ -791,11 +2004,7 @@ mod tests {
             ))
             .id();
 
-        let _ = world.run_system_once(
-            |commands: Commands, query: Query<(Entity, &mut ProjectileComponent)>| {
-                projectile_system(commands, query, 1.0);
-            },
-        );
+        let _ = world.run_system_once(run_projectile_system);
 
         // Projectile should be despawned
         assert!(
@@ -804,4 +2013,405 @@ mod tests {
                 .is_none()
         );
     }
+
+    #[test]
+    fn test_expired_warhead_self_destructs() {
+        let mut world = World::new();
+        crate::simulation::register_messages(&mut world);
+        world.insert_resource(SimulationParams::default());
+
+        let mut missile = ProjectileComponent::kinetic(
+            Entity::from_raw_u32(1000).unwrap(),
+            10.0,
+            vec![WeaponTag::Missile],
+            0.5,
+        );
+        missile.blast_radius = 50.0;
+        missile.blast_damage = 30.0;
+        let mut transform = Transform::new();
+        transform.position = Vector3::new(0.0, 0.0, 700.0);
+        world.spawn((missile, transform));
+
+        let _ = world.run_system_once(run_projectile_system);
+
+        let explosions = crate::simulation::drain_messages::<Explosion>(&mut world);
+        assert_eq!(explosions.len(), 1);
+        assert_eq!(explosions[0].position, Vector3::new(0.0, 0.0, 700.0));
+        assert_eq!(explosions[0].radius, 50.0);
+    }
+
+    fn guided_missile(owner: Entity, target: Entity) -> ProjectileComponent {
+        let mut missile = ProjectileComponent::missile(
+            owner,
+            target,
+            50.0,
+            vec![WeaponTag::Missile],
+            50,
+            30,
+            30.0,
+        );
+        missile.guidance.max_speed = 200.0;
+        missile
+    }
+
+    #[test]
+    fn test_missile_turns_toward_target_within_turn_rate() {
+        let mut world = World::new();
+        world.insert_resource(SimulationParams::default());
+        let owner = world.spawn(Transform::new()).id();
+        let target = world
+            .spawn(Transform::at_position(Vector3::new(1000.0, 0.0, 0.0)))
+            .id();
+
+        let mut transform = Transform::new();
+        transform.velocity = Vector3::new(0.0, 0.0, 100.0);
+        let missile = world.spawn((guided_missile(owner, target), transform)).id();
+
+        let _ = world.run_system_once(run_projectile_system);
+
+        // Turned 30 deg/s scaled by tracking accuracy, and sped up by thrust
+        let velocity = world.get::<Transform>(missile).unwrap().velocity;
+        let accuracy = SimulationParams::default()
+            .combat
+            .weapons
+            .missile
+            .tracking_accuracy as f32;
+        let turned = Vector3::z().angle(&velocity).to_degrees();
+        assert!((turned - 30.0 * accuracy).abs() < 0.01);
+        assert!(velocity.x > 0.0);
+        assert!((velocity.magnitude() - 150.0).abs() < 0.01);
+
+        // Top speed caps acceleration
+        for _ in 0..5 {
+            let _ = world.run_system_once(run_projectile_system);
+        }
+        let velocity = world.get::<Transform>(missile).unwrap().velocity;
+        assert!((velocity.magnitude() - 200.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_enemy_chaff_seduces_missile() {
+        let mut world = World::new();
+        let mut params = SimulationParams::default();
+        params.0.combat.countermeasures.decoy_effectiveness = 1.0;
+        params.0.combat.weapons.missile.evasion_difficulty = 0.0;
+        world.insert_resource(params);
+        world.insert_resource(SimulationRng::for_tick(7, 0));
+
+        let owner = world.spawn(Transform::new()).id();
+        let target = world
+            .spawn(Transform::at_position(Vector3::new(0.0, 0.0, 5000.0)))
+            .id();
+        let cloud = world
+            .spawn((
+                ChaffCloud {
+                    owner: target,
+                    radius: 500.0,
+                    remaining: 5.0,
+                },
+                Transform::at_position(Vector3::new(0.0, 0.0, 200.0)),
+            ))
+            .id();
+        // Chaff deployed by the launching ship is ignored
+        world.spawn((
+            ChaffCloud {
+                owner,
+                radius: 500.0,
+                remaining: 5.0,
+            },
+            Transform::at_position(Vector3::new(0.0, 100.0, 0.0)),
+        ));
+
+        let mut transform = Transform::new();
+        transform.velocity = Vector3::new(0.0, 0.0, 100.0);
+        let missile = world.spawn((guided_missile(owner, target), transform)).id();
+
+        let _ = world.run_system_once(run_projectile_system);
+
+        let projectile = world.get::<ProjectileComponent>(missile).unwrap();
+        assert_eq!(projectile.guidance.lure, Some(cloud));
+        assert_eq!(projectile.target, None);
+
+        // Reaching the lure detonates the missile
+        world.get_mut::<Transform>(missile).unwrap().position = Vector3::new(0.0, 0.0, 195.0);
+        let _ = world.run_system_once(run_projectile_system);
+        assert!(world.get_entity(missile).is_err());
+    }
+
+    #[test]
+    fn test_missile_resisting_decoy_keeps_target() {
+        let mut world = World::new();
+        let mut params = SimulationParams::default();
+        params.0.combat.weapons.missile.evasion_difficulty = 1.0;
+        world.insert_resource(params);
+        world.insert_resource(SimulationRng::for_tick(7, 0));
+
+        let owner = world.spawn(Transform::new()).id();
+        let target = world
+            .spawn(Transform::at_position(Vector3::new(0.0, 0.0, 5000.0)))
+            .id();
+        let decoy = ProjectileComponent::kinetic(target, 0.0, vec![WeaponTag::Decoy], 10.0);
+        let decoy = world
+            .spawn((decoy, Transform::at_position(Vector3::new(0.0, 0.0, 300.0))))
+            .id();
+
+        let mut transform = Transform::new();
+        transform.velocity = Vector3::new(0.0, 0.0, 100.0);
+        let missile = world.spawn((guided_missile(owner, target), transform)).id();
+
+        let _ = world.run_system_once(run_projectile_system);
+
+        let projectile = world.get::<ProjectileComponent>(missile).unwrap();
+        assert_eq!(projectile.target, Some(target));
+        assert_eq!(projectile.guidance.lure, None);
+        assert_eq!(projectile.guidance.resisted, vec![decoy]);
+    }
+
+    #[test]
+    fn test_chaff_disperses() {
+        let mut world = World::new();
+        let cloud = world
+            .spawn(ChaffCloud {
+                owner: Entity::from_raw_u32(1).unwrap(),
+                radius: 1000.0,
+                remaining: 1.5,
+            })
+            .id();
+
+        let tick = |commands: Commands, clouds: Query<(Entity, &mut ChaffCloud)>| {
+            chaff_system(commands, clouds, 1.0);
+        };
+        let _ = world.run_system_once(tick);
+        assert!(world.get_entity(cloud).is_ok());
+        let _ = world.run_system_once(tick);
+        assert!(world.get_entity(cloud).is_err());
+    }
+
+    fn spawn_armed_ship(world: &mut World, id: &str, team_id: &str) -> (Entity, Entity) {
+        let ship = world
+            .spawn((
+                ShipData::new(
+                    id.to_string(),
+                    id.to_string(),
+                    "cruiser".to_string(),
+                    team_id.to_string(),
+                    1000.0,
+                    0.0,
+                    100.0,
+                ),
+                Transform::new(),
+            ))
+            .id();
+        let mut weapon = WeaponComponent::new(
+            format!("{}-pd", id),
+            "countermissile".to_string(),
+            "countermeasure".to_string(),
+            Vec::new(),
+            0.0,
+            0.5,
+        );
+        weapon.range = 1000.0;
+        weapon.ammo_capacity = 10;
+        weapon.ammo_count = 10;
+        let pd = world
+            .spawn((weapon, Transform::new(), PointDefenseMarker, ChildOf(ship)))
+            .id();
+        (ship, pd)
+    }
+
+    #[test]
+    fn test_point_defense_engages_only_hostile_ordnance_in_range() {
+        let mut world = World::new();
+        crate::simulation::register_messages(&mut world);
+        world.insert_resource(SimulationParams::default());
+        let (ship, pd) = spawn_armed_ship(&mut world, "defender", "team1");
+        let (wingman, _) = spawn_armed_ship(&mut world, "wingman", "team1");
+        let (enemy, enemy_pd) = spawn_armed_ship(&mut world, "enemy", "team2");
+        // Only the defender's point defense is loaded
+        world
+            .get_mut::<WeaponComponent>(enemy_pd)
+            .unwrap()
+            .ammo_count = 0;
+
+        let own = world
+            .spawn((
+                guided_missile(ship, enemy),
+                Transform::at_position(Vector3::new(0.0, 0.0, 100.0)),
+            ))
+            .id();
+        let friendly = world
+            .spawn((
+                guided_missile(wingman, enemy),
+                Transform::at_position(Vector3::new(0.0, 0.0, 200.0)),
+            ))
+            .id();
+        let distant = world
+            .spawn((
+                guided_missile(enemy, ship),
+                Transform::at_position(Vector3::new(0.0, 0.0, 1500.0)),
+            ))
+            .id();
+        let hostile = world
+            .spawn((
+                guided_missile(enemy, ship),
+                Transform::at_position(Vector3::new(0.0, 0.0, 600.0)),
+            ))
+            .id();
+
+        let _ = world.run_system_once(countermeasure_system);
+
+        assert!(world.get_entity(own).is_ok());
+        assert!(world.get_entity(friendly).is_ok());
+        assert!(world.get_entity(distant).is_ok());
+        assert!(world.get_entity(hostile).is_err());
+
+        // The shot spent a round and started the cooldown
+        let weapon = world.get::<WeaponComponent>(pd).unwrap();
+        assert_eq!(weapon.ammo_count, 9);
+        assert_eq!(weapon.cooldown, 0.5);
+
+        let engagements = crate::simulation::drain_messages::<PointDefenseEngaged>(&mut world);
+        assert_eq!(engagements.len(), 1);
+        assert_eq!(engagements[0].ship, ship);
+        assert_eq!(engagements[0].attacker, enemy);
+        assert!(engagements[0].success);
+    }
+
+    #[test]
+    fn test_point_defense_rolls_effectiveness() {
+        let mut world = World::new();
+        crate::simulation::register_messages(&mut world);
+        let mut params = SimulationParams::default();
+        params.0.combat.countermeasures.antitorpedo_effectiveness = 0.0;
+        world.insert_resource(params);
+        world.insert_resource(SimulationRng::for_tick(1, 1));
+
+        let (ship, pd) = spawn_armed_ship(&mut world, "defender", "team1");
+        let (enemy, _) = spawn_armed_ship(&mut world, "enemy", "team2");
+        let mut torpedo =
+            ProjectileComponent::kinetic(enemy, 200.0, vec![WeaponTag::Torpedo], 30.0);
+        torpedo.projectile_type = ProjectileType::Torpedo;
+        torpedo.target = Some(ship);
+        let torpedo = world
+            .spawn((
+                torpedo,
+                Transform::at_position(Vector3::new(0.0, 0.0, 300.0)),
+            ))
+            .id();
+
+        let _ = world.run_system_once(countermeasure_system);
+
+        // The interceptor missed but was still spent
+        assert!(world.get_entity(torpedo).is_ok());
+        assert_eq!(world.get::<WeaponComponent>(pd).unwrap().ammo_count, 9);
+        let engagements = crate::simulation::drain_messages::<PointDefenseEngaged>(&mut world);
+        assert_eq!(engagements.len(), 1);
+        assert!(!engagements[0].success);
+        assert_eq!(
+            engagements[0].damage_type,
+            crate::events::DamageType::Torpedo
+        );
+    }
+
+    fn spawn_sensor_ship(world: &mut World, id: &str, position: Vector3<f32>) -> Entity {
+        let module = |instance_id: &str, module_id: &str| crate::models::CompiledModule {
+            instance_id: instance_id.to_string(),
+            module_id: module_id.to_string(),
+            kind: None,
+            name: instance_id.to_string(),
+            stats: Default::default(),
+            current_health: 100.0,
+            max_health: 100.0,
+            operational: true,
+            power_allocated: 1.0,
+            cooling_allocated: 1.0,
+        };
+        let mut sensors = SensorComponent::default();
+        sensors.arrays.insert(
+            "sensors".to_string(),
+            SensorArray {
+                scan_range: 10_000.0,
+                scan_time: 2.0,
+                detail_level: 5,
+            },
+        );
+        let mut stealth = StealthComponent::default();
+        stealth.systems.insert(
+            "stealth".to_string(),
+            StealthSystem {
+                detectability_reduction: 0.5,
+                scan_time_increase: 1.0,
+            },
+        );
+        world
+            .spawn((
+                ShipData::new(
+                    id.to_string(),
+                    id.to_string(),
+                    "cruiser".to_string(),
+                    id.to_string(),
+                    1000.0,
+                    0.0,
+                    100.0,
+                ),
+                ModuleStateTracker::from_compiled_modules(&[
+                    module("sensors", "sensor-array"),
+                    module("stealth", "stealth-system"),
+                ]),
+                sensors,
+                stealth,
+                Transform::at_position(position),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn test_sensor_system_detects_within_stealth_reduced_range() {
+        let mut world = World::new();
+        crate::simulation::register_messages(&mut world);
+        let observer = spawn_sensor_ship(&mut world, "observer", Vector3::zeros());
+        let target = spawn_sensor_ship(&mut world, "target", Vector3::new(4000.0, 0.0, 0.0));
+
+        // Stealth halves the 10 km range, so the target at 4 km is seen
+        let _ = world.run_system_once(sensor_system);
+        let sensor = world.get::<SensorComponent>(observer).unwrap();
+        assert_eq!(sensor.range, 10_000.0);
+        assert!(sensor.has_contact(target));
+        let changes = crate::simulation::drain_messages::<ContactChanged>(&mut world);
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|change| change.detected));
+
+        // Beyond 5 km the stealthed target slips off the scope
+        world.get_mut::<Transform>(target).unwrap().position.x = 6000.0;
+        let _ = world.run_system_once(sensor_system);
+        assert!(
+            !world
+                .get::<SensorComponent>(observer)
+                .unwrap()
+                .has_contact(target)
+        );
+        let changes = crate::simulation::drain_messages::<ContactChanged>(&mut world);
+        assert!(changes.iter().any(|change| change.ship == observer
+            && change.contact_id == "target"
+            && !change.detected));
+    }
+
+    #[test]
+    fn test_damaged_sensors_lose_range() {
+        let mut world = World::new();
+        let observer = spawn_sensor_ship(&mut world, "observer", Vector3::zeros());
+        let target = spawn_sensor_ship(&mut world, "target", Vector3::new(4000.0, 0.0, 0.0));
+        world
+            .get_mut::<ModuleStateTracker>(observer)
+            .unwrap()
+            .get_mut("sensors")
+            .unwrap()
+            .efficiency = 0.5;
+
+        let _ = world.run_system_once(sensor_system);
+
+        let sensor = world.get::<SensorComponent>(observer).unwrap();
+        assert_eq!(sensor.range, 5000.0);
+        assert!(!sensor.has_contact(target));
+    }
 }
@@ -5,15 +5,45 @@
 //! including players, teams, blueprints, and active ships.
 
 use bevy_ecs::prelude::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, RwLock};
 
 use crate::ai::AIManager;
+use crate::config::CommodityConfig;
 use crate::events::{EventQueue, GameEvent};
+use crate::generation::Relationship;
 use crate::models::ship::{CaptainLogEntry, DockingRequest, FighterCommand, HailMessage};
 use crate::models::{Player, Ship, ShipBlueprint, Team};
+use crate::replay::{Recorder, ReplayCommand};
+use crate::salvage::Wreck;
+use crate::simulation::{HelmCommand, SimulationState, WeaponComponent};
 use crate::stations::Station;
 
+mod ai_control;
+mod auxiliary;
+mod comms;
+mod countermeasures;
+mod engineering;
+mod outfitting;
+mod persistence;
+mod replay;
+mod reputation;
+mod salvage;
+mod scanning;
+mod sensors;
+mod simulation_sync;
+mod station_services;
+mod trading;
+mod weapon_control;
+
+pub use auxiliary::AuxiliaryActivation;
+pub use outfitting::RefitReceipt;
+pub use persistence::{ScanSnapshot, ShipSimulationSnapshot, WeaponSnapshot, WorldSnapshot};
+pub use reputation::FactionStanding;
+pub use salvage::{SalvageReceipt, SalvageSale};
+pub use scanning::{ScanReport, ScanStatus};
+pub use sensors::{SensorContact, ThreatTrack};
+
 /// Emits the shared `get_* / get_*_mut / get_all_*s` accessors for an entity
 /// type stored in a `HashMap<String, _>` field on `Self`.
 ///
@@ -78,6 +108,9 @@ pub struct GameWorld {
     /// Bevy ECS world for entity management
     pub world: World,
 
+    /// Fixed-timestep clock for the ECS simulation
+    pub simulation: SimulationState,
+
     /// Player registry (ID -> Player)
     players: HashMap<String, Player>,
 
@@ -90,6 +123,12 @@ pub struct GameWorld {
     /// Ship registry (ID -> Ship)
     ships: HashMap<String, Ship>,
 
+    /// ECS entity backing each simulated ship (Ship ID -> Entity)
+    ship_entities: HashMap<String, Entity>,
+
+    /// Helm commands awaiting the next simulation tick (Ship ID -> commands)
+    helm_commands: HashMap<String, Vec<HelmCommand>>,
+
     /// Captain's log entries (Ship ID -> Vec<LogEntry>)
     captain_logs: HashMap<String, Vec<CaptainLogEntry>>,
 
@@ -114,31 +153,57 @@ pub struct GameWorld {
     /// Point defense settings (ship_id -> enabled)
     point_defense_settings: HashMap<String, bool>,
 
+    /// What each ship's scans have revealed (Ship ID -> target Ship ID -> report)
+    scan_knowledge: HashMap<String, BTreeMap<String, ScanReport>>,
+
     /// Event queue for broadcasting to WebSocket clients
     event_queue: EventQueue,
 
     /// Station registry (ID -> Station)
     stations: HashMap<String, Station>,
 
+    /// Wrecks left by destroyed ships (ID -> Wreck)
+    wrecks: HashMap<String, Wreck>,
+
+    /// Commodity catalog station markets open with
+    commodities: Vec<CommodityConfig>,
+
+    /// Relationships between procedural factions (name -> name -> relationship)
+    faction_relations: HashMap<String, HashMap<String, Relationship>>,
+
     /// AI manager for autonomous ships
     pub ai_manager: AIManager,
 
     /// Player name to ID mapping for quick lookups
     player_names: HashMap<String, String>,
 
+    /// Session tokens issued at registration (Token digest -> Player ID)
+    ///
+    /// Only digests are kept, so saves never hold a usable token.
+    player_tokens: HashMap<String, String>,
+
     /// Team name to ID mapping for quick lookups
     team_names: HashMap<String, String>,
+
+    /// Recording in progress, if any
+    recorder: Option<Recorder>,
 }
 
 impl GameWorld {
     /// Create a new game world
     pub fn new() -> Self {
         Self {
-            world: World::new(),
+            world: crate::simulation::init_simulation(),
+            simulation: SimulationState {
+                seed: rand::random(),
+                ..SimulationState::new()
+            },
             players: HashMap::new(),
             teams: HashMap::new(),
             blueprints: HashMap::new(),
             ships: HashMap::new(),
+            ship_entities: HashMap::new(),
+            helm_commands: HashMap::new(),
             captain_logs: HashMap::new(),
             docking_requests: HashMap::new(),
             hail_messages: HashMap::new(),
@@ -147,11 +212,17 @@ impl GameWorld {
             countermeasure_loads: Vec::new(),
             countermeasure_activations: Vec::new(),
             point_defense_settings: HashMap::new(),
+            scan_knowledge: HashMap::new(),
             event_queue: EventQueue::new(),
             stations: HashMap::new(),
+            wrecks: HashMap::new(),
+            commodities: Vec::new(),
+            faction_relations: HashMap::new(),
             ai_manager: AIManager::new(),
             player_names: HashMap::new(),
+            player_tokens: HashMap::new(),
             team_names: HashMap::new(),
+            recorder: None,
         }
     }
 
@@ -236,9 +307,32 @@ impl GameWorld {
         get_by_name: get_player_by_name,
     }
 
+    /// Issue a new secret session token for a player
+    ///
+    /// Players may hold several tokens (e.g. one per client); all of them stay
+    /// valid until the player is removed.
+    pub fn issue_player_token(&mut self, player_id: &str) -> Result<String, String> {
+        if !self.players.contains_key(player_id) {
+            return Err(format!("Player {} not found", player_id));
+        }
+
+        let token = generate_token();
+        self.player_tokens
+            .insert(token_digest(&token), player_id.to_string());
+        Ok(token)
+    }
+
+    /// Resolve a session token to the player it was issued to
+    pub fn authenticate_token(&self, token: &str) -> Option<&Player> {
+        self.player_tokens
+            .get(&token_digest(token))
+            .and_then(|player_id| self.players.get(player_id))
+    }
+
     /// Remove a player
     ///
-    /// This also removes the player from any teams they're in.
+    /// This also removes the player from any teams they're in and revokes
+    /// their session tokens.
     pub fn remove_player(&mut self, id: &str) -> Result<(), String> {
         let player = self
             .players
@@ -246,6 +340,7 @@ impl GameWorld {
             .ok_or_else(|| format!("Player {} not found", id))?;
 
         self.player_names.remove(&player.name);
+        self.player_tokens.retain(|_, player_id| player_id != id);
 
         // Remove player from all teams
         for team in self.teams.values_mut() {
@@ -337,6 +432,13 @@ impl GameWorld {
         Ok(())
     }
 
+    /// Check whether a player belongs to a team
+    pub fn is_team_member(&self, team_id: &str, player_id: &str) -> bool {
+        self.teams
+            .get(team_id)
+            .is_some_and(|team| team.members.iter().any(|id| id == player_id))
+    }
+
     /// Remove a player from a team
     pub fn remove_player_from_team(
         &mut self,
@@ -475,6 +577,13 @@ impl GameWorld {
         self.ships.contains_key(&id.to_string())
     }
 
+    /// Check whether a player is part of a ship's crew via `player_roles`
+    pub fn is_crew_member(&self, ship_id: &str, player_id: &str) -> bool {
+        self.ships
+            .get(ship_id)
+            .is_some_and(|ship| ship.player_roles.contains_key(player_id))
+    }
+
     /// Get all ships that `player_id` is assigned to via `player_roles`.
     ///
     /// Ship IDs that cannot be parsed as UUIDs are skipped.
@@ -488,17 +597,45 @@ impl GameWorld {
     }
 
     /// Remove a ship
+    ///
+    /// Also despawns the ship's simulation entity (and its weapons), if any.
     pub fn remove_ship(&mut self, id: &str) -> Result<(), String> {
         self.ships
             .remove(id)
             .ok_or_else(|| format!("Ship {} not found", id))?;
+        self.helm_commands.remove(id);
+        self.despawn_ship_entity(id);
+        self.record(ReplayCommand::RemoveShip {
+            ship_id: id.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Raise or lower a ship's shields
+    ///
+    /// Shields only regenerate while raised. The change reaches the
+    /// simulation at the start of the next tick.
+    pub fn set_shields_raised(&mut self, ship_id: &str, raised: bool) -> Result<(), String> {
+        let ship = self
+            .ships
+            .get_mut(ship_id)
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+        ship.status.shields_raised = raised;
+        self.record(ReplayCommand::Shields {
+            ship_id: ship_id.to_string(),
+            raised,
+        });
         Ok(())
     }
 
     // ==================== Station Management ====================
 
     /// Register a new station
-    pub fn register_station(&mut self, station: Station) -> String {
+    ///
+    /// A station offering trade opens its market here once the commodity
+    /// catalog is configured.
+    pub fn register_station(&mut self, mut station: Station) -> String {
+        self.open_market(&mut station);
         let station_id = station.id.to_string();
         self.stations.insert(station_id.clone(), station);
         station_id
@@ -528,6 +665,14 @@ impl GameWorld {
         Ok(())
     }
 
+    /// The station a ship is docked at, if any
+    pub fn docked_station(&self, ship_id: &str) -> Option<&Station> {
+        let ship_uuid = uuid::Uuid::parse_str(ship_id).ok()?;
+        self.stations
+            .values()
+            .find(|station| station.is_ship_docked(ship_uuid))
+    }
+
     /// Find nearest station to a position
     pub fn find_nearest_station(&self, position: [f64; 3]) -> Option<&Station> {
         self.stations.values().min_by(|a, b| {
@@ -651,6 +796,9 @@ impl GameWorld {
             }
             crate::api::positions::countermeasures::CountermeasureType::Chaff => "chaff",
         };
+        if type_str != "chaff" {
+            self.restock_point_defense(&ship_id, quantity);
+        }
         self.countermeasure_loads
             .push((ship_id, type_str.to_string(), quantity));
     }
@@ -672,6 +820,15 @@ impl GameWorld {
 
     /// Set point defense enabled/disabled for a ship
     pub fn set_point_defense(&mut self, ship_id: String, enabled: bool) {
+        for entity in self.point_defense_entities(&ship_id) {
+            if let Some(mut weapon) = self.world.get_mut::<WeaponComponent>(entity) {
+                weapon.is_active = enabled;
+            }
+        }
+        self.record(ReplayCommand::PointDefense {
+            ship_id: ship_id.clone(),
+            enabled,
+        });
         self.point_defense_settings.insert(ship_id, enabled);
     }
 
@@ -684,185 +841,94 @@ impl GameWorld {
     }
 
     // ==================== Weapon Systems Methods ====================
-
-    /// Set energy weapon target
-    pub fn set_energy_weapon_target(&mut self, _ship_id: String, _target_id: String) {
-        // Placeholder - would integrate with simulation
-    }
-
-    /// Add weapon fire command
-    pub fn add_weapon_fire_command(&mut self, _ship_id: String, _weapon_id: String) {
-        // Placeholder - would integrate with simulation
-    }
-
-    /// Set weapon auto-fire mode
-    pub fn set_weapon_auto_fire(&mut self, _ship_id: String, _weapon_id: String, _enabled: bool) {
-        // Placeholder - would integrate with simulation
-    }
+    //
+    // Weapon station commands live in `state/weapon_control.rs`.
 
     /// Add radial weapon activation
     pub fn add_radial_weapon_activation(&mut self, _ship_id: String, _weapon_id: String) {
         // Placeholder - would integrate with simulation
     }
 
-    /// Get energy weapon target
-    pub fn get_energy_weapon_target(&self, _ship_id: &str) -> Option<String> {
-        // Placeholder - would integrate with simulation
-        None
-    }
-
-    /// Set kinetic weapon target
-    pub fn set_kinetic_weapon_target(&mut self, _ship_id: String, _target_id: String) {
-        // Placeholder - would integrate with simulation
-    }
-
-    /// Configure kinetic weapon kind
-    pub fn configure_kinetic_weapon(
-        &mut self,
-        _ship_id: String,
-        _weapon_id: String,
-        _kind: String,
-    ) {
-        // Placeholder - would integrate with simulation
-    }
-
-    /// Load kinetic ammunition
-    pub fn load_kinetic_ammo(
-        &mut self,
-        _ship_id: String,
-        _weapon_id: String,
-        _ammo_type: String,
-        _quantity: u32,
-    ) {
-        // Placeholder - would integrate with simulation
-    }
-
-    /// Fire kinetic weapon
-    pub fn fire_kinetic_weapon(&mut self, _ship_id: String, _weapon_id: String) {
-        // Placeholder - would integrate with simulation
-    }
-
-    /// Set kinetic auto-fire
-    pub fn set_kinetic_auto_fire(&mut self, _ship_id: String, _weapon_id: String, _enabled: bool) {
-        // Placeholder - would integrate with simulation
-    }
-
-    /// Set missile weapon target
-    pub fn set_missile_weapon_target(&mut self, _ship_id: String, _target_id: String) {
-        // Placeholder - would integrate with simulation
-    }
-
-    /// Load missile ordnance
-    pub fn load_missile_ordnance(
-        &mut self,
-        _ship_id: String,
-        _weapon_id: String,
-        _ordnance_type: String,
-        _quantity: u32,
-    ) {
-        // Placeholder - would integrate with simulation
-    }
-
-    /// Fire missile weapon
-    pub fn fire_missile_weapon(&mut self, _ship_id: String, _weapon_id: String) {
-        // Placeholder - would integrate with simulation
-    }
-
-    /// Set missile auto-fire
-    pub fn set_missile_auto_fire(&mut self, _ship_id: String, _weapon_id: String, _enabled: bool) {
-        // Placeholder - would integrate with simulation
-    }
-
     // ==================== Engineering Methods ====================
-
-    /// Add power allocations - applies directly to ship modules
-    pub fn add_power_allocations(
-        &mut self,
-        ship_id: String,
-        allocations: std::collections::HashMap<String, f32>,
-    ) {
-        // Get the ship and update its module power allocations
-        if let Some(_ship) = self.ships.get_mut(&ship_id) {
-            // Update each module's power allocation in the ship's compiled modules
-            for (instance_id, power_mw) in allocations {
-                // For now, store the allocation to be applied in simulation
-                // In a full implementation, this would update the ECS PowerGrid component
-                // Here we're just validating that the ship exists
-                let _ = (instance_id, power_mw); // Avoid unused warning
-            }
-        }
-        // TODO: Apply to ECS PowerGrid component in simulation
-    }
-
-    /// Add cooling allocations - applies directly to ship modules
-    pub fn add_cooling_allocations(
-        &mut self,
-        ship_id: String,
-        allocations: std::collections::HashMap<String, f32>,
-    ) {
-        // Get the ship and update its module cooling allocations
-        if let Some(_ship) = self.ships.get_mut(&ship_id) {
-            // Update each module's cooling allocation
-            for (instance_id, cooling) in allocations {
-                let _ = (instance_id, cooling); // Avoid unused warning
-            }
-        }
-        // TODO: Apply to ECS CoolingSystem component in simulation
-    }
+    //
+    // Power and cooling allocation, power priority, and repair queues live
+    // in `state/engineering.rs`; auxiliary modules in `state/auxiliary.rs`.
 
     /// Add repair command
-    pub fn add_repair_command(&mut self, _ship_id: String, _module_id: String) {
-        // Placeholder - would integrate with simulation
+    pub fn add_repair_command(&mut self, ship_id: String, module_id: String) -> Result<(), String> {
+        self.queue_repair(&ship_id, &module_id)
     }
 
     /// Add auxiliary module activation command
     pub fn add_auxiliary_activation(
         &mut self,
-        _ship_id: String,
-        _module_id: String,
-        _duration: f32,
-    ) {
-        // Placeholder - would integrate with simulation
-        // In full implementation, this would trigger the module activation in the ECS
+        ship_id: String,
+        module_id: String,
+    ) -> Result<AuxiliaryActivation, String> {
+        self.activate_auxiliary(&ship_id, &module_id)
     }
 
     // ==================== Helm Methods ====================
 
+    /// Queue a helm command for a ship
+    ///
+    /// Commands are handed to the simulation at the start of the next tick,
+    /// in the order they were issued.
+    pub fn queue_helm_command(&mut self, ship_id: String, command: HelmCommand) {
+        self.record(ReplayCommand::Helm {
+            ship_id: ship_id.clone(),
+            command: command.clone(),
+        });
+        self.helm_commands.entry(ship_id).or_default().push(command);
+    }
+
+    /// Get helm commands waiting for the next tick
+    pub fn pending_helm_commands(&self, ship_id: &str) -> &[HelmCommand] {
+        self.helm_commands
+            .get(ship_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     /// Add thrust command
-    pub fn add_thrust_command(&mut self, _ship_id: String, _x: f64, _y: f64, _z: f64) {
-        // Placeholder - would integrate with simulation
+    pub fn add_thrust_command(&mut self, ship_id: String, x: f64, y: f64, z: f64) {
+        self.queue_helm_command(ship_id, HelmCommand::Thrust { x, y, z });
     }
 
     /// Add rotation command
-    pub fn add_rotation_command(&mut self, _ship_id: String, _pitch: f64, _yaw: f64, _roll: f64) {
-        // Placeholder - would integrate with simulation
+    pub fn add_rotation_command(&mut self, ship_id: String, pitch: f64, yaw: f64, roll: f64) {
+        self.queue_helm_command(ship_id, HelmCommand::Rotate { pitch, yaw, roll });
     }
 
     /// Add stop command
-    pub fn add_stop_command(&mut self, _ship_id: String) {
-        // Placeholder - would integrate with simulation
+    pub fn add_stop_command(&mut self, ship_id: String) {
+        self.queue_helm_command(ship_id, HelmCommand::Stop);
     }
 
     /// Add warp command
-    pub fn add_warp_command(&mut self, _ship_id: String, _dest_x: f64, _dest_y: f64, _dest_z: f64) {
-        // Placeholder - would integrate with simulation
+    pub fn add_warp_command(&mut self, ship_id: String, dest_x: f64, dest_y: f64, dest_z: f64) {
+        let destination = [dest_x, dest_y, dest_z];
+        self.queue_helm_command(ship_id, HelmCommand::Warp { destination });
     }
 
     /// Add jump command
-    pub fn add_jump_command(&mut self, _ship_id: String, _dest_x: f64, _dest_y: f64, _dest_z: f64) {
-        // Placeholder - would integrate with simulation
+    pub fn add_jump_command(&mut self, ship_id: String, dest_x: f64, dest_y: f64, dest_z: f64) {
+        let destination = [dest_x, dest_y, dest_z];
+        self.queue_helm_command(ship_id, HelmCommand::Jump { destination });
     }
 
     /// Add dock command
-    pub fn add_dock_command(&mut self, _ship_id: String, _station_id: String) {
-        // Placeholder - would integrate with simulation
+    pub fn add_dock_command(&mut self, ship_id: String, station_id: String) {
+        self.queue_helm_command(ship_id, HelmCommand::Dock { station_id });
     }
 
     // ==================== Science Methods ====================
 
     /// Add scan command
-    pub fn add_scan_command(&mut self, _ship_id: String, _target_id: String) {
-        // Placeholder - would integrate with simulation
+    ///
+    /// See [`GameWorld::start_scan`].
+    pub fn add_scan_command(&mut self, ship_id: String, target_id: String) -> Result<f32, String> {
+        self.start_scan(&ship_id, &target_id)
     }
 
     // ==================== Utility Methods ====================
@@ -880,10 +946,14 @@ impl GameWorld {
     /// Clear all game state (useful for testing)
     #[cfg(test)]
     pub fn clear(&mut self) {
+        for (_, entity) in self.ship_entities.drain() {
+            self.world.despawn(entity);
+        }
         self.players.clear();
         self.teams.clear();
         self.blueprints.clear();
         self.ships.clear();
+        self.helm_commands.clear();
         self.captain_logs.clear();
         self.docking_requests.clear();
         self.hail_messages.clear();
@@ -892,7 +962,13 @@ impl GameWorld {
         self.countermeasure_loads.clear();
         self.countermeasure_activations.clear();
         self.point_defense_settings.clear();
+        self.scan_knowledge.clear();
+        self.stations.clear();
+        self.wrecks.clear();
+        self.faction_relations.clear();
+        self.ai_manager = AIManager::new();
         self.player_names.clear();
+        self.player_tokens.clear();
         self.team_names.clear();
     }
 
@@ -914,6 +990,24 @@ impl GameWorld {
     }
 }
 
+/// Generate a random secret token (64 hex digits)
+pub fn generate_token() -> String {
+    use rand::Rng;
+
+    let bytes: [u8; 32] = rand::rng().random();
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// SHA-256 digest of a secret token, as hex, for storing it at rest
+pub fn token_digest(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
 /// Helper function to calculate squared distance between two 3D points
 fn distance_squared(a: [f64; 3], b: [f64; 3]) -> f64 {
     let dx = a[0] - b[0];
@@ -1042,6 +1136,22 @@ mod tests {
         assert_eq!(team.members.len(), 0);
     }
 
+    #[test]
+    fn test_player_tokens() {
+        let mut world = GameWorld::new();
+
+        let player_id = world.register_player("Alice".to_string()).unwrap();
+        let token = world.issue_player_token(&player_id).unwrap();
+        assert_eq!(token.len(), 64);
+        assert_eq!(world.authenticate_token(&token).unwrap().id, player_id);
+        assert!(world.authenticate_token("not-a-token").is_none());
+        assert!(world.issue_player_token("missing").is_err());
+
+        // Removing the player revokes their tokens
+        world.remove_player(&player_id).unwrap();
+        assert!(world.authenticate_token(&token).is_none());
+    }
+
     #[test]
     fn test_team_creation() {
         let mut world = GameWorld::new();
@@ -1230,6 +1340,67 @@ mod tests {
         assert_eq!(team.credits, 60_000);
     }
 
+    #[test]
+    fn test_clear_despawns_ships_and_resets_registries() {
+        use crate::config::test_utils::create_test_game_config;
+        use crate::models::{Inventory, ShipStatus};
+        use crate::simulation::ShipData;
+
+        let mut world = GameWorld::new();
+        let config = create_test_game_config();
+        let player_id = world.register_player("Alice".to_string()).unwrap();
+        let token = world.issue_player_token(&player_id).unwrap();
+        let team_id = world
+            .create_team_with_credits("Red".to_string(), "Federation".to_string(), 0)
+            .unwrap();
+        world.faction_relations = HashMap::from([(
+            "Federation".to_string(),
+            HashMap::from([("Empire".to_string(), Relationship::War)]),
+        )]);
+
+        let ship_ids = [
+            uuid::Uuid::new_v4().to_string(),
+            uuid::Uuid::new_v4().to_string(),
+        ];
+        let mut entities = Vec::new();
+        for ship_id in &ship_ids {
+            world.add_ship(Ship {
+                id: ship_id.clone(),
+                name: "Ship".to_string(),
+                class: "cruiser".to_string(),
+                team_id: team_id.clone(),
+                player_roles: HashMap::new(),
+                status: ShipStatus::default(),
+                modules: vec![],
+                weapons: vec![],
+                inventory: Inventory::new(),
+            });
+            entities.push(world.spawn_ship_entity(ship_id, &config).unwrap());
+        }
+        // One ship is destroyed, leaving a wreck
+        world.world.get_mut::<ShipData>(entities[0]).unwrap().hull = 0.0;
+        world.destroy_ships(&[]);
+        assert_eq!(world.wrecks().len(), 1);
+
+        world.clear();
+
+        assert_eq!(world.get_stats().player_count, 0);
+        assert_eq!(world.get_stats().ship_count, 0);
+        assert!(world.authenticate_token(&token).is_none());
+        assert!(world.wrecks().is_empty());
+        assert!(world.faction_relations.is_empty());
+        assert!(world.get_ship_entity(&ship_ids[1]).is_none());
+        assert!(world.world.get_entity(entities[1]).is_err());
+        assert_eq!(
+            world.world.query::<&ShipData>().iter(&world.world).count(),
+            0
+        );
+
+        // The same player name can be registered again
+        let player_id = world.register_player("Alice".to_string()).unwrap();
+        assert!(world.issue_player_token(&player_id).is_ok());
+    }
+
     #[test]
     fn test_credit_operations_on_nonexistent_team() {
         let mut world = GameWorld::new();
@@ -0,0 +1,76 @@
+//! AI ship commands
+//!
+//! An AI-controlled ship acts through the same crew commands players use:
+//! each `AICommand` is translated into weapon, helm, or shield orders. The
+//! AI's decision is recorded as one `ReplayCommand::Ai`, with recording
+//! suspended while it is translated, so a replay re-issues the decision
+//! rather than the crew orders it expanded into.
+
+use super::GameWorld;
+use super::weapon_control::{ENERGY, KINETIC, MISSILE};
+use crate::ai::AICommand;
+use crate::replay::ReplayCommand;
+use crate::simulation::{HelmCommand, WeaponComponent};
+
+impl GameWorld {
+    /// Carry out orders issued by a ship's AI
+    ///
+    /// Orders the ship cannot carry out (no weapons of a type, unknown
+    /// target) are skipped. `MoveTo` and `Evade` need a helm autopilot and
+    /// are currently ignored.
+    pub fn apply_ai_commands(
+        &mut self,
+        ship_id: &str,
+        commands: Vec<AICommand>,
+    ) -> Result<(), String> {
+        if self.get_ship_entity(ship_id).is_none() {
+            return Err(format!("Ship {} is not in the simulation", ship_id));
+        }
+        self.record(ReplayCommand::Ai {
+            ship_id: ship_id.to_string(),
+            commands: commands.clone(),
+        });
+
+        let recorder = self.recorder.take();
+        for command in commands {
+            self.apply_ai_command(ship_id, command);
+        }
+        self.recorder = recorder;
+        Ok(())
+    }
+
+    fn apply_ai_command(&mut self, ship_id: &str, command: AICommand) {
+        match command {
+            AICommand::SetTarget { target_id } => {
+                let target_id = target_id.to_string();
+                for weapon_type in [ENERGY, KINETIC, MISSILE] {
+                    let _ = self.set_weapon_target(ship_id, weapon_type, &target_id);
+                }
+            }
+            AICommand::FireWeapons => {
+                for entity in self.weapon_entities(ship_id) {
+                    if let Some(mut weapon) = self.world.get_mut::<WeaponComponent>(entity)
+                        && [ENERGY, KINETIC, MISSILE].contains(&weapon.weapon_type.as_str())
+                    {
+                        weapon.is_automatic = true;
+                    }
+                }
+            }
+            AICommand::EngageFTL { destination } => {
+                self.queue_helm_command(ship_id.to_string(), HelmCommand::Warp { destination });
+            }
+            AICommand::RaiseShields => {
+                let _ = self.set_shields_raised(ship_id, true);
+            }
+            AICommand::DockAtStation { station_id } => {
+                self.queue_helm_command(
+                    ship_id.to_string(),
+                    HelmCommand::Dock {
+                        station_id: station_id.to_string(),
+                    },
+                );
+            }
+            AICommand::MoveTo { .. } | AICommand::Evade => {}
+        }
+    }
+}
@@ -0,0 +1,312 @@
+//! Auxiliary support modules
+//!
+//! Emergency power cells, repair nanobots, and the like carry a limited
+//! number of uses. Each activation applies the module's `hp_regained`,
+//! `energy_restored`, and `heat_dissipated` to the ship entity at once,
+//! spends a use, and starts the module's cooldown. Spent uses can only be
+//! restocked while docked at a station that offers the service the module
+//! needs: repair for modules that restore hull, refuel for modules that
+//! restore energy, and rearm for everything else.
+
+use super::GameWorld;
+use crate::config::simulation::{AuxiliaryConfig, SimulationConfig};
+use crate::replay::ReplayCommand;
+use crate::simulation::{
+    ModuleRuntimeState, ModuleStateTracker, PowerGrid, ShipData, SimulationParams,
+};
+use crate::stations::StationService;
+
+/// Result of activating an auxiliary module
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuxiliaryActivation {
+    /// How long the module stays active (seconds)
+    pub duration: f32,
+    /// Uses left before the module needs recharging
+    pub remaining_uses: u32,
+    /// Time until the module can be activated again (seconds)
+    pub cooldown: f32,
+}
+
+impl GameWorld {
+    /// Activate an auxiliary module
+    ///
+    /// The module stays active for its `duration` stat and can be used again
+    /// after its `recharge_time` stat, falling back to the `auxiliary`
+    /// simulation settings for either. Fails if the module is unknown, not
+    /// an auxiliary module, out of uses, cooling down, already active, or
+    /// not operational.
+    pub fn activate_auxiliary(
+        &mut self,
+        ship_id: &str,
+        module_id: &str,
+    ) -> Result<AuxiliaryActivation, String> {
+        let config = self.auxiliary_config();
+        let entity = self
+            .get_ship_entity(ship_id)
+            .ok_or_else(|| format!("Ship {} is not in the simulation", ship_id))?;
+        let duration = self
+            .ships
+            .get(ship_id)
+            .and_then(|ship| {
+                ship.modules
+                    .iter()
+                    .find(|module| module.instance_id == module_id)
+            })
+            .and_then(|module| module.get_stat_f64("duration"))
+            .unwrap_or(config.activation_duration) as f32;
+
+        let mut tracker = self
+            .world
+            .get_mut::<ModuleStateTracker>(entity)
+            .ok_or_else(|| format!("Ship {} has no modules", ship_id))?;
+        let state = tracker
+            .get_mut(module_id)
+            .ok_or_else(|| format!("Module {} not found", module_id))?;
+        state.activate(duration)?;
+        if state.current_cooldown <= 0.0 {
+            state.current_cooldown = config.cooldown as f32;
+        }
+        let activation = AuxiliaryActivation {
+            duration,
+            remaining_uses: state.remaining_uses,
+            cooldown: state.current_cooldown,
+        };
+        let (hp_regained, energy_restored, heat_dissipated) = (
+            state.hp_regained,
+            state.energy_restored,
+            state.heat_dissipated,
+        );
+
+        if heat_dissipated > 0.0 {
+            for state in tracker.states.values_mut() {
+                state.heat = (state.heat - heat_dissipated).max(0.0);
+            }
+        }
+        if let Some(mut ship_data) = self.world.get_mut::<ShipData>(entity) {
+            ship_data.hull = (ship_data.hull + hp_regained).min(ship_data.max_hull);
+        }
+        if let Some(mut power_grid) = self.world.get_mut::<PowerGrid>(entity) {
+            power_grid.stored = (power_grid.stored + energy_restored).min(power_grid.capacity);
+        }
+
+        self.record(ReplayCommand::ActivateAuxiliary {
+            ship_id: ship_id.to_string(),
+            module_id: module_id.to_string(),
+        });
+        Ok(activation)
+    }
+
+    /// Restore all uses of an auxiliary module at the station the ship is
+    /// docked at
+    ///
+    /// Fails unless the ship is docked at a station offering the service the
+    /// module needs. Returns the module's uses after recharging.
+    pub fn recharge_auxiliary(&mut self, ship_id: &str, module_id: &str) -> Result<u32, String> {
+        let station = self
+            .docked_station(ship_id)
+            .ok_or_else(|| format!("Ship {} is not docked", ship_id))?;
+        let (station_name, services) = (station.name.clone(), station.services.clone());
+        let entity = self
+            .get_ship_entity(ship_id)
+            .ok_or_else(|| format!("Ship {} is not in the simulation", ship_id))?;
+
+        let mut tracker = self
+            .world
+            .get_mut::<ModuleStateTracker>(entity)
+            .ok_or_else(|| format!("Ship {} has no modules", ship_id))?;
+        let state = tracker
+            .get_mut(module_id)
+            .ok_or_else(|| format!("Module {} not found", module_id))?;
+        if !state.is_auxiliary() {
+            return Err(format!("Module {} is not an auxiliary module", module_id));
+        }
+        let service = recharge_service(state);
+        if !services.offers(service) {
+            return Err(format!(
+                "Station {} does not offer {} service",
+                station_name,
+                service.name()
+            ));
+        }
+        state.recharge();
+        let remaining_uses = state.remaining_uses;
+
+        self.record(ReplayCommand::RechargeAuxiliary {
+            ship_id: ship_id.to_string(),
+            module_id: module_id.to_string(),
+        });
+        Ok(remaining_uses)
+    }
+
+    fn auxiliary_config(&self) -> AuxiliaryConfig {
+        self.world
+            .get_resource::<SimulationParams>()
+            .map(|params| params.systems.auxiliary.clone())
+            .unwrap_or_else(|| SimulationConfig::default().systems.auxiliary)
+    }
+}
+
+/// Station service needed to recharge an auxiliary module
+fn recharge_service(state: &ModuleRuntimeState) -> StationService {
+    if state.hp_regained > 0.0 {
+        StationService::Repair
+    } else if state.energy_restored > 0.0 {
+        StationService::Refuel
+    } else {
+        StationService::Rearm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::{CompiledModule, Inventory, Ship, ShipStatus};
+    use crate::stations::Station;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn aux_module(instance_id: &str, stats: serde_json::Value) -> CompiledModule {
+        CompiledModule {
+            instance_id: instance_id.to_string(),
+            module_id: "aux-support-system".to_string(),
+            kind: Some(instance_id.to_string()),
+            name: instance_id.to_string(),
+            stats: serde_json::from_value(stats).unwrap(),
+            current_health: 100.0,
+            max_health: 100.0,
+            operational: true,
+            power_allocated: 1.0,
+            cooling_allocated: 1.0,
+        }
+    }
+
+    /// A battered ship carrying nanobots, a power cell, and a heat sink
+    fn spawn_battered_ship(world: &mut GameWorld) -> String {
+        let ship_id = Uuid::new_v4().to_string();
+        let mut status = ShipStatus::new(1000.0, 100.0, 1000.0);
+        status.hull = 300.0;
+        status.power_capacity = 1000.0;
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Battered".to_string(),
+            class: "cruiser".to_string(),
+            team_id: "team1".to_string(),
+            player_roles: HashMap::new(),
+            status,
+            modules: vec![
+                aux_module(
+                    "nanobots",
+                    serde_json::json!({ "hp_regained": 500, "num_uses": 1 }),
+                ),
+                aux_module(
+                    "power-cell",
+                    serde_json::json!({ "energy_restored": 500, "num_uses": 3, "recharge_time": 5.0 }),
+                ),
+                aux_module(
+                    "heat-sink",
+                    serde_json::json!({ "heat_dissipated": 50, "num_uses": 2 }),
+                ),
+            ],
+            weapons: vec![],
+            inventory: Inventory::new(),
+        });
+        world
+            .spawn_ship_entity(&ship_id, &create_test_game_config())
+            .unwrap();
+        ship_id
+    }
+
+    fn module_state(world: &GameWorld, ship_id: &str, module_id: &str) -> ModuleRuntimeState {
+        let entity = world.get_ship_entity(ship_id).unwrap();
+        let tracker = world.world.get::<ModuleStateTracker>(entity).unwrap();
+        tracker.get(module_id).unwrap().clone()
+    }
+
+    fn dock(world: &mut GameWorld, ship_id: &str, services: impl FnOnce(&mut Station)) {
+        let mut station = Station::new("Haven".to_string(), [0.0; 3], "neutral".to_string());
+        station.docked_ships.push(Uuid::parse_str(ship_id).unwrap());
+        services(&mut station);
+        world.register_station(station);
+    }
+
+    #[test]
+    fn test_activation_applies_module_effects() {
+        let mut world = GameWorld::new();
+        let ship_id = spawn_battered_ship(&mut world);
+        let entity = world.get_ship_entity(&ship_id).unwrap();
+        world.world.get_mut::<PowerGrid>(entity).unwrap().stored = 100.0;
+        world
+            .world
+            .get_mut::<ModuleStateTracker>(entity)
+            .unwrap()
+            .get_mut("power-cell")
+            .unwrap()
+            .heat = 80.0;
+
+        let activation = world.activate_auxiliary(&ship_id, "nanobots").unwrap();
+        assert_eq!(activation.remaining_uses, 0);
+        assert_eq!(activation.duration, 10.0);
+        assert_eq!(world.world.get::<ShipData>(entity).unwrap().hull, 800.0);
+
+        world.activate_auxiliary(&ship_id, "power-cell").unwrap();
+        assert_eq!(world.world.get::<PowerGrid>(entity).unwrap().stored, 600.0);
+
+        world.activate_auxiliary(&ship_id, "heat-sink").unwrap();
+        assert_eq!(module_state(&world, &ship_id, "power-cell").heat, 30.0);
+
+        world.step_simulation();
+        assert_eq!(world.get_ship(&ship_id).unwrap().status.hull, 800.0);
+    }
+
+    #[test]
+    fn test_activation_enforces_uses_and_cooldown() {
+        let mut world = GameWorld::new();
+        let ship_id = spawn_battered_ship(&mut world);
+
+        // The power cell's own recharge time
+        let activation = world.activate_auxiliary(&ship_id, "power-cell").unwrap();
+        assert_eq!(activation.cooldown, 5.0);
+        assert!(world.activate_auxiliary(&ship_id, "power-cell").is_err());
+
+        // The configured default for modules without one
+        let activation = world.activate_auxiliary(&ship_id, "heat-sink").unwrap();
+        assert_eq!(activation.cooldown, 30.0);
+
+        // Usable again once its 10 second activation has run out
+        for _ in 0..(10 * 60 + 1) {
+            world.step_simulation();
+        }
+        let activation = world.activate_auxiliary(&ship_id, "power-cell").unwrap();
+        assert_eq!(activation.remaining_uses, 1);
+        assert!(world.activate_auxiliary(&ship_id, "heat-sink").is_err());
+
+        world.activate_auxiliary(&ship_id, "nanobots").unwrap();
+        for _ in 0..(10 * 60 + 1) {
+            world.step_simulation();
+        }
+        assert_eq!(
+            world.activate_auxiliary(&ship_id, "nanobots"),
+            Err("No charges remaining".to_string())
+        );
+    }
+
+    #[test]
+    fn test_recharge_requires_matching_station_service() {
+        let mut world = GameWorld::new();
+        let ship_id = spawn_battered_ship(&mut world);
+        world.activate_auxiliary(&ship_id, "nanobots").unwrap();
+        world.activate_auxiliary(&ship_id, "power-cell").unwrap();
+        assert!(world.recharge_auxiliary(&ship_id, "nanobots").is_err());
+
+        dock(&mut world, &ship_id, |station| {
+            station.services.repair = false
+        });
+        assert!(world.recharge_auxiliary(&ship_id, "nanobots").is_err());
+        assert_eq!(world.recharge_auxiliary(&ship_id, "power-cell"), Ok(3));
+
+        let state = module_state(&world, &ship_id, "power-cell");
+        assert_eq!(state.current_cooldown, 0.0);
+        assert_eq!(module_state(&world, &ship_id, "nanobots").remaining_uses, 0);
+    }
+}
@@ -0,0 +1,390 @@
+//! Ship-to-ship messaging
+//!
+//! Messages are queued on the sending ship's `CommsComponent` and delivered
+//! by the `communication_system` on the next tick, which decides who hears
+//! them: the addressee if a chain of comms links reaches it, every ship in
+//! reach for a broadcast, and any hostile ship that picks the message up on
+//! the way. Hails that reach their addressee are filed with the ship's other
+//! hail messages so they can be answered.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+use super::GameWorld;
+use crate::events::GameEvent;
+use crate::models::ship::HailMessage;
+use crate::replay::ReplayCommand;
+use crate::simulation::{
+    CommRelays, CommsComponent, CommunicationState, MessageReceived, MessageUndelivered,
+    ModuleStateTracker, RelayStation, ShipData, Transmission, drain_messages,
+};
+
+impl GameWorld {
+    /// Queue a message for transmission on the next tick
+    ///
+    /// `recipient_id` is `None` for a broadcast. The sender must be in the
+    /// simulation with a working comms system and must not be jammed.
+    /// Returns the message ID.
+    pub fn send_message(
+        &mut self,
+        ship_id: &str,
+        recipient_id: Option<&str>,
+        text: &str,
+        in_response_to: Option<&str>,
+    ) -> Result<String, String> {
+        let transmission = Transmission {
+            id: Uuid::new_v4().to_string(),
+            recipient_id: recipient_id.map(str::to_string),
+            text: text.to_string(),
+            in_response_to: in_response_to.map(str::to_string),
+        };
+        self.queue_transmission(ship_id, transmission)
+    }
+
+    /// Queue an already-identified message on a ship's comms outbox
+    pub(super) fn queue_transmission(
+        &mut self,
+        ship_id: &str,
+        transmission: Transmission,
+    ) -> Result<String, String> {
+        if let Some(recipient_id) = &transmission.recipient_id {
+            if recipient_id == ship_id {
+                return Err("A ship cannot message itself".to_string());
+            }
+            if !self.ships.contains_key(recipient_id) {
+                return Err(format!("Ship {} not found", recipient_id));
+            }
+        }
+        let entity = self
+            .get_ship_entity(ship_id)
+            .ok_or_else(|| format!("Ship {} is not in the simulation", ship_id))?;
+        if self
+            .world
+            .get::<CommunicationState>(entity)
+            .is_some_and(|state| !state.can_communicate())
+        {
+            return Err(format!("Ship {} communications are jammed", ship_id));
+        }
+        let modules = self.world.get::<ModuleStateTracker>(entity);
+        if self
+            .world
+            .get::<CommsComponent>(entity)
+            .is_none_or(|comms| comms.effective_range(modules) <= 0.0)
+        {
+            return Err(format!("Ship {} has no working comms system", ship_id));
+        }
+
+        let id = transmission.id.clone();
+        self.record(ReplayCommand::SendMessage {
+            ship_id: ship_id.to_string(),
+            transmission: transmission.clone(),
+        });
+        if let Some(mut comms) = self.world.get_mut::<CommsComponent>(entity) {
+            comms.outbox.push(transmission);
+        }
+        Ok(id)
+    }
+
+    /// Hand the station registry to the simulation as comms relays
+    pub(super) fn push_comm_relays(&mut self) {
+        let mut stations: Vec<RelayStation> = self
+            .stations
+            .values()
+            .map(|station| RelayStation {
+                id: station.id.to_string(),
+                faction: station.faction.clone(),
+                position: station.position.map(|axis| axis as f32).into(),
+            })
+            .collect();
+        stations.sort_by(|a, b| a.id.cmp(&b.id));
+        self.world.insert_resource(CommRelays { stations });
+    }
+
+    /// Emit `MessageSent` and `MessageUndelivered` events for this tick's
+    /// transmissions and file delivered hails
+    pub(super) fn emit_message_events(&mut self) {
+        for message in drain_messages::<MessageReceived>(&mut self.world) {
+            let (Some(from_ship_id), Some(receiver_id)) = (
+                self.ship_id_of(message.sender),
+                self.ship_id_of(message.receiver),
+            ) else {
+                continue;
+            };
+
+            if message.recipient_id.as_ref() == Some(&receiver_id) {
+                self.add_hail_message(HailMessage {
+                    id: message.message_id.clone(),
+                    from_ship_id: from_ship_id.clone(),
+                    to_ship_id: receiver_id.clone(),
+                    message: message.text.clone(),
+                    timestamp: SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map_or(0, |time| time.as_secs() as i64),
+                    in_response_to: message.in_response_to.clone(),
+                });
+            }
+
+            let (Ok(from_ship_id), Ok(receiver_id)) = (
+                Uuid::parse_str(&from_ship_id),
+                Uuid::parse_str(&receiver_id),
+            ) else {
+                continue;
+            };
+            self.push_event(GameEvent::MessageSent {
+                message_id: message.message_id,
+                from_ship_id,
+                to_ship_id: message
+                    .recipient_id
+                    .and_then(|id| Uuid::parse_str(&id).ok()),
+                receiver_id,
+                message: message.text,
+                garbled: message.garbled,
+                relays: message.relays,
+            });
+        }
+
+        for message in drain_messages::<MessageUndelivered>(&mut self.world) {
+            let Some(ship_id) = self
+                .ship_id_of(message.sender)
+                .and_then(|id| Uuid::parse_str(&id).ok())
+            else {
+                continue;
+            };
+            self.push_event(GameEvent::MessageUndelivered {
+                ship_id,
+                message_id: message.message_id,
+                to_ship_id: message
+                    .recipient_id
+                    .and_then(|id| Uuid::parse_str(&id).ok()),
+            });
+        }
+    }
+
+    fn ship_id_of(&self, entity: bevy_ecs::entity::Entity) -> Option<String> {
+        self.world
+            .get::<ShipData>(entity)
+            .map(|ship_data| ship_data.id.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::{CompiledModule, Inventory, Ship, ShipStatus};
+    use crate::simulation::StatusEffects;
+    use crate::stations::Station;
+    use crate::weapons::StatusEffectType;
+    use std::collections::HashMap;
+
+    fn spawn_ship(
+        world: &mut GameWorld,
+        team_id: &str,
+        position: [f64; 3],
+        comm_range: f64,
+        encryption_lvl: u8,
+    ) -> String {
+        let ship_id = Uuid::new_v4().to_string();
+        let mut status = ShipStatus::new(1000.0, 100.0, 1000.0);
+        status.position = position;
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Courier".to_string(),
+            class: "corvette".to_string(),
+            team_id: team_id.to_string(),
+            player_roles: HashMap::new(),
+            status,
+            modules: vec![CompiledModule {
+                instance_id: "comms".to_string(),
+                module_id: "comms-system".to_string(),
+                kind: None,
+                name: "Comms".to_string(),
+                stats: serde_json::from_value(serde_json::json!({
+                    "comm_range": comm_range,
+                    "encryption_lvl": encryption_lvl,
+                }))
+                .unwrap(),
+                current_health: 100.0,
+                max_health: 100.0,
+                operational: true,
+                power_allocated: 1.0,
+                cooling_allocated: 1.0,
+            }],
+            weapons: vec![],
+            inventory: Inventory::new(),
+        });
+        world
+            .spawn_ship_entity(&ship_id, &create_test_game_config())
+            .unwrap();
+        ship_id
+    }
+
+    fn received(events: &[GameEvent], receiver: &str) -> Option<(String, bool, Vec<String>)> {
+        events.iter().find_map(|event| match event {
+            GameEvent::MessageSent {
+                receiver_id,
+                message,
+                garbled,
+                relays,
+                ..
+            } if receiver_id.to_string() == receiver => {
+                Some((message.clone(), *garbled, relays.clone()))
+            }
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_hail_delivered_within_range() {
+        let mut world = GameWorld::new();
+        let sender = spawn_ship(&mut world, "team1", [0.0, 0.0, 0.0], 5000.0, 5);
+        let recipient = spawn_ship(&mut world, "team2", [3000.0, 0.0, 0.0], 5000.0, 5);
+
+        let message_id = world
+            .send_message(&sender, Some(&recipient), "Stand down", None)
+            .unwrap();
+        world.step_simulation();
+
+        let events = world.drain_events();
+        let (text, garbled, relays) = received(&events, &recipient).unwrap();
+        assert_eq!(text, "Stand down");
+        assert!(!garbled);
+        assert!(relays.is_empty());
+        assert_eq!(world.get_hail_messages_for_ship(&recipient).len(), 1);
+        assert!(world.get_hail_message(&message_id).is_some());
+    }
+
+    #[test]
+    fn test_recipient_comm_range_limits_delivery() {
+        let mut world = GameWorld::new();
+        let sender = spawn_ship(&mut world, "team1", [0.0, 0.0, 0.0], 50_000.0, 5);
+        let recipient = spawn_ship(&mut world, "team2", [10_000.0, 0.0, 0.0], 5000.0, 5);
+
+        world
+            .send_message(&sender, Some(&recipient), "Hello?", None)
+            .unwrap();
+        world.step_simulation();
+
+        let events = world.drain_events();
+        assert!(received(&events, &recipient).is_none());
+        assert!(events.iter().any(|event| matches!(
+            event,
+            GameEvent::MessageUndelivered { ship_id, .. } if ship_id.to_string() == sender
+        )));
+    }
+
+    #[test]
+    fn test_friendly_ships_and_stations_relay() {
+        let mut world = GameWorld::new();
+        let sender = spawn_ship(&mut world, "team1", [0.0, 0.0, 0.0], 5000.0, 5);
+        let relay = spawn_ship(&mut world, "team1", [4000.0, 0.0, 0.0], 5000.0, 5);
+        let recipient = spawn_ship(&mut world, "team1", [8000.0, 0.0, 0.0], 5000.0, 5);
+
+        world.step_simulation();
+        world.drain_events();
+        world
+            .send_message(&sender, Some(&recipient), "Regroup", None)
+            .unwrap();
+        world.step_simulation();
+        let (_, _, relays) = received(&world.drain_events(), &recipient).unwrap();
+        assert_eq!(relays, vec![relay.clone()]);
+
+        // A station of the sender's faction bridges a gap no ship can
+        let far = spawn_ship(&mut world, "team1", [0.0, 0.0, 60_000.0], 50_000.0, 5);
+        let station_id = world.register_station(Station::new(
+            "Relay".to_string(),
+            [0.0, 0.0, 30_000.0],
+            "Federation".to_string(),
+        ));
+        for ship_id in [&sender, &far] {
+            let entity = world.get_ship_entity(ship_id).unwrap();
+            world.world.get_mut::<ShipData>(entity).unwrap().faction = "Federation".to_string();
+        }
+        let sender_entity = world.get_ship_entity(&sender).unwrap();
+        world
+            .world
+            .get_mut::<CommsComponent>(sender_entity)
+            .unwrap()
+            .systems
+            .get_mut("comms")
+            .unwrap()
+            .comm_range = 50_000.0;
+
+        world
+            .send_message(&sender, Some(&far), "Report", None)
+            .unwrap();
+        world.step_simulation();
+        let (_, _, relays) = received(&world.drain_events(), &far).unwrap();
+        assert_eq!(relays, vec![station_id]);
+    }
+
+    #[test]
+    fn test_eavesdroppers_need_matching_encryption() {
+        let mut world = GameWorld::new();
+        let sender = spawn_ship(&mut world, "team1", [0.0, 0.0, 0.0], 5000.0, 6);
+        let recipient = spawn_ship(&mut world, "team1", [1000.0, 0.0, 0.0], 5000.0, 6);
+        let weak = spawn_ship(&mut world, "team2", [2000.0, 0.0, 0.0], 5000.0, 3);
+        let strong = spawn_ship(&mut world, "team3", [3000.0, 0.0, 0.0], 5000.0, 8);
+
+        world
+            .send_message(&sender, Some(&recipient), "Attack at dawn", None)
+            .unwrap();
+        world.step_simulation();
+
+        let events = world.drain_events();
+        assert_eq!(received(&events, &recipient).unwrap().0, "Attack at dawn");
+        let (text, garbled, _) = received(&events, &weak).unwrap();
+        assert!(garbled);
+        assert_ne!(text, "Attack at dawn");
+        assert_eq!(text.len(), "Attack at dawn".len());
+        assert_eq!(
+            received(&events, &strong).unwrap(),
+            ("Attack at dawn".to_string(), false, vec![])
+        );
+
+        // Only the addressee files the hail
+        assert!(world.get_hail_messages_for_ship(&weak).is_empty());
+    }
+
+    #[test]
+    fn test_broadcast_reaches_every_ship_in_range() {
+        let mut world = GameWorld::new();
+        let sender = spawn_ship(&mut world, "team1", [0.0, 0.0, 0.0], 5000.0, 5);
+        let ally = spawn_ship(&mut world, "team1", [1000.0, 0.0, 0.0], 5000.0, 5);
+        let stranger = spawn_ship(&mut world, "team2", [2000.0, 0.0, 0.0], 5000.0, 1);
+
+        world.send_message(&sender, None, "Mayday", None).unwrap();
+        world.step_simulation();
+
+        let events = world.drain_events();
+        assert_eq!(received(&events, &ally).unwrap().0, "Mayday");
+        assert_eq!(received(&events, &stranger).unwrap().0, "Mayday");
+        assert!(received(&events, &sender).is_none());
+    }
+
+    #[test]
+    fn test_jammed_ships_cannot_communicate() {
+        let mut world = GameWorld::new();
+        let sender = spawn_ship(&mut world, "team1", [0.0, 0.0, 0.0], 5000.0, 5);
+        let recipient = spawn_ship(&mut world, "team2", [1000.0, 0.0, 0.0], 5000.0, 5);
+
+        let entity = world.get_ship_entity(&recipient).unwrap();
+        world
+            .world
+            .get_mut::<StatusEffects>(entity)
+            .unwrap()
+            .apply(StatusEffectType::IonJam, 10.0);
+        world
+            .send_message(&sender, Some(&recipient), "Hello", None)
+            .unwrap();
+        world.step_simulation();
+        assert!(received(&world.drain_events(), &recipient).is_none());
+
+        // The jammed ship cannot transmit either
+        assert!(
+            world
+                .send_message(&recipient, Some(&sender), "Help", None)
+                .is_err()
+        );
+    }
+}
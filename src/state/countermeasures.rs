@@ -0,0 +1,153 @@
+//! Countermeasures station commands
+//!
+//! Chaff is spawned into the simulation as a `ChaffCloud` entity at the
+//! deploying ship's position, where it can seduce enemy guided missiles until
+//! it disperses. Point defense acts on the ship's `PointDefenseMarker` weapon
+//! children, whose magazines hold interceptor rounds.
+
+use bevy_ecs::prelude::*;
+
+use super::GameWorld;
+use crate::replay::ReplayCommand;
+use crate::simulation::{
+    ChaffCloud, PointDefenseMarker, SimulationParams, Transform, WeaponComponent,
+};
+
+impl GameWorld {
+    /// Restock a ship's point-defense magazines with interceptor rounds
+    ///
+    /// Rounds fill each magazine in turn up to its capacity. Returns the
+    /// number of rounds loaded.
+    pub fn restock_point_defense(&mut self, ship_id: &str, quantity: u32) -> u32 {
+        let mut remaining = quantity;
+        for entity in self.point_defense_entities(ship_id) {
+            let Some(mut weapon) = self.world.get_mut::<WeaponComponent>(entity) else {
+                continue;
+            };
+            let room = weapon.ammo_capacity.saturating_sub(weapon.ammo_count);
+            let loaded = room.min(remaining);
+            weapon.ammo_count += loaded;
+            remaining -= loaded;
+        }
+
+        let loaded = quantity - remaining;
+        if loaded > 0 {
+            self.record(ReplayCommand::RestockPointDefense {
+                ship_id: ship_id.to_string(),
+                quantity: loaded,
+            });
+        }
+        loaded
+    }
+
+    /// Point-defense weapon child entities of a ship's simulation entity
+    pub(super) fn point_defense_entities(&self, ship_id: &str) -> Vec<Entity> {
+        self.weapon_entities(ship_id)
+            .into_iter()
+            .filter(|entity| self.world.get::<PointDefenseMarker>(*entity).is_some())
+            .collect()
+    }
+
+    /// Deploy a chaff cloud around a ship
+    pub fn deploy_chaff(&mut self, ship_id: &str) -> Result<(), String> {
+        let owner = self
+            .get_ship_entity(ship_id)
+            .ok_or_else(|| format!("Ship {} is not in the simulation", ship_id))?;
+        let position = self
+            .world
+            .get::<Transform>(owner)
+            .map(|transform| transform.position)
+            .ok_or_else(|| format!("Ship {} has no position", ship_id))?;
+        let countermeasures = self
+            .world
+            .get_resource::<SimulationParams>()
+            .map(|params| params.combat.countermeasures.clone())
+            .unwrap_or_else(|| SimulationParams::default().combat.countermeasures.clone());
+
+        self.world.spawn((
+            ChaffCloud {
+                owner,
+                radius: countermeasures.chaff_range as f32,
+                remaining: countermeasures.chaff_duration as f32,
+            },
+            Transform::at_position(position),
+        ));
+        self.record(ReplayCommand::DeployChaff {
+            ship_id: ship_id.to_string(),
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::{CompiledModule, Inventory, Ship, ShipStatus};
+    use std::collections::HashMap;
+
+    fn spawn_defended_ship(world: &mut GameWorld) -> String {
+        let ship_id = "defender".to_string();
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Defender".to_string(),
+            class: "cruiser".to_string(),
+            team_id: "team1".to_string(),
+            player_roles: HashMap::new(),
+            status: ShipStatus::new(1000.0, 100.0, 1000.0),
+            modules: vec![CompiledModule {
+                instance_id: "pd-1".to_string(),
+                module_id: "countermeasure-system".to_string(),
+                kind: Some("countermissile".to_string()),
+                name: "Counter-Missile System".to_string(),
+                stats: serde_json::from_value(serde_json::json!({ "ammo_capacity": 12 })).unwrap(),
+                current_health: 100.0,
+                max_health: 100.0,
+                operational: true,
+                power_allocated: 1.0,
+                cooling_allocated: 1.0,
+            }],
+            weapons: vec![],
+            inventory: Inventory::new(),
+        });
+        world
+            .spawn_ship_entity(&ship_id, &create_test_game_config())
+            .unwrap();
+        ship_id
+    }
+
+    fn point_defense(world: &GameWorld, ship_id: &str) -> WeaponComponent {
+        let entity = world.point_defense_entities(ship_id)[0];
+        world.world.get::<WeaponComponent>(entity).unwrap().clone()
+    }
+
+    #[test]
+    fn test_restock_point_defense_fills_to_capacity() {
+        let mut world = GameWorld::new();
+        let ship_id = spawn_defended_ship(&mut world);
+        let entity = world.point_defense_entities(&ship_id)[0];
+        world
+            .world
+            .get_mut::<WeaponComponent>(entity)
+            .unwrap()
+            .ammo_count = 2;
+
+        assert_eq!(world.restock_point_defense(&ship_id, 50), 10);
+        assert_eq!(point_defense(&world, &ship_id).ammo_count, 12);
+        assert_eq!(world.restock_point_defense(&ship_id, 5), 0);
+    }
+
+    #[test]
+    fn test_point_defense_toggle_deactivates_weapons() {
+        let mut world = GameWorld::new();
+        let ship_id = spawn_defended_ship(&mut world);
+        assert!(point_defense(&world, &ship_id).is_active);
+
+        world.set_point_defense(ship_id.clone(), false);
+        assert!(!point_defense(&world, &ship_id).is_active);
+        assert!(!world.get_point_defense(&ship_id));
+
+        world.set_point_defense(ship_id.clone(), true);
+        assert!(point_defense(&world, &ship_id).can_fire());
+    }
+}
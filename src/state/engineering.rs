@@ -0,0 +1,533 @@
+//! Engineering station commands
+//!
+//! The engineer distributes power by writing megawatt allocations into the
+//! ship entity's `PowerGrid` and ranks modules by power priority in its
+//! `ModuleStateTracker`. When allocations outrun generation and reserves,
+//! `power_system` sheds the lowest-priority modules first. Cooling works
+//! the same way through the ship's `CoolingSystem`: modules denied the
+//! cooling they need heat up, take damage, and eventually shut down.
+//!
+//! Repairs are queued on the ship's `RepairComponent` and worked off by its
+//! damage-control teams in `repair_system`. Destroyed modules can only be
+//! rebuilt while the ship is docked at a station.
+
+use std::collections::HashMap;
+
+use super::GameWorld;
+use crate::replay::ReplayCommand;
+use crate::simulation::{
+    CoolingSystem, MAX_POWER_PRIORITY, ModuleStateTracker, PowerGrid, RepairComponent,
+};
+
+impl GameWorld {
+    /// Add power allocations (module instance ID -> MW)
+    ///
+    /// Allocations for modules the ship does not have are ignored, as are
+    /// ships without a simulation entity. Negative amounts are treated as 0.
+    pub fn add_power_allocations(&mut self, ship_id: String, allocations: HashMap<String, f32>) {
+        let Some(entity) = self.get_ship_entity(&ship_id) else {
+            return;
+        };
+        let Some(tracker) = self.world.get::<ModuleStateTracker>(entity) else {
+            return;
+        };
+        let known: Vec<(String, f32)> = allocations
+            .iter()
+            .filter(|(module_id, _)| tracker.get(module_id).is_some())
+            .map(|(module_id, amount)| (module_id.clone(), *amount))
+            .collect();
+
+        if let Some(mut power_grid) = self.world.get_mut::<PowerGrid>(entity) {
+            for (module_id, amount) in known {
+                power_grid.allocate(module_id, amount.max(0.0));
+            }
+        }
+        self.record(ReplayCommand::PowerAllocations {
+            ship_id,
+            allocations,
+        });
+    }
+
+    /// Add cooling allocations (module instance ID -> K/s)
+    ///
+    /// Allocations for modules the ship does not have are ignored, as are
+    /// ships without a simulation entity. Negative amounts are treated as 0.
+    pub fn add_cooling_allocations(&mut self, ship_id: String, allocations: HashMap<String, f32>) {
+        let Some(entity) = self.get_ship_entity(&ship_id) else {
+            return;
+        };
+        let Some(tracker) = self.world.get::<ModuleStateTracker>(entity) else {
+            return;
+        };
+        let known: Vec<(String, f32)> = allocations
+            .iter()
+            .filter(|(module_id, _)| tracker.get(module_id).is_some())
+            .map(|(module_id, amount)| (module_id.clone(), *amount))
+            .collect();
+
+        if let Some(mut cooling) = self.world.get_mut::<CoolingSystem>(entity) {
+            for (module_id, amount) in known {
+                cooling.allocate(module_id, amount.max(0.0));
+            }
+        }
+        self.record(ReplayCommand::CoolingAllocations {
+            ship_id,
+            allocations,
+        });
+    }
+
+    /// Set power priorities (module instance ID -> priority)
+    ///
+    /// Priorities run from 0 (shed first) to `MAX_POWER_PRIORITY` (shed
+    /// last). Nothing is changed if any module is unknown or any priority is
+    /// out of range.
+    pub fn set_power_priorities(
+        &mut self,
+        ship_id: &str,
+        priorities: HashMap<String, u8>,
+    ) -> Result<(), String> {
+        let entity = self
+            .get_ship_entity(ship_id)
+            .ok_or_else(|| format!("Ship {} is not in the simulation", ship_id))?;
+        let mut tracker = self
+            .world
+            .get_mut::<ModuleStateTracker>(entity)
+            .ok_or_else(|| format!("Ship {} has no modules", ship_id))?;
+
+        for (module_id, priority) in &priorities {
+            if tracker.get(module_id).is_none() {
+                return Err(format!("Module {} not found", module_id));
+            }
+            if *priority > MAX_POWER_PRIORITY {
+                return Err(format!(
+                    "Power priority {} exceeds maximum of {}",
+                    priority, MAX_POWER_PRIORITY
+                ));
+            }
+        }
+
+        for (module_id, priority) in &priorities {
+            if let Some(state) = tracker.get_mut(module_id) {
+                state.power_priority = *priority;
+            }
+        }
+        self.record(ReplayCommand::PowerPriorities {
+            ship_id: ship_id.to_string(),
+            priorities,
+        });
+        Ok(())
+    }
+
+    /// Queue a damaged module for repair
+    ///
+    /// Fails if the module is unknown or undamaged, or if it is destroyed
+    /// and the ship is not docked. Queuing a module that is already queued
+    /// changes nothing.
+    pub fn queue_repair(&mut self, ship_id: &str, module_id: &str) -> Result<(), String> {
+        let entity = self
+            .get_ship_entity(ship_id)
+            .ok_or_else(|| format!("Ship {} is not in the simulation", ship_id))?;
+        let state = self
+            .world
+            .get::<ModuleStateTracker>(entity)
+            .and_then(|tracker| tracker.get(module_id))
+            .ok_or_else(|| format!("Module {} not found", module_id))?;
+        if state.current_health >= state.max_health {
+            return Err(format!("Module {} is not damaged", module_id));
+        }
+        if state.is_destroyed() && self.docked_station(ship_id).is_none() {
+            return Err(format!(
+                "Module {} is destroyed and can only be repaired while docked",
+                module_id
+            ));
+        }
+
+        let mut repair = self
+            .world
+            .get_mut::<RepairComponent>(entity)
+            .ok_or_else(|| format!("Ship {} has no damage-control teams", ship_id))?;
+        if !repair.is_queued(module_id) {
+            repair.queue.push_back(module_id.to_string());
+        }
+        self.record(ReplayCommand::Repair {
+            ship_id: ship_id.to_string(),
+            module_id: module_id.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Module instance IDs queued for repair, in the order teams take them
+    ///
+    /// Empty if the ship is not in the simulation.
+    pub fn repair_queue(&self, ship_id: &str) -> Vec<String> {
+        self.get_ship_entity(ship_id)
+            .and_then(|entity| self.world.get::<RepairComponent>(entity))
+            .map(|repair| repair.queue.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::events::GameEvent;
+    use crate::models::{CompiledModule, Inventory, Ship, ShipStatus};
+    use crate::stations::Station;
+    use uuid::Uuid;
+
+    fn module(instance_id: &str, module_id: &str, stats: serde_json::Value) -> CompiledModule {
+        CompiledModule {
+            instance_id: instance_id.to_string(),
+            module_id: module_id.to_string(),
+            kind: None,
+            name: instance_id.to_string(),
+            stats: serde_json::from_value(stats).unwrap(),
+            current_health: 100.0,
+            max_health: 100.0,
+            operational: true,
+            power_allocated: 1.0,
+            cooling_allocated: 1.0,
+        }
+    }
+
+    /// A ship whose 100 MW core cannot carry its 150 MW of demand for long
+    fn spawn_overloaded_ship(world: &mut GameWorld) -> String {
+        let ship_id = Uuid::new_v4().to_string();
+        let mut status = ShipStatus::new(1000.0, 100.0, 1000.0);
+        status.power_generation = 100.0;
+        status.power_capacity = 1.0;
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Overloaded".to_string(),
+            class: "cruiser".to_string(),
+            team_id: "team1".to_string(),
+            player_roles: HashMap::new(),
+            status,
+            modules: vec![
+                module(
+                    "core",
+                    "power-core",
+                    serde_json::json!({ "production": 100.0 }),
+                ),
+                module(
+                    "shields",
+                    "shield-generator",
+                    serde_json::json!({ "power_consumption": 80.0 }),
+                ),
+                module(
+                    "sensors",
+                    "sensor-array",
+                    serde_json::json!({ "power_consumption": 70.0 }),
+                ),
+            ],
+            weapons: vec![],
+            inventory: Inventory::new(),
+        });
+        world
+            .spawn_ship_entity(&ship_id, &create_test_game_config())
+            .unwrap();
+        ship_id
+    }
+
+    fn state(world: &GameWorld, ship_id: &str, module_id: &str) -> (bool, f32) {
+        let entity = world.get_ship_entity(ship_id).unwrap();
+        let tracker = world.world.get::<ModuleStateTracker>(entity).unwrap();
+        let state = tracker.get(module_id).unwrap();
+        (state.browned_out, state.power_supplied)
+    }
+
+    #[test]
+    fn test_brownout_sheds_low_priority_module() {
+        let mut world = GameWorld::new();
+        let ship_id = spawn_overloaded_ship(&mut world);
+        world
+            .set_power_priorities(&ship_id, HashMap::from([("shields".to_string(), 9)]))
+            .unwrap();
+
+        for _ in 0..10 {
+            world.step_simulation();
+        }
+
+        assert_eq!(state(&world, &ship_id, "shields"), (false, 1.0));
+        let (browned_out, supplied) = state(&world, &ship_id, "sensors");
+        assert!(browned_out);
+        assert!((supplied - 20.0 / 70.0).abs() < 1e-4);
+
+        let brownouts: Vec<(String, bool)> = world
+            .drain_events()
+            .into_iter()
+            .filter_map(|event| match event {
+                GameEvent::ModuleBrownout {
+                    module_id,
+                    browned_out,
+                    ..
+                } => Some((module_id, browned_out)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(brownouts, vec![("sensors".to_string(), true)]);
+    }
+
+    #[test]
+    fn test_reducing_allocation_ends_brownout() {
+        let mut world = GameWorld::new();
+        let ship_id = spawn_overloaded_ship(&mut world);
+        for _ in 0..10 {
+            world.step_simulation();
+        }
+        world.drain_events();
+
+        world.add_power_allocations(
+            ship_id.clone(),
+            HashMap::from([
+                ("sensors".to_string(), 10.0),
+                ("unknown".to_string(), 500.0),
+            ]),
+        );
+        world.step_simulation();
+
+        assert_eq!(state(&world, &ship_id, "sensors"), (false, 1.0));
+        assert_eq!(state(&world, &ship_id, "shields"), (false, 1.0));
+        assert!(world.drain_events().iter().any(|event| matches!(
+            event,
+            GameEvent::ModuleBrownout {
+                browned_out: false,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_withholding_cooling_overheats_module() {
+        let mut world = GameWorld::new();
+        let ship_id = Uuid::new_v4().to_string();
+        let mut status = ShipStatus::new(1000.0, 100.0, 1000.0);
+        status.cooling_capacity = 2000.0;
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Hot".to_string(),
+            class: "cruiser".to_string(),
+            team_id: "team1".to_string(),
+            player_roles: HashMap::new(),
+            status,
+            modules: vec![
+                module(
+                    "radiator",
+                    "cooling-system",
+                    serde_json::json!({ "generated_cooling": 2000.0 }),
+                ),
+                module(
+                    "engine",
+                    "impulse-engine",
+                    serde_json::json!({ "heat_generation": 1200.0 }),
+                ),
+            ],
+            weapons: vec![],
+            inventory: Inventory::new(),
+        });
+        world
+            .spawn_ship_entity(&ship_id, &create_test_game_config())
+            .unwrap();
+
+        // Fully cooled by default
+        for _ in 0..60 {
+            world.step_simulation();
+        }
+        let heat = |world: &GameWorld| {
+            let entity = world.get_ship_entity(&ship_id).unwrap();
+            let tracker = world.world.get::<ModuleStateTracker>(entity).unwrap();
+            tracker.get("engine").unwrap().heat
+        };
+        assert_eq!(heat(&world), 0.0);
+        world.drain_events();
+
+        world.add_cooling_allocations(
+            ship_id.clone(),
+            HashMap::from([("engine".to_string(), 0.0)]),
+        );
+        for _ in 0..60 {
+            world.step_simulation();
+        }
+        assert!(heat(&world) > 900.0);
+
+        let changes: Vec<(bool, bool)> = world
+            .drain_events()
+            .into_iter()
+            .filter_map(|event| match event {
+                GameEvent::ModuleStatusChanged {
+                    operational,
+                    overheated,
+                    ..
+                } => Some((operational, overheated)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(changes, vec![(true, true)]);
+    }
+
+    #[test]
+    fn test_set_power_priorities_validates() {
+        let mut world = GameWorld::new();
+        let ship_id = spawn_overloaded_ship(&mut world);
+
+        assert!(
+            world
+                .set_power_priorities(&ship_id, HashMap::from([("missing".to_string(), 1)]))
+                .is_err()
+        );
+        assert!(
+            world
+                .set_power_priorities(
+                    &ship_id,
+                    HashMap::from([("shields".to_string(), MAX_POWER_PRIORITY + 1)])
+                )
+                .is_err()
+        );
+        assert!(
+            world
+                .set_power_priorities("missing", HashMap::new())
+                .is_err()
+        );
+    }
+
+    /// A ship with two damaged engines and `reserve` energy for repairs
+    fn spawn_damaged_ship(world: &mut GameWorld, health: [f32; 2], reserve: f32) -> String {
+        let ship_id = Uuid::new_v4().to_string();
+        let mut status = ShipStatus::new(1000.0, 100.0, 1000.0);
+        status.power_capacity = reserve;
+        let modules = ["port", "starboard"]
+            .into_iter()
+            .zip(health)
+            .map(|(instance_id, health)| CompiledModule {
+                current_health: health,
+                operational: health > 0.0,
+                ..module(instance_id, "impulse-engine", serde_json::json!({}))
+            })
+            .collect();
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Damaged".to_string(),
+            class: "cruiser".to_string(),
+            team_id: "team1".to_string(),
+            player_roles: HashMap::new(),
+            status,
+            modules,
+            weapons: vec![],
+            inventory: Inventory::new(),
+        });
+        world
+            .spawn_ship_entity(&ship_id, &create_test_game_config())
+            .unwrap();
+        ship_id
+    }
+
+    fn health(world: &GameWorld, ship_id: &str, module_id: &str) -> f32 {
+        let entity = world.get_ship_entity(ship_id).unwrap();
+        let tracker = world.world.get::<ModuleStateTracker>(entity).unwrap();
+        tracker.get(module_id).unwrap().current_health
+    }
+
+    #[test]
+    fn test_repair_restores_health_over_time() {
+        let mut world = GameWorld::new();
+        let ship_id = spawn_damaged_ship(&mut world, [50.0, 100.0], 1000.0);
+        assert!(world.queue_repair(&ship_id, "starboard").is_err());
+        world.queue_repair(&ship_id, "port").unwrap();
+
+        // 10% of max health every 2 seconds
+        for _ in 0..60 {
+            world.step_simulation();
+        }
+        assert!((health(&world, &ship_id, "port") - 55.0).abs() < 0.01);
+
+        for _ in 0..60 * 9 + 1 {
+            world.step_simulation();
+        }
+        assert_eq!(health(&world, &ship_id, "port"), 100.0);
+        assert!(world.repair_queue(&ship_id).is_empty());
+
+        let progress: Vec<f32> = world
+            .drain_events()
+            .into_iter()
+            .filter_map(|event| match event {
+                GameEvent::ModuleStatusChanged {
+                    module_id,
+                    health_percent,
+                    ..
+                } if module_id == "port" => Some(health_percent.round()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(progress, vec![60.0, 70.0, 80.0, 90.0, 100.0]);
+    }
+
+    #[test]
+    fn test_teams_limit_simultaneous_repairs() {
+        let mut world = GameWorld::new();
+        let ship_id = spawn_damaged_ship(&mut world, [90.0, 90.0], 1000.0);
+        world.queue_repair(&ship_id, "port").unwrap();
+        world.queue_repair(&ship_id, "starboard").unwrap();
+        world.queue_repair(&ship_id, "port").unwrap();
+        assert_eq!(world.repair_queue(&ship_id), vec!["port", "starboard"]);
+
+        // One team for a class without crew figures
+        for _ in 0..60 {
+            world.step_simulation();
+        }
+        assert!(health(&world, &ship_id, "port") > 94.9);
+        assert_eq!(health(&world, &ship_id, "starboard"), 90.0);
+
+        // A second team works both at once
+        let entity = world.get_ship_entity(&ship_id).unwrap();
+        world
+            .world
+            .get_mut::<RepairComponent>(entity)
+            .unwrap()
+            .teams = 2;
+        let port = health(&world, &ship_id, "port");
+        for _ in 0..30 {
+            world.step_simulation();
+        }
+        assert!(health(&world, &ship_id, "port") > port);
+        assert!(health(&world, &ship_id, "starboard") > 90.0);
+    }
+
+    #[test]
+    fn test_destroyed_module_needs_docking() {
+        let mut world = GameWorld::new();
+        let ship_id = spawn_damaged_ship(&mut world, [0.0, 100.0], 1000.0);
+        assert!(world.queue_repair(&ship_id, "port").is_err());
+
+        let mut station = Station::new("Haven".to_string(), [0.0; 3], "neutral".to_string());
+        station
+            .docked_ships
+            .push(Uuid::parse_str(&ship_id).unwrap());
+        world.register_station(station);
+        world.queue_repair(&ship_id, "port").unwrap();
+
+        for _ in 0..60 {
+            world.step_simulation();
+        }
+        assert!(health(&world, &ship_id, "port") > 4.9);
+        let entity = world.get_ship_entity(&ship_id).unwrap();
+        let tracker = world.world.get::<ModuleStateTracker>(entity).unwrap();
+        assert!(tracker.get("port").unwrap().operational);
+    }
+
+    #[test]
+    fn test_repairs_draw_reserve_power() {
+        let mut world = GameWorld::new();
+        // Enough reserve for 2 points of health at 0.5 energy each
+        let ship_id = spawn_damaged_ship(&mut world, [50.0, 100.0], 1.0);
+        world.queue_repair(&ship_id, "port").unwrap();
+
+        for _ in 0..120 {
+            world.step_simulation();
+        }
+        assert!((health(&world, &ship_id, "port") - 52.0).abs() < 0.01);
+        let entity = world.get_ship_entity(&ship_id).unwrap();
+        assert_eq!(world.world.get::<PowerGrid>(entity).unwrap().stored, 0.0);
+        assert_eq!(world.repair_queue(&ship_id), vec!["port"]);
+    }
+}
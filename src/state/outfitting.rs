@@ -0,0 +1,432 @@
+//! Station outfitting and docked refits
+//!
+//! Each station keeps a stock of modules, module variants, and ammunition,
+//! rolled the first time anyone looks at it or buys from it. How much it
+//! stocks depends on the station's type and faction (`data/outfitting.yaml`).
+//!
+//! A ship docked at a station offering repair can be refitted: the team
+//! sends the module list it wants, which is checked and compiled as a refit
+//! blueprint exactly as a new ship would be. New modules come out of the
+//! station's stock and removed modules that are still intact go into it.
+//! The team pays the difference between the refit blueprint's cost and the
+//! ship's current value, or is refunded it when the refit is cheaper, scaled
+//! by its standing with the station's faction.
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::GameWorld;
+use crate::compiler::{ShipCompiler, calculate_blueprint_cost, calculate_ship_value};
+use crate::config::GameConfig;
+use crate::models::{CompiledModule, ModuleInstance, ShipBlueprint};
+use crate::replay::ReplayCommand;
+use crate::stations::{StationInventory, StationService};
+
+/// Salt mixed into the stock seed so it differs from the market seed
+const STOCK_SEED_SALT: u64 = 0x6f75_7466_6974_7465;
+
+/// Outcome of a completed refit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefitReceipt {
+    /// Instance IDs of the modules installed
+    pub installed: Vec<String>,
+    /// Instance IDs of the modules removed
+    pub removed: Vec<String>,
+    /// Credits paid, or refunded when negative
+    pub cost: i64,
+    /// Team credits after the refit
+    pub credits: i64,
+}
+
+impl GameWorld {
+    /// Modules and ammunition a station has for sale
+    ///
+    /// A station that has not been stocked yet shows the stock it will
+    /// have once it is.
+    pub fn station_inventory(
+        &self,
+        station_id: &str,
+        config: &GameConfig,
+    ) -> Result<StationInventory, String> {
+        let station = self
+            .get_station(station_id)
+            .ok_or_else(|| format!("Station {} not found", station_id))?;
+        if station.inventory.stocked {
+            return Ok(station.inventory.clone());
+        }
+        let (high, low) = station.id.as_u64_pair();
+        let mut rng = StdRng::seed_from_u64(self.simulation.seed ^ high ^ low ^ STOCK_SEED_SALT);
+        Ok(StationInventory::stock(
+            station.station_type,
+            &station.faction,
+            config,
+            &mut rng,
+        ))
+    }
+
+    /// Stock a station that has not been stocked yet
+    pub(super) fn stock_station(&mut self, station_id: &str, config: &GameConfig) {
+        if let Ok(inventory) = self.station_inventory(station_id, config)
+            && let Some(station) = self.stations.get_mut(station_id)
+        {
+            station.inventory = inventory;
+        }
+    }
+
+    /// Swap the modules of a docked ship
+    ///
+    /// `modules` is the ship's complete module list after the refit. A
+    /// module keeps its health and runtime state if its instance ID, slot,
+    /// and variant are unchanged; any other module is installed new. Fails
+    /// without changing anything if the ship is not docked at a station
+    /// offering repair, the refit blueprint does not validate, a new module
+    /// is out of stock, nothing changes, or the team cannot pay.
+    pub fn refit_ship(
+        &mut self,
+        ship_id: &str,
+        modules: Vec<ModuleInstance>,
+        config: &GameConfig,
+    ) -> Result<RefitReceipt, String> {
+        let station = self
+            .docked_station(ship_id)
+            .ok_or_else(|| "Ship must be docked to refit".to_string())?;
+        if !station.services.offers(StationService::Repair) {
+            return Err(format!(
+                "Station {} does not offer {} service",
+                station.name,
+                StationService::Repair.name()
+            ));
+        }
+        let station_id = station.id.to_string();
+        let faction = station.faction.clone();
+        let ship = self
+            .ships
+            .get(ship_id)
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+
+        let retained: HashSet<String> = modules
+            .iter()
+            .filter(|module| {
+                ship.modules.iter().any(|current| {
+                    current.instance_id == module.id
+                        && current.module_id == module.module_slot_id
+                        && current.kind == module.variant_id
+                })
+            })
+            .map(|module| module.id.clone())
+            .collect();
+        let installed: Vec<ModuleInstance> = modules
+            .iter()
+            .filter(|module| !retained.contains(&module.id))
+            .cloned()
+            .collect();
+        let removed: Vec<CompiledModule> = ship
+            .modules
+            .iter()
+            .filter(|module| !retained.contains(&module.instance_id))
+            .cloned()
+            .collect();
+        if installed.is_empty() && removed.is_empty() {
+            return Err("Refit does not change any modules".to_string());
+        }
+
+        let blueprint = ShipBlueprint {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: ship.name.clone(),
+            class: ship.class.clone(),
+            team_id: ship.team_id.clone(),
+            player_roles: ship.player_roles.clone(),
+            modules: modules.clone(),
+            weapons: ship.weapons.clone(),
+            ready_players: ship.player_roles.keys().cloned().collect(),
+        };
+        let refitted = ShipCompiler::new(config)
+            .compile(&blueprint, self)
+            .map_err(|e| e.to_string())?;
+        let difference = calculate_blueprint_cost(&blueprint, config).map_err(|e| e.to_string())?
+            - calculate_ship_value(ship, config);
+        let team_id = ship.team_id.clone();
+        let cost = if difference > 0 {
+            (difference as f32 * self.price_factor(&team_id, &faction)).ceil() as i64
+        } else {
+            (difference as f32 * self.sale_factor(&team_id, &faction, 1.0)).ceil() as i64
+        };
+
+        let mut inventory = self.station_inventory(&station_id, config)?;
+        for module in &installed {
+            inventory.take_module(&module.module_slot_id, module.variant_id.as_deref())?;
+        }
+        for module in removed.iter().filter(|module| !module.is_destroyed()) {
+            inventory.add_module(&module.module_id, module.kind.as_deref());
+        }
+        if cost > 0 {
+            self.deduct_team_credits(&team_id, cost)?;
+        } else if cost < 0 {
+            self.refund_team_credits(&team_id, -cost)?;
+        }
+        if let Some(station) = self.stations.get_mut(&station_id) {
+            station.inventory = inventory;
+        }
+
+        let saved = self
+            .get_ship_entity(ship_id)
+            .and_then(|entity| self.snapshot_ship_simulation(ship_id, entity));
+        if let Some(ship) = self.ships.get_mut(ship_id) {
+            let mut new_modules = refitted.modules;
+            for module in &mut new_modules {
+                if !retained.contains(&module.instance_id) {
+                    continue;
+                }
+                if let Some(current) = ship
+                    .modules
+                    .iter()
+                    .find(|current| current.instance_id == module.instance_id)
+                {
+                    module.current_health = current.current_health;
+                    module.operational = current.operational;
+                    module.power_allocated = current.power_allocated;
+                    module.cooling_allocated = current.cooling_allocated;
+                }
+            }
+            let mut status = refitted.status;
+            status.hull = ship.status.hull.min(status.max_hull);
+            status.shields = ship.status.shields.min(status.max_shields);
+            status.shields_raised = ship.status.shields_raised;
+            status.status_effects = ship.status.status_effects.clone();
+            status.position = ship.status.position;
+            status.velocity = ship.status.velocity;
+            status.module_health = new_modules
+                .iter()
+                .map(|module| (module.instance_id.clone(), module.current_health))
+                .collect();
+            ship.modules = new_modules;
+            ship.status = status;
+        }
+
+        if self.get_ship_entity(ship_id).is_some() {
+            self.rebuild_ship_entity(ship_id, config)?;
+            if let Some(mut saved) = saved {
+                saved.modules.retain(|id, _| retained.contains(id));
+                saved
+                    .power_allocations
+                    .retain(|id, _| retained.contains(id));
+                saved.repair_queue.retain(|id| retained.contains(id));
+                self.restore_ship_simulation(ship_id, saved);
+            }
+        }
+
+        self.record(ReplayCommand::Refit {
+            ship_id: ship_id.to_string(),
+            modules,
+        });
+        Ok(RefitReceipt {
+            installed: installed.into_iter().map(|module| module.id).collect(),
+            removed: removed
+                .into_iter()
+                .map(|module| module.instance_id)
+                .collect(),
+            cost,
+            credits: self.get_team(&team_id).map_or(0, |team| team.credits),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ModuleSlot;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::role::ShipRole;
+    use crate::simulation::ModuleStateTracker;
+    use crate::stations::{Station, StockedModule};
+    use uuid::Uuid;
+
+    fn module_slot(id: &str, credit_cost: i64) -> ModuleSlot {
+        ModuleSlot {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            extended_desc: String::new(),
+            groups: vec!["Support".to_string()],
+            required: false,
+            has_varients: false,
+            base_cost: 10,
+            credit_cost,
+            max_slots: 4,
+            base_hp: 10,
+            base_power_consumption: 0.0,
+            base_heat_generation: 0.0,
+            base_weight: 100,
+        }
+    }
+
+    fn test_config() -> GameConfig {
+        let mut config = create_test_game_config();
+        for slot in [
+            module_slot("cargo-bay", 1_000),
+            module_slot("armor-plating", 2_500),
+        ] {
+            config.module_slots.insert(slot.id.clone(), slot);
+        }
+        config
+    }
+
+    fn module(id: &str, slot: &str) -> ModuleInstance {
+        ModuleInstance {
+            id: id.to_string(),
+            module_slot_id: slot.to_string(),
+            variant_id: None,
+        }
+    }
+
+    /// A ship with one cargo bay docked at a station stocking one armor
+    /// plate, for a team holding 10,000 credits
+    ///
+    /// Returns the station and ship IDs.
+    fn dock_cargo_ship(world: &mut GameWorld, config: &GameConfig) -> (String, String) {
+        let player_id = world.register_player("Rigger".to_string()).unwrap();
+        let team_id = world
+            .create_team_with_credits("Riggers".to_string(), "neutral".to_string(), 10_000)
+            .unwrap();
+        world.add_player_to_team(&team_id, &player_id).unwrap();
+
+        let mut blueprint =
+            ShipBlueprint::new("Hauler".to_string(), "cruiser".to_string(), team_id);
+        blueprint.set_player_roles(player_id.clone(), vec![ShipRole::Captain]);
+        blueprint.mark_ready(player_id);
+        blueprint.modules.push(module("bay-1", "cargo-bay"));
+        let ship = ShipCompiler::new(config)
+            .compile(&blueprint, world)
+            .unwrap();
+        let ship_id = ship.id.clone();
+        world.register_ship(ship);
+        world.spawn_ship_entity(&ship_id, config).unwrap();
+
+        let mut station = Station::new("Drydock".to_string(), [0.0; 3], "neutral".to_string());
+        station
+            .docked_ships
+            .push(Uuid::parse_str(&ship_id).unwrap());
+        station.inventory = StationInventory {
+            stocked: true,
+            modules: vec![StockedModule {
+                module_slot_id: "armor-plating".to_string(),
+                variant_id: None,
+                quantity: 1,
+            }],
+            ammunition: Default::default(),
+        };
+        let station_id = world.register_station(station);
+        (station_id, ship_id)
+    }
+
+    fn credits(world: &GameWorld, ship_id: &str) -> i64 {
+        let team_id = &world.get_ship(ship_id).unwrap().team_id;
+        world.get_team(team_id).unwrap().credits
+    }
+
+    #[test]
+    fn test_refit_charges_the_cost_difference() {
+        let mut world = GameWorld::new();
+        let config = test_config();
+        let (station_id, ship_id) = dock_cargo_ship(&mut world, &config);
+        world.ships.get_mut(&ship_id).unwrap().modules[0].current_health = 4.0;
+
+        let receipt = world
+            .refit_ship(
+                &ship_id,
+                vec![
+                    module("bay-1", "cargo-bay"),
+                    module("plate-1", "armor-plating"),
+                ],
+                &config,
+            )
+            .unwrap();
+        assert_eq!(receipt.installed, vec!["plate-1".to_string()]);
+        assert!(receipt.removed.is_empty());
+        assert_eq!(receipt.cost, 2_500);
+        assert_eq!(credits(&world, &ship_id), 7_500);
+
+        // The kept bay is as damaged as before and both modules are simulated
+        let ship = world.get_ship(&ship_id).unwrap();
+        assert_eq!(ship.modules.len(), 2);
+        assert_eq!(ship.modules[0].current_health, 4.0);
+        let entity = world.get_ship_entity(&ship_id).unwrap();
+        let tracker = world.world.get::<ModuleStateTracker>(entity).unwrap();
+        assert!(tracker.get("plate-1").is_some());
+        let station = world.get_station(&station_id).unwrap();
+        assert_eq!(station.inventory.module_quantity("armor-plating", None), 0);
+
+        // Dropping the bay refunds its cost and puts it in stock
+        let receipt = world
+            .refit_ship(&ship_id, vec![module("plate-1", "armor-plating")], &config)
+            .unwrap();
+        assert_eq!(receipt.removed, vec!["bay-1".to_string()]);
+        assert_eq!(receipt.cost, -1_000);
+        assert_eq!(credits(&world, &ship_id), 8_500);
+        let station = world.get_station(&station_id).unwrap();
+        assert_eq!(station.inventory.module_quantity("cargo-bay", None), 1);
+    }
+
+    #[test]
+    fn test_refit_fails_cleanly() {
+        let mut world = GameWorld::new();
+        let config = test_config();
+        let (station_id, ship_id) = dock_cargo_ship(&mut world, &config);
+        let two_plates = vec![
+            module("bay-1", "cargo-bay"),
+            module("plate-1", "armor-plating"),
+            module("plate-2", "armor-plating"),
+        ];
+
+        assert_eq!(
+            world.refit_ship(&ship_id, two_plates.clone(), &config),
+            Err("Module armor-plating is out of stock".to_string())
+        );
+        assert!(
+            world
+                .refit_ship(&ship_id, vec![module("bay-1", "cargo-bay")], &config)
+                .is_err()
+        );
+        assert!(
+            world
+                .refit_ship(&ship_id, vec![module("bay-1", "warp-core")], &config)
+                .is_err()
+        );
+        assert_eq!(credits(&world, &ship_id), 10_000);
+        assert_eq!(world.get_ship(&ship_id).unwrap().modules.len(), 1);
+        let station = world.get_station(&station_id).unwrap();
+        assert_eq!(station.inventory.module_quantity("armor-plating", None), 1);
+
+        world
+            .get_station_mut(&station_id)
+            .unwrap()
+            .docked_ships
+            .clear();
+        assert_eq!(
+            world.refit_ship(&ship_id, two_plates, &config),
+            Err("Ship must be docked to refit".to_string())
+        );
+    }
+
+    #[test]
+    fn test_station_inventory_is_rolled_once() {
+        let mut world = GameWorld::new();
+        let config = test_config();
+        let station_id = world.register_station(Station::new(
+            "Depot".to_string(),
+            [0.0; 3],
+            "neutral".to_string(),
+        ));
+
+        let preview = world.station_inventory(&station_id, &config).unwrap();
+        assert!(preview.stocked);
+        assert_eq!(preview.modules.len(), 2);
+        assert!(!world.get_station(&station_id).unwrap().inventory.stocked);
+
+        world.stock_station(&station_id, &config);
+        let station = world.get_station(&station_id).unwrap();
+        assert_eq!(station.inventory, preview);
+    }
+}
@@ -0,0 +1,469 @@
+//! Snapshots of the whole game world for saving sessions
+//!
+//! A [`WorldSnapshot`] holds every registry in `GameWorld` plus, for each
+//! ship in the simulation, the ECS state that is not already mirrored into
+//! its `ShipStatus`: orientation, helm orders, status effects, module
+//! runtime state, power allocations and reserves, weapon magazines,
+//! cooldowns, and targets, any scan under way, and queued repairs.
+//!
+//! Restoring re-spawns each ship entity from its `Ship` (which seeds hull,
+//! shields, position, and inventory) and then applies the saved ECS state.
+//! Projectiles in flight and commands still queued for the next tick are not
+//! saved.
+
+use bevy_ecs::prelude::*;
+use nalgebra::{Quaternion, UnitQuaternion, Vector3};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::{GameWorld, ScanReport};
+use crate::ai::AIRegistration;
+use crate::config::GameConfig;
+use crate::generation::Relationship;
+use crate::models::ship::{CaptainLogEntry, DockingRequest, FighterCommand, HailMessage};
+use crate::models::{Player, Ship, ShipBlueprint, Team};
+use crate::salvage::Wreck;
+use crate::simulation::{
+    HelmCommand, HelmComponent, ModuleRuntimeState, ModuleStateTracker, Ordnance, PowerGrid,
+    RepairComponent, ScanProgress, ShipData, SimulationState, StatusEffects, TargetingComponent,
+    Transform, WeaponComponent,
+};
+use crate::stations::Station;
+use crate::weapons::StatusEffectType;
+
+/// Everything needed to rebuild a `GameWorld`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    pub simulation: SimulationState,
+    pub players: HashMap<String, Player>,
+    /// Session token digests, so players keep their credentials across a
+    /// restart without the save holding a usable token
+    pub player_tokens: HashMap<String, String>,
+    pub teams: HashMap<String, Team>,
+    pub blueprints: HashMap<String, ShipBlueprint>,
+    pub ships: HashMap<String, Ship>,
+    pub stations: HashMap<String, Station>,
+    /// Wrecks left by destroyed ships
+    #[serde(default)]
+    pub wrecks: HashMap<String, Wreck>,
+    pub captain_logs: HashMap<String, Vec<CaptainLogEntry>>,
+    pub docking_requests: HashMap<String, DockingRequest>,
+    pub hail_messages: HashMap<String, HailMessage>,
+    pub fighter_commands: HashMap<String, FighterCommand>,
+    pub point_defense_settings: HashMap<String, bool>,
+    /// Scan knowledge bases (Ship ID -> target Ship ID -> report)
+    #[serde(default)]
+    pub scan_knowledge: HashMap<String, BTreeMap<String, ScanReport>>,
+    /// Relationships between procedural factions (name -> name -> relationship)
+    #[serde(default)]
+    pub faction_relations: HashMap<String, HashMap<String, Relationship>>,
+    pub ai_ships: Vec<AIRegistration>,
+    /// ECS state of each ship with a simulation entity (Ship ID -> state)
+    pub ship_simulation: HashMap<String, ShipSimulationSnapshot>,
+}
+
+/// ECS state of one simulated ship that `ShipStatus` does not carry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShipSimulationSnapshot {
+    /// Orientation quaternion as `[x, y, z, w]`
+    pub rotation: [f32; 4],
+    pub angular_velocity: [f32; 3],
+    pub thrust: [f32; 3],
+    pub braking: bool,
+    pub helm_queue: Vec<HelmCommand>,
+    /// Active status effects (effect -> remaining seconds)
+    pub status_effects: HashMap<StatusEffectType, f32>,
+    /// Module runtime state (instance ID -> state)
+    pub modules: BTreeMap<String, ModuleRuntimeState>,
+    /// Engineering power allocations (instance ID -> MW)
+    #[serde(default)]
+    pub power_allocations: BTreeMap<String, f32>,
+    /// Energy in the power grid's reserve
+    #[serde(default)]
+    pub power_stored: Option<f32>,
+    pub weapons: Vec<WeaponSnapshot>,
+    /// Scan under way, if any
+    #[serde(default)]
+    pub scan: Option<ScanSnapshot>,
+    /// Module instance IDs queued for repair, in order
+    #[serde(default)]
+    pub repair_queue: Vec<String>,
+}
+
+/// A science scan under way
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSnapshot {
+    pub target_id: String,
+    pub tier_time: f32,
+    pub tiers: u8,
+    pub elapsed: f32,
+    pub revealed: u8,
+}
+
+/// Magazine, readiness, and targeting of one weapon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponSnapshot {
+    pub id: String,
+    pub cooldown: f32,
+    pub ammunition: Option<String>,
+    pub ammo_count: u32,
+    pub ordnance: Option<Ordnance>,
+    pub is_automatic: bool,
+    pub is_active: bool,
+    /// Ship ID of the weapon's target
+    pub target: Option<String>,
+    pub lock_progress: f32,
+    pub is_locked: bool,
+}
+
+impl GameWorld {
+    /// Capture the entire world, including simulation state
+    pub fn snapshot(&self) -> WorldSnapshot {
+        let ship_simulation = self
+            .ship_entities
+            .iter()
+            .filter_map(|(ship_id, entity)| {
+                self.snapshot_ship_simulation(ship_id, *entity)
+                    .map(|snapshot| (ship_id.clone(), snapshot))
+            })
+            .collect();
+
+        WorldSnapshot {
+            simulation: self.simulation.clone(),
+            players: self.players.clone(),
+            player_tokens: self.player_tokens.clone(),
+            teams: self.teams.clone(),
+            blueprints: self.blueprints.clone(),
+            ships: self.ships.clone(),
+            stations: self.stations.clone(),
+            wrecks: self.wrecks.clone(),
+            captain_logs: self.captain_logs.clone(),
+            docking_requests: self.docking_requests.clone(),
+            hail_messages: self.hail_messages.clone(),
+            fighter_commands: self.fighter_commands.clone(),
+            point_defense_settings: self.point_defense_settings.clone(),
+            scan_knowledge: self.scan_knowledge.clone(),
+            faction_relations: self.faction_relations.clone(),
+            ai_ships: self.ai_manager.registrations(),
+            ship_simulation,
+        }
+    }
+
+    /// Rebuild a world from a snapshot
+    ///
+    /// Ships that were simulated when the snapshot was taken get fresh ECS
+    /// entities carrying their saved state. Fails if a ship cannot be
+    /// spawned.
+    pub fn from_snapshot(snapshot: WorldSnapshot, config: &GameConfig) -> Result<Self, String> {
+        let mut world = GameWorld::new();
+        world.configure_simulation(&config.simulation_params);
+        world.configure_markets(&config.commodities);
+
+        world.simulation = snapshot.simulation;
+        world.player_names = snapshot
+            .players
+            .values()
+            .map(|player| (player.name.clone(), player.id.clone()))
+            .collect();
+        world.team_names = snapshot
+            .teams
+            .values()
+            .map(|team| (team.name.clone(), team.id.clone()))
+            .collect();
+        world.players = snapshot.players;
+        world.player_tokens = snapshot.player_tokens;
+        world.teams = snapshot.teams;
+        world.blueprints = snapshot.blueprints;
+        world.ships = snapshot.ships;
+        world.stations = snapshot.stations;
+        world.wrecks = snapshot.wrecks;
+        world.captain_logs = snapshot.captain_logs;
+        world.docking_requests = snapshot.docking_requests;
+        world.hail_messages = snapshot.hail_messages;
+        world.fighter_commands = snapshot.fighter_commands;
+        world.point_defense_settings = snapshot.point_defense_settings;
+        world.scan_knowledge = snapshot.scan_knowledge;
+        world.faction_relations = snapshot.faction_relations;
+        for registration in snapshot.ai_ships {
+            world.ai_manager.restore(registration);
+        }
+
+        // Spawn every entity first so weapon targets can be resolved, in ID
+        // order so the ECS layout (and with it system iteration order) is the
+        // same every time a snapshot is restored
+        let mut ship_ids: Vec<&String> = snapshot.ship_simulation.keys().collect();
+        ship_ids.sort();
+        for ship_id in ship_ids {
+            world.spawn_ship_entity(ship_id, config)?;
+        }
+        for (ship_id, state) in snapshot.ship_simulation {
+            world.restore_ship_simulation(&ship_id, state);
+        }
+
+        Ok(world)
+    }
+
+    /// Capture the ECS state of one ship entity
+    pub(super) fn snapshot_ship_simulation(
+        &self,
+        ship_id: &str,
+        entity: Entity,
+    ) -> Option<ShipSimulationSnapshot> {
+        let transform = self.world.get::<Transform>(entity)?;
+        let helm = self.world.get::<HelmComponent>(entity);
+        let q = transform.rotation.coords;
+
+        let weapons = self
+            .weapon_entities(ship_id)
+            .into_iter()
+            .filter_map(|weapon_entity| {
+                let weapon = self.world.get::<WeaponComponent>(weapon_entity)?;
+                let targeting = self.world.get::<TargetingComponent>(weapon_entity);
+                let target = targeting
+                    .and_then(|targeting| targeting.target)
+                    .and_then(|target| self.world.get::<ShipData>(target))
+                    .map(|ship_data| ship_data.id.clone());
+                Some(WeaponSnapshot {
+                    id: weapon.id.clone(),
+                    cooldown: weapon.cooldown,
+                    ammunition: weapon.ammunition.clone(),
+                    ammo_count: weapon.ammo_count,
+                    ordnance: weapon.ordnance.clone(),
+                    is_automatic: weapon.is_automatic,
+                    is_active: weapon.is_active,
+                    target,
+                    lock_progress: targeting.map_or(0.0, |t| t.lock_progress),
+                    is_locked: targeting.is_some_and(|t| t.is_locked),
+                })
+            })
+            .collect();
+
+        let power_grid = self.world.get::<PowerGrid>(entity);
+
+        Some(ShipSimulationSnapshot {
+            rotation: [q.x, q.y, q.z, q.w],
+            angular_velocity: transform.angular_velocity.into(),
+            thrust: helm.map_or([0.0; 3], |helm| helm.thrust.into()),
+            braking: helm.is_some_and(|helm| helm.braking),
+            helm_queue: helm.map_or_else(Vec::new, |helm| helm.queue.iter().cloned().collect()),
+            status_effects: self
+                .world
+                .get::<StatusEffects>(entity)
+                .map(|effects| effects.effects.clone())
+                .unwrap_or_default(),
+            modules: self
+                .world
+                .get::<ModuleStateTracker>(entity)
+                .map(|tracker| tracker.states.clone())
+                .unwrap_or_default(),
+            power_allocations: power_grid
+                .map(|grid| grid.distribution.clone())
+                .unwrap_or_default(),
+            power_stored: power_grid.map(|grid| grid.stored),
+            weapons,
+            scan: self
+                .world
+                .get::<ScanProgress>(entity)
+                .map(|scan| ScanSnapshot {
+                    target_id: scan.target_id.clone(),
+                    tier_time: scan.tier_time,
+                    tiers: scan.tiers,
+                    elapsed: scan.elapsed,
+                    revealed: scan.revealed,
+                }),
+            repair_queue: self
+                .world
+                .get::<RepairComponent>(entity)
+                .map(|repair| repair.queue.iter().cloned().collect())
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Apply saved ECS state to a freshly spawned ship entity
+    pub(super) fn restore_ship_simulation(&mut self, ship_id: &str, state: ShipSimulationSnapshot) {
+        let Some(entity) = self.get_ship_entity(ship_id) else {
+            return;
+        };
+
+        if let Some(mut transform) = self.world.get_mut::<Transform>(entity) {
+            let [x, y, z, w] = state.rotation;
+            transform.rotation = UnitQuaternion::from_quaternion(Quaternion::new(w, x, y, z));
+            transform.angular_velocity = Vector3::from(state.angular_velocity);
+        }
+        if let Some(mut helm) = self.world.get_mut::<HelmComponent>(entity) {
+            helm.thrust = Vector3::from(state.thrust);
+            helm.braking = state.braking;
+            helm.queue = state.helm_queue.into();
+        }
+        if let Some(mut effects) = self.world.get_mut::<StatusEffects>(entity) {
+            effects.effects = state.status_effects;
+        }
+        if let Some(mut tracker) = self.world.get_mut::<ModuleStateTracker>(entity) {
+            tracker.states.extend(state.modules);
+        }
+        if let Some(mut power_grid) = self.world.get_mut::<PowerGrid>(entity) {
+            power_grid.distribution.extend(state.power_allocations);
+            if let Some(stored) = state.power_stored {
+                power_grid.stored = stored.min(power_grid.capacity);
+            }
+        }
+        if let Some(mut repair) = self.world.get_mut::<RepairComponent>(entity) {
+            repair.queue = state.repair_queue.into();
+        }
+        if let Some(scan) = state.scan
+            && let Some(target) = self.get_ship_entity(&scan.target_id)
+        {
+            self.world.entity_mut(entity).insert(ScanProgress {
+                target,
+                target_id: scan.target_id,
+                tier_time: scan.tier_time,
+                tiers: scan.tiers,
+                elapsed: scan.elapsed,
+                revealed: scan.revealed,
+            });
+        }
+
+        let mut saved: HashMap<String, WeaponSnapshot> = state
+            .weapons
+            .into_iter()
+            .map(|weapon| (weapon.id.clone(), weapon))
+            .collect();
+        for weapon_entity in self.weapon_entities(ship_id) {
+            let Some(id) = self
+                .world
+                .get::<WeaponComponent>(weapon_entity)
+                .map(|weapon| weapon.id.clone())
+            else {
+                continue;
+            };
+            let Some(snapshot) = saved.remove(&id) else {
+                continue;
+            };
+            let target = snapshot
+                .target
+                .as_deref()
+                .and_then(|target| self.get_ship_entity(target));
+
+            if let Some(mut weapon) = self.world.get_mut::<WeaponComponent>(weapon_entity) {
+                weapon.cooldown = snapshot.cooldown;
+                weapon.ammunition = snapshot.ammunition;
+                weapon.ammo_count = snapshot.ammo_count;
+                weapon.ordnance = snapshot.ordnance;
+                weapon.is_automatic = snapshot.is_automatic;
+                weapon.is_active = snapshot.is_active;
+            }
+            if let Some(mut targeting) = self.world.get_mut::<TargetingComponent>(weapon_entity) {
+                targeting.target = target;
+                targeting.lock_progress = snapshot.lock_progress;
+                targeting.is_locked = target.is_some() && snapshot.is_locked;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::ShipStatus;
+    use crate::models::role::ShipRole;
+    use crate::models::ship::CompiledModule;
+    use crate::models::status::Inventory;
+
+    fn register_ship(world: &mut GameWorld, team_id: &str, player_id: &str) -> String {
+        let ship_id = uuid::Uuid::new_v4().to_string();
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Persistent".to_string(),
+            class: "cruiser".to_string(),
+            team_id: team_id.to_string(),
+            player_roles: [(player_id.to_string(), vec![ShipRole::Captain])].into(),
+            status: ShipStatus::new(1000.0, 200.0, 1000.0),
+            modules: vec![CompiledModule {
+                instance_id: "laser".to_string(),
+                module_id: "de-weapon".to_string(),
+                kind: None,
+                name: "Laser".to_string(),
+                stats: serde_json::from_value(serde_json::json!({ "damage": 20 })).unwrap(),
+                current_health: 50.0,
+                max_health: 50.0,
+                operational: true,
+                power_allocated: 1.0,
+                cooling_allocated: 1.0,
+            }],
+            weapons: vec![],
+            inventory: Inventory::new(),
+        });
+        ship_id
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let config = create_test_game_config();
+        let mut world = GameWorld::new();
+        let player_id = world.register_player("alice".to_string()).unwrap();
+        let token = world.issue_player_token(&player_id).unwrap();
+        let team_id = world
+            .create_team("Alpha".to_string(), "Federation".to_string())
+            .unwrap();
+        world.add_player_to_team(&team_id, &player_id).unwrap();
+        let ship_id = register_ship(&mut world, &team_id, &player_id);
+        let target_id = register_ship(&mut world, &team_id, &player_id);
+        let entity = world.spawn_ship_entity(&ship_id, &config).unwrap();
+        world.spawn_ship_entity(&target_id, &config).unwrap();
+
+        world.add_thrust_command(ship_id.clone(), 0.0, 0.0, 5.0);
+        world
+            .set_energy_weapon_target(ship_id.clone(), target_id.clone())
+            .unwrap();
+        world
+            .world
+            .get_mut::<StatusEffects>(entity)
+            .unwrap()
+            .apply(StatusEffectType::IonJam, 4.0);
+        for _ in 0..3 {
+            world.step_simulation();
+        }
+
+        // Through JSON, as a save file would be
+        let json = serde_json::to_string(&world.snapshot()).unwrap();
+        assert!(!json.contains(&token));
+        let snapshot: WorldSnapshot = serde_json::from_str(&json).unwrap();
+        let restored = GameWorld::from_snapshot(snapshot, &config).unwrap();
+
+        assert_eq!(restored.simulation.tick, 3);
+        assert_eq!(restored.get_player_by_name("alice").unwrap().id, player_id);
+        assert_eq!(restored.authenticate_token(&token).unwrap().id, player_id);
+        assert_eq!(restored.get_team_by_name("Alpha").unwrap().id, team_id);
+        assert_eq!(
+            restored.get_ship(&ship_id).unwrap().status.position,
+            world.get_ship(&ship_id).unwrap().status.position
+        );
+
+        let entity = restored.get_ship_entity(&ship_id).unwrap();
+        let helm = restored.world.get::<HelmComponent>(entity).unwrap();
+        assert_eq!(helm.thrust, Vector3::new(0.0, 0.0, 5.0));
+        assert!(
+            restored
+                .world
+                .get::<StatusEffects>(entity)
+                .unwrap()
+                .has_effect(StatusEffectType::IonJam)
+        );
+        assert_eq!(
+            restored.get_energy_weapon_target(&ship_id),
+            Some(target_id.clone())
+        );
+    }
+
+    #[test]
+    fn test_unsimulated_ships_stay_unsimulated() {
+        let config = create_test_game_config();
+        let mut world = GameWorld::new();
+        let ship_id = register_ship(&mut world, "team1", "player1");
+
+        let restored = GameWorld::from_snapshot(world.snapshot(), &config).unwrap();
+        assert!(restored.get_ship(&ship_id).is_some());
+        assert!(restored.get_ship_entity(&ship_id).is_none());
+    }
+}
@@ -0,0 +1,208 @@
+//! Recording the commands applied to a session
+//!
+//! While a `Recorder` is attached, every command entry point that changes the
+//! simulation appends a [`ReplayCommand`] stamped with the current tick, and
+//! `step_simulation` folds the events of each tick into the recording's
+//! digest. Commands are recorded only once they have been applied
+//! successfully, so replaying them against the same world cannot fail
+//! unless the replay has already diverged.
+
+use super::GameWorld;
+use crate::config::GameConfig;
+use crate::replay::{Recorder, Recording, ReplayCommand};
+
+impl GameWorld {
+    /// Start recording the session
+    ///
+    /// The world is first rebuilt from its own snapshot, so the recorded
+    /// session and its replays start from an identical ECS layout. Queued
+    /// helm orders and events carry over, but projectiles in flight are lost
+    /// (snapshots do not hold them).
+    pub fn start_recording(&mut self, name: &str, config: &GameConfig) -> Result<(), String> {
+        if self.recorder.is_some() {
+            return Err("A recording is already in progress".to_string());
+        }
+
+        let initial = self.snapshot();
+        let mut rebuilt = GameWorld::from_snapshot(initial.clone(), config)?;
+        rebuilt.event_queue = std::mem::take(&mut self.event_queue);
+        rebuilt.jam_attempts = std::mem::take(&mut self.jam_attempts);
+        rebuilt.countermeasure_loads = std::mem::take(&mut self.countermeasure_loads);
+        rebuilt.countermeasure_activations = std::mem::take(&mut self.countermeasure_activations);
+        let mut pending: Vec<_> = std::mem::take(&mut self.helm_commands)
+            .into_iter()
+            .collect();
+        pending.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        *self = rebuilt;
+        self.recorder = Some(Recorder::new(name, initial));
+        for (ship_id, commands) in pending {
+            for command in commands {
+                self.queue_helm_command(ship_id.clone(), command);
+            }
+        }
+        Ok(())
+    }
+
+    /// Stop recording, returning the finished recording
+    pub fn stop_recording(&mut self) -> Option<Recording> {
+        let recorder = self.recorder.take()?;
+        Some(recorder.finish(self.simulation.tick))
+    }
+
+    /// Whether a recording is in progress
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Commands recorded so far, if recording
+    pub fn recorded_command_count(&self) -> Option<usize> {
+        self.recorder.as_ref().map(Recorder::command_count)
+    }
+
+    /// Record an applied command, if recording
+    pub(super) fn record(&mut self, command: ReplayCommand) {
+        let tick = self.simulation.tick;
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(tick, command);
+        }
+    }
+
+    /// Fold the events queued since `first_event` into the recording digest
+    pub(super) fn record_tick_events(&mut self, first_event: usize) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_tick(self.event_queue.since(first_event));
+        }
+    }
+
+    /// Apply a recorded command through the same entry point that recorded it
+    pub fn apply_replay_command(
+        &mut self,
+        command: ReplayCommand,
+        config: &GameConfig,
+    ) -> Result<(), String> {
+        match command {
+            ReplayCommand::Helm { ship_id, command } => {
+                self.queue_helm_command(ship_id, command);
+                Ok(())
+            }
+            ReplayCommand::WeaponTarget {
+                ship_id,
+                weapon_type,
+                target_id,
+            } => self.set_weapon_target(&ship_id, &weapon_type, &target_id),
+            ReplayCommand::FireWeapon {
+                ship_id,
+                weapon_type,
+                weapon_id,
+            } => self.fire_weapon(&ship_id, &weapon_id, &weapon_type),
+            ReplayCommand::AutoFire {
+                ship_id,
+                weapon_type,
+                weapon_id,
+                enabled,
+            } => self.set_auto_fire(&ship_id, &weapon_id, &weapon_type, enabled),
+            ReplayCommand::LoadWeapon {
+                ship_id,
+                weapon_type,
+                weapon_id,
+                ammo_type,
+                quantity,
+            } => self
+                .load_weapon(
+                    &ship_id,
+                    &weapon_id,
+                    &weapon_type,
+                    &ammo_type,
+                    quantity,
+                    config,
+                )
+                .map(|_| ()),
+            ReplayCommand::PowerAllocations {
+                ship_id,
+                allocations,
+            } => {
+                self.add_power_allocations(ship_id, allocations);
+                Ok(())
+            }
+            ReplayCommand::CoolingAllocations {
+                ship_id,
+                allocations,
+            } => {
+                self.add_cooling_allocations(ship_id, allocations);
+                Ok(())
+            }
+            ReplayCommand::PowerPriorities {
+                ship_id,
+                priorities,
+            } => self.set_power_priorities(&ship_id, priorities),
+            ReplayCommand::Repair { ship_id, module_id } => self.queue_repair(&ship_id, &module_id),
+            ReplayCommand::ActivateAuxiliary { ship_id, module_id } => {
+                self.activate_auxiliary(&ship_id, &module_id).map(|_| ())
+            }
+            ReplayCommand::RechargeAuxiliary { ship_id, module_id } => {
+                self.recharge_auxiliary(&ship_id, &module_id).map(|_| ())
+            }
+            ReplayCommand::StationService {
+                station_id,
+                ship_id,
+                request,
+            } => self
+                .perform_station_service(&station_id, &ship_id, request, config)
+                .map(|_| ()),
+            ReplayCommand::Trade {
+                station_id,
+                ship_id,
+                side,
+                commodity_id,
+                quantity,
+            } => self
+                .trade_commodity(&station_id, &ship_id, side, &commodity_id, quantity, config)
+                .map(|_| ()),
+            ReplayCommand::Refit { ship_id, modules } => {
+                self.refit_ship(&ship_id, modules, config).map(|_| ())
+            }
+            ReplayCommand::Tractor {
+                ship_id,
+                wreck_id,
+                engaged,
+            } => self.set_tractor(&ship_id, &wreck_id, engaged),
+            ReplayCommand::Salvage { ship_id, wreck_id } => {
+                self.salvage_wreck(&ship_id, &wreck_id, config).map(|_| ())
+            }
+            ReplayCommand::SellSalvage {
+                station_id,
+                ship_id,
+                module_id,
+            } => self
+                .sell_salvage(&station_id, &ship_id, &module_id, config)
+                .map(|_| ()),
+            ReplayCommand::CompleteContract { team_id, faction } => {
+                self.complete_contract(&team_id, &faction).map(|_| ())
+            }
+            ReplayCommand::Shields { ship_id, raised } => self.set_shields_raised(&ship_id, raised),
+            ReplayCommand::DeployChaff { ship_id } => self.deploy_chaff(&ship_id),
+            ReplayCommand::PointDefense { ship_id, enabled } => {
+                self.set_point_defense(ship_id, enabled);
+                Ok(())
+            }
+            ReplayCommand::RestockPointDefense { ship_id, quantity } => {
+                self.restock_point_defense(&ship_id, quantity);
+                Ok(())
+            }
+            ReplayCommand::SendMessage {
+                ship_id,
+                transmission,
+            } => self.queue_transmission(&ship_id, transmission).map(|_| ()),
+            ReplayCommand::Scan { ship_id, target_id } => {
+                self.start_scan(&ship_id, &target_id).map(|_| ())
+            }
+            ReplayCommand::Ai { ship_id, commands } => self.apply_ai_commands(&ship_id, commands),
+            ReplayCommand::SpawnShip { ship } => {
+                let ship_id = self.register_ship(*ship);
+                self.spawn_ship_entity(&ship_id, config).map(|_| ())
+            }
+            ReplayCommand::RemoveShip { ship_id } => self.remove_ship(&ship_id),
+        }
+    }
+}
@@ -0,0 +1,520 @@
+//! Team standing with factions
+//!
+//! Every team has a standing with every faction, from `min_standing` to
+//! `max_standing`. A faction the team has not dealt with yet starts from
+//! the procedural relationship between the team's faction and it, scaled by
+//! `standing_per_relationship`; without a generated universe every faction
+//! starts neutral at 0.
+//!
+//! Damaging a faction's ships costs standing with it, more if the faction
+//! was not already hostile to the team, and earns some with the factions
+//! hostile to it. Trading at a faction's stations and completing its
+//! contracts earn standing. Standing decides whether the faction's stations
+//! clear the team's ships to dock, scales their prices, and turns the
+//! faction's AI ships hostile at or below `hostile_threshold`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::GameWorld;
+use crate::config::simulation::{ReputationConfig, SimulationConfig};
+use crate::events::{GameEvent, ReputationCause};
+use crate::generation::{ProceduralFaction, Relationship};
+use crate::replay::ReplayCommand;
+use crate::simulation::SimulationParams;
+
+/// A team's standing with one faction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FactionStanding {
+    pub faction: String,
+    pub standing: f32,
+    /// Relationship level the standing amounts to
+    pub relationship: Relationship,
+    /// Whether the faction's AI ships treat the team as hostile
+    pub hostile: bool,
+    /// Whether the faction's stations clear the team's ships to dock
+    pub docking_allowed: bool,
+    /// Multiplier on the faction's station prices
+    pub price_factor: f32,
+}
+
+impl GameWorld {
+    /// Install the relationships between the factions of a procedural
+    /// universe, keyed by faction name
+    ///
+    /// Standings a team has already earned are kept; the rest start from
+    /// the new relationships. AI ships are turned hostile or not to match.
+    pub fn configure_faction_relations(&mut self, factions: &[ProceduralFaction]) {
+        let names: HashMap<&str, &str> = factions
+            .iter()
+            .map(|faction| (faction.id.as_str(), faction.name.as_str()))
+            .collect();
+        self.faction_relations = factions
+            .iter()
+            .map(|faction| {
+                let relations = faction
+                    .relationships
+                    .iter()
+                    .filter_map(|(other_id, relationship)| {
+                        names
+                            .get(other_id.as_str())
+                            .map(|name| (name.to_string(), *relationship))
+                    })
+                    .collect();
+                (faction.name.clone(), relations)
+            })
+            .collect();
+
+        let reputation = self.reputation_config();
+        for faction in self.known_factions() {
+            for team_id in self.teams.keys() {
+                let hostile = self.standing(team_id, &faction) <= reputation.hostile_threshold;
+                self.ai_manager
+                    .set_team_hostility(&faction, team_id, hostile);
+            }
+        }
+    }
+
+    /// A team's standing with a faction
+    ///
+    /// Unknown teams stand at 0 with everyone.
+    pub fn standing(&self, team_id: &str, faction: &str) -> f32 {
+        let Some(team) = self.teams.get(team_id) else {
+            return 0.0;
+        };
+        match team.reputation.get(faction) {
+            Some(standing) => *standing,
+            None => self.seed_standing(&team.faction, faction),
+        }
+    }
+
+    /// A team's standing with every faction it has dealt with or that
+    /// the world knows of
+    pub fn reputation(&self, team_id: &str) -> Result<Vec<FactionStanding>, String> {
+        if !self.teams.contains_key(team_id) {
+            return Err(format!("Team {} not found", team_id));
+        }
+        let reputation = self.reputation_config();
+
+        Ok(self
+            .known_factions()
+            .into_iter()
+            .map(|faction| {
+                let standing = self.standing(team_id, &faction);
+                let relationship = if reputation.standing_per_relationship > 0.0 {
+                    Relationship::from_value(
+                        (standing / reputation.standing_per_relationship).round() as i32,
+                    )
+                } else {
+                    Relationship::Neutral
+                };
+                FactionStanding {
+                    relationship,
+                    hostile: standing <= reputation.hostile_threshold,
+                    docking_allowed: standing >= reputation.docking_threshold,
+                    price_factor: self.price_factor(team_id, &faction),
+                    standing,
+                    faction,
+                }
+            })
+            .collect())
+    }
+
+    /// IDs of the teams a faction's AI ships should treat as hostile
+    pub fn hostile_teams(&self, faction: &str) -> Vec<String> {
+        let threshold = self.reputation_config().hostile_threshold;
+        let mut teams: Vec<String> = self
+            .teams
+            .keys()
+            .filter(|team_id| self.standing(team_id, faction) <= threshold)
+            .cloned()
+            .collect();
+        teams.sort();
+        teams
+    }
+
+    /// Multiplier on what a faction's stations charge a team
+    ///
+    /// What they pay is scaled by [`Self::sale_factor`] instead.
+    pub fn price_factor(&self, team_id: &str, faction: &str) -> f32 {
+        let reputation = self.reputation_config();
+        if reputation.max_standing <= 0.0 {
+            return 1.0;
+        }
+        let standing = self.standing(team_id, faction) / reputation.max_standing;
+        1.0 - standing.clamp(-1.0, 1.0) * reputation.price_spread
+    }
+
+    /// Multiplier on what a faction's stations pay a team for goods they
+    /// normally buy at `resale_ratio` of their asking price
+    ///
+    /// Payments are divided by the price factor, but never rise above what
+    /// the team would be charged to buy the goods back, so no standing makes
+    /// a round trip pay.
+    pub fn sale_factor(&self, team_id: &str, faction: &str, resale_ratio: f32) -> f32 {
+        let price_factor = self.price_factor(team_id, faction);
+        let factor = 1.0 / price_factor;
+        if resale_ratio > 0.0 {
+            factor.min(price_factor / resale_ratio)
+        } else {
+            factor
+        }
+    }
+
+    /// Whether a station's faction will clear a ship to dock
+    ///
+    /// Ships that are not registered, or stations that do not exist, are
+    /// left to the station's own checks.
+    pub fn docking_permitted(&self, ship_id: &str, station_id: &str) -> bool {
+        let (Some(ship), Some(station)) = (self.ships.get(ship_id), self.stations.get(station_id))
+        else {
+            return true;
+        };
+        self.standing(&ship.team_id, &station.faction) >= self.reputation_config().docking_threshold
+    }
+
+    /// Reward a team for completing a contract for a faction
+    ///
+    /// Only server-side contract logic may call this; there is no client
+    /// route, since a client could report contracts without end. Returns the
+    /// team's new standing with the faction.
+    pub(crate) fn complete_contract(
+        &mut self,
+        team_id: &str,
+        faction: &str,
+    ) -> Result<f32, String> {
+        if !self.teams.contains_key(team_id) {
+            return Err(format!("Team {} not found", team_id));
+        }
+        if faction.is_empty() {
+            return Err("Faction cannot be empty".to_string());
+        }
+
+        let gain = self.reputation_config().contract_gain;
+        self.adjust_reputation(team_id, faction, gain, ReputationCause::Contract);
+        self.record(ReplayCommand::CompleteContract {
+            team_id: team_id.to_string(),
+            faction: faction.to_string(),
+        });
+        Ok(self.standing(team_id, faction))
+    }
+
+    /// Settle the standing a trade earns with a station's faction
+    pub(super) fn reward_trade(&mut self, team_id: &str, faction: &str, credits: i64) {
+        let gain = credits as f32 * self.reputation_config().trade_gain_per_credit;
+        self.adjust_reputation(team_id, faction, gain, ReputationCause::Trade);
+    }
+
+    /// Settle the standing this tick's hits cost and earn
+    ///
+    /// Each hit is (attacking ship ID, damaged ship ID, damage). Changes
+    /// are summed per team and faction before they are applied, so a
+    /// volley raises one event per faction.
+    pub(super) fn settle_combat_reputation(&mut self, hits: &[(String, String, f32)]) {
+        let reputation = self.reputation_config();
+        let mut losses: BTreeMap<(String, String), f32> = BTreeMap::new();
+        let mut gains: BTreeMap<(String, String), f32> = BTreeMap::new();
+
+        for (attacker_id, target_id, damage) in hits {
+            let (Some(attacker), Some(target)) =
+                (self.ships.get(attacker_id), self.ships.get(target_id))
+            else {
+                continue;
+            };
+            if attacker.team_id == target.team_id {
+                continue;
+            }
+            let Some(faction) = self
+                .teams
+                .get(&target.team_id)
+                .map(|team| team.faction.clone())
+                .filter(|faction| !faction.is_empty())
+            else {
+                continue;
+            };
+
+            let mut loss = damage * reputation.combat_loss_per_damage;
+            if self.standing(&attacker.team_id, &faction) > reputation.hostile_threshold {
+                loss *= reputation.neutral_attack_multiplier;
+            }
+            *losses
+                .entry((attacker.team_id.clone(), faction.clone()))
+                .or_insert(0.0) += loss;
+            for (enemy, relations) in &self.faction_relations {
+                if relations.get(&faction).is_some_and(|relationship| {
+                    relationship.value() <= Relationship::Hostile.value()
+                }) {
+                    *gains
+                        .entry((attacker.team_id.clone(), enemy.clone()))
+                        .or_insert(0.0) += loss * reputation.enemy_gain_ratio;
+                }
+            }
+        }
+
+        for ((team_id, faction), loss) in losses {
+            self.adjust_reputation(&team_id, &faction, -loss, ReputationCause::Combat);
+        }
+        for ((team_id, faction), gain) in gains {
+            self.adjust_reputation(&team_id, &faction, gain, ReputationCause::EnemyCombat);
+        }
+    }
+
+    /// Change a team's standing with a faction, within the configured range
+    ///
+    /// Turns the faction's AI ships hostile or back to neutral when the
+    /// standing crosses `hostile_threshold`, and broadcasts the change.
+    fn adjust_reputation(
+        &mut self,
+        team_id: &str,
+        faction: &str,
+        change: f32,
+        cause: ReputationCause,
+    ) {
+        let reputation = self.reputation_config();
+        let before = self.standing(team_id, faction);
+        let standing = (before + change).clamp(reputation.min_standing, reputation.max_standing);
+        if standing == before {
+            return;
+        }
+        let Some(team) = self.teams.get_mut(team_id) else {
+            return;
+        };
+        team.reputation.insert(faction.to_string(), standing);
+
+        let hostile = standing <= reputation.hostile_threshold;
+        if hostile != (before <= reputation.hostile_threshold) {
+            self.ai_manager
+                .set_team_hostility(faction, team_id, hostile);
+        }
+        self.push_event(GameEvent::ReputationChanged {
+            team_id: team_id.to_string(),
+            faction: faction.to_string(),
+            standing,
+            change: standing - before,
+            cause,
+            hostile,
+        });
+    }
+
+    /// Standing a team of `team_faction` starts with toward `faction`
+    fn seed_standing(&self, team_faction: &str, faction: &str) -> f32 {
+        let reputation = self.reputation_config();
+        let steps = self
+            .faction_relations
+            .get(team_faction)
+            .and_then(|relations| relations.get(faction))
+            .map_or(0, |relationship| relationship.value());
+        (steps as f32 * reputation.standing_per_relationship)
+            .clamp(reputation.min_standing, reputation.max_standing)
+    }
+
+    /// Every faction with relations, stations, teams, or standings on record
+    fn known_factions(&self) -> BTreeSet<String> {
+        let mut factions: BTreeSet<String> = self.faction_relations.keys().cloned().collect();
+        for relations in self.faction_relations.values() {
+            factions.extend(relations.keys().cloned());
+        }
+        factions.extend(
+            self.stations
+                .values()
+                .map(|station| station.faction.clone()),
+        );
+        for team in self.teams.values() {
+            factions.insert(team.faction.clone());
+            factions.extend(team.reputation.keys().cloned());
+        }
+        factions.remove("");
+        factions
+    }
+
+    fn reputation_config(&self) -> ReputationConfig {
+        self.world
+            .get_resource::<SimulationParams>()
+            .map(|params| params.reputation.clone())
+            .unwrap_or_else(|| SimulationConfig::default().reputation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Inventory, Ship, ShipStatus};
+    use crate::stations::Station;
+    use uuid::Uuid;
+
+    /// A world where the Federation is at war with the Empire and friendly
+    /// with the Alliance
+    fn world_with_relations() -> GameWorld {
+        let mut world = GameWorld::new();
+        world.faction_relations = HashMap::from([
+            (
+                "Federation".to_string(),
+                HashMap::from([
+                    ("Empire".to_string(), Relationship::War),
+                    ("Alliance".to_string(), Relationship::Friendly),
+                ]),
+            ),
+            (
+                "Alliance".to_string(),
+                HashMap::from([("Empire".to_string(), Relationship::Hostile)]),
+            ),
+        ]);
+        world
+    }
+
+    fn spawn_ship(world: &mut GameWorld, team_id: &str) -> String {
+        let ship_id = Uuid::new_v4().to_string();
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Ship".to_string(),
+            class: "cruiser".to_string(),
+            team_id: team_id.to_string(),
+            player_roles: HashMap::new(),
+            status: ShipStatus::default(),
+            modules: vec![],
+            weapons: vec![],
+            inventory: Inventory::new(),
+        });
+        ship_id
+    }
+
+    #[test]
+    fn test_standing_starts_from_faction_relations() {
+        let mut world = world_with_relations();
+        let team_id = world
+            .create_team_with_credits("Red".to_string(), "Federation".to_string(), 0)
+            .unwrap();
+
+        assert_eq!(world.standing(&team_id, "Empire"), -60.0);
+        assert_eq!(world.standing(&team_id, "Alliance"), 40.0);
+        assert_eq!(world.standing(&team_id, "Pirates"), 0.0);
+        assert_eq!(world.hostile_teams("Empire"), vec![team_id.clone()]);
+        assert!(world.hostile_teams("Alliance").is_empty());
+
+        // Better standing, lower prices
+        assert!((world.price_factor(&team_id, "Alliance") - 0.9).abs() < 1e-6);
+        assert!((world.price_factor(&team_id, "Empire") - 1.15).abs() < 1e-6);
+        // Sales pay less with poor standing, but never more than the ask
+        assert!((world.sale_factor(&team_id, "Empire", 0.9) - 1.0 / 1.15).abs() < 1e-6);
+        assert!((world.sale_factor(&team_id, "Alliance", 0.9) - 1.0).abs() < 1e-6);
+        assert!((world.sale_factor(&team_id, "Alliance", 0.5) - 1.0 / 0.9).abs() < 1e-6);
+
+        let standings = world.reputation(&team_id).unwrap();
+        let empire = standings.iter().find(|s| s.faction == "Empire").unwrap();
+        assert_eq!(empire.relationship, Relationship::War);
+        assert!(empire.hostile);
+        assert!(!empire.docking_allowed);
+        let alliance = standings.iter().find(|s| s.faction == "Alliance").unwrap();
+        assert_eq!(alliance.relationship, Relationship::Friendly);
+        assert!(alliance.docking_allowed);
+
+        assert!(world.reputation("missing").is_err());
+    }
+
+    #[test]
+    fn test_attacking_neutrals_costs_more_and_pleases_their_enemies() {
+        let mut world = world_with_relations();
+        let red = world
+            .create_team_with_credits("Red".to_string(), "Federation".to_string(), 0)
+            .unwrap();
+        let blue = world
+            .create_team_with_credits("Blue".to_string(), "Empire".to_string(), 0)
+            .unwrap();
+        let green = world
+            .create_team_with_credits("Green".to_string(), "Pirates".to_string(), 0)
+            .unwrap();
+        let attacker = spawn_ship(&mut world, &red);
+        let imperial = spawn_ship(&mut world, &blue);
+        let pirate = spawn_ship(&mut world, &green);
+        world.drain_events();
+
+        // The Empire is already hostile to Red: 100 damage costs 2 standing,
+        // half of which the factions hostile to the Empire pay back
+        world.settle_combat_reputation(&[(attacker.clone(), imperial, 100.0)]);
+        assert!((world.standing(&red, "Empire") + 62.0).abs() < 1e-4);
+        assert!((world.standing(&red, "Alliance") - 41.0).abs() < 1e-4);
+
+        // Pirates were neutral, so the same damage costs three times as much
+        world.settle_combat_reputation(&[(attacker, pirate, 100.0)]);
+        assert!((world.standing(&red, "Pirates") + 6.0).abs() < 1e-4);
+
+        let changes: Vec<(String, ReputationCause)> = world
+            .drain_events()
+            .into_iter()
+            .filter_map(|event| match event {
+                GameEvent::ReputationChanged { faction, cause, .. } => Some((faction, cause)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("Empire".to_string(), ReputationCause::Combat),
+                ("Alliance".to_string(), ReputationCause::EnemyCombat),
+                ("Federation".to_string(), ReputationCause::EnemyCombat),
+                ("Pirates".to_string(), ReputationCause::Combat),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crossing_the_hostile_threshold_turns_ai_ships() {
+        let mut world = GameWorld::new();
+        let red = world
+            .create_team_with_credits("Red".to_string(), "Federation".to_string(), 0)
+            .unwrap();
+        let blue = world
+            .create_team_with_credits("Blue".to_string(), "Pirates".to_string(), 0)
+            .unwrap();
+        let attacker = spawn_ship(&mut world, &red);
+        let pirate = spawn_ship(&mut world, &blue);
+        world.ai_manager.register_ship(
+            Uuid::new_v4(),
+            "Pirates".to_string(),
+            crate::ai::AIPersonality::Aggressive,
+        );
+        let hostile_teams =
+            |world: &GameWorld| world.ai_manager.registrations()[0].hostile_teams.clone();
+        assert!(hostile_teams(&world).is_empty());
+
+        // 900 damage to a neutral costs 54 standing
+        world.settle_combat_reputation(&[(attacker, pirate, 900.0)]);
+        assert!(world.standing(&red, "Pirates") <= -50.0);
+        assert_eq!(hostile_teams(&world), vec![red.clone()]);
+
+        // Enough contracts bring them back around
+        world.complete_contract(&red, "Pirates").unwrap();
+        assert!(hostile_teams(&world).is_empty());
+        assert!(world.complete_contract(&red, "").is_err());
+    }
+
+    #[test]
+    fn test_low_standing_denies_docking() {
+        let mut world = world_with_relations();
+        let red = world
+            .create_team_with_credits("Red".to_string(), "Federation".to_string(), 0)
+            .unwrap();
+        let ship_id = spawn_ship(&mut world, &red);
+        let imperial = world.register_station(Station::new(
+            "Outpost".to_string(),
+            [0.0; 3],
+            "Empire".to_string(),
+        ));
+        let allied = world.register_station(Station::new(
+            "Haven".to_string(),
+            [0.0; 3],
+            "Alliance".to_string(),
+        ));
+
+        assert!(!world.docking_permitted(&ship_id, &imperial));
+        assert!(world.docking_permitted(&ship_id, &allied));
+        // Unregistered ships are left to the station
+        assert!(world.docking_permitted(&Uuid::new_v4().to_string(), &imperial));
+
+        for _ in 0..4 {
+            world.complete_contract(&red, "Empire").unwrap();
+        }
+        assert_eq!(world.standing(&red, "Empire"), -20.0);
+        assert!(world.docking_permitted(&ship_id, &imperial));
+    }
+}
@@ -0,0 +1,729 @@
+//! Ship destruction, wrecks, and salvage
+//!
+//! A ship whose hull is gone is destroyed at the end of the tick: it leaves
+//! the registries and the simulation, and a [`Wreck`] takes its place with
+//! part of its hold and the modules that survived. A ship within
+//! `tractor_range` of a wreck can lock its tractor beam on; each tick the
+//! beam pulls the wreck in at `tractor_speed` and matches it to the ship's
+//! velocity, letting go if the ship moves out of range. Once the wreck is
+//! within `salvage_range`, the ship holding it can salvage it: all of its
+//! ammunition, and its cargo and modules as far as the hold has room.
+//!
+//! Salvaged modules ride in the hold, `module_volume` each, and sell at any
+//! station with a market for `sale_fraction` of their value, scaled by
+//! their health and by the team's standing with the station's faction.
+//! Sold modules join the station's outfitting stock.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+use super::{GameWorld, distance_squared};
+use crate::compiler::calculate_module_value;
+use crate::config::GameConfig;
+use crate::config::simulation::{SalvageConfig, SimulationConfig};
+use crate::events::GameEvent;
+use crate::replay::ReplayCommand;
+use crate::salvage::Wreck;
+use crate::simulation::{ShipData, SimulationParams, SimulationRng};
+use crate::stations::StationService;
+
+/// What a ship took from a wreck
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SalvageReceipt {
+    pub wreck_id: String,
+    /// Rounds taken (ammo_type -> rounds)
+    pub ammunition: BTreeMap<String, u32>,
+    /// Cargo taken (item_id -> quantity)
+    pub cargo: BTreeMap<String, u32>,
+    /// Instance IDs of the modules taken
+    pub modules: Vec<String>,
+    /// Whether the wreck was stripped bare (and is gone)
+    pub emptied: bool,
+}
+
+/// A salvaged module sold to a station
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SalvageSale {
+    /// Instance ID of the module sold
+    pub module_id: String,
+    pub name: String,
+    /// Credits paid for it
+    pub credits: i64,
+    /// Team credit balance afterwards
+    pub balance: i64,
+}
+
+impl GameWorld {
+    /// Get reference to wrecks HashMap
+    pub fn wrecks(&self) -> &HashMap<String, Wreck> {
+        &self.wrecks
+    }
+
+    /// Get wreck by ID
+    pub fn get_wreck(&self, id: &str) -> Option<&Wreck> {
+        self.wrecks.get(id)
+    }
+
+    /// Lock or release a ship's tractor beam on a wreck
+    ///
+    /// A ship holds one wreck at a time, so locking on releases any other.
+    /// Locking needs the wreck within `tractor_range` and not held by
+    /// another ship.
+    pub fn set_tractor(
+        &mut self,
+        ship_id: &str,
+        wreck_id: &str,
+        engaged: bool,
+    ) -> Result<(), String> {
+        let salvage = self.salvage_config();
+        let ship = self
+            .ships
+            .get(ship_id)
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+        let wreck = self
+            .wrecks
+            .get(wreck_id)
+            .ok_or_else(|| format!("Wreck {} not found", wreck_id))?;
+
+        if engaged {
+            let distance = distance_squared(ship.status.position, wreck.position).sqrt();
+            if distance > salvage.tractor_range {
+                return Err(format!(
+                    "Wreck is out of tractor range ({:.0} m away, range {:.0} m)",
+                    distance, salvage.tractor_range
+                ));
+            }
+            if wreck
+                .tractored_by
+                .as_ref()
+                .is_some_and(|holder| holder != ship_id)
+            {
+                return Err("Wreck is held by another ship's tractor beam".to_string());
+            }
+            for wreck in self.wrecks.values_mut() {
+                if wreck.tractored_by.as_deref() == Some(ship_id) {
+                    wreck.tractored_by = None;
+                }
+            }
+            if let Some(wreck) = self.wrecks.get_mut(wreck_id) {
+                wreck.tractored_by = Some(ship_id.to_string());
+            }
+        } else {
+            if wreck.tractored_by.as_deref() != Some(ship_id) {
+                return Err("Ship's tractor beam is not holding that wreck".to_string());
+            }
+            if let Some(wreck) = self.wrecks.get_mut(wreck_id) {
+                wreck.tractored_by = None;
+            }
+        }
+
+        self.record(ReplayCommand::Tractor {
+            ship_id: ship_id.to_string(),
+            wreck_id: wreck_id.to_string(),
+            engaged,
+        });
+        Ok(())
+    }
+
+    /// Move a wreck's contents into the hold of the ship holding it
+    ///
+    /// Takes all ammunition, then cargo in item order and modules as far as
+    /// the hold has room. A wreck with nothing left is removed. Fails
+    /// without changing anything if the ship's tractor beam is not holding
+    /// the wreck, the wreck is out of salvage range, or nothing fits.
+    pub fn salvage_wreck(
+        &mut self,
+        ship_id: &str,
+        wreck_id: &str,
+        config: &GameConfig,
+    ) -> Result<SalvageReceipt, String> {
+        let salvage = self.salvage_config();
+        let ship = self
+            .ships
+            .get(ship_id)
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+        let wreck = self
+            .wrecks
+            .get(wreck_id)
+            .ok_or_else(|| format!("Wreck {} not found", wreck_id))?;
+        if wreck.tractored_by.as_deref() != Some(ship_id) {
+            return Err("Lock a tractor beam on the wreck before salvaging it".to_string());
+        }
+        let distance = distance_squared(ship.status.position, wreck.position).sqrt();
+        if distance > salvage.salvage_range {
+            return Err(format!(
+                "Wreck is out of salvage range ({:.0} m away, range {:.0} m)",
+                distance, salvage.salvage_range
+            ));
+        }
+
+        let mut free = self.cargo_capacity(ship, config) - self.cargo_volume(ship);
+        let ammunition: BTreeMap<String, u32> = wreck
+            .inventory
+            .ammunition
+            .iter()
+            .map(|(ammo_type, rounds)| (ammo_type.clone(), *rounds))
+            .collect();
+        let mut cargo = BTreeMap::new();
+        let mut items: Vec<(&String, &u32)> = wreck.inventory.cargo.iter().collect();
+        items.sort();
+        for (item_id, quantity) in items {
+            let volume = self.commodity_volume(item_id);
+            let fits = if volume > 0.0 {
+                ((free / volume).floor().max(0.0) as u32).min(*quantity)
+            } else {
+                *quantity
+            };
+            if fits > 0 {
+                free -= fits as f32 * volume;
+                cargo.insert(item_id.clone(), fits);
+            }
+        }
+        let mut modules = Vec::new();
+        for module in wreck.modules.iter().chain(&wreck.inventory.salvage) {
+            if free < salvage.module_volume {
+                break;
+            }
+            free -= salvage.module_volume;
+            modules.push(module.instance_id.clone());
+        }
+        if ammunition.is_empty() && cargo.is_empty() && modules.is_empty() {
+            return Err("Not enough cargo space to salvage anything".to_string());
+        }
+
+        let Some(mut wreck) = self.wrecks.remove(wreck_id) else {
+            return Err(format!("Wreck {} not found", wreck_id));
+        };
+        wreck.inventory.ammunition.clear();
+        for (item_id, quantity) in &cargo {
+            if let Some(left) = wreck.inventory.cargo.get_mut(item_id) {
+                *left -= quantity;
+                if *left == 0 {
+                    wreck.inventory.cargo.remove(item_id);
+                }
+            }
+        }
+        let mut taken = Vec::new();
+        for stack in [&mut wreck.modules, &mut wreck.inventory.salvage] {
+            let (moved, kept) = std::mem::take(stack)
+                .into_iter()
+                .partition(|module| modules.contains(&module.instance_id));
+            *stack = kept;
+            taken.extend::<Vec<_>>(moved);
+        }
+
+        if let Some(ship) = self.ships.get_mut(ship_id) {
+            for (ammo_type, rounds) in &ammunition {
+                ship.inventory.add_ammunition(ammo_type.clone(), *rounds);
+            }
+            for (item_id, quantity) in &cargo {
+                *ship.inventory.cargo.entry(item_id.clone()).or_insert(0) += quantity;
+            }
+            ship.inventory.salvage.extend(taken);
+        }
+        self.sync_inventory_component(ship_id);
+
+        let emptied = wreck.is_empty();
+        if !emptied {
+            self.wrecks.insert(wreck_id.to_string(), wreck);
+        }
+
+        self.record(ReplayCommand::Salvage {
+            ship_id: ship_id.to_string(),
+            wreck_id: wreck_id.to_string(),
+        });
+        Ok(SalvageReceipt {
+            wreck_id: wreck_id.to_string(),
+            ammunition,
+            cargo,
+            modules,
+            emptied,
+        })
+    }
+
+    /// Sell a salvaged module from a ship's hold to the station it is
+    /// docked at
+    ///
+    /// Fails without changing anything if the ship is not docked at the
+    /// station, the station has no market, or the ship carries no such
+    /// module.
+    pub fn sell_salvage(
+        &mut self,
+        station_id: &str,
+        ship_id: &str,
+        module_id: &str,
+        config: &GameConfig,
+    ) -> Result<SalvageSale, String> {
+        let station = self
+            .get_station(station_id)
+            .ok_or_else(|| format!("Station {} not found", station_id))?;
+        if self
+            .docked_station(ship_id)
+            .is_none_or(|docked| docked.id != station.id)
+        {
+            return Err("Ship must be docked to sell salvage".to_string());
+        }
+        if !station.services.offers(StationService::Trade) {
+            return Err("Trade services not available".to_string());
+        }
+        let faction = station.faction.clone();
+        let ship = self
+            .ships
+            .get(ship_id)
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+        let module = ship
+            .inventory
+            .salvage
+            .iter()
+            .find(|module| module.instance_id == module_id)
+            .ok_or_else(|| format!("Ship carries no salvaged module {}", module_id))?
+            .clone();
+        let team_id = ship.team_id.clone();
+
+        let wear = if module.max_health > 0.0 {
+            (module.current_health / module.max_health).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        let sale_fraction = self.salvage_config().sale_fraction;
+        let value = calculate_module_value(&module, config) as f32 * sale_fraction * wear;
+        let credits = (value * self.sale_factor(&team_id, &faction, sale_fraction)).floor() as i64;
+        let balance = self.add_team_credits(&team_id, credits)?;
+
+        if let Some(ship) = self.ships.get_mut(ship_id) {
+            ship.inventory
+                .salvage
+                .retain(|carried| carried.instance_id != module_id);
+        }
+        self.stock_station(station_id, config);
+        if let Some(station) = self.stations.get_mut(station_id) {
+            station
+                .inventory
+                .add_module(&module.module_id, module.kind.as_deref());
+        }
+        self.reward_trade(&team_id, &faction, credits);
+
+        self.record(ReplayCommand::SellSalvage {
+            station_id: station_id.to_string(),
+            ship_id: ship_id.to_string(),
+            module_id: module_id.to_string(),
+        });
+        Ok(SalvageSale {
+            module_id: module_id.to_string(),
+            name: module.name,
+            credits,
+            balance,
+        })
+    }
+
+    /// Destroy every ship whose hull is gone, leaving wrecks
+    ///
+    /// `hits` are this tick's (attacking ship ID, damaged ship ID, damage);
+    /// the last hit on a ship is credited with destroying it. Ships go in ID
+    /// order so wrecks roll the same way every time.
+    pub(super) fn destroy_ships(&mut self, hits: &[(String, String, f32)]) {
+        let mut destroyed: Vec<String> = self
+            .ship_entities
+            .iter()
+            .filter(|(_, entity)| {
+                self.world
+                    .get::<ShipData>(**entity)
+                    .is_some_and(|ship_data| ship_data.hull <= 0.0)
+            })
+            .map(|(ship_id, _)| ship_id.clone())
+            .collect();
+        destroyed.sort();
+
+        let salvage = self.salvage_config();
+        for ship_id in destroyed {
+            let destroyed_by = hits
+                .iter()
+                .rev()
+                .find(|(_, target_id, _)| *target_id == ship_id)
+                .and_then(|(attacker_id, _, _)| Uuid::parse_str(attacker_id).ok());
+            self.destroy_ship(&ship_id, destroyed_by, &salvage);
+        }
+    }
+
+    /// Remove a destroyed ship and leave its wreck
+    fn destroy_ship(&mut self, ship_id: &str, destroyed_by: Option<Uuid>, salvage: &SalvageConfig) {
+        let Ok(ship_uuid) = Uuid::parse_str(ship_id) else {
+            return;
+        };
+        let Some(ship) = self.ships.remove(ship_id) else {
+            return;
+        };
+        self.helm_commands.remove(ship_id);
+        self.scan_knowledge.remove(ship_id);
+        self.despawn_ship_entity(ship_id);
+        self.ai_manager.unregister_ship(ship_uuid);
+        for station in self.stations.values_mut() {
+            station.docked_ships.retain(|docked| *docked != ship_uuid);
+            station
+                .docking_requests
+                .retain(|(requester, _)| *requester != ship_uuid);
+        }
+        for wreck in self.wrecks.values_mut() {
+            if wreck.tractored_by.as_deref() == Some(ship_id) {
+                wreck.tractored_by = None;
+            }
+        }
+
+        let mut rng = self
+            .world
+            .get_resource_or_insert_with(SimulationRng::default);
+        let wreck_id = Uuid::from_u128(rng.random()).to_string();
+        let wreck = Wreck::from_ship(wreck_id.clone(), &ship, salvage, &mut rng.0);
+        let position = wreck.position;
+        self.wrecks.insert(wreck_id.clone(), wreck);
+
+        self.push_event(GameEvent::ShipDestroyed {
+            ship_id: ship_uuid,
+            destroyed_by,
+        });
+        self.push_event(GameEvent::WreckSpawned {
+            wreck_id,
+            ship_id: ship_uuid,
+            position,
+        });
+    }
+
+    /// Move every wreck by one timestep
+    ///
+    /// Free wrecks drift. Wrecks in a tractor beam match the holding ship's
+    /// velocity and close to half of `salvage_range`; the beam lets go if
+    /// the ship is gone or out of `tractor_range`.
+    pub(super) fn update_wrecks(&mut self) {
+        let salvage = self.salvage_config();
+        let dt = self.simulation.timestep as f64;
+
+        for wreck in self.wrecks.values_mut() {
+            let holder = wreck
+                .tractored_by
+                .as_ref()
+                .and_then(|ship_id| self.ships.get(ship_id))
+                .filter(|ship| {
+                    distance_squared(ship.status.position, wreck.position)
+                        <= salvage.tractor_range.powi(2)
+                });
+            let Some(ship) = holder else {
+                wreck.tractored_by = None;
+                for axis in 0..3 {
+                    wreck.position[axis] += wreck.velocity[axis] * dt;
+                }
+                continue;
+            };
+
+            let distance = distance_squared(ship.status.position, wreck.position).sqrt();
+            let pull =
+                (salvage.tractor_speed * dt).min((distance - salvage.salvage_range / 2.0).max(0.0));
+            wreck.velocity = ship.status.velocity;
+            for (axis, target) in ship.status.position.iter().enumerate() {
+                let closing = if distance > 0.0 {
+                    (target - wreck.position[axis]) / distance * pull
+                } else {
+                    0.0
+                };
+                wreck.position[axis] += wreck.velocity[axis] * dt + closing;
+            }
+        }
+    }
+
+    pub(super) fn salvage_config(&self) -> SalvageConfig {
+        self.world
+            .get_resource::<SimulationParams>()
+            .map(|params| params.salvage.clone())
+            .unwrap_or_else(|| SimulationConfig::default().salvage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ModuleSlot;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::{CompiledModule, Inventory, Ship, ShipStatus};
+    use crate::stations::Station;
+
+    fn module(instance_id: &str, health: f32) -> CompiledModule {
+        CompiledModule {
+            instance_id: instance_id.to_string(),
+            module_id: "cargo-bay".to_string(),
+            kind: None,
+            name: "Cargo Bay".to_string(),
+            stats: Default::default(),
+            current_health: health,
+            max_health: 10.0,
+            operational: false,
+            power_allocated: 0.0,
+            cooling_allocated: 0.0,
+        }
+    }
+
+    /// 20 m³ of hold, and cargo bays worth 1,000 credits
+    fn test_config() -> GameConfig {
+        let mut config = create_test_game_config();
+        config.ship_classes[0].cargo_capacity = Some(20.0);
+        config.module_slots.insert(
+            "cargo-bay".to_string(),
+            ModuleSlot {
+                id: "cargo-bay".to_string(),
+                name: "Cargo Bay".to_string(),
+                description: String::new(),
+                extended_desc: String::new(),
+                groups: vec!["Support".to_string()],
+                required: false,
+                has_varients: false,
+                base_cost: 10,
+                credit_cost: 1_000,
+                max_slots: 4,
+                base_hp: 10,
+                base_power_consumption: 0.0,
+                base_heat_generation: 0.0,
+                base_weight: 100,
+            },
+        );
+        config
+    }
+
+    fn add_ship(world: &mut GameWorld, config: &GameConfig, position: [f64; 3]) -> String {
+        let team_id = world
+            .create_team_with_credits(Uuid::new_v4().to_string(), "neutral".to_string(), 0)
+            .unwrap();
+        let ship_id = Uuid::new_v4().to_string();
+        let mut inventory = Inventory::new();
+        inventory.add_ammunition("slug".to_string(), 40);
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Salvor".to_string(),
+            class: "cruiser".to_string(),
+            team_id,
+            player_roles: HashMap::new(),
+            status: ShipStatus {
+                position,
+                ..ShipStatus::default()
+            },
+            modules: vec![],
+            weapons: vec![],
+            inventory,
+        });
+        world.spawn_ship_entity(&ship_id, config).unwrap();
+        ship_id
+    }
+
+    /// A wreck 500 m out holding ammunition, ore, and three modules
+    fn add_wreck(world: &mut GameWorld) -> String {
+        let mut inventory = Inventory::new();
+        inventory.add_ammunition("slug".to_string(), 10);
+        inventory.cargo.insert("ore".to_string(), 10);
+        let wreck = Wreck {
+            id: "wreck-1".to_string(),
+            ship_id: Uuid::new_v4().to_string(),
+            name: "Derelict".to_string(),
+            class: "cruiser".to_string(),
+            position: [500.0, 0.0, 0.0],
+            velocity: [0.0; 3],
+            inventory,
+            modules: vec![module("a", 10.0), module("b", 10.0), module("c", 10.0)],
+            tractored_by: None,
+        };
+        world.wrecks.insert(wreck.id.clone(), wreck);
+        "wreck-1".to_string()
+    }
+
+    fn hull_breach(world: &mut GameWorld, ship_id: &str) {
+        let entity = world.get_ship_entity(ship_id).unwrap();
+        world.world.get_mut::<ShipData>(entity).unwrap().hull = 0.0;
+    }
+
+    #[test]
+    fn test_destroyed_ship_leaves_a_wreck() {
+        let config = test_config();
+        let mut world = GameWorld::new();
+        let attacker = add_ship(&mut world, &config, [0.0; 3]);
+        let victim = add_ship(&mut world, &config, [100.0, 0.0, 0.0]);
+        let bystander = add_ship(&mut world, &config, [200.0, 0.0, 0.0]);
+        world.drain_events();
+
+        hull_breach(&mut world, &victim);
+        world.step_simulation();
+
+        assert!(world.get_ship(&victim).is_none());
+        assert!(world.get_ship_entity(&victim).is_none());
+        let wreck = world.wrecks().values().next().unwrap();
+        assert_eq!(wreck.ship_id, victim);
+        assert_eq!(wreck.position, [100.0, 0.0, 0.0]);
+        assert!((10..=30).contains(&wreck.inventory.ammunition["slug"]));
+        let wreck_id = wreck.id.clone();
+
+        let events = world.drain_events();
+        assert!(events.iter().any(|event| matches!(
+            event,
+            GameEvent::ShipDestroyed { ship_id, destroyed_by: None }
+                if ship_id.to_string() == victim
+        )));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            GameEvent::WreckSpawned { wreck_id: id, .. } if *id == wreck_id
+        )));
+
+        // The last ship to hit is credited with the kill
+        hull_breach(&mut world, &bystander);
+        world.destroy_ships(&[(attacker.clone(), bystander.clone(), 50.0)]);
+        assert!(world.drain_events().iter().any(|event| matches!(
+            event,
+            GameEvent::ShipDestroyed { destroyed_by: Some(by), .. }
+                if by.to_string() == attacker
+        )));
+
+        // Wrecks outlive a save
+        let restored = GameWorld::from_snapshot(world.snapshot(), &config).unwrap();
+        assert_eq!(restored.wrecks().len(), 2);
+    }
+
+    #[test]
+    fn test_tractor_and_salvage() {
+        let config = test_config();
+        let mut world = GameWorld::new();
+        let ship_id = add_ship(&mut world, &config, [0.0; 3]);
+        let wreck_id = add_wreck(&mut world);
+
+        assert_eq!(
+            world
+                .salvage_wreck(&ship_id, &wreck_id, &config)
+                .unwrap_err(),
+            "Lock a tractor beam on the wreck before salvaging it"
+        );
+        world.set_tractor(&ship_id, &wreck_id, true).unwrap();
+        assert!(
+            world
+                .salvage_wreck(&ship_id, &wreck_id, &config)
+                .unwrap_err()
+                .starts_with("Wreck is out of salvage range")
+        );
+
+        // The beam pulls the wreck in at 50 m/s to half the salvage range
+        for _ in 0..600 {
+            world.update_wrecks();
+        }
+        let position = world.get_wreck(&wreck_id).unwrap().position;
+        assert!((position[0] - 50.0).abs() < 1e-6);
+
+        // 20 m³ of hold: 10 ore and two 5 m³ modules
+        let receipt = world.salvage_wreck(&ship_id, &wreck_id, &config).unwrap();
+        assert_eq!(receipt.ammunition["slug"], 10);
+        assert_eq!(receipt.cargo["ore"], 10);
+        assert_eq!(receipt.modules, vec!["a".to_string(), "b".to_string()]);
+        assert!(!receipt.emptied);
+        let ship = world.get_ship(&ship_id).unwrap();
+        assert_eq!(ship.inventory.ammunition["slug"], 50);
+        assert_eq!(ship.inventory.salvage.len(), 2);
+        assert_eq!(world.cargo_volume(ship), 20.0);
+
+        assert_eq!(
+            world
+                .salvage_wreck(&ship_id, &wreck_id, &config)
+                .unwrap_err(),
+            "Not enough cargo space to salvage anything"
+        );
+
+        // Make room and strip it bare
+        world
+            .get_ship_mut(&ship_id)
+            .unwrap()
+            .inventory
+            .cargo
+            .clear();
+        let receipt = world.salvage_wreck(&ship_id, &wreck_id, &config).unwrap();
+        assert_eq!(receipt.modules, vec!["c".to_string()]);
+        assert!(receipt.emptied);
+        assert!(world.get_wreck(&wreck_id).is_none());
+    }
+
+    #[test]
+    fn test_tractor_range_and_holder() {
+        let config = test_config();
+        let mut world = GameWorld::new();
+        let ship_id = add_ship(&mut world, &config, [0.0; 3]);
+        let rival_id = add_ship(&mut world, &config, [1000.0, 0.0, 0.0]);
+        let far_id = add_ship(&mut world, &config, [10_000.0, 0.0, 0.0]);
+        let wreck_id = add_wreck(&mut world);
+
+        assert!(
+            world
+                .set_tractor(&far_id, &wreck_id, true)
+                .unwrap_err()
+                .starts_with("Wreck is out of tractor range")
+        );
+        world.set_tractor(&ship_id, &wreck_id, true).unwrap();
+        assert_eq!(
+            world.set_tractor(&rival_id, &wreck_id, true).unwrap_err(),
+            "Wreck is held by another ship's tractor beam"
+        );
+        assert!(world.set_tractor(&rival_id, &wreck_id, false).is_err());
+
+        // The beam lets go once the ship is out of range
+        world.get_ship_mut(&ship_id).unwrap().status.position = [5000.0, 0.0, 0.0];
+        world.update_wrecks();
+        assert!(world.get_wreck(&wreck_id).unwrap().tractored_by.is_none());
+        world.set_tractor(&rival_id, &wreck_id, true).unwrap();
+    }
+
+    #[test]
+    fn test_sell_salvage_at_a_station() {
+        let config = test_config();
+        let mut world = GameWorld::new();
+        let ship_id = add_ship(&mut world, &config, [0.0; 3]);
+        world
+            .get_ship_mut(&ship_id)
+            .unwrap()
+            .inventory
+            .salvage
+            .push(module("bay", 5.0));
+
+        let station = Station::new("Scrapyard".to_string(), [0.0; 3], "neutral".to_string());
+        let station_id = world.register_station(station);
+        assert_eq!(
+            world
+                .sell_salvage(&station_id, &ship_id, "bay", &config)
+                .unwrap_err(),
+            "Ship must be docked to sell salvage"
+        );
+        world
+            .get_station_mut(&station_id)
+            .unwrap()
+            .docked_ships
+            .push(Uuid::parse_str(&ship_id).unwrap());
+
+        // 40% of 1,000 credits, halved for wear
+        let sale = world
+            .sell_salvage(&station_id, &ship_id, "bay", &config)
+            .unwrap();
+        assert_eq!(sale.credits, 200);
+        assert_eq!(sale.balance, 200);
+        assert!(
+            world
+                .get_ship(&ship_id)
+                .unwrap()
+                .inventory
+                .salvage
+                .is_empty()
+        );
+        let station = world.get_station(&station_id).unwrap();
+        assert!(
+            station
+                .inventory
+                .modules
+                .iter()
+                .any(|stocked| stocked.module_slot_id == "cargo-bay" && stocked.quantity > 0)
+        );
+
+        assert!(
+            world
+                .sell_salvage(&station_id, &ship_id, "bay", &config)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,405 @@
+//! Progressive science scans and each ship's knowledge base
+//!
+//! A scan attaches a `ScanProgress` to the scanning ship's entity; the
+//! `scanning_system` then reveals one [`ScanTier`] of the target at a time.
+//! Each revealed tier is copied from the target into the scanning ship's
+//! [`ScanReport`] for it, where it stays after the scan ends or the target
+//! drops off sensors. Later scans refresh the report.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::GameWorld;
+use crate::events::GameEvent;
+use crate::replay::ReplayCommand;
+use crate::simulation::{
+    ModuleStateTracker, ScanProgress, ScanTier, ScanTierRevealed, SensorComponent, ShipData,
+    StatusEffects, StealthComponent, WeaponComponent, drain_messages,
+};
+use crate::weapons::StatusEffectType;
+
+/// What a ship's scans have revealed about another ship
+///
+/// Each field is filled in once its tier has been revealed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScanReport {
+    /// Tiers revealed so far, in reveal order
+    pub revealed: Vec<ScanTier>,
+    /// Simulation tick the report was last updated
+    pub updated_tick: u64,
+    pub class: Option<String>,
+    pub team_id: Option<String>,
+    pub faction: Option<String>,
+    /// Installed module names and weapon IDs
+    pub loadout: Option<Vec<String>>,
+    /// Module health (instance ID -> percentage)
+    pub module_health: Option<BTreeMap<String, f32>>,
+    pub hull_percentage: Option<f32>,
+    pub shields_percentage: Option<f32>,
+    /// Rounds in the hold and in weapon magazines (ammunition type -> count)
+    pub ammunition: Option<BTreeMap<String, u32>>,
+    /// Active status effects (effect -> remaining seconds)
+    pub status_effects: Option<BTreeMap<String, f32>>,
+}
+
+/// A scan under way, as reported to the science station
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanStatus {
+    pub target_id: String,
+    /// Scanning time accumulated so far (seconds)
+    pub elapsed: f32,
+    /// Total time for the scan to complete (seconds)
+    pub duration: f32,
+    pub revealed: u8,
+    pub tiers: u8,
+}
+
+impl GameWorld {
+    /// Start scanning another ship
+    ///
+    /// Each tier takes the best working sensor array's `scan_time`, plus the
+    /// target's stealth `scan_time_increase`, and the array's `detail_level`
+    /// sets how many tiers can be revealed. Any scan already under way is
+    /// abandoned. Returns the scan's total duration in seconds.
+    pub fn start_scan(&mut self, ship_id: &str, target_id: &str) -> Result<f32, String> {
+        if ship_id == target_id {
+            return Err("A ship cannot scan itself".to_string());
+        }
+        let entity = self
+            .get_ship_entity(ship_id)
+            .ok_or_else(|| format!("Ship {} is not in the simulation", ship_id))?;
+        let target = self
+            .get_ship_entity(target_id)
+            .ok_or_else(|| format!("Ship {} is not in the simulation", target_id))?;
+        if !self.can_see(ship_id, target_id) {
+            return Err(format!("Ship {} is not on sensors", target_id));
+        }
+
+        let modules = self.world.get::<ModuleStateTracker>(entity);
+        let scanner = self
+            .world
+            .get::<SensorComponent>(entity)
+            .zip(modules)
+            .and_then(|(sensor, modules)| sensor.best_scanner(modules))
+            .ok_or_else(|| format!("Ship {} has no working sensor array", ship_id))?;
+        let stealth = self
+            .world
+            .get::<StealthComponent>(target)
+            .map_or(0.0, |stealth| {
+                stealth.scan_time_increase(self.world.get::<ModuleStateTracker>(target))
+            });
+
+        let scan = ScanProgress {
+            target,
+            target_id: target_id.to_string(),
+            tier_time: (scanner.scan_time + stealth).max(0.0),
+            tiers: scanner.scan_tiers(),
+            elapsed: 0.0,
+            revealed: 0,
+        };
+        let duration = scan.duration();
+        self.world.entity_mut(entity).insert(scan);
+
+        self.record(ReplayCommand::Scan {
+            ship_id: ship_id.to_string(),
+            target_id: target_id.to_string(),
+        });
+        Ok(duration)
+    }
+
+    /// The scan a ship currently has under way, if any
+    pub fn scan_status(&self, ship_id: &str) -> Option<ScanStatus> {
+        let entity = self.get_ship_entity(ship_id)?;
+        let scan = self.world.get::<ScanProgress>(entity)?;
+        Some(ScanStatus {
+            target_id: scan.target_id.clone(),
+            elapsed: scan.elapsed,
+            duration: scan.duration(),
+            revealed: scan.revealed,
+            tiers: scan.tiers,
+        })
+    }
+
+    /// What a ship's scans have revealed about another ship
+    pub fn scan_report(&self, ship_id: &str, target_id: &str) -> Option<&ScanReport> {
+        self.scan_knowledge.get(ship_id)?.get(target_id)
+    }
+
+    /// Every scan report a ship holds (target Ship ID -> report)
+    pub fn scan_knowledge(&self, ship_id: &str) -> Option<&BTreeMap<String, ScanReport>> {
+        self.scan_knowledge.get(ship_id)
+    }
+
+    /// Record revealed scan tiers in the knowledge base and emit events
+    pub(super) fn emit_scan_events(&mut self) {
+        for reveal in drain_messages::<ScanTierRevealed>(&mut self.world) {
+            let Some(ship_id) = self
+                .world
+                .get::<ShipData>(reveal.ship)
+                .map(|ship_data| ship_data.id.clone())
+            else {
+                continue;
+            };
+
+            self.reveal_tier(&ship_id, &reveal.target_id, reveal.tier);
+
+            if let (Ok(ship_uuid), Ok(target_uuid)) = (
+                Uuid::parse_str(&ship_id),
+                Uuid::parse_str(&reveal.target_id),
+            ) {
+                self.push_event(GameEvent::ScanTierRevealed {
+                    ship_id: ship_uuid,
+                    target_id: target_uuid,
+                    tier: reveal.tier.name().to_string(),
+                    complete: reveal.complete,
+                });
+            }
+        }
+    }
+
+    /// Copy one tier of the target's current state into a ship's report
+    fn reveal_tier(&mut self, ship_id: &str, target_id: &str, tier: ScanTier) {
+        let Some(target) = self.ships.get(target_id) else {
+            return;
+        };
+        let entity = self.get_ship_entity(target_id);
+        let mut report = self
+            .scan_report(ship_id, target_id)
+            .cloned()
+            .unwrap_or_default();
+
+        match tier {
+            ScanTier::Class => report.class = Some(target.class.clone()),
+            ScanTier::Faction => {
+                report.team_id = Some(target.team_id.clone());
+                report.faction = self
+                    .teams
+                    .get(&target.team_id)
+                    .map(|team| team.faction.clone());
+            }
+            ScanTier::Loadout => {
+                let modules = target.modules.iter().map(|module| module.name.clone());
+                let weapons = target.weapons.iter().map(|weapon| weapon.weapon_id.clone());
+                report.loadout = Some(modules.chain(weapons).collect());
+            }
+            ScanTier::ModuleHealth => {
+                report.module_health = entity
+                    .and_then(|entity| self.world.get::<ModuleStateTracker>(entity))
+                    .map(|tracker| {
+                        tracker
+                            .states
+                            .iter()
+                            .map(|(id, state)| (id.clone(), state.health_percentage()))
+                            .collect()
+                    });
+                report.hull_percentage =
+                    Some(percentage(target.status.hull, target.status.max_hull));
+                report.shields_percentage =
+                    Some(percentage(target.status.shields, target.status.max_shields));
+            }
+            ScanTier::Ammunition => {
+                let mut ammunition: BTreeMap<String, u32> = target
+                    .inventory
+                    .ammunition
+                    .iter()
+                    .map(|(ammo_type, count)| (ammo_type.clone(), *count))
+                    .collect();
+                for weapon_entity in self.weapon_entities(target_id) {
+                    if let Some(weapon) = self.world.get::<WeaponComponent>(weapon_entity)
+                        && let Some(ammo_type) = &weapon.ammunition
+                        && weapon.ammo_count > 0
+                    {
+                        *ammunition.entry(ammo_type.clone()).or_default() += weapon.ammo_count;
+                    }
+                }
+                report.ammunition = Some(ammunition);
+            }
+            ScanTier::StatusEffects => {
+                report.status_effects = Some(
+                    entity
+                        .and_then(|entity| self.world.get::<StatusEffects>(entity))
+                        .map(|effects| {
+                            effects
+                                .effects
+                                .iter()
+                                .map(|(effect, remaining)| (effect_name(*effect), *remaining))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                );
+            }
+        }
+
+        if !report.revealed.contains(&tier) {
+            report.revealed.push(tier);
+            report.revealed.sort();
+        }
+        report.updated_tick = self.simulation.tick;
+        self.scan_knowledge
+            .entry(ship_id.to_string())
+            .or_default()
+            .insert(target_id.to_string(), report);
+    }
+}
+
+fn percentage(value: f32, max: f32) -> f32 {
+    if max > 0.0 { value / max * 100.0 } else { 0.0 }
+}
+
+fn effect_name(effect: StatusEffectType) -> String {
+    match effect {
+        StatusEffectType::IonJam => "ion_jam",
+        StatusEffectType::GravitonWeight => "graviton_weight",
+        StatusEffectType::TachyonWarpBlock => "tachyon_warp_block",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::{CompiledModule, Inventory, Ship, ShipStatus};
+    use std::collections::HashMap;
+
+    fn module(instance_id: &str, module_id: &str, stats: serde_json::Value) -> CompiledModule {
+        CompiledModule {
+            instance_id: instance_id.to_string(),
+            module_id: module_id.to_string(),
+            kind: None,
+            name: instance_id.to_string(),
+            stats: serde_json::from_value(stats).unwrap(),
+            current_health: 100.0,
+            max_health: 100.0,
+            operational: true,
+            power_allocated: 1.0,
+            cooling_allocated: 1.0,
+        }
+    }
+
+    fn spawn_ship(world: &mut GameWorld, team_id: &str, modules: Vec<CompiledModule>) -> String {
+        let ship_id = Uuid::new_v4().to_string();
+        let mut status = ShipStatus::new(1000.0, 100.0, 1000.0);
+        status.position = [0.0, 0.0, 1000.0 * world.ships.len() as f64];
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Ship".to_string(),
+            class: "frigate".to_string(),
+            team_id: team_id.to_string(),
+            player_roles: HashMap::new(),
+            status,
+            modules,
+            weapons: vec![],
+            inventory: Inventory::new(),
+        });
+        world
+            .spawn_ship_entity(&ship_id, &create_test_game_config())
+            .unwrap();
+        ship_id
+    }
+
+    fn sensors(detail_level: u8) -> CompiledModule {
+        module(
+            "sensors",
+            "sensor-array",
+            serde_json::json!({
+                "scan_range": 5000.0,
+                "scan_time": 2.0,
+                "detail_level": detail_level,
+            }),
+        )
+    }
+
+    fn step_for(world: &mut GameWorld, seconds: f32) {
+        let ticks = (seconds / world.simulation.timestep).round() as u32;
+        for _ in 0..ticks {
+            world.step_simulation();
+        }
+    }
+
+    #[test]
+    fn test_scan_reveals_tiers_over_time() {
+        let mut world = GameWorld::new();
+        let scanner = spawn_ship(&mut world, "team1", vec![sensors(10)]);
+        let stealth = module(
+            "stealth",
+            "stealth-system",
+            serde_json::json!({ "scan_time_increase": 1.0 }),
+        );
+        let target = spawn_ship(&mut world, "team2", vec![stealth]);
+        world.step_simulation();
+
+        // Six tiers of 2s scan time plus 1s of stealth each
+        let duration = world.start_scan(&scanner, &target).unwrap();
+        assert!((duration - 18.0).abs() < 1e-4);
+
+        step_for(&mut world, 4.0);
+        let report = world.scan_report(&scanner, &target).unwrap();
+        assert_eq!(report.revealed, vec![ScanTier::Class]);
+        assert_eq!(report.class.as_deref(), Some("frigate"));
+        assert!(report.team_id.is_none());
+
+        step_for(&mut world, 15.0);
+        let report = world.scan_report(&scanner, &target).unwrap();
+        assert_eq!(report.revealed, ScanTier::ALL.to_vec());
+        assert_eq!(report.team_id.as_deref(), Some("team2"));
+        assert_eq!(report.hull_percentage, Some(100.0));
+        assert!(world.scan_status(&scanner).is_none());
+
+        let revealed = world
+            .drain_events()
+            .into_iter()
+            .filter(|event| matches!(event, GameEvent::ScanTierRevealed { .. }))
+            .count();
+        assert_eq!(revealed, 6);
+    }
+
+    #[test]
+    fn test_detail_level_limits_tiers() {
+        let mut world = GameWorld::new();
+        let scanner = spawn_ship(&mut world, "team1", vec![sensors(4)]);
+        let target = spawn_ship(&mut world, "team2", vec![]);
+        world.step_simulation();
+
+        world.start_scan(&scanner, &target).unwrap();
+        step_for(&mut world, 20.0);
+
+        let report = world.scan_report(&scanner, &target).unwrap();
+        assert_eq!(report.revealed.len(), 3);
+        assert!(report.loadout.is_some());
+        assert!(report.module_health.is_none());
+    }
+
+    #[test]
+    fn test_ion_jamming_pauses_scan() {
+        let mut world = GameWorld::new();
+        let scanner = spawn_ship(&mut world, "team1", vec![sensors(10)]);
+        let target = spawn_ship(&mut world, "team2", vec![]);
+        world.step_simulation();
+        world.start_scan(&scanner, &target).unwrap();
+
+        let entity = world.get_ship_entity(&scanner).unwrap();
+        world
+            .world
+            .get_mut::<StatusEffects>(entity)
+            .unwrap()
+            .apply(StatusEffectType::IonJam, 100.0);
+        step_for(&mut world, 5.0);
+
+        let status = world.scan_status(&scanner).unwrap();
+        assert_eq!(status.elapsed, 0.0);
+        assert!(world.scan_report(&scanner, &target).is_none());
+    }
+
+    #[test]
+    fn test_scan_requires_contact_and_sensors() {
+        let mut world = GameWorld::new();
+        let blind = spawn_ship(&mut world, "team1", vec![]);
+        let target = spawn_ship(&mut world, "team2", vec![]);
+        world.step_simulation();
+
+        assert!(world.start_scan(&blind, &target).is_err());
+        assert!(world.start_scan(&target, &blind).is_err());
+    }
+}
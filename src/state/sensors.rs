@@ -0,0 +1,274 @@
+//! Sensor contacts and fog of war
+//!
+//! `sensor_system` keeps each ship's `SensorComponent` contact list current;
+//! these queries read it back for the science station. Other ships are only
+//! visible to a crew once their sensors hold them as contacts, unless they
+//! fly for the same team.
+
+use bevy_ecs::prelude::*;
+use nalgebra::Vector3;
+
+use super::GameWorld;
+use crate::simulation::{
+    ProjectileComponent, ProjectileType, SensorComponent, ShipData, Transform,
+};
+
+/// A ship held on sensors
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensorContact {
+    /// Ship ID of the contact
+    pub ship_id: String,
+    /// Distance from the detecting ship (meters)
+    pub distance: f64,
+    /// Bearing relative to the detecting ship's heading (degrees, clockwise)
+    pub bearing: f64,
+}
+
+/// Hostile ordnance closing on a ship
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreatTrack {
+    /// Simulation ID of the projectile
+    pub id: String,
+    /// Kind of ordnance (missile, torpedo, kinetic)
+    pub threat_type: String,
+    /// Distance from the threatened ship (meters)
+    pub distance: f64,
+    /// Time until impact at the current closing speed (seconds)
+    pub time_to_impact: f64,
+}
+
+impl GameWorld {
+    /// Ships currently held on a ship's sensors, nearest first
+    ///
+    /// Empty if the ship is not in the simulation.
+    pub fn get_contacts(&self, ship_id: &str) -> Vec<SensorContact> {
+        let Some((sensor, transform)) = self.sensor_view(ship_id) else {
+            return Vec::new();
+        };
+
+        let mut contacts: Vec<SensorContact> = sensor
+            .contacts
+            .iter()
+            .filter_map(|(entity, contact_id)| {
+                let target = self.world.get::<Transform>(*entity)?;
+                let offset = target.position - transform.position;
+                Some(SensorContact {
+                    ship_id: contact_id.clone(),
+                    distance: offset.magnitude() as f64,
+                    bearing: relative_bearing(transform, &offset),
+                })
+            })
+            .collect();
+        contacts.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        contacts
+    }
+
+    /// Hostile ordnance aimed at a ship and within its sensor range,
+    /// soonest impact first
+    ///
+    /// Only ordnance closing on the ship is listed.
+    pub fn get_threats(&self, ship_id: &str) -> Vec<ThreatTrack> {
+        let (Some(entity), Some((sensor, transform))) =
+            (self.get_ship_entity(ship_id), self.sensor_view(ship_id))
+        else {
+            return Vec::new();
+        };
+        let Some(mut projectiles) = self
+            .world
+            .try_query::<(Entity, &ProjectileComponent, &Transform)>()
+        else {
+            return Vec::new();
+        };
+        let defender = self.world.get::<ShipData>(entity);
+
+        let mut threats: Vec<ThreatTrack> = projectiles
+            .iter(&self.world)
+            .filter(|(_, projectile, _)| {
+                projectile.target == Some(entity)
+                    && projectile.projectile_type != ProjectileType::Beam
+                    && !self
+                        .world
+                        .get::<ShipData>(projectile.owner)
+                        .zip(defender)
+                        .is_some_and(|(attacker, defender)| attacker.is_friendly(defender))
+            })
+            .filter_map(|(projectile_entity, projectile, projectile_transform)| {
+                let offset = transform.position - projectile_transform.position;
+                let distance = offset.magnitude();
+                if distance > sensor.range {
+                    return None;
+                }
+                let closing_speed = (projectile_transform.velocity - transform.velocity)
+                    .dot(&offset.try_normalize(f32::EPSILON)?);
+                if closing_speed <= 0.0 {
+                    return None;
+                }
+                Some(ThreatTrack {
+                    id: projectile_entity.to_bits().to_string(),
+                    threat_type: threat_type(projectile.projectile_type).to_string(),
+                    distance: distance as f64,
+                    time_to_impact: (distance / closing_speed) as f64,
+                })
+            })
+            .collect();
+        threats.sort_by(|a, b| a.time_to_impact.total_cmp(&b.time_to_impact));
+        threats
+    }
+
+    /// Whether a ship's crew may see another ship
+    ///
+    /// Teammates are always visible; anyone else only while held on sensors.
+    pub fn can_see(&self, observer_id: &str, target_id: &str) -> bool {
+        if observer_id == target_id {
+            return true;
+        }
+        let (Some(observer), Some(target)) =
+            (self.ships.get(observer_id), self.ships.get(target_id))
+        else {
+            return false;
+        };
+        if observer.team_id == target.team_id {
+            return true;
+        }
+        let (Some(observer), Some(target)) = (
+            self.get_ship_entity(observer_id),
+            self.get_ship_entity(target_id),
+        ) else {
+            return false;
+        };
+        self.world
+            .get::<SensorComponent>(observer)
+            .is_some_and(|sensor| sensor.has_contact(target))
+    }
+
+    /// A ship's sensors and position
+    fn sensor_view(&self, ship_id: &str) -> Option<(&SensorComponent, &Transform)> {
+        let entity = self.get_ship_entity(ship_id)?;
+        Some((
+            self.world.get::<SensorComponent>(entity)?,
+            self.world.get::<Transform>(entity)?,
+        ))
+    }
+}
+
+/// Bearing of `offset` from the ship's heading in its horizontal plane
+///
+/// 0° is dead ahead (+z) and 90° is to starboard (+x).
+fn relative_bearing(transform: &Transform, offset: &Vector3<f32>) -> f64 {
+    let local = transform.rotation.inverse() * offset;
+    let bearing = (local.x as f64).atan2(local.z as f64).to_degrees();
+    bearing.rem_euclid(360.0)
+}
+
+fn threat_type(projectile_type: ProjectileType) -> &'static str {
+    match projectile_type {
+        ProjectileType::Missile { .. } => "missile",
+        ProjectileType::Torpedo => "torpedo",
+        ProjectileType::Kinetic => "kinetic",
+        ProjectileType::Beam => "beam",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::events::GameEvent;
+    use crate::models::{CompiledModule, Inventory, Ship, ShipStatus};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn spawn_scout(world: &mut GameWorld, team_id: &str, position: [f64; 3]) -> String {
+        let ship_id = Uuid::new_v4().to_string();
+        let mut status = ShipStatus::new(1000.0, 100.0, 1000.0);
+        status.position = position;
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Scout".to_string(),
+            class: "corvette".to_string(),
+            team_id: team_id.to_string(),
+            player_roles: HashMap::new(),
+            status,
+            modules: vec![CompiledModule {
+                instance_id: "sensors".to_string(),
+                module_id: "sensor-array".to_string(),
+                kind: Some("short-range-sensors".to_string()),
+                name: "Short-Range Sensor Array".to_string(),
+                stats: serde_json::from_value(serde_json::json!({ "scan_range": 5000.0 })).unwrap(),
+                current_health: 100.0,
+                max_health: 100.0,
+                operational: true,
+                power_allocated: 1.0,
+                cooling_allocated: 1.0,
+            }],
+            weapons: vec![],
+            inventory: Inventory::new(),
+        });
+        world
+            .spawn_ship_entity(&ship_id, &create_test_game_config())
+            .unwrap();
+        ship_id
+    }
+
+    #[test]
+    fn test_contacts_follow_sensor_range() {
+        let mut world = GameWorld::new();
+        let observer = spawn_scout(&mut world, "team1", [0.0, 0.0, 0.0]);
+        let near = spawn_scout(&mut world, "team2", [3000.0, 0.0, 0.0]);
+        let far = spawn_scout(&mut world, "team2", [0.0, 0.0, 20_000.0]);
+
+        world.step_simulation();
+
+        let contacts = world.get_contacts(&observer);
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].ship_id, near);
+        assert!((contacts[0].distance - 3000.0).abs() < 1.0);
+        assert!((contacts[0].bearing - 90.0).abs() < 0.1);
+
+        assert!(world.can_see(&observer, &near));
+        assert!(!world.can_see(&observer, &far));
+
+        let detected = world
+            .drain_events()
+            .iter()
+            .filter(|event| {
+                matches!(event, GameEvent::ContactDetected { detecting_ship_id, contact_id, .. }
+                    if detecting_ship_id.to_string() == observer && contact_id.to_string() == near)
+            })
+            .count();
+        assert_eq!(detected, 1);
+    }
+
+    #[test]
+    fn test_threats_report_time_to_impact() {
+        let mut world = GameWorld::new();
+        let defender = spawn_scout(&mut world, "team1", [0.0, 0.0, 0.0]);
+        let attacker = spawn_scout(&mut world, "team2", [0.0, 0.0, 4000.0]);
+        let defender_entity = world.get_ship_entity(&defender).unwrap();
+        let attacker_entity = world.get_ship_entity(&attacker).unwrap();
+
+        let mut transform = Transform::at_position(Vector3::new(0.0, 0.0, 2000.0));
+        transform.velocity = Vector3::new(0.0, 0.0, -400.0);
+        world.world.spawn((
+            ProjectileComponent::missile(
+                attacker_entity,
+                defender_entity,
+                50.0,
+                vec![],
+                0,
+                0,
+                30.0,
+            ),
+            transform,
+        ));
+        world.step_simulation();
+
+        let threats = world.get_threats(&defender);
+        assert_eq!(threats.len(), 1);
+        assert_eq!(threats[0].threat_type, "missile");
+        assert!((threats[0].time_to_impact - threats[0].distance / 400.0).abs() < 0.01);
+
+        // The attacker is not threatened by its own missile
+        assert!(world.get_threats(&attacker).is_empty());
+    }
+}
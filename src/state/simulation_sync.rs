@@ -0,0 +1,834 @@
+//! Bridge between the ECS simulation and the `GameWorld` registries
+//!
+//! The simulation runs on the Bevy `World` owned by `GameWorld`. After each
+//! tick, the resulting component changes are translated into `GameEvent`s
+//! and pushed onto the event queue for the `EventBroadcaster`.
+
+use bevy_ecs::prelude::*;
+use nalgebra::{UnitQuaternion, Vector3};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::{GameWorld, distance_squared};
+use crate::config::GameConfig;
+use crate::config::simulation::{DockingConfig, SimulationConfig};
+use crate::events::{ContactType, GameEvent};
+use crate::replay::ReplayCommand;
+use crate::simulation::{
+    ContactChanged, Docked, Explosion, HelmCommand, HelmComponent, ModuleBrownout,
+    ModuleStateTracker, ModuleStatusChanged, PointDefenseEngaged, ShieldComponent, ShipDamaged,
+    ShipData, SimulationParams, SimulationRng, Transform, drain_messages, run_simulation_tick,
+    spawn_ship,
+};
+use crate::stations::DockingStatus;
+
+/// Kinematic state captured before a tick, used to detect movement
+type KinematicSnapshot = (Vector3<f32>, Vector3<f32>, UnitQuaternion<f32>);
+
+impl GameWorld {
+    /// Install the simulation tuning parameters from `data/simulation.yaml`
+    pub fn configure_simulation(&mut self, config: &SimulationConfig) {
+        self.world.insert_resource(SimulationParams(config.clone()));
+    }
+
+    /// Spawn the simulation entity hierarchy for a registered ship
+    ///
+    /// Any entity previously spawned for the ship is replaced.
+    pub fn spawn_ship_entity(
+        &mut self,
+        ship_id: &str,
+        config: &GameConfig,
+    ) -> Result<Entity, String> {
+        let entity = self.rebuild_ship_entity(ship_id, config)?;
+        if let Some(ship) = self.ships.get(ship_id) {
+            self.record(ReplayCommand::SpawnShip {
+                ship: Box::new(ship.clone()),
+            });
+        }
+        Ok(entity)
+    }
+
+    /// Spawn a ship's entity hierarchy without recording it
+    ///
+    /// For commands that change a ship's build and record themselves.
+    pub(super) fn rebuild_ship_entity(
+        &mut self,
+        ship_id: &str,
+        config: &GameConfig,
+    ) -> Result<Entity, String> {
+        let ship = self
+            .ships
+            .get(ship_id)
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+
+        let entity = spawn_ship(&mut self.world, ship, config);
+        if let Some(team) = self.teams.get(&ship.team_id)
+            && let Some(mut ship_data) = self.world.get_mut::<ShipData>(entity)
+        {
+            ship_data.faction = team.faction.clone();
+        }
+        if let Some(previous) = self.ship_entities.insert(ship_id.to_string(), entity) {
+            self.world.despawn(previous);
+        }
+        Ok(entity)
+    }
+
+    /// Get the simulation entity for a ship
+    pub fn get_ship_entity(&self, ship_id: &str) -> Option<Entity> {
+        self.ship_entities.get(ship_id).copied()
+    }
+
+    /// Despawn a ship's simulation entity and its weapon children
+    pub(crate) fn despawn_ship_entity(&mut self, ship_id: &str) {
+        if let Some(entity) = self.ship_entities.remove(ship_id) {
+            self.world.despawn(entity);
+        }
+    }
+
+    /// Advance the ECS simulation by one fixed timestep (a no-op while paused)
+    ///
+    /// 1. Push crew commands, comm relays and docked ships into the ECS.
+    /// 2. Seed the tick's `SimulationRng` from the session seed and tick.
+    /// 3. Run every system once and advance the simulation clock.
+    /// 4. Sync results back into each ship's `ShipStatus`.
+    /// 5. Complete docking approaches and let station markets drift.
+    /// 6. Queue events for movement and damage, and settle the reputation
+    ///    hits cost.
+    /// 7. Queue events for brownouts, module status, explosions, point
+    ///    defense, contacts, scans and messages.
+    /// 8. Destroy ships whose hull gave out, leaving wrecks.
+    /// 9. Move wrecks, queue a closing `SimulationTick` and record the
+    ///    tick's events.
+    pub fn step_simulation(&mut self) {
+        if self.simulation.paused {
+            return;
+        }
+
+        let first_event = self.event_count();
+        self.push_ship_commands();
+        self.push_comm_relays();
+        self.push_docked_ships();
+        let before = self.snapshot_ship_kinematics();
+
+        self.world.insert_resource(SimulationRng::for_tick(
+            self.simulation.seed,
+            self.simulation.tick,
+        ));
+        run_simulation_tick(&mut self.world, self.simulation.timestep);
+        self.simulation.advance();
+
+        self.sync_ship_status();
+        self.complete_docking_approaches();
+        self.update_markets();
+        self.emit_movement_events(&before);
+        let hits = self.emit_damage_events();
+        self.settle_combat_reputation(&hits);
+        self.emit_brownout_events();
+        self.emit_module_status_events();
+        self.emit_explosion_events();
+        self.emit_point_defense_events();
+        self.emit_contact_events();
+        self.emit_scan_events();
+        self.emit_message_events();
+        self.destroy_ships(&hits);
+        self.update_wrecks();
+
+        self.push_event(GameEvent::SimulationTick {
+            tick: self.simulation.tick,
+            time: self.simulation.time,
+        });
+        self.record_tick_events(first_event);
+    }
+
+    /// Copy crew-controlled state and queued helm commands into the
+    /// simulation
+    ///
+    /// Docking orders are resolved here against the station registry; all
+    /// other helm commands are handed to the ship's `HelmComponent`.
+    /// Commands for ships without a simulation entity are dropped.
+    fn push_ship_commands(&mut self) {
+        for (ship_id, entity) in &self.ship_entities {
+            let Some(ship) = self.ships.get(ship_id) else {
+                continue;
+            };
+            if let Some(mut shield) = self.world.get_mut::<ShieldComponent>(*entity) {
+                shield.raised = ship.status.shields_raised;
+            }
+        }
+
+        for (ship_id, commands) in std::mem::take(&mut self.helm_commands) {
+            for command in commands {
+                match command {
+                    HelmCommand::Dock { station_id } => {
+                        // A refused order leaves its denial on the station
+                        let _ = self.request_docking(&ship_id, &station_id);
+                    }
+                    command => {
+                        let Some(entity) = self.ship_entities.get(&ship_id) else {
+                            continue;
+                        };
+                        if let Some(mut helm) = self.world.get_mut::<HelmComponent>(*entity) {
+                            helm.queue.push_back(command);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Mark ships docked at a station with `Docked`, and unmark the rest
+    fn push_docked_ships(&mut self) {
+        for (ship_id, entity) in &self.ship_entities {
+            let station_id = self
+                .docked_station(ship_id)
+                .map(|station| station.id.to_string());
+            let mut ship = self.world.entity_mut(*entity);
+            match station_id {
+                Some(station_id) => {
+                    ship.insert(Docked { station_id });
+                }
+                None => {
+                    ship.remove::<Docked>();
+                }
+            }
+        }
+    }
+
+    /// Request and approve docking for a ship within request range
+    ///
+    /// The station judges the request by the faction of the ship's team, and
+    /// denies ships whose team stands too low with its own faction. On
+    /// approval the ship is `Approaching` and docks once it is inside final
+    /// range and slow enough. Shared by the helm's `Dock` order and the
+    /// docking endpoints; `Err` carries the reason the request was refused.
+    pub fn request_docking(&mut self, ship_id: &str, station_id: &str) -> Result<(), String> {
+        let docking = self.docking_config();
+        let ship = self
+            .ships
+            .get(ship_id)
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+        let ship_uuid =
+            Uuid::parse_str(ship_id).map_err(|_| format!("Invalid ship ID: {}", ship_id))?;
+        let faction = self
+            .teams
+            .get(&ship.team_id)
+            .map(|team| team.faction.clone())
+            .unwrap_or_default();
+        let position = ship.status.position;
+        let permitted = self.docking_permitted(ship_id, station_id);
+
+        let station = self
+            .stations
+            .get_mut(station_id)
+            .ok_or_else(|| format!("Station {} not found", station_id))?;
+        if distance_squared(position, station.position) > docking.request_range.powi(2) {
+            return Err("Docking denied: Ship is out of range".to_string());
+        }
+        if !permitted {
+            station.deny_docking(ship_uuid);
+            return Err("Docking denied: Reputation too low".to_string());
+        }
+        if station.request_docking(ship_uuid, &faction) {
+            station.approve_docking(ship_uuid);
+            return Ok(());
+        }
+        Err(match station.get_docking_status(ship_uuid) {
+            Some(DockingStatus::Denied) if station.is_hostile_to(&faction) => {
+                "Docking denied: Hostile faction".to_string()
+            }
+            Some(DockingStatus::Denied) => "Docking denied: Station is full".to_string(),
+            _ => "Docking denied".to_string(),
+        })
+    }
+
+    /// Dock every approaching ship that has arrived
+    ///
+    /// Ships still on approach are checked again next tick.
+    fn complete_docking_approaches(&mut self) {
+        let approaching: Vec<(String, String)> = self
+            .stations
+            .iter()
+            .flat_map(|(station_id, station)| {
+                station
+                    .docking_requests
+                    .iter()
+                    .filter(|(_, status)| *status == DockingStatus::Approaching)
+                    .map(move |(ship_id, _)| (ship_id.to_string(), station_id.clone()))
+            })
+            .collect();
+
+        for (ship_id, station_id) in approaching {
+            let _ = self.complete_docking(&ship_id, &station_id);
+        }
+    }
+
+    /// Dock an approaching ship once it is inside final range and slow enough
+    ///
+    /// Brings the ship to rest and raises `ShipDocked`. `Err` explains why
+    /// the ship cannot dock yet.
+    pub fn complete_docking(&mut self, ship_id: &str, station_id: &str) -> Result<(), String> {
+        let docking = self.docking_config();
+        let ship = self
+            .ships
+            .get(ship_id)
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+        let ship_uuid =
+            Uuid::parse_str(ship_id).map_err(|_| format!("Invalid ship ID: {}", ship_id))?;
+        let (position, velocity) = (ship.status.position, ship.status.velocity);
+
+        let station = self
+            .stations
+            .get_mut(station_id)
+            .ok_or_else(|| format!("Station {} not found", station_id))?;
+        if station.get_docking_status(ship_uuid) != Some(DockingStatus::Approaching) {
+            return Err("Ship is not approaching this station".to_string());
+        }
+        if distance_squared(position, station.position) > docking.final_range.powi(2) {
+            return Err("Ship is outside final docking range".to_string());
+        }
+        if distance_squared(velocity, [0.0; 3]) > docking.approach_speed.powi(2) {
+            return Err("Ship is moving too fast to dock".to_string());
+        }
+        station.complete_docking(ship_uuid);
+        let station_uuid = station.id;
+
+        if let Some(entity) = self.ship_entities.get(ship_id).copied() {
+            if let Some(mut transform) = self.world.get_mut::<Transform>(entity) {
+                transform.velocity = Vector3::zeros();
+            }
+            if let Some(mut helm) = self.world.get_mut::<HelmComponent>(entity) {
+                helm.thrust = Vector3::zeros();
+            }
+        }
+        if let Some(ship) = self.ships.get_mut(ship_id) {
+            ship.status.velocity = [0.0; 3];
+        }
+        self.push_event(GameEvent::ShipDocked {
+            ship_id: ship_uuid,
+            station_id: station_uuid,
+        });
+        Ok(())
+    }
+
+    /// Docking ranges from the installed simulation parameters
+    fn docking_config(&self) -> DockingConfig {
+        self.world
+            .get_resource::<SimulationParams>()
+            .map(|params| params.docking.clone())
+            .unwrap_or_else(|| SimulationConfig::default().docking)
+    }
+
+    /// Write authoritative hull, shields, position, and module health back
+    /// into each ship's `ShipStatus`
+    fn sync_ship_status(&mut self) {
+        for (ship_id, entity) in &self.ship_entities {
+            let Some(ship) = self.ships.get_mut(ship_id) else {
+                continue;
+            };
+            let status = &mut ship.status;
+
+            if let Some(ship_data) = self.world.get::<ShipData>(*entity) {
+                status.hull = ship_data.hull.max(0.0);
+            }
+            if let Some(shield) = self.world.get::<ShieldComponent>(*entity) {
+                status.shields = shield.strength;
+            }
+            if let Some(transform) = self.world.get::<Transform>(*entity) {
+                status.position = vector_to_array(&transform.position);
+                status.velocity = vector_to_array(&transform.velocity);
+            }
+            if let Some(tracker) = self.world.get::<ModuleStateTracker>(*entity) {
+                for module in &mut ship.modules {
+                    if let Some(state) = tracker.get(&module.instance_id) {
+                        module.current_health = state.current_health;
+                        module.operational = state.operational;
+                        status
+                            .module_health
+                            .insert(module.instance_id.clone(), state.current_health);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Capture position, velocity, and rotation for every ship entity
+    fn snapshot_ship_kinematics(&mut self) -> HashMap<Entity, KinematicSnapshot> {
+        let mut query = self
+            .world
+            .query_filtered::<(Entity, &Transform), With<ShipData>>();
+        query
+            .iter(&self.world)
+            .map(|(entity, t)| (entity, (t.position, t.velocity, t.rotation)))
+            .collect()
+    }
+
+    /// Queue `ShipMoved` for ships whose transform differs from `before`
+    fn emit_movement_events(&mut self, before: &HashMap<Entity, KinematicSnapshot>) {
+        let mut query = self.world.query::<(Entity, &ShipData, &Transform)>();
+        let mut events = Vec::new();
+
+        for (entity, ship_data, transform) in query.iter(&self.world) {
+            let unchanged = before.get(&entity).is_some_and(|(p, v, r)| {
+                *p == transform.position && *v == transform.velocity && *r == transform.rotation
+            });
+            if unchanged {
+                continue;
+            }
+            let Ok(ship_id) = Uuid::parse_str(&ship_data.id) else {
+                continue;
+            };
+
+            let q = transform.rotation.coords;
+            events.push(GameEvent::ShipMoved {
+                ship_id,
+                position: vector_to_array(&transform.position),
+                velocity: vector_to_array(&transform.velocity),
+                rotation: [q.x as f64, q.y as f64, q.z as f64, q.w as f64],
+            });
+        }
+
+        for event in events {
+            self.push_event(event);
+        }
+    }
+
+    /// Translate this tick's `ShipDamaged` messages into `DamageTaken` events
+    ///
+    /// Returns each hit by a ship as (attacking ship ID, damaged ship ID,
+    /// damage).
+    fn emit_damage_events(&mut self) -> Vec<(String, String, f32)> {
+        let mut hits = Vec::new();
+        for damaged in drain_messages::<ShipDamaged>(&mut self.world) {
+            let Some(ship_data) = self.world.get::<ShipData>(damaged.ship) else {
+                continue;
+            };
+            if let Some(attacker) = self.world.get::<ShipData>(damaged.attacker) {
+                hits.push((attacker.id.clone(), ship_data.id.clone(), damaged.amount));
+            }
+            let Ok(ship_id) = Uuid::parse_str(&ship_data.id) else {
+                continue;
+            };
+            let hull_percent = ship_data.hull_percentage() * 100.0;
+            let shield_percent = self
+                .world
+                .get::<ShieldComponent>(damaged.ship)
+                .map(|shield| {
+                    if shield.max_strength > 0.0 {
+                        shield.strength / shield.max_strength * 100.0
+                    } else {
+                        0.0
+                    }
+                })
+                .unwrap_or(0.0);
+
+            self.push_event(GameEvent::DamageTaken {
+                ship_id,
+                damage_type: damaged.damage_type,
+                amount: damaged.amount,
+                hull_percent,
+                shield_percent,
+            });
+        }
+        hits
+    }
+
+    /// Translate this tick's `ModuleBrownout` messages into events
+    fn emit_brownout_events(&mut self) {
+        for brownout in drain_messages::<ModuleBrownout>(&mut self.world) {
+            let Some(ship_id) = self
+                .world
+                .get::<ShipData>(brownout.ship)
+                .and_then(|ship_data| Uuid::parse_str(&ship_data.id).ok())
+            else {
+                continue;
+            };
+
+            self.push_event(GameEvent::ModuleBrownout {
+                ship_id,
+                module_id: brownout.module_id,
+                power_percent: brownout.power_supplied * 100.0,
+                browned_out: brownout.browned_out,
+            });
+        }
+    }
+
+    /// Translate this tick's `ModuleStatusChanged` messages into events
+    fn emit_module_status_events(&mut self) {
+        for change in drain_messages::<ModuleStatusChanged>(&mut self.world) {
+            let Some(ship_id) = self
+                .world
+                .get::<ShipData>(change.ship)
+                .and_then(|ship_data| Uuid::parse_str(&ship_data.id).ok())
+            else {
+                continue;
+            };
+
+            self.push_event(GameEvent::ModuleStatusChanged {
+                ship_id,
+                module_id: change.module_id,
+                health_percent: change.health_percent,
+                operational: change.operational,
+                overheated: change.overheated,
+            });
+        }
+    }
+
+    /// Translate this tick's `Explosion` messages into events
+    fn emit_explosion_events(&mut self) {
+        for explosion in drain_messages::<Explosion>(&mut self.world) {
+            let source_ship_id = self
+                .world
+                .get::<ShipData>(explosion.owner)
+                .and_then(|ship_data| Uuid::parse_str(&ship_data.id).ok());
+
+            self.push_event(GameEvent::Explosion {
+                source_ship_id,
+                damage_type: explosion.damage_type,
+                position: vector_to_array(&explosion.position),
+                radius: explosion.radius,
+                damage: explosion.damage,
+            });
+        }
+    }
+
+    /// Translate this tick's `ContactChanged` messages into events
+    fn emit_contact_events(&mut self) {
+        for change in drain_messages::<ContactChanged>(&mut self.world) {
+            let Some(detecting_ship_id) = self
+                .world
+                .get::<ShipData>(change.ship)
+                .and_then(|ship_data| Uuid::parse_str(&ship_data.id).ok())
+            else {
+                continue;
+            };
+            let Ok(contact_id) = Uuid::parse_str(&change.contact_id) else {
+                continue;
+            };
+
+            self.push_event(if change.detected {
+                GameEvent::ContactDetected {
+                    detecting_ship_id,
+                    contact_id,
+                    contact_type: ContactType::Ship,
+                }
+            } else {
+                GameEvent::ContactLost {
+                    detecting_ship_id,
+                    contact_id,
+                }
+            });
+        }
+    }
+
+    /// Translate this tick's `PointDefenseEngaged` messages into events
+    fn emit_point_defense_events(&mut self) {
+        for engagement in drain_messages::<PointDefenseEngaged>(&mut self.world) {
+            let Some(ship_id) = self
+                .world
+                .get::<ShipData>(engagement.ship)
+                .and_then(|ship_data| Uuid::parse_str(&ship_data.id).ok())
+            else {
+                continue;
+            };
+            let target_id = self
+                .world
+                .get::<ShipData>(engagement.attacker)
+                .and_then(|ship_data| Uuid::parse_str(&ship_data.id).ok());
+
+            self.push_event(GameEvent::PointDefenseEngaged {
+                ship_id,
+                weapon_id: engagement.weapon_id,
+                target_id,
+                damage_type: engagement.damage_type,
+                success: engagement.success,
+            });
+        }
+    }
+}
+
+fn vector_to_array(v: &Vector3<f32>) -> [f64; 3] {
+    [v.x as f64, v.y as f64, v.z as f64]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::DamageType;
+    use crate::models::WeaponTag;
+    use crate::simulation::{ProjectileComponent, StatusEffects};
+
+    fn spawn_ship(world: &mut GameWorld, id: Uuid, velocity: Vector3<f32>) -> Entity {
+        let mut transform = Transform::new();
+        transform.velocity = velocity;
+        world
+            .world
+            .spawn((
+                ShipData::new(
+                    id.to_string(),
+                    "Test Ship".to_string(),
+                    "cruiser".to_string(),
+                    "team1".to_string(),
+                    1000.0,
+                    100.0,
+                    1000.0,
+                ),
+                ShieldComponent::new(100.0, 0.0, 0.0),
+                StatusEffects::new(),
+                transform,
+            ))
+            .id()
+    }
+
+    fn register_test_ship(world: &mut GameWorld) -> String {
+        let ship_id = Uuid::new_v4().to_string();
+        let mut status = crate::models::ShipStatus::new(1000.0, 200.0, 1000.0);
+        status.velocity = [5.0, 0.0, 0.0];
+        world.register_ship(crate::models::Ship {
+            id: ship_id.clone(),
+            name: "Synced".to_string(),
+            class: "cruiser".to_string(),
+            team_id: "team1".to_string(),
+            player_roles: HashMap::new(),
+            status,
+            modules: vec![],
+            weapons: vec![],
+            inventory: crate::models::Inventory::new(),
+        });
+        ship_id
+    }
+
+    #[test]
+    fn test_spawn_and_remove_ship_entity() {
+        let mut world = GameWorld::new();
+        let config = crate::config::test_utils::create_test_game_config();
+        let ship_id = register_test_ship(&mut world);
+
+        assert!(world.spawn_ship_entity("missing", &config).is_err());
+        let entity = world.spawn_ship_entity(&ship_id, &config).unwrap();
+        assert_eq!(world.get_ship_entity(&ship_id), Some(entity));
+
+        world.remove_ship(&ship_id).unwrap();
+        assert!(world.get_ship_entity(&ship_id).is_none());
+        assert!(world.world.get_entity(entity).is_err());
+    }
+
+    #[test]
+    fn test_step_simulation_syncs_ship_status() {
+        let mut world = GameWorld::new();
+        let config = crate::config::test_utils::create_test_game_config();
+        let ship_id = register_test_ship(&mut world);
+        let entity = world.spawn_ship_entity(&ship_id, &config).unwrap();
+
+        world.world.get_mut::<ShipData>(entity).unwrap().hull = 600.0;
+        world
+            .world
+            .get_mut::<ShieldComponent>(entity)
+            .unwrap()
+            .strength = 50.0;
+        world.step_simulation();
+
+        let status = &world.get_ship(&ship_id).unwrap().status;
+        assert_eq!(status.hull, 600.0);
+        assert_eq!(status.shields, 50.0);
+        assert!(status.position[0] > 0.0);
+    }
+
+    #[test]
+    fn test_helm_commands_reach_simulation() {
+        let mut world = GameWorld::new();
+        let config = crate::config::test_utils::create_test_game_config();
+        let ship_id = register_test_ship(&mut world);
+        let entity = world.spawn_ship_entity(&ship_id, &config).unwrap();
+        world.world.get_mut::<Transform>(entity).unwrap().velocity = Vector3::zeros();
+
+        world.add_thrust_command(ship_id.clone(), 0.0, 0.0, 5.0);
+        assert_eq!(world.pending_helm_commands(&ship_id).len(), 1);
+        world.step_simulation();
+
+        assert!(world.pending_helm_commands(&ship_id).is_empty());
+        let helm = world.world.get::<HelmComponent>(entity).unwrap();
+        assert_eq!(helm.thrust, Vector3::new(0.0, 0.0, 5.0));
+        assert!(world.get_ship(&ship_id).unwrap().status.velocity[2] > 0.0);
+    }
+
+    #[test]
+    fn test_dock_command_docks_slow_ship_in_range() {
+        let mut world = GameWorld::new();
+        let config = crate::config::test_utils::create_test_game_config();
+        let ship_id = register_test_ship(&mut world);
+        world.spawn_ship_entity(&ship_id, &config).unwrap();
+        let station_id = world.register_station(crate::stations::Station::new(
+            "Outpost".to_string(),
+            [20.0, 0.0, 0.0],
+            "Neutral".to_string(),
+        ));
+
+        world.add_dock_command(ship_id.clone(), station_id.clone());
+        world.step_simulation();
+
+        let ship_uuid = Uuid::parse_str(&ship_id).unwrap();
+        let station = world.get_station(&station_id).unwrap();
+        assert!(station.is_ship_docked(ship_uuid));
+        assert!(world.drain_events().iter().any(|event| matches!(
+            event,
+            GameEvent::ShipDocked { ship_id, .. } if *ship_id == ship_uuid
+        )));
+    }
+
+    #[test]
+    fn test_dock_command_out_of_range_ignored() {
+        let mut world = GameWorld::new();
+        let config = crate::config::test_utils::create_test_game_config();
+        let ship_id = register_test_ship(&mut world);
+        world.spawn_ship_entity(&ship_id, &config).unwrap();
+        let station_id = world.register_station(crate::stations::Station::new(
+            "Distant".to_string(),
+            [1.0e6, 0.0, 0.0],
+            "Neutral".to_string(),
+        ));
+
+        world.add_dock_command(ship_id.clone(), station_id.clone());
+        world.step_simulation();
+
+        let station = world.get_station(&station_id).unwrap();
+        assert!(station.docking_requests.is_empty());
+    }
+
+    #[test]
+    fn test_step_simulation_advances_clock() {
+        let mut world = GameWorld::new();
+
+        world.step_simulation();
+        world.step_simulation();
+
+        assert_eq!(world.simulation.tick, 2);
+        let events = world.drain_events();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events[1],
+            GameEvent::SimulationTick { tick: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn test_step_simulation_paused() {
+        let mut world = GameWorld::new();
+        world.simulation.pause();
+
+        world.step_simulation();
+
+        assert_eq!(world.simulation.tick, 0);
+        assert_eq!(world.event_count(), 0);
+    }
+
+    #[test]
+    fn test_step_simulation_emits_movement() {
+        let mut world = GameWorld::new();
+        let moving = Uuid::new_v4();
+        let idle = Uuid::new_v4();
+        spawn_ship(&mut world, moving, Vector3::new(10.0, 0.0, 0.0));
+        spawn_ship(&mut world, idle, Vector3::zeros());
+
+        world.step_simulation();
+
+        let moved: Vec<Uuid> = world
+            .drain_events()
+            .into_iter()
+            .filter_map(|event| match event {
+                GameEvent::ShipMoved { ship_id, .. } => Some(ship_id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(moved, vec![moving]);
+    }
+
+    #[test]
+    fn test_step_simulation_emits_damage() {
+        let mut world = GameWorld::new();
+        let ship_id = Uuid::new_v4();
+        let ship = spawn_ship(&mut world, ship_id, Vector3::zeros());
+
+        world.world.spawn((
+            ProjectileComponent {
+                projectile_type: crate::simulation::ProjectileType::Kinetic,
+                owner: Entity::from_raw_u32(1000).unwrap(),
+                target: Some(ship),
+                damage: 50.0,
+                tags: vec![WeaponTag::SingleFire],
+                lifetime: 1.0,
+                accuracy: 1.0,
+                blast_radius: 0.0,
+                blast_damage: 0.0,
+                guidance: Default::default(),
+            },
+            Transform::new(),
+        ));
+
+        world.step_simulation();
+
+        let damage = world
+            .drain_events()
+            .into_iter()
+            .find_map(|event| match event {
+                GameEvent::DamageTaken {
+                    ship_id: id,
+                    damage_type,
+                    shield_percent,
+                    ..
+                } => Some((id, damage_type, shield_percent)),
+                _ => None,
+            })
+            .expect("expected a DamageTaken event");
+        assert_eq!(damage.0, ship_id);
+        assert_eq!(damage.1, DamageType::Kinetic);
+        assert!(damage.2 < 100.0);
+    }
+
+    #[test]
+    fn test_warhead_explosion_damages_ships_in_radius() {
+        let mut world = GameWorld::new();
+        let target_id = Uuid::new_v4();
+        let firer_id = Uuid::new_v4();
+        let far_id = Uuid::new_v4();
+        let target = spawn_ship(&mut world, target_id, Vector3::zeros());
+        let firer = spawn_ship(&mut world, firer_id, Vector3::zeros());
+        let far = spawn_ship(&mut world, far_id, Vector3::zeros());
+        world.world.get_mut::<Transform>(firer).unwrap().position = Vector3::new(50.0, 0.0, 0.0);
+        world.world.get_mut::<Transform>(far).unwrap().position = Vector3::new(500.0, 0.0, 0.0);
+
+        let mut torpedo = ProjectileComponent::kinetic(firer, 0.0, vec![], 10.0);
+        torpedo.projectile_type = crate::simulation::ProjectileType::Torpedo;
+        torpedo.target = Some(target);
+        torpedo.blast_radius = 100.0;
+        torpedo.blast_damage = 200.0;
+        world.world.spawn((torpedo, Transform::new()));
+        let mut slug = ProjectileComponent::kinetic(far, 10.0, vec![], 10.0);
+        slug.target = Some(far);
+        let mut slug_transform = Transform::new();
+        slug_transform.position = Vector3::new(20.0, 0.0, 0.0);
+        let slug = world.world.spawn((slug, slug_transform)).id();
+
+        world.step_simulation();
+
+        let shields = |entity| world.world.get::<ShieldComponent>(entity).unwrap().strength;
+        assert!(shields(target) <= 0.0);
+        assert!(shields(firer) < 100.0);
+        assert_eq!(shields(far), 100.0);
+        assert!(world.world.get_entity(slug).is_err());
+
+        let explosion = world
+            .drain_events()
+            .into_iter()
+            .find_map(|event| match event {
+                GameEvent::Explosion {
+                    source_ship_id,
+                    damage_type,
+                    radius,
+                    ..
+                } => Some((source_ship_id, damage_type, radius)),
+                _ => None,
+            })
+            .expect("expected an Explosion event");
+        assert_eq!(explosion, (Some(firer_id), DamageType::Torpedo, 100.0));
+    }
+}
@@ -0,0 +1,679 @@
+//! Station services for docked ships
+//!
+//! A ship docked at a station can buy repairs, power, and ammunition from
+//! it. Each service is priced from what it actually delivers: health points
+//! restored at the station's `repair_cost`, energy added to the power
+//! reserve at its `refuel_cost`, and rounds at their `AmmunitionConfig::cost`
+//! scaled by its `rearm_cost`, all scaled by the team's standing with the
+//! station's faction. Rounds come out of the station's ammunition
+//! stock, so a station can run dry. The bill goes to the ship's team;
+//! nothing changes unless the team can pay all of it.
+
+use bevy_ecs::prelude::*;
+
+use super::GameWorld;
+use super::weapon_control::ammo_category;
+use crate::config::GameConfig;
+use crate::replay::ReplayCommand;
+use crate::simulation::{ModuleStateTracker, PowerGrid, WeaponComponent};
+use crate::stations::{
+    ServiceRequest, ServiceResponse, StationInventory, StationService, StationServices,
+};
+
+/// What a service will deliver once paid for
+enum ServiceOrder {
+    /// Health to restore (module instance ID, health points)
+    Repair(Vec<(String, f32)>),
+    /// Energy to add to the power reserve
+    Refuel(f32),
+    /// Rounds to add to the hold (ammunition type, rounds)
+    Stock(String, u32),
+    /// Rounds to add to weapon magazines (weapon entity, ammunition type,
+    /// rounds)
+    Reload(Vec<(Entity, String, u32)>),
+}
+
+impl ServiceOrder {
+    /// Rounds the order draws from station stock (ammunition type, rounds)
+    fn ammunition(&self) -> Vec<(String, u32)> {
+        match self {
+            ServiceOrder::Stock(ammo_type, rounds) => vec![(ammo_type.clone(), *rounds)],
+            ServiceOrder::Reload(reloads) => reloads
+                .iter()
+                .map(|(_, ammo_type, rounds)| (ammo_type.clone(), *rounds))
+                .collect(),
+            ServiceOrder::Repair(_) | ServiceOrder::Refuel(_) => Vec::new(),
+        }
+    }
+}
+
+impl GameWorld {
+    /// Perform a service for a ship docked at a station and bill its team
+    ///
+    /// `RepairModule` and `RepairAll` restore module health to full,
+    /// `Refuel` adds up to `amount` energy to the power reserve, `RearmWeapon`
+    /// puts rounds a weapon can load into the hold, and `RearmAll` refills
+    /// the magazine of every weapon with ammunition loaded, as far as the
+    /// station's stock goes. Fails without changing anything if the ship is
+    /// not docked at the station, the station does not offer the service,
+    /// there is nothing to do, the station is out of stock, or the team
+    /// cannot afford it.
+    pub fn perform_station_service(
+        &mut self,
+        station_id: &str,
+        ship_id: &str,
+        request: ServiceRequest,
+        config: &GameConfig,
+    ) -> Result<ServiceResponse, String> {
+        let station = self
+            .get_station(station_id)
+            .ok_or_else(|| format!("Station {} not found", station_id))?;
+        if self
+            .docked_station(ship_id)
+            .is_none_or(|docked| docked.id != station.id)
+        {
+            return Err("Ship must be docked to request services".to_string());
+        }
+        let services = station.services.clone();
+        let faction = station.faction.clone();
+        let service = request_service(&request);
+        if !services.offers(service) {
+            return Err(format!("{} services not available", service_label(service)));
+        }
+
+        let stock = self.station_inventory(station_id, config)?;
+        let (order, cost, message) =
+            self.quote_service(ship_id, &request, &services, &stock, config)?;
+        let team_id = self
+            .ships
+            .get(ship_id)
+            .map(|ship| ship.team_id.clone())
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+        let credits = (cost * self.price_factor(&team_id, &faction)).ceil() as i64;
+
+        // Draw the rounds from a copy of the stock, so nothing is taken
+        // unless the team can pay and nothing is billed unless it's in stock
+        let ammunition = order.ammunition();
+        let mut remaining = None;
+        if !ammunition.is_empty() {
+            let mut inventory = stock;
+            for (ammo_type, rounds) in ammunition {
+                inventory.take_ammunition(&ammo_type, rounds)?;
+            }
+            remaining = Some(inventory);
+        }
+        if credits > 0 {
+            self.deduct_team_credits(&team_id, credits)?;
+        }
+        if let (Some(inventory), Some(station)) = (remaining, self.stations.get_mut(station_id)) {
+            station.inventory = inventory;
+        }
+        self.fill_order(ship_id, order);
+
+        self.record(ReplayCommand::StationService {
+            station_id: station_id.to_string(),
+            ship_id: ship_id.to_string(),
+            request,
+        });
+        Ok(ServiceResponse {
+            success: true,
+            message,
+            cost: credits as f32,
+        })
+    }
+
+    /// Work out what a service will deliver and what it costs
+    fn quote_service(
+        &self,
+        ship_id: &str,
+        request: &ServiceRequest,
+        services: &StationServices,
+        stock: &StationInventory,
+        config: &GameConfig,
+    ) -> Result<(ServiceOrder, f32, String), String> {
+        let entity = self
+            .get_ship_entity(ship_id)
+            .ok_or_else(|| format!("Ship {} is not in the simulation", ship_id))?;
+
+        match request {
+            ServiceRequest::RepairModule { module_id } => {
+                let state = self
+                    .world
+                    .get::<ModuleStateTracker>(entity)
+                    .and_then(|tracker| tracker.get(module_id))
+                    .ok_or_else(|| format!("Module {} not found", module_id))?;
+                let damage = state.max_health - state.current_health;
+                if damage <= 0.0 {
+                    return Err(format!("Module {} is not damaged", module_id));
+                }
+                Ok((
+                    ServiceOrder::Repair(vec![(module_id.clone(), damage)]),
+                    damage * services.repair_cost,
+                    format!("Repaired module {} ({:.0} health)", module_id, damage),
+                ))
+            }
+            ServiceRequest::RepairAll => {
+                let repairs: Vec<(String, f32)> = self
+                    .world
+                    .get::<ModuleStateTracker>(entity)
+                    .map(|tracker| {
+                        tracker
+                            .states
+                            .values()
+                            .filter(|state| state.current_health < state.max_health)
+                            .map(|state| {
+                                (
+                                    state.instance_id.clone(),
+                                    state.max_health - state.current_health,
+                                )
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if repairs.is_empty() {
+                    return Err("Ship has no damaged modules".to_string());
+                }
+                let damage: f32 = repairs.iter().map(|(_, amount)| amount).sum();
+                let message = format!("Repaired {} modules ({:.0} health)", repairs.len(), damage);
+                Ok((
+                    ServiceOrder::Repair(repairs),
+                    damage * services.repair_cost,
+                    message,
+                ))
+            }
+            ServiceRequest::Refuel { amount } => {
+                let power_grid = self
+                    .world
+                    .get::<PowerGrid>(entity)
+                    .ok_or_else(|| format!("Ship {} has no power grid", ship_id))?;
+                let amount = amount.min(power_grid.capacity - power_grid.stored);
+                if amount <= 0.0 {
+                    return Err("Power reserve is already full".to_string());
+                }
+                Ok((
+                    ServiceOrder::Refuel(amount),
+                    amount * services.refuel_cost,
+                    format!("Refueled {:.0} units", amount),
+                ))
+            }
+            ServiceRequest::RearmWeapon {
+                weapon_id,
+                ammunition_type,
+                quantity,
+            } => {
+                let weapon = self
+                    .weapon_entities(ship_id)
+                    .into_iter()
+                    .filter_map(|weapon| self.world.get::<WeaponComponent>(weapon))
+                    .find(|weapon| weapon.id == *weapon_id)
+                    .ok_or_else(|| format!("Weapon {} not found on ship {}", weapon_id, ship_id))?;
+                let ammo = config
+                    .get_ammunition(ammunition_type)
+                    .ok_or_else(|| format!("Unknown ammunition type {}", ammunition_type))?;
+                if Some(ammo.category.as_str()) != ammo_category(weapon) {
+                    return Err(format!(
+                        "Weapon {} cannot load {} ammunition",
+                        weapon_id, ammo.category
+                    ));
+                }
+                if *quantity == 0 {
+                    return Err("Quantity must be positive".to_string());
+                }
+                let available = stock.ammunition_quantity(ammunition_type);
+                if available < *quantity {
+                    return Err(format!(
+                        "Station has only {} rounds of {}",
+                        available, ammunition_type
+                    ));
+                }
+                Ok((
+                    ServiceOrder::Stock(ammunition_type.clone(), *quantity),
+                    *quantity as f32 * ammo.cost * services.rearm_cost,
+                    format!(
+                        "Rearmed {} with {} x {}",
+                        weapon_id, quantity, ammunition_type
+                    ),
+                ))
+            }
+            ServiceRequest::RearmAll => {
+                let mut reloads = Vec::new();
+                let mut cost = 0.0;
+                let mut remaining = stock.ammunition.clone();
+                let mut short = false;
+                for weapon_entity in self.weapon_entities(ship_id) {
+                    let Some(weapon) = self.world.get::<WeaponComponent>(weapon_entity) else {
+                        continue;
+                    };
+                    let Some(ammo) = weapon
+                        .ammunition
+                        .as_deref()
+                        .and_then(|ammo_type| config.get_ammunition(ammo_type))
+                    else {
+                        continue;
+                    };
+                    let needed = weapon.ammo_capacity.saturating_sub(weapon.ammo_count);
+                    let available = remaining.entry(ammo.id.clone()).or_insert(0);
+                    let rounds = needed.min(*available);
+                    short |= rounds < needed;
+                    if rounds > 0 {
+                        *available -= rounds;
+                        reloads.push((weapon_entity, ammo.id.clone(), rounds));
+                        cost += rounds as f32 * ammo.cost * services.rearm_cost;
+                    }
+                }
+                if reloads.is_empty() {
+                    return Err(if short {
+                        "Station is out of ammunition for these weapons".to_string()
+                    } else {
+                        "All magazines are already full".to_string()
+                    });
+                }
+                let message = format!("Rearmed {} weapons", reloads.len());
+                Ok((ServiceOrder::Reload(reloads), cost, message))
+            }
+        }
+    }
+
+    /// Deliver a paid-for service
+    fn fill_order(&mut self, ship_id: &str, order: ServiceOrder) {
+        let Some(entity) = self.get_ship_entity(ship_id) else {
+            return;
+        };
+        match order {
+            ServiceOrder::Repair(repairs) => {
+                if let Some(mut tracker) = self.world.get_mut::<ModuleStateTracker>(entity) {
+                    for (module_id, amount) in &repairs {
+                        if let Some(state) = tracker.get_mut(module_id) {
+                            state.repair(*amount);
+                        }
+                    }
+                }
+                if let Some(ship) = self.ships.get_mut(ship_id) {
+                    for module in &mut ship.modules {
+                        if repairs.iter().any(|(id, _)| *id == module.instance_id) {
+                            module.current_health = module.max_health;
+                            module.operational = true;
+                            ship.status
+                                .module_health
+                                .insert(module.instance_id.clone(), module.max_health);
+                        }
+                    }
+                }
+            }
+            ServiceOrder::Refuel(amount) => {
+                if let Some(mut power_grid) = self.world.get_mut::<PowerGrid>(entity) {
+                    power_grid.stored = (power_grid.stored + amount).min(power_grid.capacity);
+                }
+            }
+            ServiceOrder::Stock(ammo_type, rounds) => {
+                if let Some(ship) = self.ships.get_mut(ship_id) {
+                    ship.inventory.add_ammunition(ammo_type, rounds);
+                }
+                self.sync_inventory_component(ship_id);
+            }
+            ServiceOrder::Reload(reloads) => {
+                for (weapon_entity, _, rounds) in reloads {
+                    if let Some(mut weapon) = self.world.get_mut::<WeaponComponent>(weapon_entity) {
+                        weapon.ammo_count += rounds;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Station service a request draws on
+fn request_service(request: &ServiceRequest) -> StationService {
+    match request {
+        ServiceRequest::RepairModule { .. } | ServiceRequest::RepairAll => StationService::Repair,
+        ServiceRequest::Refuel { .. } => StationService::Refuel,
+        ServiceRequest::RearmWeapon { .. } | ServiceRequest::RearmAll => StationService::Rearm,
+    }
+}
+
+fn service_label(service: StationService) -> &'static str {
+    match service {
+        StationService::Repair => "Repair",
+        StationService::Refuel => "Refuel",
+        StationService::Rearm => "Rearm",
+        StationService::Trade => "Trade",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AmmunitionConfig;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::{CompiledModule, Inventory, Ship, ShipStatus};
+    use crate::stations::Station;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn ammunition(id: &str, category: &str) -> AmmunitionConfig {
+        AmmunitionConfig {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            cost: 10.0,
+            weight: 1.0,
+            impact_damage: 30.0,
+            blast_radius: 0.0,
+            blast_damage: 0.0,
+            velocity: 2000.0,
+            armor_penetration: 0.0,
+            acceleration: 100.0,
+            max_speed: 800.0,
+            max_turn_rate: 30.0,
+            lifetime: 20.0,
+            weapon_tags: vec![],
+            category: category.to_string(),
+            ammo_type: String::new(),
+            ammo_size: String::new(),
+        }
+    }
+
+    fn test_config() -> GameConfig {
+        let mut config = create_test_game_config();
+        config.ammunition_types = vec![
+            ammunition("slug-20mm-st", "kinetic"),
+            ammunition("test-missile", "missiles"),
+        ];
+        config
+    }
+
+    fn weapon_module(instance_id: &str, slot: &str, stats: serde_json::Value) -> CompiledModule {
+        CompiledModule {
+            instance_id: instance_id.to_string(),
+            module_id: slot.to_string(),
+            kind: None,
+            name: instance_id.to_string(),
+            stats: serde_json::from_value(stats).unwrap(),
+            current_health: 20.0,
+            max_health: 50.0,
+            operational: true,
+            power_allocated: 1.0,
+            cooling_allocated: 1.0,
+        }
+    }
+
+    /// A damaged ship docked at a fresh station, with a team holding `credits`
+    ///
+    /// Returns the station and ship IDs.
+    fn dock_damaged_ship(world: &mut GameWorld, credits: i64) -> (String, String) {
+        let team_id = world
+            .create_team_with_credits("Traders".to_string(), "neutral".to_string(), credits)
+            .unwrap();
+        let ship_id = Uuid::new_v4().to_string();
+        let mut status = ShipStatus::new(1000.0, 100.0, 1000.0);
+        status.power_capacity = 100.0;
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Damaged".to_string(),
+            class: "cruiser".to_string(),
+            team_id,
+            player_roles: HashMap::new(),
+            status,
+            modules: vec![
+                weapon_module(
+                    "cannon",
+                    "kinetic-weapon",
+                    serde_json::json!({ "reload_time": 1.0 }),
+                ),
+                weapon_module(
+                    "launcher",
+                    "missile-launcher",
+                    serde_json::json!({ "reload_time": 5.0, "ammo_capacity": 2 }),
+                ),
+            ],
+            weapons: vec![],
+            inventory: Inventory::new(),
+        });
+        world.spawn_ship_entity(&ship_id, &test_config()).unwrap();
+
+        let mut station = Station::new("Haven".to_string(), [0.0; 3], "neutral".to_string());
+        station
+            .docked_ships
+            .push(Uuid::parse_str(&ship_id).unwrap());
+        let station_id = world.register_station(station);
+        (station_id, ship_id)
+    }
+
+    fn credits(world: &GameWorld, ship_id: &str) -> i64 {
+        let team_id = &world.get_ship(ship_id).unwrap().team_id;
+        world.get_team(team_id).unwrap().credits
+    }
+
+    fn health(world: &GameWorld, ship_id: &str, module_id: &str) -> f32 {
+        let entity = world.get_ship_entity(ship_id).unwrap();
+        let tracker = world.world.get::<ModuleStateTracker>(entity).unwrap();
+        tracker.get(module_id).unwrap().current_health
+    }
+
+    #[test]
+    fn test_repair_bills_per_health_point_restored() {
+        let mut world = GameWorld::new();
+        let config = test_config();
+        let (station_id, ship_id) = dock_damaged_ship(&mut world, 10_000);
+
+        let response = world
+            .perform_station_service(
+                &station_id,
+                &ship_id,
+                ServiceRequest::RepairModule {
+                    module_id: "cannon".to_string(),
+                },
+                &config,
+            )
+            .unwrap();
+        // 30 health at 10 credits each
+        assert_eq!(response.cost, 300.0);
+        assert_eq!(credits(&world, &ship_id), 9_700);
+        assert_eq!(health(&world, &ship_id, "cannon"), 50.0);
+        assert_eq!(health(&world, &ship_id, "launcher"), 20.0);
+
+        let response = world
+            .perform_station_service(&station_id, &ship_id, ServiceRequest::RepairAll, &config)
+            .unwrap();
+        assert_eq!(response.cost, 300.0);
+        assert_eq!(health(&world, &ship_id, "launcher"), 50.0);
+        let ship = world.get_ship(&ship_id).unwrap();
+        assert!(
+            ship.modules
+                .iter()
+                .all(|m| m.current_health == m.max_health)
+        );
+
+        assert!(
+            world
+                .perform_station_service(&station_id, &ship_id, ServiceRequest::RepairAll, &config)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_refuel_and_rearm() {
+        let mut world = GameWorld::new();
+        let config = test_config();
+        let (station_id, ship_id) = dock_damaged_ship(&mut world, 10_000);
+        let entity = world.get_ship_entity(&ship_id).unwrap();
+        world.world.get_mut::<PowerGrid>(entity).unwrap().stored = 40.0;
+
+        // Topped up to capacity, 5 credits per unit
+        let response = world
+            .perform_station_service(
+                &station_id,
+                &ship_id,
+                ServiceRequest::Refuel { amount: 500.0 },
+                &config,
+            )
+            .unwrap();
+        assert_eq!(response.cost, 300.0);
+        assert_eq!(world.world.get::<PowerGrid>(entity).unwrap().stored, 100.0);
+
+        // 10 rounds at 10 credits, marked up by 1.5
+        let rearm = |ammunition_type: &str| ServiceRequest::RearmWeapon {
+            weapon_id: "cannon".to_string(),
+            ammunition_type: ammunition_type.to_string(),
+            quantity: 10,
+        };
+        let response = world
+            .perform_station_service(&station_id, &ship_id, rearm("slug-20mm-st"), &config)
+            .unwrap();
+        assert_eq!(response.cost, 150.0);
+        let ship = world.get_ship(&ship_id).unwrap();
+        assert_eq!(ship.inventory.ammunition.get("slug-20mm-st"), Some(&10));
+        assert!(
+            world
+                .perform_station_service(&station_id, &ship_id, rearm("test-missile"), &config)
+                .is_err()
+        );
+
+        let launcher = world
+            .weapon_entities(&ship_id)
+            .into_iter()
+            .find(|weapon| world.world.get::<WeaponComponent>(*weapon).unwrap().id == "launcher")
+            .unwrap();
+        world
+            .world
+            .get_mut::<WeaponComponent>(launcher)
+            .unwrap()
+            .ammunition = Some("test-missile".to_string());
+        let response = world
+            .perform_station_service(&station_id, &ship_id, ServiceRequest::RearmAll, &config)
+            .unwrap();
+        assert_eq!(response.cost, 30.0);
+        assert_eq!(
+            world
+                .world
+                .get::<WeaponComponent>(launcher)
+                .unwrap()
+                .ammo_count,
+            2
+        );
+        assert_eq!(credits(&world, &ship_id), 10_000 - 300 - 150 - 30);
+    }
+
+    #[test]
+    fn test_rearm_draws_on_station_stock() {
+        let mut world = GameWorld::new();
+        let config = test_config();
+        let (station_id, ship_id) = dock_damaged_ship(&mut world, 10_000);
+        world.get_station_mut(&station_id).unwrap().inventory = StationInventory {
+            stocked: true,
+            modules: vec![],
+            ammunition: [
+                ("slug-20mm-st".to_string(), 15),
+                ("test-missile".to_string(), 1),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let rearm = ServiceRequest::RearmWeapon {
+            weapon_id: "cannon".to_string(),
+            ammunition_type: "slug-20mm-st".to_string(),
+            quantity: 10,
+        };
+        world
+            .perform_station_service(&station_id, &ship_id, rearm.clone(), &config)
+            .unwrap();
+        assert_eq!(
+            world
+                .perform_station_service(&station_id, &ship_id, rearm, &config)
+                .unwrap_err(),
+            "Station has only 5 rounds of slug-20mm-st"
+        );
+
+        // Only one of the two missiles the launcher holds is in stock
+        let launcher = world
+            .weapon_entities(&ship_id)
+            .into_iter()
+            .find(|weapon| world.world.get::<WeaponComponent>(*weapon).unwrap().id == "launcher")
+            .unwrap();
+        world
+            .world
+            .get_mut::<WeaponComponent>(launcher)
+            .unwrap()
+            .ammunition = Some("test-missile".to_string());
+        let response = world
+            .perform_station_service(&station_id, &ship_id, ServiceRequest::RearmAll, &config)
+            .unwrap();
+        assert_eq!(response.cost, 15.0);
+        assert_eq!(
+            world
+                .world
+                .get::<WeaponComponent>(launcher)
+                .unwrap()
+                .ammo_count,
+            1
+        );
+        let station = world.get_station(&station_id).unwrap();
+        assert_eq!(station.inventory.ammunition_quantity("test-missile"), 0);
+        assert!(
+            world
+                .perform_station_service(&station_id, &ship_id, ServiceRequest::RearmAll, &config)
+                .is_err()
+        );
+        assert_eq!(credits(&world, &ship_id), 10_000 - 150 - 15);
+    }
+
+    #[test]
+    fn test_service_fails_cleanly() {
+        let mut world = GameWorld::new();
+        let config = test_config();
+        let (station_id, ship_id) = dock_damaged_ship(&mut world, 100);
+
+        // Short of the 600 credits a full repair costs
+        assert!(
+            world
+                .perform_station_service(&station_id, &ship_id, ServiceRequest::RepairAll, &config)
+                .is_err()
+        );
+        assert_eq!(credits(&world, &ship_id), 100);
+        assert_eq!(health(&world, &ship_id, "cannon"), 20.0);
+
+        // Nor the 150 ten rounds cost; the station keeps its stock
+        world.get_station_mut(&station_id).unwrap().inventory = StationInventory {
+            stocked: true,
+            modules: vec![],
+            ammunition: [("slug-20mm-st".to_string(), 15)].into_iter().collect(),
+        };
+        let rearm = ServiceRequest::RearmWeapon {
+            weapon_id: "cannon".to_string(),
+            ammunition_type: "slug-20mm-st".to_string(),
+            quantity: 10,
+        };
+        assert!(
+            world
+                .perform_station_service(&station_id, &ship_id, rearm, &config)
+                .is_err()
+        );
+        assert_eq!(credits(&world, &ship_id), 100);
+        let station = world.get_station(&station_id).unwrap();
+        assert_eq!(station.inventory.ammunition_quantity("slug-20mm-st"), 15);
+        let ship = world.get_ship(&ship_id).unwrap();
+        assert!(ship.inventory.ammunition.is_empty());
+
+        world.get_station_mut(&station_id).unwrap().services.refuel = false;
+        let error = world
+            .perform_station_service(
+                &station_id,
+                &ship_id,
+                ServiceRequest::Refuel { amount: 10.0 },
+                &config,
+            )
+            .unwrap_err();
+        assert_eq!(error, "Refuel services not available");
+
+        let other = world.register_station(Station::new(
+            "Outpost".to_string(),
+            [0.0; 3],
+            "neutral".to_string(),
+        ));
+        assert!(
+            world
+                .perform_station_service(&other, &ship_id, ServiceRequest::RepairAll, &config)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,503 @@
+//! Commodity trading at station markets
+//!
+//! Stations offering trade open a market over the commodity catalog when
+//! they are registered. A ship docked at one can buy commodities into its
+//! cargo hold, as far as the hold has room, and sell what it carries back.
+//! The hold's capacity is the ship class's `cargo_capacity` plus the space
+//! of each intact cargo bay. Credits move through the ship's team wallet,
+//! at prices scaled by the team's standing with the station's faction, and
+//! each trade earns standing with it. Standing never lets a station pay more
+//! for goods than it charges for them.
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use super::GameWorld;
+use crate::config::simulation::{SimulationConfig, TradeConfig};
+use crate::config::{CommodityConfig, GameConfig};
+use crate::market::{MarketQuote, StationMarket, TradeReceipt, TradeSide};
+use crate::models::Ship;
+use crate::replay::ReplayCommand;
+use crate::simulation::{SimulationParams, SimulationRng};
+use crate::stations::{Station, StationService};
+
+/// Module slot that adds cargo space
+const CARGO_BAY_SLOT: &str = "cargo-bay";
+
+impl GameWorld {
+    /// Install the commodity catalog from `data/commodities.yaml`
+    ///
+    /// Stations already registered that offer trade but have no market yet
+    /// open one.
+    pub fn configure_markets(&mut self, commodities: &[CommodityConfig]) {
+        self.commodities = commodities.to_vec();
+        let mut station_ids: Vec<String> = self.stations.keys().cloned().collect();
+        station_ids.sort();
+        for station_id in station_ids {
+            if let Some(mut station) = self.stations.remove(&station_id) {
+                self.open_market(&mut station);
+                self.stations.insert(station_id, station);
+            }
+        }
+    }
+
+    /// Open a market at a station that offers trade and has none yet
+    ///
+    /// Each station's stock is rolled from the session seed and its ID, so
+    /// the same session always opens the same markets.
+    pub(super) fn open_market(&self, station: &mut Station) {
+        if station.market.is_open() || !station.services.trade || self.commodities.is_empty() {
+            return;
+        }
+        let (high, low) = station.id.as_u64_pair();
+        let mut rng = StdRng::seed_from_u64(self.simulation.seed ^ high ^ low);
+        station.market = StationMarket::open(&self.commodities, &self.trade_config(), &mut rng);
+    }
+
+    /// Current prices and stock at a station's market
+    pub fn market_quotes(&self, station_id: &str) -> Result<Vec<MarketQuote>, String> {
+        let station = self
+            .get_station(station_id)
+            .ok_or_else(|| format!("Station {} not found", station_id))?;
+        if !station.services.offers(StationService::Trade) || !station.market.is_open() {
+            return Err(format!("Station {} has no market", station.name));
+        }
+        let config = self.trade_config();
+        Ok(station
+            .market
+            .listings
+            .iter()
+            .map(|listing| {
+                let commodity = self
+                    .commodities
+                    .iter()
+                    .find(|commodity| commodity.id == listing.commodity_id);
+                MarketQuote {
+                    commodity_id: listing.commodity_id.clone(),
+                    name: commodity
+                        .map(|commodity| commodity.name.clone())
+                        .unwrap_or_else(|| listing.commodity_id.clone()),
+                    buy_price: listing.price(&config),
+                    sell_price: listing.bid(&config),
+                    supply: listing.available(),
+                    demand: listing.demand.round() as u32,
+                    volume: commodity.map_or(1.0, |commodity| commodity.volume),
+                }
+            })
+            .collect())
+    }
+
+    /// Buy or sell a commodity at the station a ship is docked at
+    ///
+    /// Buying needs the station to have the units in stock, the hold to have
+    /// room for them, and the team to afford them. Selling needs the ship to
+    /// carry the units and the station to list the commodity. Prices move
+    /// with each unit traded. Fails without changing anything otherwise.
+    pub fn trade_commodity(
+        &mut self,
+        station_id: &str,
+        ship_id: &str,
+        side: TradeSide,
+        commodity_id: &str,
+        quantity: u32,
+        config: &GameConfig,
+    ) -> Result<TradeReceipt, String> {
+        if quantity == 0 {
+            return Err("Quantity must be positive".to_string());
+        }
+        let station = self
+            .get_station(station_id)
+            .ok_or_else(|| format!("Station {} not found", station_id))?;
+        if self
+            .docked_station(ship_id)
+            .is_none_or(|docked| docked.id != station.id)
+        {
+            return Err("Ship must be docked to trade".to_string());
+        }
+        if !station.services.offers(StationService::Trade) {
+            return Err("Trade services not available".to_string());
+        }
+        let listing = station
+            .market
+            .listing(commodity_id)
+            .ok_or_else(|| format!("{} does not trade in {}", station.name, commodity_id))?;
+        let ship = self
+            .ships
+            .get(ship_id)
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+
+        let trade = self.trade_config();
+        let team_id = ship.team_id.clone();
+        let faction = station.faction.clone();
+        let price_factor = self.price_factor(&team_id, &faction);
+        let total = listing.quote(side, quantity, &trade);
+        let credits = match side {
+            TradeSide::Buy => {
+                if listing.available() < quantity {
+                    return Err(format!(
+                        "Only {} units of {} in stock",
+                        listing.available(),
+                        commodity_id
+                    ));
+                }
+                let needed = self.commodity_volume(commodity_id) * quantity as f32;
+                let free = self.cargo_capacity(ship, config) - self.cargo_volume(ship);
+                if needed > free {
+                    return Err(format!(
+                        "Not enough cargo space ({:.1} m³ needed, {:.1} m³ free)",
+                        needed, free
+                    ));
+                }
+                let cost = (total * price_factor).ceil() as i64;
+                self.deduct_team_credits(&team_id, cost)?;
+                cost
+            }
+            TradeSide::Sell => {
+                let carried = ship.inventory.cargo.get(commodity_id).copied().unwrap_or(0);
+                if carried < quantity {
+                    return Err(format!(
+                        "Insufficient cargo: {} (have {}, need {})",
+                        commodity_id, carried, quantity
+                    ));
+                }
+                let revenue =
+                    (total * self.sale_factor(&team_id, &faction, trade.sell_ratio)).floor() as i64;
+                self.add_team_credits(&team_id, revenue)?;
+                revenue
+            }
+        };
+
+        if let Some(listing) = self
+            .stations
+            .get_mut(station_id)
+            .and_then(|station| station.market.listing_mut(commodity_id))
+        {
+            listing.settle(side, quantity);
+        }
+        if let Some(ship) = self.ships.get_mut(ship_id) {
+            let cargo = ship
+                .inventory
+                .cargo
+                .entry(commodity_id.to_string())
+                .or_insert(0);
+            match side {
+                TradeSide::Buy => *cargo += quantity,
+                TradeSide::Sell => *cargo -= quantity,
+            }
+            if *cargo == 0 {
+                ship.inventory.cargo.remove(commodity_id);
+            }
+        }
+        self.sync_inventory_component(ship_id);
+        self.reward_trade(&team_id, &faction, credits);
+
+        self.record(ReplayCommand::Trade {
+            station_id: station_id.to_string(),
+            ship_id: ship_id.to_string(),
+            side,
+            commodity_id: commodity_id.to_string(),
+            quantity,
+        });
+        Ok(TradeReceipt {
+            side,
+            commodity_id: commodity_id.to_string(),
+            quantity,
+            total: credits,
+            credits: self.get_team(&team_id).map_or(0, |team| team.credits),
+        })
+    }
+
+    /// Cargo space of a ship's hold (cubic meters)
+    pub fn cargo_capacity(&self, ship: &Ship, config: &GameConfig) -> f32 {
+        let trade = self.trade_config();
+        let class_capacity = config
+            .get_ship_class(&ship.class)
+            .and_then(|class| class.cargo_capacity)
+            .unwrap_or(0.0);
+        let bay_capacity: f32 = ship
+            .modules
+            .iter()
+            .filter(|module| module.module_id == CARGO_BAY_SLOT && !module.is_destroyed())
+            .map(|module| {
+                module
+                    .get_stat_f64("cargo_capacity")
+                    .map_or(trade.cargo_bay_capacity, |capacity| capacity as f32)
+            })
+            .sum();
+        class_capacity + bay_capacity
+    }
+
+    /// Cargo space a ship's hold is using (cubic meters)
+    ///
+    /// Items outside the commodity catalog take one cubic meter each, and
+    /// salvaged modules take the salvage `module_volume` each.
+    pub fn cargo_volume(&self, ship: &Ship) -> f32 {
+        let cargo: f32 = ship
+            .inventory
+            .cargo
+            .iter()
+            .map(|(item_id, quantity)| self.commodity_volume(item_id) * *quantity as f32)
+            .sum();
+        cargo + ship.inventory.salvage.len() as f32 * self.salvage_config().module_volume
+    }
+
+    /// Let every open market drift by one timestep
+    ///
+    /// Called once per tick after the simulation systems have run, drawing
+    /// on that tick's `SimulationRng`.
+    pub(super) fn update_markets(&mut self) {
+        let config = self.trade_config();
+        let dt = self.simulation.timestep;
+        let mut station_ids: Vec<&String> = self
+            .stations
+            .iter()
+            .filter(|(_, station)| station.market.is_open())
+            .map(|(station_id, _)| station_id)
+            .collect();
+        station_ids.sort();
+        let station_ids: Vec<String> = station_ids.into_iter().cloned().collect();
+
+        let Some(mut rng) = self.world.get_resource_mut::<SimulationRng>() else {
+            return;
+        };
+        for station_id in station_ids {
+            if let Some(station) = self.stations.get_mut(&station_id) {
+                station.market.drift(dt, &config, &mut rng.0);
+            }
+        }
+    }
+
+    /// Cargo space taken by one unit of an item (cubic meters)
+    pub(super) fn commodity_volume(&self, item_id: &str) -> f32 {
+        self.commodities
+            .iter()
+            .find(|commodity| commodity.id == item_id)
+            .map_or(1.0, |commodity| commodity.volume)
+    }
+
+    fn trade_config(&self) -> TradeConfig {
+        self.world
+            .get_resource::<SimulationParams>()
+            .map(|params| params.trade.clone())
+            .unwrap_or_else(|| SimulationConfig::default().trade)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::{CompiledModule, Inventory, ShipStatus};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn ore() -> CommodityConfig {
+        CommodityConfig {
+            id: "ore".to_string(),
+            name: "Raw Ore".to_string(),
+            description: String::new(),
+            base_price: 10.0,
+            volume: 2.0,
+            stock: 1000.0,
+        }
+    }
+
+    /// 10 m³ from the class plus 100 m³ from a cargo bay
+    fn test_config() -> GameConfig {
+        let mut config = create_test_game_config();
+        config.ship_classes[0].cargo_capacity = Some(10.0);
+        config.commodities = vec![ore()];
+        config
+    }
+
+    /// A world whose markets all open at their usual stock
+    fn trading_world(config: &GameConfig) -> GameWorld {
+        let mut world = GameWorld::new();
+        let mut simulation = SimulationConfig::default();
+        simulation.trade.stock_variance = 0.0;
+        world.configure_simulation(&simulation);
+        world.configure_markets(&config.commodities);
+        world
+    }
+
+    /// A freighter docked at a fresh station, with a team holding 10,000
+    /// credits
+    fn dock_freighter(world: &mut GameWorld) -> (String, String) {
+        let team_id = world
+            .create_team_with_credits("Traders".to_string(), "neutral".to_string(), 10_000)
+            .unwrap();
+        let ship_id = Uuid::new_v4().to_string();
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Freighter".to_string(),
+            class: "cruiser".to_string(),
+            team_id,
+            player_roles: HashMap::new(),
+            status: ShipStatus::default(),
+            modules: vec![CompiledModule {
+                instance_id: "hold".to_string(),
+                module_id: CARGO_BAY_SLOT.to_string(),
+                kind: None,
+                name: "Cargo Bay".to_string(),
+                stats: Default::default(),
+                current_health: 8.0,
+                max_health: 8.0,
+                operational: true,
+                power_allocated: 1.0,
+                cooling_allocated: 1.0,
+            }],
+            weapons: vec![],
+            inventory: Inventory::new(),
+        });
+
+        let mut station = Station::new("Exchange".to_string(), [0.0; 3], "neutral".to_string());
+        station
+            .docked_ships
+            .push(Uuid::parse_str(&ship_id).unwrap());
+        let station_id = world.register_station(station);
+        (station_id, ship_id)
+    }
+
+    fn credits(world: &GameWorld, ship_id: &str) -> i64 {
+        let team_id = &world.get_ship(ship_id).unwrap().team_id;
+        world.get_team(team_id).unwrap().credits
+    }
+
+    #[test]
+    fn test_buy_fills_the_hold_and_raises_the_price() {
+        let config = test_config();
+        let mut world = trading_world(&config);
+        let (station_id, ship_id) = dock_freighter(&mut world);
+        let ship = world.get_ship(&ship_id).unwrap();
+        assert_eq!(world.cargo_capacity(ship, &config), 110.0);
+        assert_eq!(world.market_quotes(&station_id).unwrap()[0].buy_price, 10.0);
+
+        let receipt = world
+            .trade_commodity(&station_id, &ship_id, TradeSide::Buy, "ore", 50, &config)
+            .unwrap();
+        // Each unit costs a little more than the one before
+        assert!(receipt.total > 500 && receipt.total < 520);
+        assert_eq!(receipt.credits, 10_000 - receipt.total);
+        assert_eq!(credits(&world, &ship_id), receipt.credits);
+        let ship = world.get_ship(&ship_id).unwrap();
+        assert_eq!(ship.inventory.cargo.get("ore"), Some(&50));
+        assert_eq!(world.cargo_volume(ship), 100.0);
+
+        let quote = &world.market_quotes(&station_id).unwrap()[0];
+        assert_eq!(quote.supply, 950);
+        assert!(quote.buy_price > 10.0);
+
+        // 10 m³ left, so 6 more units (12 m³) do not fit
+        assert!(
+            world
+                .trade_commodity(&station_id, &ship_id, TradeSide::Buy, "ore", 6, &config)
+                .is_err()
+        );
+        assert_eq!(credits(&world, &ship_id), receipt.credits);
+    }
+
+    #[test]
+    fn test_sell_pays_the_bid_price() {
+        let config = test_config();
+        let mut world = trading_world(&config);
+        let (station_id, ship_id) = dock_freighter(&mut world);
+        world
+            .get_ship_mut(&ship_id)
+            .unwrap()
+            .inventory
+            .cargo
+            .insert("ore".to_string(), 20);
+
+        let receipt = world
+            .trade_commodity(&station_id, &ship_id, TradeSide::Sell, "ore", 20, &config)
+            .unwrap();
+        // Just under 90% of the base price per unit
+        assert!(receipt.total > 170 && receipt.total < 180);
+        assert_eq!(credits(&world, &ship_id), 10_000 + receipt.total);
+        assert!(world.get_ship(&ship_id).unwrap().inventory.cargo.is_empty());
+        assert_eq!(world.market_quotes(&station_id).unwrap()[0].supply, 1020);
+
+        assert!(
+            world
+                .trade_commodity(&station_id, &ship_id, TradeSide::Sell, "ore", 1, &config)
+                .is_err()
+        );
+        assert!(
+            world
+                .trade_commodity(&station_id, &ship_id, TradeSide::Buy, "gold", 1, &config)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_round_trips_never_pay() {
+        let config = test_config();
+        for standing in [-100.0, -50.0, 0.0, 50.0, 100.0] {
+            let mut world = trading_world(&config);
+            let (station_id, ship_id) = dock_freighter(&mut world);
+            let team_id = world.get_ship(&ship_id).unwrap().team_id.clone();
+            world
+                .get_team_mut(&team_id)
+                .unwrap()
+                .reputation
+                .insert("neutral".to_string(), standing);
+
+            let before = credits(&world, &ship_id);
+            for side in [TradeSide::Buy, TradeSide::Sell] {
+                world
+                    .trade_commodity(&station_id, &ship_id, side, "ore", 1, &config)
+                    .unwrap();
+            }
+            assert!(
+                credits(&world, &ship_id) <= before,
+                "buying and selling back at standing {} paid",
+                standing
+            );
+        }
+    }
+
+    #[test]
+    fn test_trade_requires_docking_and_market() {
+        let config = test_config();
+        let mut world = trading_world(&config);
+        let (_, ship_id) = dock_freighter(&mut world);
+
+        let elsewhere = world.register_station(Station::new(
+            "Elsewhere".to_string(),
+            [0.0; 3],
+            "neutral".to_string(),
+        ));
+        let error = world
+            .trade_commodity(&elsewhere, &ship_id, TradeSide::Buy, "ore", 1, &config)
+            .unwrap_err();
+        assert_eq!(error, "Ship must be docked to trade");
+
+        let mut closed = Station::new("Depot".to_string(), [0.0; 3], "neutral".to_string());
+        closed.services.trade = false;
+        let closed = world.register_station(closed);
+        assert!(world.market_quotes(&closed).is_err());
+    }
+
+    #[test]
+    fn test_markets_drift_back_toward_usual_stock() {
+        let config = test_config();
+        let mut world = trading_world(&config);
+        let (station_id, ship_id) = dock_freighter(&mut world);
+        world
+            .trade_commodity(&station_id, &ship_id, TradeSide::Buy, "ore", 50, &config)
+            .unwrap();
+
+        for _ in 0..(60 * 60) {
+            world.step_simulation();
+        }
+        let listing = world
+            .get_station(&station_id)
+            .unwrap()
+            .market
+            .listing("ore")
+            .unwrap();
+        // About 45% of the 50 units bought restocked in a minute
+        assert!(listing.supply > 970.0 && listing.supply < 975.0);
+        assert_ne!(listing.demand, 1000.0);
+    }
+}
@@ -0,0 +1,812 @@
+//! Weapon station commands
+//!
+//! The energy, kinetic, and missile weapons officers act on the
+//! `WeaponComponent`/`TargetingComponent` children of their ship's
+//! simulation entity. Targets are selected per station, manual fire spawns a
+//! projectile immediately, and loading moves rounds from the ship's hold into
+//! a weapon's magazine.
+
+use bevy_ecs::prelude::*;
+
+use super::GameWorld;
+use crate::config::GameConfig;
+use crate::models::WeaponTag;
+use crate::replay::ReplayCommand;
+use crate::simulation::{
+    InventoryComponent, ShipData, TargetingComponent, Transform, WeaponComponent,
+    launch_projectile, ordnance_from_config,
+};
+
+/// Weapon type handled by the energy weapons station
+pub(super) const ENERGY: &str = "energy";
+/// Weapon type handled by the kinetic weapons station
+pub(super) const KINETIC: &str = "kinetic";
+/// Weapon type handled by the missile weapons station
+pub(super) const MISSILE: &str = "missile";
+
+impl GameWorld {
+    // ==================== Energy Weapons ====================
+
+    /// Set energy weapon target
+    pub fn set_energy_weapon_target(
+        &mut self,
+        ship_id: String,
+        target_id: String,
+    ) -> Result<(), String> {
+        self.set_weapon_target(&ship_id, ENERGY, &target_id)
+    }
+
+    /// Get energy weapon target
+    pub fn get_energy_weapon_target(&self, ship_id: &str) -> Option<String> {
+        self.get_weapon_target(ship_id, ENERGY)
+    }
+
+    /// Fire an energy weapon at the current target
+    pub fn add_weapon_fire_command(
+        &mut self,
+        ship_id: String,
+        weapon_id: String,
+    ) -> Result<(), String> {
+        self.fire_weapon(&ship_id, &weapon_id, ENERGY)
+    }
+
+    /// Set energy weapon auto-fire mode
+    pub fn set_weapon_auto_fire(
+        &mut self,
+        ship_id: String,
+        weapon_id: String,
+        enabled: bool,
+    ) -> Result<(), String> {
+        self.set_auto_fire(&ship_id, &weapon_id, ENERGY, enabled)
+    }
+
+    // ==================== Kinetic Weapons ====================
+
+    /// Set kinetic weapon target
+    pub fn set_kinetic_weapon_target(
+        &mut self,
+        ship_id: String,
+        target_id: String,
+    ) -> Result<(), String> {
+        self.set_weapon_target(&ship_id, KINETIC, &target_id)
+    }
+
+    /// Get kinetic weapon target
+    pub fn get_kinetic_weapon_target(&self, ship_id: &str) -> Option<String> {
+        self.get_weapon_target(ship_id, KINETIC)
+    }
+
+    /// Configure kinetic weapon kind
+    pub fn configure_kinetic_weapon(
+        &mut self,
+        ship_id: String,
+        weapon_id: String,
+        kind: String,
+    ) -> Result<(), String> {
+        self.find_weapon(&ship_id, &weapon_id, KINETIC)?;
+        let ship = self
+            .ships
+            .get_mut(&ship_id)
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+        if let Some(instance) = ship.weapons.iter_mut().find(|w| w.id == weapon_id) {
+            instance.kind = Some(kind);
+        }
+        Ok(())
+    }
+
+    /// Load kinetic ammunition from the ship's hold
+    ///
+    /// Returns the number of rounds loaded.
+    pub fn load_kinetic_ammo(
+        &mut self,
+        ship_id: String,
+        weapon_id: String,
+        ammo_type: String,
+        quantity: u32,
+        config: &GameConfig,
+    ) -> Result<u32, String> {
+        self.load_weapon(&ship_id, &weapon_id, KINETIC, &ammo_type, quantity, config)
+    }
+
+    /// Fire a kinetic weapon at the current target
+    pub fn fire_kinetic_weapon(
+        &mut self,
+        ship_id: String,
+        weapon_id: String,
+    ) -> Result<(), String> {
+        self.fire_weapon(&ship_id, &weapon_id, KINETIC)
+    }
+
+    /// Set kinetic auto-fire
+    pub fn set_kinetic_auto_fire(
+        &mut self,
+        ship_id: String,
+        weapon_id: String,
+        enabled: bool,
+    ) -> Result<(), String> {
+        self.set_auto_fire(&ship_id, &weapon_id, KINETIC, enabled)
+    }
+
+    // ==================== Missile Weapons ====================
+
+    /// Set missile weapon target
+    pub fn set_missile_weapon_target(
+        &mut self,
+        ship_id: String,
+        target_id: String,
+    ) -> Result<(), String> {
+        self.set_weapon_target(&ship_id, MISSILE, &target_id)
+    }
+
+    /// Get missile weapon target
+    pub fn get_missile_weapon_target(&self, ship_id: &str) -> Option<String> {
+        self.get_weapon_target(ship_id, MISSILE)
+    }
+
+    /// Load missile or torpedo ordnance from the ship's hold
+    ///
+    /// Returns the number of rounds loaded.
+    pub fn load_missile_ordnance(
+        &mut self,
+        ship_id: String,
+        weapon_id: String,
+        ordnance_type: String,
+        quantity: u32,
+        config: &GameConfig,
+    ) -> Result<u32, String> {
+        self.load_weapon(
+            &ship_id,
+            &weapon_id,
+            MISSILE,
+            &ordnance_type,
+            quantity,
+            config,
+        )
+    }
+
+    /// Fire a missile weapon at the current target
+    pub fn fire_missile_weapon(
+        &mut self,
+        ship_id: String,
+        weapon_id: String,
+    ) -> Result<(), String> {
+        self.fire_weapon(&ship_id, &weapon_id, MISSILE)
+    }
+
+    /// Set missile auto-fire
+    pub fn set_missile_auto_fire(
+        &mut self,
+        ship_id: String,
+        weapon_id: String,
+        enabled: bool,
+    ) -> Result<(), String> {
+        self.set_auto_fire(&ship_id, &weapon_id, MISSILE, enabled)
+    }
+
+    // ==================== Shared ====================
+
+    /// Get the simulated state of every weapon on a ship
+    ///
+    /// Returns an empty list for ships that are not in the simulation.
+    pub fn get_ship_weapons(&self, ship_id: &str) -> Vec<WeaponComponent> {
+        self.weapon_entities(ship_id)
+            .into_iter()
+            .filter_map(|entity| self.world.get::<WeaponComponent>(entity).cloned())
+            .collect()
+    }
+
+    /// Mirror a ship's hold into its simulation `InventoryComponent`
+    pub(crate) fn sync_inventory_component(&mut self, ship_id: &str) {
+        let (Some(ship), Some(entity)) = (self.ships.get(ship_id), self.ship_entities.get(ship_id))
+        else {
+            return;
+        };
+        if let Some(mut inventory) = self.world.get_mut::<InventoryComponent>(*entity) {
+            inventory.ammunition = ship.inventory.ammunition.clone();
+            inventory.cargo = ship.inventory.cargo.clone();
+        }
+    }
+
+    /// Weapon child entities of a ship's simulation entity
+    pub(super) fn weapon_entities(&self, ship_id: &str) -> Vec<Entity> {
+        self.ship_entities
+            .get(ship_id)
+            .and_then(|entity| self.world.get::<Children>(*entity))
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|child| self.world.get::<WeaponComponent>(*child).is_some())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Find a weapon of the given type, returning `(ship_entity, weapon_entity)`
+    fn find_weapon(
+        &self,
+        ship_id: &str,
+        weapon_id: &str,
+        weapon_type: &str,
+    ) -> Result<(Entity, Entity), String> {
+        let ship_entity = self
+            .get_ship_entity(ship_id)
+            .ok_or_else(|| format!("Ship {} is not in the simulation", ship_id))?;
+        let weapon_entity = self
+            .weapon_entities(ship_id)
+            .into_iter()
+            .find(|entity| {
+                self.world
+                    .get::<WeaponComponent>(*entity)
+                    .is_some_and(|weapon| weapon.id == weapon_id)
+            })
+            .ok_or_else(|| format!("Weapon {} not found on ship {}", weapon_id, ship_id))?;
+
+        let weapon = self.world.get::<WeaponComponent>(weapon_entity).unwrap();
+        if weapon.weapon_type != weapon_type {
+            return Err(format!(
+                "Weapon {} is not a {} weapon",
+                weapon_id, weapon_type
+            ));
+        }
+        Ok((ship_entity, weapon_entity))
+    }
+
+    /// Point every weapon of a type on a ship at a target ship
+    pub(super) fn set_weapon_target(
+        &mut self,
+        ship_id: &str,
+        weapon_type: &str,
+        target_id: &str,
+    ) -> Result<(), String> {
+        if ship_id == target_id {
+            return Err("A ship cannot target itself".to_string());
+        }
+        if self.get_ship_entity(ship_id).is_none() {
+            return Err(format!("Ship {} is not in the simulation", ship_id));
+        }
+        let target = self
+            .get_ship_entity(target_id)
+            .ok_or_else(|| format!("Target {} not found", target_id))?;
+
+        for entity in self.weapon_entities(ship_id) {
+            let matches = self
+                .world
+                .get::<WeaponComponent>(entity)
+                .is_some_and(|weapon| weapon.weapon_type == weapon_type);
+            if matches && let Some(mut targeting) = self.world.get_mut::<TargetingComponent>(entity)
+            {
+                targeting.set_target(target);
+            }
+        }
+        self.record(ReplayCommand::WeaponTarget {
+            ship_id: ship_id.to_string(),
+            weapon_type: weapon_type.to_string(),
+            target_id: target_id.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Ship ID targeted by the first weapon of a type that has a target
+    fn get_weapon_target(&self, ship_id: &str, weapon_type: &str) -> Option<String> {
+        self.weapon_entities(ship_id)
+            .into_iter()
+            .find_map(|entity| {
+                let weapon = self.world.get::<WeaponComponent>(entity)?;
+                if weapon.weapon_type != weapon_type {
+                    return None;
+                }
+                let target = self.world.get::<TargetingComponent>(entity)?.target?;
+                self.world
+                    .get::<ShipData>(target)
+                    .map(|ship_data| ship_data.id.clone())
+            })
+    }
+
+    /// Fire a weapon at its selected target
+    pub(super) fn fire_weapon(
+        &mut self,
+        ship_id: &str,
+        weapon_id: &str,
+        weapon_type: &str,
+    ) -> Result<(), String> {
+        let (ship_entity, weapon_entity) = self.find_weapon(ship_id, weapon_id, weapon_type)?;
+
+        let targeting = self
+            .world
+            .get::<TargetingComponent>(weapon_entity)
+            .cloned()
+            .unwrap_or_default();
+        if targeting.disabled {
+            return Err("Targeting is jammed".to_string());
+        }
+        let target = targeting
+            .target
+            .ok_or_else(|| format!("Weapon {} has no target", weapon_id))?;
+        let target_transform = self
+            .world
+            .get::<Transform>(target)
+            .cloned()
+            .ok_or_else(|| "Target is no longer in range".to_string())?;
+
+        let weapon = self
+            .world
+            .get::<WeaponComponent>(weapon_entity)
+            .cloned()
+            .unwrap();
+        if !weapon.has_ammunition() {
+            return Err(format!("Weapon {} is out of ammunition", weapon_id));
+        }
+        if !weapon.can_fire() {
+            return Err(format!("Weapon {} is not ready to fire", weapon_id));
+        }
+        let origin = self
+            .world
+            .get::<Transform>(weapon_entity)
+            .cloned()
+            .unwrap_or_default();
+
+        self.world.spawn(launch_projectile(
+            ship_entity,
+            &weapon,
+            &origin,
+            target,
+            &target_transform,
+        ));
+        if let Some(mut weapon) = self.world.get_mut::<WeaponComponent>(weapon_entity) {
+            weapon.fire();
+        }
+        self.record(ReplayCommand::FireWeapon {
+            ship_id: ship_id.to_string(),
+            weapon_type: weapon_type.to_string(),
+            weapon_id: weapon_id.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Enable or disable automatic fire for a weapon
+    pub(super) fn set_auto_fire(
+        &mut self,
+        ship_id: &str,
+        weapon_id: &str,
+        weapon_type: &str,
+        enabled: bool,
+    ) -> Result<(), String> {
+        let (_, weapon_entity) = self.find_weapon(ship_id, weapon_id, weapon_type)?;
+        if let Some(mut weapon) = self.world.get_mut::<WeaponComponent>(weapon_entity) {
+            weapon.is_automatic = enabled;
+        }
+        self.record(ReplayCommand::AutoFire {
+            ship_id: ship_id.to_string(),
+            weapon_type: weapon_type.to_string(),
+            weapon_id: weapon_id.to_string(),
+            enabled,
+        });
+        Ok(())
+    }
+
+    /// Move rounds from the ship's hold into a weapon's magazine
+    ///
+    /// Rounds of a different ammunition type already in the magazine are
+    /// returned to the hold first. Loading stops at the magazine capacity.
+    pub(super) fn load_weapon(
+        &mut self,
+        ship_id: &str,
+        weapon_id: &str,
+        weapon_type: &str,
+        ammo_type: &str,
+        quantity: u32,
+        config: &GameConfig,
+    ) -> Result<u32, String> {
+        let (_, weapon_entity) = self.find_weapon(ship_id, weapon_id, weapon_type)?;
+        let ammo = config
+            .get_ammunition(ammo_type)
+            .ok_or_else(|| format!("Unknown ammunition type {}", ammo_type))?;
+
+        let weapon = self
+            .world
+            .get::<WeaponComponent>(weapon_entity)
+            .cloned()
+            .unwrap();
+        if Some(ammo.category.as_str()) != ammo_category(&weapon) {
+            return Err(format!(
+                "Weapon {} cannot load {} ammunition",
+                weapon_id, ammo.category
+            ));
+        }
+
+        let ship = self
+            .ships
+            .get_mut(ship_id)
+            .ok_or_else(|| format!("Ship {} not found", ship_id))?;
+
+        // Unload rounds of a different type back into the hold
+        let same_type = weapon.ammunition.as_deref() == Some(ammo_type);
+        let loaded = if same_type { weapon.ammo_count } else { 0 };
+        if !same_type
+            && weapon.ammo_count > 0
+            && let Some(previous) = &weapon.ammunition
+        {
+            ship.inventory
+                .add_ammunition(previous.clone(), weapon.ammo_count);
+        }
+
+        let room = if weapon.ammo_capacity > 0 {
+            weapon.ammo_capacity.saturating_sub(loaded)
+        } else {
+            u32::MAX
+        };
+        let quantity = quantity.min(room);
+        if quantity == 0 {
+            return Err(format!("Weapon {} magazine is full", weapon_id));
+        }
+        if let Err(e) = ship.inventory.remove_ammunition(ammo_type, quantity) {
+            // Put back anything we unloaded above
+            if !same_type
+                && weapon.ammo_count > 0
+                && let Some(previous) = &weapon.ammunition
+            {
+                let _ = ship
+                    .inventory
+                    .remove_ammunition(previous, weapon.ammo_count);
+            }
+            return Err(e);
+        }
+        if let Some(instance) = ship.weapons.iter_mut().find(|w| w.id == weapon_id) {
+            instance.loaded_ammunition = Some(ammo_type.to_string());
+        }
+
+        if let Some(mut weapon) = self.world.get_mut::<WeaponComponent>(weapon_entity) {
+            weapon.ammunition = Some(ammo_type.to_string());
+            weapon.ordnance = Some(ordnance_from_config(ammo));
+            weapon.ammo_count = loaded + quantity;
+        }
+        self.sync_inventory_component(ship_id);
+        self.record(ReplayCommand::LoadWeapon {
+            ship_id: ship_id.to_string(),
+            weapon_type: weapon_type.to_string(),
+            weapon_id: weapon_id.to_string(),
+            ammo_type: ammo_type.to_string(),
+            quantity,
+        });
+        Ok(quantity)
+    }
+}
+
+/// Ammunition category a weapon loads, if it takes ammunition at all
+pub(super) fn ammo_category(weapon: &WeaponComponent) -> Option<&'static str> {
+    match weapon.weapon_type.as_str() {
+        KINETIC => Some("kinetic"),
+        MISSILE if weapon.tags.contains(&WeaponTag::Torpedo) => Some("torpedos"),
+        MISSILE => Some("missiles"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AmmunitionConfig;
+    use crate::config::test_utils::create_test_game_config;
+    use crate::models::{CompiledModule, Inventory, Ship, ShipStatus};
+    use crate::simulation::{ProjectileComponent, ProjectileType};
+    use nalgebra::Vector3;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn ammunition(id: &str, category: &str, tags: &[&str]) -> AmmunitionConfig {
+        AmmunitionConfig {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            cost: 10.0,
+            weight: 1.0,
+            impact_damage: 30.0,
+            blast_radius: 0.0,
+            blast_damage: 0.0,
+            velocity: 2000.0,
+            armor_penetration: 0.0,
+            acceleration: 100.0,
+            max_speed: 800.0,
+            max_turn_rate: 30.0,
+            lifetime: 20.0,
+            weapon_tags: tags.iter().map(|t| t.to_string()).collect(),
+            category: category.to_string(),
+            ammo_type: String::new(),
+            ammo_size: String::new(),
+        }
+    }
+
+    fn test_config() -> GameConfig {
+        let mut config = create_test_game_config();
+        config.ammunition_types = vec![
+            ammunition("slug-20mm-st", "kinetic", &["Kinetic"]),
+            ammunition("test-missile", "missiles", &["Missile"]),
+        ];
+        config
+    }
+
+    fn weapon_module(instance_id: &str, slot: &str, stats: serde_json::Value) -> CompiledModule {
+        CompiledModule {
+            instance_id: instance_id.to_string(),
+            module_id: slot.to_string(),
+            kind: None,
+            name: instance_id.to_string(),
+            stats: serde_json::from_value(stats).unwrap(),
+            current_health: 50.0,
+            max_health: 50.0,
+            operational: true,
+            power_allocated: 1.0,
+            cooling_allocated: 1.0,
+        }
+    }
+
+    fn spawn_ship(world: &mut GameWorld, position: [f64; 3]) -> String {
+        let config = test_config();
+        let ship_id = Uuid::new_v4().to_string();
+        let mut status = ShipStatus::new(1000.0, 100.0, 1000.0);
+        status.position = position;
+        let mut inventory = Inventory::new();
+        inventory.add_ammunition("slug-20mm-st".to_string(), 50);
+        inventory.add_ammunition("test-missile".to_string(), 4);
+        world.register_ship(Ship {
+            id: ship_id.clone(),
+            name: "Armed".to_string(),
+            class: "cruiser".to_string(),
+            team_id: "team1".to_string(),
+            player_roles: HashMap::new(),
+            status,
+            modules: vec![
+                weapon_module(
+                    "laser",
+                    "de-weapon",
+                    serde_json::json!({ "damage": 20, "recharge_time": 2.0, "weapon_tags": ["Beam"] }),
+                ),
+                weapon_module(
+                    "cannon",
+                    "kinetic-weapon",
+                    serde_json::json!({ "reload_time": 1.0 }),
+                ),
+                weapon_module(
+                    "launcher",
+                    "missile-launcher",
+                    serde_json::json!({ "reload_time": 5.0, "ammo_capacity": 2 }),
+                ),
+            ],
+            weapons: vec![],
+            inventory,
+        });
+        world.spawn_ship_entity(&ship_id, &config).unwrap();
+        ship_id
+    }
+
+    fn projectiles(world: &mut GameWorld) -> Vec<ProjectileComponent> {
+        let mut query = world.world.query::<&ProjectileComponent>();
+        query.iter(&world.world).cloned().collect()
+    }
+
+    #[test]
+    fn test_set_and_get_target() {
+        let mut world = GameWorld::new();
+        let ship = spawn_ship(&mut world, [0.0; 3]);
+        let enemy = spawn_ship(&mut world, [1000.0, 0.0, 0.0]);
+
+        assert!(
+            world
+                .set_energy_weapon_target(ship.clone(), "missing".to_string())
+                .is_err()
+        );
+        world
+            .set_energy_weapon_target(ship.clone(), enemy.clone())
+            .unwrap();
+
+        assert_eq!(world.get_energy_weapon_target(&ship), Some(enemy));
+        assert_eq!(world.get_kinetic_weapon_target(&ship), None);
+    }
+
+    #[test]
+    fn test_fire_energy_weapon_spawns_beam() {
+        let mut world = GameWorld::new();
+        let ship = spawn_ship(&mut world, [0.0; 3]);
+        let enemy = spawn_ship(&mut world, [1000.0, 0.0, 0.0]);
+
+        // No target yet
+        assert!(
+            world
+                .add_weapon_fire_command(ship.clone(), "laser".to_string())
+                .is_err()
+        );
+
+        world
+            .set_energy_weapon_target(ship.clone(), enemy.clone())
+            .unwrap();
+        world
+            .add_weapon_fire_command(ship.clone(), "laser".to_string())
+            .unwrap();
+
+        let fired = projectiles(&mut world);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].projectile_type, ProjectileType::Beam);
+        assert_eq!(fired[0].target, world.get_ship_entity(&enemy));
+        assert_eq!(fired[0].owner, world.get_ship_entity(&ship).unwrap());
+
+        // Weapon is cooling down
+        assert!(
+            world
+                .add_weapon_fire_command(ship.clone(), "laser".to_string())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_fire_wrong_station_rejected() {
+        let mut world = GameWorld::new();
+        let ship = spawn_ship(&mut world, [0.0; 3]);
+
+        let err = world
+            .fire_kinetic_weapon(ship.clone(), "laser".to_string())
+            .unwrap_err();
+        assert!(err.contains("not a kinetic weapon"));
+    }
+
+    #[test]
+    fn test_load_and_fire_kinetic() {
+        let mut world = GameWorld::new();
+        let config = test_config();
+        let ship = spawn_ship(&mut world, [0.0; 3]);
+        let enemy = spawn_ship(&mut world, [0.0, 0.0, 500.0]);
+
+        // Empty magazine
+        world
+            .set_kinetic_weapon_target(ship.clone(), enemy.clone())
+            .unwrap();
+        let err = world
+            .fire_kinetic_weapon(ship.clone(), "cannon".to_string())
+            .unwrap_err();
+        assert!(err.contains("out of ammunition"));
+
+        let loaded = world
+            .load_kinetic_ammo(
+                ship.clone(),
+                "cannon".to_string(),
+                "slug-20mm-st".to_string(),
+                10,
+                &config,
+            )
+            .unwrap();
+        assert_eq!(loaded, 10);
+        assert_eq!(
+            world.get_ship(&ship).unwrap().inventory.ammunition["slug-20mm-st"],
+            40
+        );
+        let entity = world.get_ship_entity(&ship).unwrap();
+        let magazine = world.world.get::<InventoryComponent>(entity).unwrap();
+        assert_eq!(magazine.get_ammo_count("slug-20mm-st"), 40);
+
+        world
+            .fire_kinetic_weapon(ship.clone(), "cannon".to_string())
+            .unwrap();
+        let fired = projectiles(&mut world);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].projectile_type, ProjectileType::Kinetic);
+
+        let cannon = world
+            .get_ship_weapons(&ship)
+            .into_iter()
+            .find(|w| w.id == "cannon")
+            .unwrap();
+        assert_eq!(cannon.ammo_count, 9);
+    }
+
+    #[test]
+    fn test_fired_round_hits_a_moving_target() {
+        let mut world = GameWorld::new();
+        let config = test_config();
+        let ship = spawn_ship(&mut world, [0.0; 3]);
+        let enemy = spawn_ship(&mut world, [2000.0, 0.0, 0.0]);
+
+        // A crossing target, and rounds that cover several hull lengths a tick
+        let enemy_entity = world.get_ship_entity(&enemy).unwrap();
+        world
+            .world
+            .get_mut::<Transform>(enemy_entity)
+            .unwrap()
+            .velocity = Vector3::new(0.0, 0.0, 100.0);
+        world
+            .load_kinetic_ammo(
+                ship.clone(),
+                "cannon".to_string(),
+                "slug-20mm-st".to_string(),
+                1,
+                &config,
+            )
+            .unwrap();
+        world
+            .set_kinetic_weapon_target(ship.clone(), enemy.clone())
+            .unwrap();
+        world
+            .fire_kinetic_weapon(ship.clone(), "cannon".to_string())
+            .unwrap();
+
+        let status = &world.get_ship(&enemy).unwrap().status;
+        let before = status.hull + status.shields;
+        for _ in 0..120 {
+            world.step_simulation();
+        }
+
+        let status = &world.get_ship(&enemy).unwrap().status;
+        assert!(status.hull + status.shields < before);
+        assert!(projectiles(&mut world).is_empty());
+    }
+
+    #[test]
+    fn test_load_rejects_insufficient_and_incompatible_ammo() {
+        let mut world = GameWorld::new();
+        let config = test_config();
+        let ship = spawn_ship(&mut world, [0.0; 3]);
+
+        assert!(
+            world
+                .load_kinetic_ammo(
+                    ship.clone(),
+                    "cannon".to_string(),
+                    "slug-20mm-st".to_string(),
+                    500,
+                    &config,
+                )
+                .is_err()
+        );
+        assert!(
+            world
+                .load_missile_ordnance(
+                    ship.clone(),
+                    "launcher".to_string(),
+                    "slug-20mm-st".to_string(),
+                    1,
+                    &config,
+                )
+                .is_err()
+        );
+        assert_eq!(
+            world.get_ship(&ship).unwrap().inventory.ammunition["slug-20mm-st"],
+            50
+        );
+    }
+
+    #[test]
+    fn test_load_missile_respects_capacity() {
+        let mut world = GameWorld::new();
+        let config = test_config();
+        let ship = spawn_ship(&mut world, [0.0; 3]);
+
+        let loaded = world
+            .load_missile_ordnance(
+                ship.clone(),
+                "launcher".to_string(),
+                "test-missile".to_string(),
+                4,
+                &config,
+            )
+            .unwrap();
+        assert_eq!(loaded, 2);
+        assert_eq!(
+            world.get_ship(&ship).unwrap().inventory.ammunition["test-missile"],
+            2
+        );
+    }
+
+    #[test]
+    fn test_auto_fire_toggle() {
+        let mut world = GameWorld::new();
+        let ship = spawn_ship(&mut world, [0.0; 3]);
+
+        world
+            .set_missile_auto_fire(ship.clone(), "launcher".to_string(), true)
+            .unwrap();
+        let launcher = world
+            .get_ship_weapons(&ship)
+            .into_iter()
+            .find(|w| w.id == "launcher")
+            .unwrap();
+        assert!(launcher.is_automatic);
+    }
+}
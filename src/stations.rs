@@ -1,13 +1,20 @@
 //! Station system for docking, services, and trade
 //!
 //! This module provides the station entity type and related functionality
-//! for ships to dock at stations, receive repairs, refuel, rearm, and trade.
+//! for ships to dock at stations, receive repairs, refuel, rearm, trade, and
+//! refit.
 
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+use crate::config::GameConfig;
+use crate::generation::StationType;
+use crate::market::StationMarket;
+
 /// Docking status for a ship at a station
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, juniper::GraphQLEnum)]
 #[serde(rename_all = "snake_case")]
 pub enum DockingStatus {
     /// Ship has requested docking permission
@@ -41,6 +48,40 @@ pub struct StationServices {
     pub rearm_cost: f32,
 }
 
+/// A service a station may offer to docked ships
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StationService {
+    Repair,
+    Refuel,
+    Rearm,
+    Trade,
+}
+
+impl StationService {
+    /// Service name
+    pub fn name(&self) -> &'static str {
+        match self {
+            StationService::Repair => "repair",
+            StationService::Refuel => "refuel",
+            StationService::Rearm => "rearm",
+            StationService::Trade => "trade",
+        }
+    }
+}
+
+impl StationServices {
+    /// Whether the station offers a service
+    pub fn offers(&self, service: StationService) -> bool {
+        match service {
+            StationService::Repair => self.repair,
+            StationService::Refuel => self.refuel,
+            StationService::Rearm => self.rearm,
+            StationService::Trade => self.trade,
+        }
+    }
+}
+
 impl Default for StationServices {
     fn default() -> Self {
         Self {
@@ -78,10 +119,162 @@ pub struct Station {
     pub hostile_factions: Vec<String>,
     /// Station size (affects docking bay capacity)
     pub size: StationSize,
+    /// Commodity market
+    #[serde(default)]
+    pub market: StationMarket,
+    /// What the station mainly does (affects outfitting stock)
+    #[serde(default)]
+    pub station_type: StationType,
+    /// Modules and ammunition for sale, stocked on first use
+    #[serde(default)]
+    pub inventory: StationInventory,
+}
+
+/// A module or module variant a station has in stock
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StockedModule {
+    pub module_slot_id: String,
+    pub variant_id: Option<String>,
+    pub quantity: u32,
+}
+
+/// Modules and ammunition a station has for sale
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StationInventory {
+    /// Whether the station has been stocked
+    pub stocked: bool,
+    /// Modules in stock
+    pub modules: Vec<StockedModule>,
+    /// Ammunition in stock (ammo_type -> rounds)
+    pub ammunition: BTreeMap<String, u32>,
+}
+
+impl StationInventory {
+    /// Roll a station's stock from the module and ammunition catalogs
+    ///
+    /// Each module slot without variants, each variant, and each
+    /// ammunition type is stocked at the level `config.outfitting` gives
+    /// the station's type and faction, give or take half.
+    pub fn stock(
+        station_type: StationType,
+        faction: &str,
+        config: &GameConfig,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let mut roll = |typical: f32| (typical * rng.random_range(0.5..=1.5)).round() as u32;
+
+        let mut slot_ids: Vec<&String> = config.module_slots.keys().collect();
+        slot_ids.sort();
+        let mut modules = Vec::new();
+        for slot_id in slot_ids {
+            let slot = &config.module_slots[slot_id];
+            let typical =
+                config
+                    .outfitting
+                    .module_stock(station_type, faction, slot_id, &slot.groups);
+            let variant_ids: Vec<Option<String>> = match config.get_module_variants(slot_id) {
+                Some(variants) => variants.iter().map(|v| Some(v.id.clone())).collect(),
+                None => vec![None],
+            };
+            for variant_id in variant_ids {
+                let quantity = roll(typical);
+                if quantity > 0 {
+                    modules.push(StockedModule {
+                        module_slot_id: slot_id.clone(),
+                        variant_id,
+                        quantity,
+                    });
+                }
+            }
+        }
+
+        let typical = config.outfitting.ammunition_stock(station_type, faction);
+        let ammunition = config
+            .ammunition_types
+            .iter()
+            .map(|ammo| (ammo.id.clone(), roll(typical)))
+            .filter(|(_, rounds)| *rounds > 0)
+            .collect();
+
+        Self {
+            stocked: true,
+            modules,
+            ammunition,
+        }
+    }
+
+    /// Units in stock of a module slot and variant
+    pub fn module_quantity(&self, module_slot_id: &str, variant_id: Option<&str>) -> u32 {
+        self.modules
+            .iter()
+            .find(|stocked| {
+                stocked.module_slot_id == module_slot_id
+                    && stocked.variant_id.as_deref() == variant_id
+            })
+            .map_or(0, |stocked| stocked.quantity)
+    }
+
+    /// Take a module out of stock
+    pub fn take_module(
+        &mut self,
+        module_slot_id: &str,
+        variant_id: Option<&str>,
+    ) -> Result<(), String> {
+        let stocked = self
+            .modules
+            .iter_mut()
+            .find(|stocked| {
+                stocked.module_slot_id == module_slot_id
+                    && stocked.variant_id.as_deref() == variant_id
+                    && stocked.quantity > 0
+            })
+            .ok_or_else(|| {
+                format!(
+                    "Module {} is out of stock",
+                    variant_id.unwrap_or(module_slot_id)
+                )
+            })?;
+        stocked.quantity -= 1;
+        Ok(())
+    }
+
+    /// Put a module into stock
+    pub fn add_module(&mut self, module_slot_id: &str, variant_id: Option<&str>) {
+        match self.modules.iter_mut().find(|stocked| {
+            stocked.module_slot_id == module_slot_id && stocked.variant_id.as_deref() == variant_id
+        }) {
+            Some(stocked) => stocked.quantity += 1,
+            None => self.modules.push(StockedModule {
+                module_slot_id: module_slot_id.to_string(),
+                variant_id: variant_id.map(str::to_string),
+                quantity: 1,
+            }),
+        }
+    }
+
+    /// Rounds in stock of an ammunition type
+    pub fn ammunition_quantity(&self, ammo_type: &str) -> u32 {
+        self.ammunition.get(ammo_type).copied().unwrap_or(0)
+    }
+
+    /// Take rounds out of stock
+    pub fn take_ammunition(&mut self, ammo_type: &str, rounds: u32) -> Result<(), String> {
+        let available = self.ammunition_quantity(ammo_type);
+        if available < rounds {
+            return Err(format!(
+                "Station has only {} rounds of {}",
+                available, ammo_type
+            ));
+        }
+        if let Some(stock) = self.ammunition.get_mut(ammo_type) {
+            *stock -= rounds;
+        }
+        Ok(())
+    }
 }
 
 /// Station size classification
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, juniper::GraphQLEnum)]
 #[serde(rename_all = "snake_case")]
 pub enum StationSize {
     Small,
@@ -104,6 +297,9 @@ impl Station {
             docking_requests: Vec::new(),
             hostile_factions: Vec::new(),
             size: StationSize::Medium,
+            market: StationMarket::default(),
+            station_type: StationType::default(),
+            inventory: StationInventory::default(),
         }
     }
 
@@ -127,6 +323,9 @@ impl Station {
             docking_requests: Vec::new(),
             hostile_factions: Vec::new(),
             size,
+            market: StationMarket::default(),
+            station_type: StationType::default(),
+            inventory: StationInventory::default(),
         }
     }
 
@@ -161,6 +360,17 @@ impl Station {
         true
     }
 
+    /// Deny a ship docking permission outright
+    ///
+    /// Does nothing if the ship is already docked or has a request pending.
+    pub fn deny_docking(&mut self, ship_id: Uuid) {
+        if !self.docked_ships.contains(&ship_id)
+            && !self.docking_requests.iter().any(|(id, _)| *id == ship_id)
+        {
+            self.docking_requests.push((ship_id, DockingStatus::Denied));
+        }
+    }
+
     /// Approve a docking request
     pub fn approve_docking(&mut self, ship_id: Uuid) -> bool {
         if let Some((_, status)) = self
@@ -11,6 +11,8 @@
 //! - Fire modes (Manual, Automatic)
 
 use crate::models::WeaponTag;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Damage calculation result with modifiers applied
@@ -44,7 +46,7 @@ pub struct StatusEffect {
 }
 
 /// Types of status effects
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum StatusEffectType {
     /// Jams communications and science, disables targeting
     IonJam,
@@ -385,6 +387,27 @@ impl WeaponTagCalculator {
         })
     }
 
+    /// Calculate damage for a single hit, rolling for the status effect
+    ///
+    /// Like [`calculate_damage`](Self::calculate_damage), but the status
+    /// effect is only kept if a roll on `rng` lands under its
+    /// `application_chance`. Pass the simulation's seeded generator so hits
+    /// resolve the same way on replay.
+    pub fn roll_damage<R: Rng + ?Sized>(
+        &self,
+        base_damage: f32,
+        tags: &[WeaponTag],
+        rng: &mut R,
+    ) -> Result<DamageResult, String> {
+        let mut result = self.calculate_damage(base_damage, tags)?;
+        if let Some(effect) = &result.status_effect
+            && rng.random::<f32>() >= effect.application_chance
+        {
+            result.status_effect = None;
+        }
+        Ok(result)
+    }
+
     /// Validate that weapon tag combinations are valid
     ///
     /// Checks for mutually exclusive tags:
@@ -574,6 +597,32 @@ mod tests {
         assert_eq!(effect.application_chance, 0.8);
     }
 
+    #[test]
+    fn test_roll_damage_applies_status_effect_by_chance() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+
+        let calculator = WeaponTagCalculator::new();
+        let mut rng = StdRng::seed_from_u64(1);
+        let applied = (0..1000)
+            .filter(|_| {
+                calculator
+                    .roll_damage(100.0, &[WeaponTag::Ion], &mut rng)
+                    .unwrap()
+                    .status_effect
+                    .is_some()
+            })
+            .count();
+        assert!((700..900).contains(&applied), "applied {} of 1000", applied);
+
+        let roll = |seed| {
+            calculator
+                .roll_damage(100.0, &[WeaponTag::Ion], &mut StdRng::seed_from_u64(seed))
+                .unwrap()
+        };
+        assert_eq!(roll(9), roll(9));
+    }
+
     #[test]
     fn test_graviton_status_effect() {
         let calculator = WeaponTagCalculator::new();
@@ -35,11 +35,8 @@ pub enum SubscriptionRequest {
     /// Unsubscribe from player
     UnsubscribeFromPlayer { player_id: Uuid },
 
-    /// Authentication with player credentials
-    Authenticate {
-        player_id: Uuid,
-        // In a real implementation, this would include a token or credentials
-    },
+    /// Authenticate with the session token issued at player registration
+    Authenticate { token: String },
 }
 
 /// WebSocket subscription response
@@ -62,11 +59,29 @@ pub enum SubscriptionResponse {
 /// Client subscription state
 #[derive(Debug, Clone, Default)]
 pub struct ClientSubscription {
+    /// Authenticated player, set once the client presents a valid token
     pub player_id: Option<Uuid>,
     pub ship_ids: Vec<Uuid>,
+    /// Teams the authenticated player belongs to, for team events
+    pub team_ids: Vec<String>,
     pub subscribe_to_simulation: bool,
 }
 
+impl ClientSubscription {
+    /// Look up the authenticated player's teams again, as membership changes
+    pub fn refresh_teams(&mut self, world: &GameWorld) {
+        self.team_ids = match self.player_id {
+            Some(player_id) => world
+                .teams()
+                .keys()
+                .filter(|team_id| world.is_team_member(team_id, &player_id.to_string()))
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+    }
+}
+
 /// WebSocket connection manager
 pub struct WebSocketManager {
     /// Event broadcaster
@@ -134,35 +149,14 @@ impl WebSocketManager {
             return true;
         }
 
-        // Check if event is related to subscribed ships
-        let event_ship_id = match event {
-            GameEvent::ShipMoved { ship_id, .. } => Some(*ship_id),
-            GameEvent::WeaponFired { ship_id, .. } => Some(*ship_id),
-            GameEvent::DamageTaken { ship_id, .. } => Some(*ship_id),
-            GameEvent::ShieldChanged { ship_id, .. } => Some(*ship_id),
-            GameEvent::StatusEffectApplied { ship_id, .. } => Some(*ship_id),
-            GameEvent::StatusEffectRemoved { ship_id, .. } => Some(*ship_id),
-            GameEvent::ModuleStatusChanged { ship_id, .. } => Some(*ship_id),
-            GameEvent::PowerAllocationChanged { ship_id, .. } => Some(*ship_id),
-            GameEvent::CoolingAllocationChanged { ship_id, .. } => Some(*ship_id),
-            GameEvent::MessageSent { from_ship_id, .. } => Some(*from_ship_id),
-            GameEvent::ShipDocked { ship_id, .. } => Some(*ship_id),
-            GameEvent::ShipUndocked { ship_id, .. } => Some(*ship_id),
-            GameEvent::ContactDetected {
-                detecting_ship_id, ..
-            } => Some(*detecting_ship_id),
-            GameEvent::ContactLost {
-                detecting_ship_id, ..
-            } => Some(*detecting_ship_id),
-            GameEvent::ShipDestroyed { ship_id, .. } => Some(*ship_id),
-            GameEvent::CountermeasureActivated { ship_id, .. } => Some(*ship_id),
-            GameEvent::PointDefenseEngaged { ship_id, .. } => Some(*ship_id),
-            GameEvent::FtlEngaged { ship_id, .. } => Some(*ship_id),
-            GameEvent::FtlDisengaged { ship_id, .. } => Some(*ship_id),
-            GameEvent::SimulationTick { .. } => None,
-        };
+        // Team events only go to the team's members
+        if let Some(team_id) = event.team_id() {
+            return subscription.subscribe_to_simulation
+                && subscription.team_ids.iter().any(|id| id == team_id);
+        }
 
-        if let Some(ship_id) = event_ship_id {
+        // Check if event is related to subscribed ships
+        if let Some(ship_id) = event.ship_id() {
             subscription.ship_ids.contains(&ship_id)
         } else {
             subscription.subscribe_to_simulation
@@ -239,6 +233,11 @@ pub fn ws_handler(
                     event = event_rx.recv() => {
                         match event {
                             Ok(game_event) => {
+                                if game_event.team_id().is_some() {
+                                    subscription.refresh_teams(&game_world.read().unwrap());
+                                    ws_manager.update_subscription(&client_id, subscription.clone());
+                                }
+
                                 // Check if client should receive this event
                                 if ws_manager.should_receive_event(&client_id, &game_event) {
                                     let event_json = serde_json::to_string(&game_event).unwrap();
@@ -270,19 +269,40 @@ pub fn ws_handler(
 }
 
 /// Handle a subscription request
+///
+/// Clients must authenticate before subscribing to anything, and may only
+/// follow ships flying for their own teams.
 fn handle_subscription_request(
     request: SubscriptionRequest,
     subscription: &mut ClientSubscription,
     game_world: &Arc<RwLock<GameWorld>>,
 ) -> SubscriptionResponse {
+    let subscribing = matches!(
+        request,
+        SubscriptionRequest::SubscribeToShip { .. }
+            | SubscriptionRequest::SubscribeToPlayer { .. }
+            | SubscriptionRequest::SubscribeToSimulation
+    );
+    if subscribing && subscription.player_id.is_none() {
+        return SubscriptionResponse::Error {
+            message: "Authenticate before subscribing".to_string(),
+        };
+    }
+
     match request {
         SubscriptionRequest::SubscribeToShip { ship_id } => {
-            // Verify ship exists
+            // Verify ship exists and flies for one of the player's teams
             let world = game_world.read().unwrap();
-            if !world.ship_exists(ship_id) {
+            let Some(ship) = world.get_ship(&ship_id.to_string()) else {
                 return SubscriptionResponse::Error {
                     message: format!("Ship {} not found", ship_id),
                 };
+            };
+            let player_id = subscription.player_id.unwrap_or_default().to_string();
+            if !world.is_team_member(&ship.team_id, &player_id) {
+                return SubscriptionResponse::Error {
+                    message: "Players may only subscribe to their own team's ships".to_string(),
+                };
             }
 
             if !subscription.ship_ids.contains(&ship_id) {
@@ -295,12 +315,16 @@ fn handle_subscription_request(
         }
 
         SubscriptionRequest::SubscribeToPlayer { player_id } => {
+            if subscription.player_id != Some(player_id) {
+                return SubscriptionResponse::Error {
+                    message: "Players may only subscribe to their own ships".to_string(),
+                };
+            }
+
             // Verify player exists and get their ships
             let world = game_world.read().unwrap();
             let player_id_str = player_id.to_string();
             if let Some(player) = world.get_player(&player_id_str) {
-                subscription.player_id = Some(player_id);
-
                 // Subscribe to all player's ships
                 for ship_id in world.get_player_ships(player_id) {
                     if !subscription.ship_ids.contains(&ship_id) {
@@ -334,7 +358,6 @@ fn handle_subscription_request(
 
         SubscriptionRequest::UnsubscribeFromPlayer { player_id } => {
             if subscription.player_id == Some(player_id) {
-                subscription.player_id = None;
                 subscription.ship_ids.clear();
                 SubscriptionResponse::Unsubscribed {
                     message: format!("Unsubscribed from player {}", player_id),
@@ -346,17 +369,20 @@ fn handle_subscription_request(
             }
         }
 
-        SubscriptionRequest::Authenticate { player_id } => {
-            // In a real implementation, verify credentials here
+        SubscriptionRequest::Authenticate { token } => {
             let world = game_world.read().unwrap();
-            let player_id_str = player_id.to_string();
-            if world.get_player(&player_id_str).is_some() {
-                subscription.player_id = Some(player_id);
-                SubscriptionResponse::Authenticated { player_id }
-            } else {
-                SubscriptionResponse::Error {
-                    message: "Authentication failed".to_string(),
+            let player_id = world
+                .authenticate_token(&token)
+                .and_then(|player| Uuid::parse_str(&player.id).ok());
+            match player_id {
+                Some(player_id) => {
+                    subscription.player_id = Some(player_id);
+                    subscription.refresh_teams(&world);
+                    SubscriptionResponse::Authenticated { player_id }
                 }
+                None => SubscriptionResponse::Error {
+                    message: "Authentication failed".to_string(),
+                },
             }
         }
     }
@@ -474,6 +500,167 @@ mod tests {
         assert!(manager.should_receive_event(client_id, &event));
     }
 
+    #[test]
+    fn test_reputation_changes_reach_only_the_team() {
+        let world = GameWorld::new_shared();
+        let (red, token) = {
+            let mut world = world.write().unwrap();
+            let player_id = world.register_player("alice".to_string()).unwrap();
+            let red = world
+                .create_team("Red".to_string(), "Federation".to_string())
+                .unwrap();
+            world
+                .create_team("Blue".to_string(), "Federation".to_string())
+                .unwrap();
+            world.add_player_to_team(&red, &player_id).unwrap();
+            (red, world.issue_player_token(&player_id).unwrap())
+        };
+        let blue = world
+            .read()
+            .unwrap()
+            .get_team_by_name("Blue")
+            .unwrap()
+            .id
+            .clone();
+
+        let manager = WebSocketManager::new();
+        manager.register_client("client1".to_string());
+        let mut subscription = ClientSubscription::default();
+        for request in [
+            SubscriptionRequest::Authenticate { token },
+            SubscriptionRequest::SubscribeToSimulation,
+        ] {
+            handle_subscription_request(request, &mut subscription, &world);
+        }
+        manager.update_subscription("client1", subscription);
+
+        let change = |team_id: &str| GameEvent::ReputationChanged {
+            team_id: team_id.to_string(),
+            faction: "Empire".to_string(),
+            standing: 10.0,
+            change: 10.0,
+            cause: crate::events::ReputationCause::Trade,
+            hostile: false,
+        };
+        assert!(manager.should_receive_event("client1", &change(&red)));
+        assert!(!manager.should_receive_event("client1", &change(&blue)));
+    }
+
+    #[test]
+    fn test_subscribing_requires_authentication() {
+        let world = GameWorld::new_shared();
+        let token = {
+            let mut world = world.write().unwrap();
+            let player_id = world.register_player("alice".to_string()).unwrap();
+            world.issue_player_token(&player_id).unwrap()
+        };
+        let mut subscription = ClientSubscription::default();
+
+        let response = handle_subscription_request(
+            SubscriptionRequest::SubscribeToSimulation,
+            &mut subscription,
+            &world,
+        );
+        assert!(matches!(response, SubscriptionResponse::Error { .. }));
+        assert!(!subscription.subscribe_to_simulation);
+
+        let response = handle_subscription_request(
+            SubscriptionRequest::Authenticate {
+                token: "forged".to_string(),
+            },
+            &mut subscription,
+            &world,
+        );
+        assert!(matches!(response, SubscriptionResponse::Error { .. }));
+
+        let response = handle_subscription_request(
+            SubscriptionRequest::Authenticate { token },
+            &mut subscription,
+            &world,
+        );
+        assert!(matches!(
+            response,
+            SubscriptionResponse::Authenticated { .. }
+        ));
+
+        let response = handle_subscription_request(
+            SubscriptionRequest::SubscribeToPlayer {
+                player_id: Uuid::new_v4(),
+            },
+            &mut subscription,
+            &world,
+        );
+        assert!(matches!(response, SubscriptionResponse::Error { .. }));
+
+        let response = handle_subscription_request(
+            SubscriptionRequest::SubscribeToSimulation,
+            &mut subscription,
+            &world,
+        );
+        assert!(matches!(response, SubscriptionResponse::Subscribed { .. }));
+        assert!(subscription.subscribe_to_simulation);
+    }
+
+    #[test]
+    fn test_ship_subscriptions_are_limited_to_own_team() {
+        use crate::models::Ship;
+
+        let world = GameWorld::new_shared();
+        let (token, own_ship, other_ship) = {
+            let mut world = world.write().unwrap();
+            let player_id = world.register_player("alice".to_string()).unwrap();
+            let team_id = world
+                .create_team("Alpha".to_string(), "alliance".to_string())
+                .unwrap();
+            world.add_player_to_team(&team_id, &player_id).unwrap();
+            let mut add_ship = |team_id: &str| {
+                let ship_id = Uuid::new_v4();
+                world.add_ship(Ship {
+                    id: ship_id.to_string(),
+                    name: "Ship".to_string(),
+                    class: "cruiser".to_string(),
+                    team_id: team_id.to_string(),
+                    player_roles: HashMap::new(),
+                    status: Default::default(),
+                    modules: vec![],
+                    weapons: vec![],
+                    inventory: Default::default(),
+                });
+                ship_id
+            };
+            let own_ship = add_ship(&team_id);
+            let other_ship = add_ship("rivals");
+            (
+                world.issue_player_token(&player_id).unwrap(),
+                own_ship,
+                other_ship,
+            )
+        };
+        let mut subscription = ClientSubscription::default();
+        handle_subscription_request(
+            SubscriptionRequest::Authenticate { token },
+            &mut subscription,
+            &world,
+        );
+
+        let response = handle_subscription_request(
+            SubscriptionRequest::SubscribeToShip {
+                ship_id: other_ship,
+            },
+            &mut subscription,
+            &world,
+        );
+        assert!(matches!(response, SubscriptionResponse::Error { .. }));
+
+        let response = handle_subscription_request(
+            SubscriptionRequest::SubscribeToShip { ship_id: own_ship },
+            &mut subscription,
+            &world,
+        );
+        assert!(matches!(response, SubscriptionResponse::Subscribed { .. }));
+        assert_eq!(subscription.ship_ids, vec![own_ship]);
+    }
+
     #[test]
     fn test_event_broadcast() {
         let manager = WebSocketManager::new();
@@ -7,6 +7,8 @@ use hyperion::api::generation::UniverseState;
 use hyperion::config::test_utils::{create_test_game_config, create_test_ship_class};
 use hyperion::config::*;
 use hyperion::models::*;
+use hyperion::replay::ReplayStore;
+use hyperion::save::SaveStore;
 use hyperion::state::GameWorld;
 use hyperion::stations::Station;
 use hyperion::websocket::WebSocketManager;
@@ -21,14 +23,24 @@ fn create_test_rocket() -> rocket::Rocket<rocket::Build> {
     let game_world = GameWorld::new_shared();
     let ws_manager = Arc::new(WebSocketManager::new());
     let universe_state = Arc::new(RwLock::new(UniverseState::new()));
+    let test_dir = std::env::temp_dir()
+        .join("hyperion-tests")
+        .join(uuid::Uuid::new_v4().to_string());
+    let save_store = Arc::new(SaveStore::new(test_dir.join("saves")));
+    let replay_store = Arc::new(ReplayStore::new(test_dir.join("replays")));
 
     rocket::build()
+        .manage(Arc::new(config.clone()))
         .manage(config)
+        .manage(api::graphql::schema())
         .manage(game_world)
         .manage(ws_manager)
         .manage(universe_state)
+        .manage(save_store)
+        .manage(replay_store)
         .mount("/", api::routes())
         .mount("/graphql", api::graphql_routes())
+        .register("/", api::auth::catchers())
 }
 
 /// Helper to create a minimal test configuration.